@@ -0,0 +1,870 @@
+//! Analytic narrow phase tests for the most common pairs of primitives
+//!
+//! Spheres, capsules, axis aligned boxes and half-spaces, and in 2D circles against convex
+//! polygons, have closed form contacts, which are both faster and more accurate than the iterative
+//! [`GJK`](../minkowski/struct.GJK.html) and EPA. Use
+//! [`intersection`](fn.intersection.html) to route any pair of primitives to the fastest test.
+//!
+//! All contacts follow the conventions of `GJK`: the normal points from the left shape towards the
+//! right shape, and the contact point is on the surface of the left shape, in world space.
+
+use cgmath::{BaseFloat, Point2, Point3, Vector2, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, CollisionStrategy, Contact, Line, Line3};
+use crate::algorithm::minkowski::GJK3;
+use crate::algorithm::sat;
+use crate::primitive::util::closest_points_on_segments;
+use crate::primitive::{Circle, ConvexPolygon, HalfSpace, Primitive3};
+
+/// Intersection test between two spheres.
+///
+/// ## Parameters:
+///
+/// - `strategy`: strategy to use, if `CollisionOnly` only a boolean result is computed
+/// - `left_center`, `left_radius`: left sphere in world space
+/// - `right_center`, `right_radius`: right sphere in world space
+///
+/// ## Returns:
+///
+/// Will optionally return a contact if the spheres intersect. Concentric spheres are separated
+/// along the X axis.
+pub fn sphere_sphere<S>(
+    strategy: &CollisionStrategy,
+    left_center: Point3<S>,
+    left_radius: S,
+    right_center: Point3<S>,
+    right_radius: S,
+) -> Option<Contact<Point3<S>>>
+where
+    S: BaseFloat,
+{
+    let d = right_center - left_center;
+    let radiuses = left_radius + right_radius;
+    let distance2 = d.magnitude2();
+    if distance2 > radiuses * radiuses {
+        return None;
+    }
+    if *strategy == CollisionStrategy::CollisionOnly {
+        return Some(Contact::new(CollisionStrategy::CollisionOnly));
+    }
+    let distance = distance2.sqrt();
+    let normal = if distance.is_zero() {
+        Vector3::unit_x()
+    } else {
+        d / distance
+    };
+    Some(Contact::new_with_point(
+        CollisionStrategy::FullResolution,
+        normal,
+        radiuses - distance,
+        left_center + normal * left_radius,
+    ))
+}
+
+/// Intersection test between a sphere and an axis aligned box.
+///
+/// ## Parameters:
+///
+/// - `strategy`: strategy to use, if `CollisionOnly` only a boolean result is computed
+/// - `center`, `radius`: left sphere in world space
+/// - `aabb`: right box in world space
+///
+/// ## Returns:
+///
+/// Will optionally return a contact if the sphere and the box intersect. When the center of the
+/// sphere is inside the box, the sphere is pushed out through the nearest face.
+pub fn sphere_aabb<S>(
+    strategy: &CollisionStrategy,
+    center: Point3<S>,
+    radius: S,
+    aabb: &Aabb3<S>,
+) -> Option<Contact<Point3<S>>>
+where
+    S: BaseFloat,
+{
+    let closest = Point3::new(
+        center.x.max(aabb.min.x).min(aabb.max.x),
+        center.y.max(aabb.min.y).min(aabb.max.y),
+        center.z.max(aabb.min.z).min(aabb.max.z),
+    );
+    let d = closest - center;
+    let distance2 = d.magnitude2();
+    if distance2 > radius * radius {
+        return None;
+    }
+    if *strategy == CollisionStrategy::CollisionOnly {
+        return Some(Contact::new(CollisionStrategy::CollisionOnly));
+    }
+    let (normal, depth) = if distance2.is_zero() {
+        // the center is inside the box, find the nearest face
+        let mut normal = Vector3::zero();
+        let mut face_distance = S::infinity();
+        for i in 0..3 {
+            for &(distance, sign) in &[
+                (center[i] - aabb.min[i], S::one()),
+                (aabb.max[i] - center[i], -S::one()),
+            ] {
+                if distance < face_distance {
+                    face_distance = distance;
+                    normal = Vector3::zero();
+                    normal[i] = sign;
+                }
+            }
+        }
+        (normal, face_distance + radius)
+    } else {
+        let distance = distance2.sqrt();
+        (d / distance, radius - distance)
+    };
+    Some(Contact::new_with_point(
+        CollisionStrategy::FullResolution,
+        normal,
+        depth,
+        center + normal * radius,
+    ))
+}
+
+/// Intersection test between two axis aligned boxes.
+///
+/// ## Parameters:
+///
+/// - `strategy`: strategy to use, if `CollisionOnly` only a boolean result is computed
+/// - `left`: left box in world space
+/// - `right`: right box in world space
+///
+/// ## Returns:
+///
+/// Will optionally return a contact if the boxes intersect. The normal and depth are the shortest
+/// move of the right box along an axis that separates the boxes, also when one box is nested
+/// inside the other, and the contact point is in the middle of the overlap, on the face of the
+/// left box.
+pub fn aabb_aabb<S>(
+    strategy: &CollisionStrategy,
+    left: &Aabb3<S>,
+    right: &Aabb3<S>,
+) -> Option<Contact<Point3<S>>>
+where
+    S: BaseFloat,
+{
+    // the distance to move the right box along each axis, forwards or backwards, to separate
+    // the boxes, which differ when one box is nested inside the other
+    let forward = left.max - right.min;
+    let backward = right.max - left.min;
+    if (0..3).any(|i| forward[i] < S::zero() || backward[i] < S::zero()) {
+        return None;
+    }
+    if *strategy == CollisionStrategy::CollisionOnly {
+        return Some(Contact::new(CollisionStrategy::CollisionOnly));
+    }
+    let depth = |i: usize| forward[i].min(backward[i]);
+    let axis = (1..3).fold(0, |axis, i| if depth(i) < depth(axis) { i } else { axis });
+    let min = Point3::new(
+        left.min.x.max(right.min.x),
+        left.min.y.max(right.min.y),
+        left.min.z.max(right.min.z),
+    );
+    let max = Point3::new(
+        left.max.x.min(right.max.x),
+        left.max.y.min(right.max.y),
+        left.max.z.min(right.max.z),
+    );
+    let mut normal = Vector3::zero();
+    let mut point = min.midpoint(max);
+    if forward[axis] <= backward[axis] {
+        normal[axis] = S::one();
+        point[axis] = left.max[axis];
+    } else {
+        normal[axis] = -S::one();
+        point[axis] = left.min[axis];
+    }
+    Some(Contact::new_with_point(
+        CollisionStrategy::FullResolution,
+        normal,
+        depth(axis),
+        point,
+    ))
+}
+
+/// Intersection test between a half-space and a sphere.
+///
+/// ## Parameters:
+///
+/// - `strategy`: strategy to use, if `CollisionOnly` only a boolean result is computed
+/// - `half_space`: left half-space in world space
+/// - `center`, `radius`: right sphere in world space
+///
+/// ## Returns:
+///
+/// Will optionally return a contact if the sphere intersects the half-space. Like
+/// [`HalfSpace::intersection_primitive`](../../primitive/struct.HalfSpace.html#method.intersection_primitive),
+/// the normal is the normal of the half-space, and the contact point is on its boundary.
+pub fn half_space_sphere<S>(
+    strategy: &CollisionStrategy,
+    half_space: &HalfSpace<S>,
+    center: Point3<S>,
+    radius: S,
+) -> Option<Contact<Point3<S>>>
+where
+    S: BaseFloat,
+{
+    let depth = half_space.offset + radius - half_space.normal.dot(center.to_vec());
+    if depth < S::zero() {
+        return None;
+    }
+    if *strategy == CollisionStrategy::CollisionOnly {
+        return Some(Contact::new(CollisionStrategy::CollisionOnly));
+    }
+    let deepest = center - half_space.normal * radius;
+    Some(Contact::new_with_point(
+        CollisionStrategy::FullResolution,
+        half_space.normal,
+        depth,
+        deepest + half_space.normal * depth,
+    ))
+}
+
+/// Intersection test between two capsules, in 2D or 3D.
+///
+/// A capsule is the set of all points within the radius of its core segment, so the capsules
+/// intersect if the distance between the closest points on the segments is at most the sum of the
+/// radiuses.
+///
+/// ## Parameters:
+///
+/// - `strategy`: strategy to use, if `CollisionOnly` only a boolean result is computed
+/// - `left`, `left_radius`: core segment and radius of the left capsule, in world space
+/// - `right`, `right_radius`: core segment and radius of the right capsule, in world space
+///
+/// ## Returns:
+///
+/// Will optionally return a contact if the capsules intersect. When the segments intersect, the
+/// normal is perpendicular to the left segment.
+pub fn capsule_capsule<S, P>(
+    strategy: &CollisionStrategy,
+    left: &Line<S, P::Diff, P>,
+    left_radius: S,
+    right: &Line<S, P::Diff, P>,
+    right_radius: S,
+) -> Option<Contact<P>>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace + Array<Element = S>,
+{
+    let (left_point, right_point) =
+        closest_points_on_segments(left.origin, left.dest, right.origin, right.dest);
+    let d = right_point - left_point;
+    let radiuses = left_radius + right_radius;
+    let distance2 = d.magnitude2();
+    if distance2 > radiuses * radiuses {
+        return None;
+    }
+    if *strategy == CollisionStrategy::CollisionOnly {
+        return Some(Contact::new(CollisionStrategy::CollisionOnly));
+    }
+    let distance = distance2.sqrt();
+    let normal = if distance.is_zero() {
+        let normal = perpendicular(left.dest - left.origin);
+        let towards = P::midpoint(right.origin, right.dest) - P::midpoint(left.origin, left.dest);
+        if normal.dot(towards) < S::zero() {
+            normal * -S::one()
+        } else {
+            normal
+        }
+    } else {
+        d / distance
+    };
+    Some(Contact::new_with_point(
+        CollisionStrategy::FullResolution,
+        normal,
+        radiuses - distance,
+        left_point + normal * left_radius,
+    ))
+}
+
+/// Intersection test between a capsule and a sphere, in 2D or 3D.
+///
+/// ## Parameters:
+///
+/// - `strategy`: strategy to use, if `CollisionOnly` only a boolean result is computed
+/// - `left`, `left_radius`: core segment and radius of the left capsule, in world space
+/// - `center`, `radius`: right sphere in world space
+///
+/// ## Returns:
+///
+/// Will optionally return a contact if the capsule and the sphere intersect.
+pub fn capsule_sphere<S, P>(
+    strategy: &CollisionStrategy,
+    left: &Line<S, P::Diff, P>,
+    left_radius: S,
+    center: P,
+    radius: S,
+) -> Option<Contact<P>>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace + Array<Element = S>,
+{
+    capsule_capsule(
+        strategy,
+        left,
+        left_radius,
+        &Line::new(center, center),
+        radius,
+    )
+}
+
+/// Intersection test between a sphere and a capsule, in 2D or 3D.
+///
+/// Same as [`capsule_sphere`](fn.capsule_sphere.html), with the shapes swapped, so the normal
+/// points from the sphere towards the capsule, and the contact point is on the sphere.
+pub fn sphere_capsule<S, P>(
+    strategy: &CollisionStrategy,
+    center: P,
+    radius: S,
+    right: &Line<S, P::Diff, P>,
+    right_radius: S,
+) -> Option<Contact<P>>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace + Array<Element = S>,
+{
+    capsule_sphere(strategy, right, right_radius, center, radius).map(|contact| contact.flipped())
+}
+
+/// Intersection test between any two primitives, using the analytic tests for pairs of spheres,
+/// capsules and axis aligned boxes, and [`GJK`](../minkowski/struct.GJK.html) for all other pairs.
+///
+/// Cuboids and cubes are only tested as boxes when their transforms do not rotate them.
+///
+/// ## Parameters:
+///
+/// - `strategy`: strategy to use, if `CollisionOnly` only a boolean result is computed
+/// - `left`: left primitive
+/// - `left_transform`: model-to-world-transform for the left primitive
+/// - `right`: right primitive
+/// - `right_transform`: model-to-world-transform for the right primitive
+///
+/// ## Returns:
+///
+/// Will optionally return a contact if the primitives intersect.
+pub fn intersection<S, TL, TR>(
+    strategy: &CollisionStrategy,
+    left: &Primitive3<S>,
+    left_transform: &TL,
+    right: &Primitive3<S>,
+    right_transform: &TR,
+) -> Option<Contact<Point3<S>>>
+where
+    S: BaseFloat,
+    TL: Transform<Point3<S>>,
+    TR: Transform<Point3<S>>,
+{
+    if let Some((left_center, left_radius)) = left.as_sphere(left_transform) {
+        if let Some((right_center, right_radius)) = right.as_sphere(right_transform) {
+            return sphere_sphere(
+                strategy,
+                left_center,
+                left_radius,
+                right_center,
+                right_radius,
+            );
+        }
+        if let Some(aabb) = right.as_aabb(right_transform) {
+            return sphere_aabb(strategy, left_center, left_radius, &aabb);
+        }
+    } else if let Some(left_aabb) = left.as_aabb(left_transform) {
+        if let Some(right_aabb) = right.as_aabb(right_transform) {
+            return aabb_aabb(strategy, &left_aabb, &right_aabb);
+        }
+        if let Some((center, radius)) = right.as_sphere(right_transform) {
+            return sphere_aabb(strategy, center, radius, &left_aabb)
+                .map(|contact| contact.flipped());
+        }
+    }
+    if let (Some((left_segment, left_radius)), Some((right_segment, right_radius))) = (
+        as_swept_sphere(left, left_transform),
+        as_swept_sphere(right, right_transform),
+    ) {
+        return capsule_capsule(
+            strategy,
+            &left_segment,
+            left_radius,
+            &right_segment,
+            right_radius,
+        );
+    }
+    GJK3::new().intersection(strategy, left, left_transform, right, right_transform)
+}
+
+/// Intersection test between a half-space and any primitive, using the analytic test for spheres,
+/// and [`HalfSpace::intersection_primitive`](../../primitive/struct.HalfSpace.html#method.intersection_primitive)
+/// for all other primitives.
+pub fn intersection_half_space<S, T>(
+    strategy: &CollisionStrategy,
+    half_space: &HalfSpace<S>,
+    primitive: &Primitive3<S>,
+    transform: &T,
+) -> Option<Contact<Point3<S>>>
+where
+    S: BaseFloat,
+    T: Transform<Point3<S>>,
+{
+    match primitive.as_sphere(transform) {
+        Some((center, radius)) => half_space_sphere(strategy, half_space, center, radius),
+        None => half_space.intersection_primitive(strategy, primitive, transform),
+    }
+}
+
+/// Intersection test between a convex polygon and a circle.
+///
+/// Finds the closest edge or vertex of the polygon to the center of the circle, using the Voronoi
+/// regions of the polygon, see [`sat::polygon_circle`](../sat/fn.polygon_circle.html).
+///
+/// ## Parameters:
+///
+/// - `strategy`: strategy to use, if `CollisionOnly` only a boolean result is computed
+/// - `left`: left polygon, with vertices in CCW order
+/// - `left_transform`: model-to-world-transform for the polygon
+/// - `right`: right circle
+/// - `right_transform`: model-to-world-transform for the circle
+///
+/// ## Returns:
+///
+/// Will optionally return a contact if the shapes intersect.
+pub fn polygon_circle<S, TL, TR>(
+    strategy: &CollisionStrategy,
+    left: &ConvexPolygon<S>,
+    left_transform: &TL,
+    right: &Circle<S>,
+    right_transform: &TR,
+) -> Option<Contact<Point2<S>>>
+where
+    S: BaseFloat,
+    TL: Transform<Point2<S>>,
+    TR: Transform<Point2<S>>,
+{
+    let overlap = sat::polygon_circle(left, left_transform, right, right_transform)?;
+    if *strategy == CollisionStrategy::CollisionOnly {
+        return Some(Contact::new(CollisionStrategy::CollisionOnly));
+    }
+    let center = right_transform.transform_point(Point2::origin());
+    let radius = right_transform
+        .transform_vector(Vector2::new(right.radius, S::zero()))
+        .magnitude();
+    Some(Contact::new_with_point(
+        CollisionStrategy::FullResolution,
+        overlap.axis,
+        overlap.depth,
+        center + overlap.axis * (overlap.depth - radius),
+    ))
+}
+
+/// Intersection test between a circle and a convex polygon.
+///
+/// Same as [`polygon_circle`](fn.polygon_circle.html), with the shapes swapped, so the normal
+/// points from the circle towards the polygon, and the contact point is on the circle.
+pub fn circle_polygon<S, TL, TR>(
+    strategy: &CollisionStrategy,
+    left: &Circle<S>,
+    left_transform: &TL,
+    right: &ConvexPolygon<S>,
+    right_transform: &TR,
+) -> Option<Contact<Point2<S>>>
+where
+    S: BaseFloat,
+    TL: Transform<Point2<S>>,
+    TR: Transform<Point2<S>>,
+{
+    polygon_circle(strategy, right, right_transform, left, left_transform)
+        .map(|contact| contact.flipped())
+}
+
+/// Core segment and radius in world space, if the primitive is a capsule, a sphere or a particle
+fn as_swept_sphere<S, T>(primitive: &Primitive3<S>, transform: &T) -> Option<(Line3<S>, S)>
+where
+    S: BaseFloat,
+    T: Transform<Point3<S>>,
+{
+    primitive.as_capsule(transform).or_else(|| {
+        primitive
+            .as_sphere(transform)
+            .map(|(center, radius)| (Line3::new(center, center), radius))
+    })
+}
+
+/// Any unit vector perpendicular to the given vector, or along the first axis for a zero vector
+fn perpendicular<S, V>(v: V) -> V
+where
+    S: BaseFloat,
+    V: InnerSpace<Scalar = S> + Array<Element = S>,
+{
+    // the axis with the smallest component is the furthest from being parallel
+    let mut axis = V::zero();
+    let i = (1..V::len()).fold(0, |i, j| if v[j].abs() < v[i].abs() { j } else { i });
+    axis[i] = S::one();
+    let length2 = v.magnitude2();
+    if length2.is_zero() {
+        return axis;
+    }
+    (axis - v * (v.dot(axis) / length2)).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::{assert_relative_eq, assert_ulps_eq};
+    use cgmath::{Basis2, Decomposed, Point2, Point3, Quaternion, Rad, Vector2, Vector3};
+    use cgmath::prelude::*;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK2;
+    use crate::{Line2, Line3};
+    use crate::primitive::{Capsule, Cube, Cuboid, Sphere};
+
+    fn transform(
+        x: f32,
+        y: f32,
+        z: f32,
+        angle_z: f32,
+    ) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            disp: Vector3::new(x, y, z),
+            rot: Quaternion::from_angle_z(Rad(angle_z)),
+            scale: 1.,
+        }
+    }
+
+    #[test]
+    fn test_sphere_sphere() {
+        let contact = sphere_sphere(
+            &CollisionStrategy::FullResolution,
+            Point3::new(0., 0., 0.),
+            1.,
+            Point3::new(0., 1.5, 0.),
+            1.,
+        ).unwrap();
+        assert_ulps_eq!(Vector3::unit_y(), contact.normal);
+        assert_ulps_eq!(0.5, contact.penetration_depth);
+        assert_ulps_eq!(Point3::new(0., 1., 0.), contact.contact_point);
+        assert!(
+            sphere_sphere(
+                &CollisionStrategy::CollisionOnly,
+                Point3::new(0., 0., 0.),
+                1.,
+                Point3::new(0., 2.5, 0.),
+                1.,
+            ).is_none()
+        );
+    }
+
+    #[test]
+    fn test_sphere_aabb() {
+        let aabb = Aabb3::new(Point3::new(-1., -1., -1.), Point3::new(1., 1., 1.));
+        let contact = sphere_aabb(
+            &CollisionStrategy::FullResolution,
+            Point3::new(1.5, 0., 0.),
+            1.,
+            &aabb,
+        ).unwrap();
+        assert_ulps_eq!(-Vector3::unit_x(), contact.normal);
+        assert_ulps_eq!(0.5, contact.penetration_depth);
+        assert_ulps_eq!(Point3::new(0.5, 0., 0.), contact.contact_point);
+
+        // center inside the box
+        let contact = sphere_aabb(
+            &CollisionStrategy::FullResolution,
+            Point3::new(0., 0., -0.75),
+            0.5,
+            &aabb,
+        ).unwrap();
+        assert_ulps_eq!(Vector3::unit_z(), contact.normal);
+        assert_ulps_eq!(0.75, contact.penetration_depth);
+
+        assert!(
+            sphere_aabb(
+                &CollisionStrategy::FullResolution,
+                Point3::new(1.8, 1.8, 0.),
+                1.,
+                &aabb,
+            ).is_none()
+        );
+    }
+
+    #[test]
+    fn test_aabb_aabb() {
+        let left = Aabb3::new(Point3::new(0., 0., 0.), Point3::new(2., 2., 2.));
+        let right = Aabb3::new(Point3::new(1.5, 1., 1.), Point3::new(3., 3., 3.));
+        let contact = aabb_aabb(&CollisionStrategy::FullResolution, &left, &right).unwrap();
+        assert_ulps_eq!(Vector3::unit_x(), contact.normal);
+        assert_ulps_eq!(0.5, contact.penetration_depth);
+        assert_ulps_eq!(Point3::new(2., 1.5, 1.5), contact.contact_point);
+
+        let contact = aabb_aabb(&CollisionStrategy::FullResolution, &right, &left).unwrap();
+        assert_ulps_eq!(-Vector3::unit_x(), contact.normal);
+        assert_ulps_eq!(Point3::new(1.5, 1.5, 1.5), contact.contact_point);
+
+        let right = Aabb3::new(Point3::new(2.5, 0., 0.), Point3::new(3., 3., 3.));
+        assert!(aabb_aabb(&CollisionStrategy::CollisionOnly, &left, &right).is_none());
+    }
+
+    #[test]
+    fn test_aabb_aabb_nested() {
+        let outer = Aabb3::new(Point3::new(-2., -2., -2.), Point3::new(2., 2., 2.));
+        let inner = Aabb3::new(Point3::new(-0.5, -0.5, -0.5), Point3::new(0.5, 0.5, 0.5));
+        let contact = aabb_aabb(&CollisionStrategy::FullResolution, &outer, &inner).unwrap();
+        assert_ulps_eq!(2.5, contact.penetration_depth);
+
+        // off center, the inner box leaves the outer box through the closest face
+        let outer = Aabb3::new(Point3::new(0., 0., 0.), Point3::new(4., 4., 4.));
+        let inner = Aabb3::new(Point3::new(2.5, 1., 1.), Point3::new(3.5, 3., 3.));
+        let contact = aabb_aabb(&CollisionStrategy::FullResolution, &outer, &inner).unwrap();
+        assert_ulps_eq!(Vector3::unit_x(), contact.normal);
+        assert_ulps_eq!(1.5, contact.penetration_depth);
+        let contact = aabb_aabb(&CollisionStrategy::FullResolution, &inner, &outer).unwrap();
+        assert_ulps_eq!(-Vector3::unit_x(), contact.normal);
+        assert_ulps_eq!(1.5, contact.penetration_depth);
+        assert_ulps_eq!(Point3::new(2.5, 2., 2.), contact.contact_point);
+    }
+
+    #[test]
+    fn test_half_space_sphere() {
+        let half_space = HalfSpace::new(Vector3::unit_y(), 1.);
+        let sphere = Primitive3::from(Sphere::new(1.));
+        let t = transform(2., 1.5, 0., 0.);
+        let contact = half_space_sphere(
+            &CollisionStrategy::FullResolution,
+            &half_space,
+            Point3::new(2., 1.5, 0.),
+            1.,
+        ).unwrap();
+        let expected = half_space
+            .intersection_primitive(&CollisionStrategy::FullResolution, &sphere, &t)
+            .unwrap();
+        assert_ulps_eq!(expected.normal, contact.normal);
+        assert_ulps_eq!(expected.penetration_depth, contact.penetration_depth);
+        assert_ulps_eq!(0.5, contact.penetration_depth);
+        assert_ulps_eq!(Point3::new(2., 1., 0.), contact.contact_point);
+        assert!(
+            intersection_half_space(
+                &CollisionStrategy::FullResolution,
+                &half_space,
+                &sphere,
+                &transform(0., 2.5, 0., 0.),
+            ).is_none()
+        );
+    }
+
+    #[test]
+    fn test_intersection_matches_gjk() {
+        let sphere = Primitive3::from(Sphere::new(1.));
+        let cuboid = Primitive3::from(Cuboid::new(2., 2., 2.));
+        let cube = Primitive3::from(Cube::new(2.));
+        let lt = transform(0., 0., 0., 0.);
+        let pairs = [
+            (&sphere, &sphere, transform(1.5, 0.2, 0., 0.)),
+            (&sphere, &cuboid, transform(1.7, 0., 0., 0.)),
+            (&cuboid, &sphere, transform(0., -1.6, 0., 0.)),
+            (&cuboid, &cube, transform(0.3, 0.2, 1.8, 0.)),
+            (&cube, &cuboid, transform(1.5, 0.5, 0., 1.)),
+        ];
+        let gjk = GJK3::new();
+        for &(left, right, ref rt) in &pairs {
+            let expected = gjk
+                .intersection(&CollisionStrategy::FullResolution, left, &lt, right, rt)
+                .unwrap();
+            let contact =
+                intersection(&CollisionStrategy::FullResolution, left, &lt, right, rt).unwrap();
+            assert_relative_eq!(expected.normal, contact.normal, epsilon = 1e-2);
+            assert_relative_eq!(
+                expected.penetration_depth,
+                contact.penetration_depth,
+                epsilon = 1e-2
+            );
+        }
+        assert!(
+            intersection(
+                &CollisionStrategy::CollisionOnly,
+                &sphere,
+                &lt,
+                &cuboid,
+                &transform(2.5, 0., 0., 0.),
+            ).is_none()
+        );
+    }
+
+    #[test]
+    fn test_capsule_capsule() {
+        // crossing capsules
+        let left = Line3::new(Point3::new(-1., 0., 0.), Point3::new(1., 0., 0.));
+        let right = Line3::new(Point3::new(0.5, 0.8, -1.), Point3::new(0.5, 0.8, 1.));
+        let contact =
+            capsule_capsule(&CollisionStrategy::FullResolution, &left, 0.5, &right, 0.5).unwrap();
+        assert_ulps_eq!(Vector3::unit_y(), contact.normal);
+        assert_ulps_eq!(0.2, contact.penetration_depth, epsilon = 1e-6);
+        assert_ulps_eq!(Point3::new(0.5, 0.5, 0.), contact.contact_point);
+
+        // segments intersect, the normal is perpendicular to the left segment
+        let right = Line3::new(Point3::new(0.5, -0.2, -1.), Point3::new(0.5, 0.2, 1.));
+        let contact =
+            capsule_capsule(&CollisionStrategy::FullResolution, &left, 0.5, &right, 0.5).unwrap();
+        assert_ulps_eq!(0., contact.normal.dot(Vector3::unit_x()));
+        assert_ulps_eq!(1., contact.normal.magnitude());
+        assert_ulps_eq!(1., contact.penetration_depth);
+
+        let right = Line3::new(Point3::new(0.5, 1.1, -1.), Point3::new(0.5, 1.1, 1.));
+        assert!(
+            capsule_capsule(&CollisionStrategy::CollisionOnly, &left, 0.5, &right, 0.5).is_none()
+        );
+    }
+
+    #[test]
+    fn test_capsule_sphere_2d() {
+        let capsule = Line2::new(Point2::new(0., -1.), Point2::new(0., 1.));
+        let contact = capsule_sphere(
+            &CollisionStrategy::FullResolution,
+            &capsule,
+            0.5,
+            Point2::new(0.6, 1.6),
+            0.5,
+        ).unwrap();
+        let normal = Vector2::new(1., 1.).normalize();
+        assert_ulps_eq!(normal, contact.normal);
+        assert_ulps_eq!(1. - 0.6f32.hypot(0.6), contact.penetration_depth);
+        assert_ulps_eq!(Point2::new(0., 1.) + normal * 0.5, contact.contact_point);
+
+        let contact = sphere_capsule(
+            &CollisionStrategy::FullResolution,
+            Point2::new(0.8, 0.),
+            0.5,
+            &capsule,
+            0.5,
+        ).unwrap();
+        assert_ulps_eq!(Vector2::new(-1., 0.), contact.normal);
+        assert_ulps_eq!(0.2, contact.penetration_depth, epsilon = 1e-6);
+        assert_ulps_eq!(Point2::new(0.3, 0.), contact.contact_point, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_intersection_capsules_matches_gjk() {
+        let capsule = Primitive3::from(Capsule::new(1., 0.5));
+        let sphere = Primitive3::from(Sphere::new(0.5));
+        let lt = transform(0., 0., 0., 0.3);
+        let pairs = [
+            (&capsule, transform(0.8, 0.5, 0., -0.4)),
+            (&capsule, transform(0.2, 1.6, 0.3, 1.2)),
+            (&sphere, transform(0.7, 0.9, 0., 0.)),
+        ];
+        let gjk = GJK3::new();
+        for &(right, ref rt) in &pairs {
+            let expected = gjk
+                .intersection(&CollisionStrategy::FullResolution, &capsule, &lt, right, rt)
+                .unwrap();
+            let contact =
+                intersection(&CollisionStrategy::FullResolution, &capsule, &lt, right, rt).unwrap();
+            assert_relative_eq!(expected.normal, contact.normal, epsilon = 1e-2);
+            assert_relative_eq!(
+                expected.penetration_depth,
+                contact.penetration_depth,
+                epsilon = 1e-2
+            );
+        }
+    }
+
+    fn transform2(x: f32, y: f32, angle: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            disp: Vector2::new(x, y),
+            rot: Rotation2::from_angle(Rad(angle)),
+            scale: 1.,
+        }
+    }
+
+    #[test]
+    fn test_polygon_circle() {
+        let polygon = ConvexPolygon::new(vec![
+            Point2::new(-1., -1.),
+            Point2::new(1., -1.),
+            Point2::new(1., 1.),
+            Point2::new(-1., 1.),
+        ]);
+        let circle = Circle::new(0.5);
+        let lt = transform2(0., 0., 0.);
+
+        // edge region
+        let contact = polygon_circle(
+            &CollisionStrategy::FullResolution,
+            &polygon,
+            &lt,
+            &circle,
+            &transform2(0.2, 1.3, 0.),
+        ).unwrap();
+        assert_ulps_eq!(Vector2::new(0., 1.), contact.normal);
+        assert_ulps_eq!(0.2, contact.penetration_depth, epsilon = 1e-6);
+        assert_ulps_eq!(Point2::new(0.2, 1.), contact.contact_point, epsilon = 1e-6);
+
+        // vertex region
+        let contact = polygon_circle(
+            &CollisionStrategy::FullResolution,
+            &polygon,
+            &lt,
+            &circle,
+            &transform2(1.3, 1.3, 0.),
+        ).unwrap();
+        assert_ulps_eq!(Vector2::new(1., 1.).normalize(), contact.normal);
+        assert_ulps_eq!(Point2::new(1., 1.), contact.contact_point, epsilon = 1e-6);
+
+        // swapped shapes
+        let contact = circle_polygon(
+            &CollisionStrategy::FullResolution,
+            &circle,
+            &transform2(0.2, 1.3, 0.),
+            &polygon,
+            &lt,
+        ).unwrap();
+        assert_ulps_eq!(Vector2::new(0., -1.), contact.normal);
+        assert_ulps_eq!(Point2::new(0.2, 0.8), contact.contact_point, epsilon = 1e-6);
+
+        assert!(
+            polygon_circle(
+                &CollisionStrategy::CollisionOnly,
+                &polygon,
+                &lt,
+                &circle,
+                &transform2(1.4, 1.4, 0.),
+            ).is_none()
+        );
+    }
+
+    #[test]
+    fn test_polygon_circle_matches_gjk() {
+        let polygon = ConvexPolygon::new(vec![
+            Point2::new(-1., -0.5),
+            Point2::new(1., -1.),
+            Point2::new(0.5, 1.),
+            Point2::new(-1., 0.5),
+        ]);
+        let circle = Circle::new(0.5);
+        let lt = transform2(0.1, -0.2, 0.3);
+        let gjk = GJK2::new();
+        for rt in &[
+            transform2(1.2, 0.3, 0.),
+            transform2(-0.2, 1.1, 0.),
+            transform2(-0.9, -0.5, 0.),
+        ] {
+            let expected = gjk
+                .intersection(&CollisionStrategy::FullResolution, &polygon, &lt, &circle, rt)
+                .unwrap();
+            let contact =
+                polygon_circle(&CollisionStrategy::FullResolution, &polygon, &lt, &circle, rt)
+                    .unwrap();
+            assert_relative_eq!(expected.normal, contact.normal, epsilon = 1e-2);
+            assert_relative_eq!(expected.contact_point, contact.contact_point, epsilon = 1e-2);
+            assert_relative_eq!(
+                expected.penetration_depth,
+                contact.penetration_depth,
+                epsilon = 1e-2
+            );
+        }
+    }
+}
@@ -2,7 +2,10 @@ use crate::prelude::*;
 
 /// Broad phase collision detection brute force implementation.
 ///
-/// Will simply do bounding box intersection tests for all shape combinations.
+/// Will simply do bounding box intersection tests for all shape combinations, which is O(n²).
+/// This is fast enough for scenes with a handful of shapes, where building an acceleration
+/// structure costs more than it saves. Having no state, and no heuristics, it is also useful as
+/// the reference to check the other broad phase algorithms against in tests.
 #[derive(Debug, Default)]
 pub struct BruteForce;
 
@@ -21,6 +24,19 @@ impl BruteForce {
     where
         A: HasBound,
         A::Bound: Discrete<A::Bound>,
+    {
+        self.find_collider_pairs_by(shapes, |_, _| true)
+    }
+
+    /// Find all potentially colliding pairs of shapes, that are accepted by the given filter.
+    ///
+    /// The filter is called for the pairs with intersecting bounds, and rejected pairs are never
+    /// added to the result.
+    pub fn find_collider_pairs_by<A, F>(&self, shapes: &[A], mut filter: F) -> Vec<(usize, usize)>
+    where
+        A: HasBound,
+        A::Bound: Discrete<A::Bound>,
+        F: FnMut(&A, &A) -> bool,
     {
         let mut pairs = Vec::default();
         if shapes.len() <= 1 {
@@ -30,7 +46,8 @@ impl BruteForce {
         for left_index in 0..(shapes.len() - 1) {
             let left = &shapes[left_index];
             for right_index in (left_index + 1)..shapes.len() {
-                if left.bound().intersects(shapes[right_index].bound()) {
+                let right = &shapes[right_index];
+                if left.bound().intersects(right.bound()) && filter(left, right) {
                     pairs.push((left_index, right_index));
                 }
             }
@@ -2,7 +2,12 @@
 //! broad phase collision detection algorithm
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
 
+use cgmath::EuclideanSpace;
+
+use super::BroadPhase;
 use crate::dbvt::{DiscreteVisitor, DynamicBoundingVolumeTree, TreeValue};
 use crate::prelude::*;
 
@@ -62,3 +67,142 @@ impl DbvtBroadPhase {
         potentials
     }
 }
+
+/// Object stored in the tree
+#[derive(Debug, Clone)]
+pub(super) struct Proxy<ID, B> {
+    pub(super) id: ID,
+    pub(super) bound: B,
+}
+
+impl<ID, B> TreeValue for Proxy<ID, B>
+where
+    ID: Clone,
+    B: Bound + Clone,
+{
+    type Bound = B;
+
+    fn bound(&self) -> &B {
+        &self.bound
+    }
+
+    fn get_bound_with_margin(&self) -> B {
+        self.bound.clone()
+    }
+}
+
+/// [`BroadPhase`](trait.BroadPhase.html) storing the objects in a
+/// [`Dynamic Bounding Volume Tree`](../../dbvt/struct.DynamicBoundingVolumeTree.html).
+///
+/// The tree is refitted after every change, so the queries can be used at any time. Moving
+/// objects should use a margin, so small motions do not change the tree.
+///
+/// # Type parameters:
+///
+/// - `ID`: Id of an object
+/// - `B`: Bounding volume type
+pub struct TreeBroadPhase<ID, B>
+where
+    ID: Clone,
+    B: Bound + Clone,
+{
+    tree: DynamicBoundingVolumeTree<Proxy<ID, B>>,
+    nodes: HashMap<ID, usize>,
+}
+
+impl<ID, B> Default for TreeBroadPhase<ID, B>
+where
+    ID: Copy + Eq + Hash,
+    B: Bound + Clone + Contains<B> + SurfaceArea + Union<B, Output = B>,
+{
+    fn default() -> Self {
+        Self {
+            tree: DynamicBoundingVolumeTree::new(),
+            nodes: HashMap::default(),
+        }
+    }
+}
+
+impl<ID, B> TreeBroadPhase<ID, B>
+where
+    ID: Copy + Eq + Hash,
+    B: Bound + Clone + Contains<B> + SurfaceArea + Union<B, Output = B>,
+{
+    /// Create a new empty tree, without any margin on the bounds
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new empty tree, that extends the bounds of all objects by the given margin
+    pub fn with_margin(margin: <B::Point as EuclideanSpace>::Diff) -> Self {
+        Self {
+            tree: DynamicBoundingVolumeTree::with_margin(margin),
+            nodes: HashMap::default(),
+        }
+    }
+}
+
+impl<ID, B> BroadPhase<ID> for TreeBroadPhase<ID, B>
+where
+    ID: Copy + Eq + Hash,
+    B: Bound + Clone + Contains<B> + SurfaceArea + Union<B, Output = B> + Discrete<B>,
+{
+    type Bound = B;
+
+    fn insert(&mut self, id: ID, bound: B) {
+        if !self.update(id, bound.clone()) {
+            let node_index = self.tree.insert(Proxy { id, bound });
+            self.nodes.insert(id, node_index);
+            self.tree.do_refit();
+        }
+    }
+
+    fn remove(&mut self, id: ID) -> bool {
+        match self.nodes.remove(&id) {
+            Some(node_index) => {
+                self.tree.remove(node_index);
+                self.tree.do_refit();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn update(&mut self, id: ID, bound: B) -> bool {
+        match self.nodes.get(&id) {
+            Some(&node_index) => {
+                self.tree.update_node(node_index, Proxy { id, bound });
+                self.tree.tick();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn compute_filtered_pairs<F>(&mut self, mut filter: F) -> Vec<(ID, ID)>
+    where
+        F: FnMut(&ID, &ID) -> bool,
+    {
+        let values = self.tree.values();
+        self.tree
+            .overlapping_pairs_by(|left, right| filter(&left.id, &right.id))
+            .into_iter()
+            .map(|(left, right)| (values[left].1.id, values[right].1.id))
+            .collect()
+    }
+
+    fn query<R>(&self, target: &R) -> Vec<ID>
+    where
+        B: Discrete<R>,
+    {
+        self.tree
+            .query(&mut DiscreteVisitor::<R, Proxy<ID, B>>::new(target))
+            .into_iter()
+            .map(|(value, _)| value.id)
+            .collect()
+    }
+}
@@ -0,0 +1,93 @@
+/// Collision groups of an object, used to reject pairs of objects that can never interact before
+/// the narrow phase.
+///
+/// There are 32 groups. An object is a member of any number of groups, and has a filter with the
+/// groups it can interact with. Two objects can interact if each is a member of a group in the
+/// filter of the other, so for example debris can be made to collide with the level, but not
+/// with other debris.
+///
+/// Give the groups to the broad phase with
+/// [`BroadPhase::compute_filtered_pairs`](trait.BroadPhase.html#method.compute_filtered_pairs).
+///
+/// # Examples
+///
+/// ```
+/// use collision::algorithm::broad_phase::CollisionGroups;
+///
+/// const LEVEL: u32 = 1;
+/// const DEBRIS: u32 = 1 << 1;
+///
+/// let level = CollisionGroups::new(LEVEL, !0);
+/// let debris = CollisionGroups::new(DEBRIS, !DEBRIS);
+/// assert!(level.can_interact(&debris));
+/// assert!(!debris.can_interact(&debris));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CollisionGroups {
+    memberships: u32,
+    filter: u32,
+}
+
+impl Default for CollisionGroups {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl CollisionGroups {
+    /// Create new collision groups, from a bitmask of the groups the object is a member of, and a
+    /// bitmask of the groups it can interact with
+    pub fn new(memberships: u32, filter: u32) -> Self {
+        Self {
+            memberships,
+            filter,
+        }
+    }
+
+    /// Create collision groups that are a member of all groups, and interact with all groups
+    pub fn all() -> Self {
+        Self::new(!0, !0)
+    }
+
+    /// Create collision groups that are not a member of any group, and never interact
+    pub fn none() -> Self {
+        Self::new(0, 0)
+    }
+
+    /// Get the bitmask of the groups the object is a member of
+    pub fn memberships(&self) -> u32 {
+        self.memberships
+    }
+
+    /// Get the bitmask of the groups the object can interact with
+    pub fn filter(&self) -> u32 {
+        self.filter
+    }
+
+    /// Return true if objects with these groups and the given groups can interact
+    pub fn can_interact(&self, other: &CollisionGroups) -> bool {
+        self.memberships & other.filter != 0 && other.memberships & self.filter != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_interact() {
+        let player = CollisionGroups::new(0b001, 0b110);
+        let enemy = CollisionGroups::new(0b010, 0b101);
+        let sensor = CollisionGroups::new(0b100, 0b001);
+        assert!(player.can_interact(&enemy));
+        assert!(enemy.can_interact(&player));
+        assert!(player.can_interact(&sensor));
+        // the sensor does not look for enemies
+        assert!(!enemy.can_interact(&sensor));
+        assert!(!sensor.can_interact(&enemy));
+        assert!(!player.can_interact(&player));
+
+        assert!(CollisionGroups::default().can_interact(&player));
+        assert!(!CollisionGroups::none().can_interact(&CollisionGroups::all()));
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::{BroadPhase, FindPairs};
+use crate::prelude::*;
+
+/// Object stored in the list
+#[derive(Debug, Clone)]
+struct Entry<ID, B> {
+    id: ID,
+    bound: B,
+}
+
+impl<ID, B: Bound> HasBound for Entry<ID, B> {
+    type Bound = B;
+
+    fn bound(&self) -> &B {
+        &self.bound
+    }
+}
+
+/// [`BroadPhase`](trait.BroadPhase.html) storing the objects in a list, and using one of the
+/// algorithms working on lists of shapes to find the pairs.
+///
+/// The list is kept between calls, so algorithms that reorder it, like
+/// [`SweepAndPrune`](struct.SweepAndPrune.html), can use the order from the last call. The region
+/// and ray queries test all objects in the list.
+///
+/// # Type parameters:
+///
+/// - `ID`: Id of an object
+/// - `B`: Bounding volume type
+/// - `F`: Algorithm finding the pairs, [`BruteForce`](struct.BruteForce.html),
+///   [`SweepAndPrune`](struct.SweepAndPrune.html) or [`SpatialHash`](struct.SpatialHash.html)
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cgmath;
+/// # extern crate collision;
+/// use cgmath::Point2;
+/// use collision::Aabb2;
+/// use collision::algorithm::broad_phase::{BroadPhase, ListBroadPhase, SweepAndPrune2};
+///
+/// # fn main() {
+/// let mut broad_phase = ListBroadPhase::new(SweepAndPrune2::new());
+/// broad_phase.insert("box", Aabb2::new(Point2::new(0., 0.), Point2::new(2., 2.)));
+/// broad_phase.insert("ball", Aabb2::new(Point2::new(1., 1.), Point2::new(3., 3.)));
+/// assert_eq!(vec![("box", "ball")], broad_phase.compute_pairs());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ListBroadPhase<ID, B, F> {
+    entries: Vec<Entry<ID, B>>,
+    positions: HashMap<ID, usize>,
+    find_pairs: F,
+}
+
+impl<ID, B, F> ListBroadPhase<ID, B, F>
+where
+    ID: Copy + Eq + Hash,
+{
+    /// Create a new empty list, using the given algorithm to find the pairs
+    pub fn new(find_pairs: F) -> Self {
+        Self {
+            entries: Vec::default(),
+            positions: HashMap::default(),
+            find_pairs,
+        }
+    }
+}
+
+impl<ID, B, F> BroadPhase<ID> for ListBroadPhase<ID, B, F>
+where
+    ID: Copy + Eq + Hash,
+    B: Bound,
+    F: FindPairs<B>,
+{
+    type Bound = B;
+
+    fn insert(&mut self, id: ID, bound: B) {
+        match self.positions.get(&id) {
+            Some(&position) => self.entries[position].bound = bound,
+            None => {
+                self.positions.insert(id, self.entries.len());
+                self.entries.push(Entry { id, bound });
+            }
+        }
+    }
+
+    fn remove(&mut self, id: ID) -> bool {
+        match self.positions.remove(&id) {
+            Some(position) => {
+                self.entries.swap_remove(position);
+                if let Some(moved) = self.entries.get(position) {
+                    self.positions.insert(moved.id, position);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn update(&mut self, id: ID, bound: B) -> bool {
+        match self.positions.get(&id) {
+            Some(&position) => {
+                self.entries[position].bound = bound;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn compute_filtered_pairs<P>(&mut self, mut filter: P) -> Vec<(ID, ID)>
+    where
+        P: FnMut(&ID, &ID) -> bool,
+    {
+        let pairs = self
+            .find_pairs
+            .find_pairs_by(&mut self.entries, |left, right| filter(&left.id, &right.id));
+        // the algorithm may have reordered the list
+        for (position, entry) in self.entries.iter().enumerate() {
+            self.positions.insert(entry.id, position);
+        }
+        pairs
+            .into_iter()
+            .map(|(left, right)| (self.entries[left].id, self.entries[right].id))
+            .collect()
+    }
+
+    fn query<R>(&self, target: &R) -> Vec<ID>
+    where
+        B: Discrete<R>,
+    {
+        self.entries
+            .iter()
+            .filter(|entry| entry.bound.intersects(target))
+            .map(|entry| entry.id)
+            .collect()
+    }
+}
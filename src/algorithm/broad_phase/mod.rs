@@ -1,9 +1,23 @@
 //! Broad phase collision detection algorithms
 
 pub use self::brute_force::BruteForce;
-pub use self::dbvt::DbvtBroadPhase;
+pub use self::dbvt::{DbvtBroadPhase, TreeBroadPhase};
+pub use self::groups::CollisionGroups;
+pub use self::list::ListBroadPhase;
+pub use self::orthtree::OrthtreeBroadPhase;
+pub use self::pairs::{PairEvent, PairTracker};
+pub use self::spatial_hash::SpatialHash;
+pub use self::swept::SweptBroadPhase;
 pub use self::sweep_prune::{SweepAndPrune, SweepAndPrune2, SweepAndPrune3, Variance};
+pub use self::traits::{BroadPhase, FindPairs};
 
 mod brute_force;
 mod sweep_prune;
 mod dbvt;
+mod spatial_hash;
+mod pairs;
+mod traits;
+mod list;
+mod swept;
+mod orthtree;
+mod groups;
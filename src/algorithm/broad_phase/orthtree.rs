@@ -0,0 +1,140 @@
+//! [`Orthtree`](../../orthtree/struct.Orthtree.html) accelerated broad phase collision detection
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use cgmath::BaseFloat;
+
+use super::BroadPhase;
+use super::dbvt::Proxy;
+use crate::dbvt::DiscreteVisitor;
+use crate::orthtree::Orthtree;
+use crate::prelude::*;
+
+/// [`BroadPhase`](trait.BroadPhase.html) storing the objects in a
+/// [Quadtree or Octree](../../orthtree/struct.Orthtree.html).
+///
+/// The tree covers a fixed region, given on creation. Objects outside of it are still found, but
+/// are kept in the root, and tested against everything.
+///
+/// # Type parameters:
+///
+/// - `ID`: Id of an object
+/// - `B`: Bounding box type, [`Aabb2`](../../struct.Aabb2.html) or
+///   [`Aabb3`](../../struct.Aabb3.html)
+pub struct OrthtreeBroadPhase<ID, B>
+where
+    ID: Clone,
+    B: Aabb + Bound + Clone,
+{
+    tree: Orthtree<B, Proxy<ID, B>>,
+    indices: HashMap<ID, usize>,
+}
+
+impl<S, ID, B> OrthtreeBroadPhase<ID, B>
+where
+    S: BaseFloat,
+    ID: Copy + Eq + Hash,
+    B: Aabb<Scalar = S> + Bound + Copy + Contains<B>,
+{
+    /// Create a new empty tree covering the given region, see
+    /// [`Orthtree::new`](../../orthtree/struct.Orthtree.html#method.new)
+    pub fn new(bound: B) -> Self {
+        Self {
+            tree: Orthtree::new(bound),
+            indices: HashMap::default(),
+        }
+    }
+
+    /// Use the given maximum depth, see
+    /// [`Orthtree::with_max_depth`](../../orthtree/struct.Orthtree.html#method.with_max_depth)
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.tree = self.tree.with_max_depth(max_depth);
+        self
+    }
+
+    /// Use the given leaf capacity, see
+    /// [`Orthtree::with_leaf_capacity`](../../orthtree/struct.Orthtree.html#method.with_leaf_capacity)
+    pub fn with_leaf_capacity(mut self, leaf_capacity: usize) -> Self {
+        self.tree = self.tree.with_leaf_capacity(leaf_capacity);
+        self
+    }
+
+    /// Make the tree loose, see
+    /// [`Orthtree::with_looseness`](../../orthtree/struct.Orthtree.html#method.with_looseness)
+    pub fn with_looseness(mut self, looseness: S) -> Self {
+        self.tree = self.tree.with_looseness(looseness);
+        self
+    }
+}
+
+impl<S, ID, B> BroadPhase<ID> for OrthtreeBroadPhase<ID, B>
+where
+    S: BaseFloat,
+    ID: Copy + Eq + Hash,
+    B: Aabb<Scalar = S> + Bound + Copy + Contains<B> + Discrete<B>,
+{
+    type Bound = B;
+
+    fn insert(&mut self, id: ID, bound: B) {
+        if !self.update(id, bound) {
+            let index = self.tree.insert(Proxy { id, bound });
+            self.indices.insert(id, index);
+        }
+    }
+
+    fn remove(&mut self, id: ID) -> bool {
+        match self.indices.remove(&id) {
+            Some(index) => {
+                self.tree.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn update(&mut self, id: ID, bound: B) -> bool {
+        match self.indices.get(&id) {
+            Some(&index) => {
+                self.tree.update(index, Proxy { id, bound });
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn compute_filtered_pairs<F>(&mut self, mut filter: F) -> Vec<(ID, ID)>
+    where
+        F: FnMut(&ID, &ID) -> bool,
+    {
+        let mut pairs = Vec::default();
+        for (index, value) in self.tree.iter() {
+            let hits = self
+                .tree
+                .query_for_indices(&mut DiscreteVisitor::<B, Proxy<ID, B>>::new(&value.bound));
+            // each pair is found from both sides, only keep it from the lower index
+            for (hit, _) in hits.into_iter().filter(|&(hit, _)| hit > index) {
+                let other = self.tree.get(hit).unwrap().id;
+                if filter(&value.id, &other) {
+                    pairs.push((value.id, other));
+                }
+            }
+        }
+        pairs
+    }
+
+    fn query<R>(&self, target: &R) -> Vec<ID>
+    where
+        B: Discrete<R>,
+    {
+        self.tree
+            .query(&mut DiscreteVisitor::<R, Proxy<ID, B>>::new(target))
+            .into_iter()
+            .map(|(value, _)| value.id)
+            .collect()
+    }
+}
@@ -0,0 +1,213 @@
+use std::cmp::Ordering;
+
+/// Change in a potentially colliding pair, between two calls to
+/// [`PairTracker::update`](struct.PairTracker.html#method.update)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PairEvent<K> {
+    /// The pair started overlapping
+    Added(K, K),
+
+    /// The pair was overlapping in the last update too
+    Persisting(K, K),
+
+    /// The pair stopped overlapping
+    Removed(K, K),
+}
+
+impl<K: Copy> PairEvent<K> {
+    /// Get the pair the event is for
+    pub fn pair(&self) -> (K, K) {
+        match *self {
+            PairEvent::Added(left, right)
+            | PairEvent::Persisting(left, right)
+            | PairEvent::Removed(left, right) => (left, right),
+        }
+    }
+}
+
+/// Tracks the potentially colliding pairs found by a broad phase from one frame to the next, and
+/// reports when pairs start and stop overlapping.
+///
+/// The broad phase algorithms return a flat list of pairs each frame. Feeding that list into the
+/// tracker gives enter and exit events, for triggers and for starting and ending contacts.
+///
+/// The pairs are identified by keys chosen by the user. The indices returned by the broad phase
+/// algorithms can be used directly if the shapes list keeps its order between frames, otherwise
+/// they should be mapped to stable ids first. This is needed for
+/// [`SweepAndPrune`](struct.SweepAndPrune.html), which sorts the shapes list.
+///
+/// # Type parameters:
+///
+/// - `K`: Key identifying a shape
+#[derive(Debug, Clone)]
+pub struct PairTracker<K> {
+    pairs: Vec<(K, K)>,
+    next: Vec<(K, K)>,
+}
+
+impl<K> Default for PairTracker<K> {
+    fn default() -> Self {
+        Self {
+            pairs: Vec::default(),
+            next: Vec::default(),
+        }
+    }
+}
+
+impl<K> PairTracker<K>
+where
+    K: Ord + Copy,
+{
+    /// Create a new tracker, without any pairs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the pairs given in the last update, sorted, with the lowest key first in each pair
+    pub fn pairs(&self) -> &[(K, K)] {
+        &self.pairs
+    }
+
+    /// Remove all pairs, without reporting them as removed
+    pub fn clear(&mut self) {
+        self.pairs.clear();
+    }
+
+    /// Update the tracker with the pairs found this frame.
+    ///
+    /// ## Parameters
+    ///
+    /// - `pairs`: All potentially colliding pairs this frame. The order of the pairs, and of the
+    ///   keys in each pair, does not matter, and duplicates are ignored.
+    ///
+    /// ## Returns
+    ///
+    /// Returns an event for every pair in this frame or the last, sorted by the pairs. The lowest
+    /// key is always first in the pair.
+    pub fn update<I>(&mut self, pairs: I) -> Vec<PairEvent<K>>
+    where
+        I: IntoIterator<Item = (K, K)>,
+    {
+        self.next.clear();
+        self.next.extend(pairs.into_iter().map(|(left, right)| {
+            if right < left {
+                (right, left)
+            } else {
+                (left, right)
+            }
+        }));
+        self.next.sort_unstable();
+        self.next.dedup();
+
+        let mut events = Vec::with_capacity(self.next.len());
+        let (mut old, mut new) = (0, 0);
+        loop {
+            let event = match (self.pairs.get(old), self.next.get(new)) {
+                (Some(&o), Some(&n)) => match o.cmp(&n) {
+                    Ordering::Less => {
+                        old += 1;
+                        PairEvent::Removed(o.0, o.1)
+                    }
+                    Ordering::Greater => {
+                        new += 1;
+                        PairEvent::Added(n.0, n.1)
+                    }
+                    Ordering::Equal => {
+                        old += 1;
+                        new += 1;
+                        PairEvent::Persisting(n.0, n.1)
+                    }
+                },
+                (Some(&o), None) => {
+                    old += 1;
+                    PairEvent::Removed(o.0, o.1)
+                }
+                (None, Some(&n)) => {
+                    new += 1;
+                    PairEvent::Added(n.0, n.1)
+                }
+                (None, None) => break,
+            };
+            events.push(event);
+        }
+
+        std::mem::swap(&mut self.pairs, &mut self.next);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Point2;
+
+    use super::*;
+    use crate::algorithm::broad_phase::BruteForce;
+    use crate::prelude::*;
+    use crate::Aabb2;
+
+    struct Shape {
+        bound: Aabb2<f32>,
+    }
+
+    impl HasBound for Shape {
+        type Bound = Aabb2<f32>;
+
+        fn bound(&self) -> &Aabb2<f32> {
+            &self.bound
+        }
+    }
+
+    #[test]
+    fn test_pair_events() {
+        let mut tracker = PairTracker::new();
+        assert_eq!(
+            vec![PairEvent::Added(1, 2), PairEvent::Added(1, 3)],
+            tracker.update(vec![(3, 1), (1, 2), (2, 1)])
+        );
+        assert_eq!(&[(1, 2), (1, 3)], tracker.pairs());
+        assert_eq!(
+            vec![
+                PairEvent::Persisting(1, 2),
+                PairEvent::Removed(1, 3),
+                PairEvent::Added(2, 3),
+            ],
+            tracker.update(vec![(2, 3), (1, 2)])
+        );
+        assert_eq!(
+            vec![PairEvent::Removed(1, 2), PairEvent::Removed(2, 3)],
+            tracker.update(None)
+        );
+        assert!(tracker.update(None).is_empty());
+
+        tracker.update(vec![(4, 5)]);
+        tracker.clear();
+        assert_eq!(vec![PairEvent::Added(4, 5)], tracker.update(vec![(4, 5)]));
+        assert_eq!((4, 5), PairEvent::Removed(4, 5).pair());
+    }
+
+    #[test]
+    fn test_pair_events_broad_phase() {
+        let mut shapes = vec![
+            Shape {
+                bound: Aabb2::new(Point2::new(0., 0.), Point2::new(1., 1.)),
+            },
+            Shape {
+                bound: Aabb2::new(Point2::new(0.5, 0.5), Point2::new(1.5, 1.5)),
+            },
+            Shape {
+                bound: Aabb2::new(Point2::new(3., 3.), Point2::new(4., 4.)),
+            },
+        ];
+        let mut tracker = PairTracker::new();
+        assert_eq!(
+            vec![PairEvent::Added(0, 1)],
+            tracker.update(BruteForce.find_collider_pairs(&shapes))
+        );
+
+        shapes[1].bound = Aabb2::new(Point2::new(2.5, 2.5), Point2::new(3.5, 3.5));
+        assert_eq!(
+            vec![PairEvent::Removed(0, 1), PairEvent::Added(1, 2)],
+            tracker.update(BruteForce.find_collider_pairs(&shapes))
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use cgmath::BaseFloat;
+use cgmath::prelude::*;
+use cgmath::num_traits::NumCast;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::prelude::*;
+
+/// Integer coordinates of a grid cell, unused dimensions are zero
+type Cell = [i64; 3];
+
+/// Broad phase collision detection on a uniform grid.
+///
+/// Every shape is added to all grid cells its bounding volume touches, and only shapes sharing
+/// a cell are tested against each other. The cells are stored in a hash map, so the grid has no
+/// fixed extent.
+///
+/// Works best when the shapes are of similar size, and the cell size is around the size of the
+/// largest shapes. Very large shapes touch many cells, which makes the algorithm slow, so they
+/// are better kept in a separate broad phase. Each cell is independent of the others, which
+/// makes the pair search simple to split over several threads, see
+/// [`par_find_collider_pairs`](#method.par_find_collider_pairs) with the `parallel` feature.
+///
+/// The cells are kept between calls, so for shapes that move a little between frames, most of
+/// the cell storage is reused.
+#[derive(Debug)]
+pub struct SpatialHash<S> {
+    cell_size: S,
+    cells: HashMap<Cell, Vec<usize>>,
+    ranges: Vec<(Cell, Cell)>,
+}
+
+impl<S> SpatialHash<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new spatial hash with the given cell size, used along all axes
+    pub fn new(cell_size: S) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            ranges: Vec::default(),
+        }
+    }
+
+    /// Get the cell size
+    pub fn cell_size(&self) -> S {
+        self.cell_size
+    }
+
+    /// Find all potentially colliding pairs of shapes.
+    ///
+    /// ## Parameters
+    ///
+    /// - `shapes`: Shapes to do find potential collisions for. The bounds must be finite.
+    ///
+    /// ## Returns
+    ///
+    /// Returns tuples with indices into the shapes list, of all potentially colliding pairs.
+    /// The first value in the tuple will always be first in the list, and the tuples are sorted.
+    pub fn find_collider_pairs<A>(&mut self, shapes: &[A]) -> Vec<(usize, usize)>
+    where
+        A: HasBound,
+        A::Bound: Discrete<A::Bound>,
+        <A::Bound as Bound>::Point: EuclideanSpace<Scalar = S>,
+    {
+        self.find_collider_pairs_by(shapes, |_, _| true)
+    }
+
+    /// Find all potentially colliding pairs of shapes, that are accepted by the given filter.
+    ///
+    /// The filter is called for the pairs with intersecting bounds, and rejected pairs are never
+    /// added to the result.
+    pub fn find_collider_pairs_by<A, F>(
+        &mut self,
+        shapes: &[A],
+        mut filter: F,
+    ) -> Vec<(usize, usize)>
+    where
+        A: HasBound,
+        A::Bound: Discrete<A::Bound>,
+        <A::Bound as Bound>::Point: EuclideanSpace<Scalar = S>,
+        F: FnMut(&A, &A) -> bool,
+    {
+        self.fill(shapes);
+        let mut pairs = self
+            .cells
+            .iter()
+            .flat_map(|(cell, indices)| cell_pairs(shapes, &self.ranges, cell, indices))
+            .filter(|&(left, right)| filter(&shapes[left], &shapes[right]))
+            .collect::<Vec<_>>();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// Find all potentially colliding pairs of shapes, splitting the cells over several threads.
+    ///
+    /// The shapes are added to the cells the same way as in
+    /// [`find_collider_pairs`](#method.find_collider_pairs), and then the pairs in each cell are
+    /// found in parallel.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the same pairs as [`find_collider_pairs`](#method.find_collider_pairs), sorted, so
+    /// the order does not depend on how the work was split over the threads.
+    #[cfg(feature = "parallel")]
+    pub fn par_find_collider_pairs<A>(&mut self, shapes: &[A]) -> Vec<(usize, usize)>
+    where
+        A: HasBound + Sync,
+        A::Bound: Discrete<A::Bound>,
+        <A::Bound as Bound>::Point: EuclideanSpace<Scalar = S>,
+    {
+        self.fill(shapes);
+        let ranges = &self.ranges;
+        let mut pairs = self
+            .cells
+            .par_iter()
+            .flat_map_iter(|(cell, indices)| cell_pairs(shapes, ranges, cell, indices))
+            .collect::<Vec<_>>();
+        pairs.par_sort_unstable();
+        pairs
+    }
+
+    /// Add all shapes to the cells they touch
+    fn fill<A>(&mut self, shapes: &[A])
+    where
+        A: HasBound,
+        <A::Bound as Bound>::Point: EuclideanSpace<Scalar = S>,
+    {
+        // keep the storage of the cells used in the last call
+        self.cells.retain(|_, indices| {
+            let used = !indices.is_empty();
+            indices.clear();
+            used
+        });
+
+        self.ranges.clear();
+        for (index, shape) in shapes.iter().enumerate() {
+            let range = (
+                self.cell(shape.bound().min_extent()),
+                self.cell(shape.bound().max_extent()),
+            );
+            for cell in cells(range) {
+                self.cells.entry(cell).or_default().push(index);
+            }
+            self.ranges.push(range);
+        }
+    }
+
+    /// Cell containing the given point
+    fn cell<P>(&self, point: P) -> Cell
+    where
+        P: EuclideanSpace<Scalar = S>,
+    {
+        let mut cell = [0; 3];
+        for (i, c) in cell.iter_mut().enumerate().take(P::len()) {
+            *c = NumCast::from((point[i] / self.cell_size).floor()).unwrap();
+        }
+        cell
+    }
+}
+
+/// Pairs of shapes in the given cell with intersecting bounds, that do not share an earlier cell
+fn cell_pairs<'a, A>(
+    shapes: &'a [A],
+    ranges: &'a [(Cell, Cell)],
+    cell: &'a Cell,
+    indices: &'a [usize],
+) -> impl Iterator<Item = (usize, usize)> + 'a
+where
+    A: HasBound,
+    A::Bound: Discrete<A::Bound>,
+{
+    indices
+        .iter()
+        .enumerate()
+        .flat_map(move |(i, &left_index)| {
+            indices[(i + 1)..]
+                .iter()
+                .filter(move |&&right_index| {
+                    // shapes sharing several cells are only tested in the first shared cell
+                    let (left_min, _) = ranges[left_index];
+                    let (right_min, _) = ranges[right_index];
+                    let first = [
+                        left_min[0].max(right_min[0]),
+                        left_min[1].max(right_min[1]),
+                        left_min[2].max(right_min[2]),
+                    ];
+                    first == *cell
+                        && shapes[left_index]
+                            .bound()
+                            .intersects(shapes[right_index].bound())
+                })
+                .map(move |&right_index| (left_index, right_index))
+        })
+}
+
+/// All cells in the given range, inclusive
+fn cells((min, max): (Cell, Cell)) -> impl Iterator<Item = Cell> {
+    (min[0]..=max[0]).flat_map(move |x| {
+        (min[1]..=max[1]).flat_map(move |y| (min[2]..=max[2]).map(move |z| [x, y, z]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Point2, Point3};
+
+    use super::*;
+    use crate::algorithm::broad_phase::BruteForce;
+    use crate::{Aabb2, Aabb3};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Shape<B> {
+        bound: B,
+    }
+
+    impl<B: Bound> HasBound for Shape<B> {
+        type Bound = B;
+
+        fn bound(&self) -> &B {
+            &self.bound
+        }
+    }
+
+    #[test]
+    fn test_spatial_hash_2d() {
+        let shapes = vec![
+            bound2(0., 0., 1., 1.),
+            bound2(0.5, 0.5, 1.5, 1.5),
+            bound2(5., 5., 6., 6.),
+            // spans several cells, shared with the first two shapes
+            bound2(-3., 0.9, 3., 1.2),
+            bound2(-1.5, -1.5, -1.2, -1.2),
+        ];
+        let mut hash = SpatialHash::new(1.);
+        assert_eq!(
+            vec![(0, 1), (0, 3), (1, 3)],
+            hash.find_collider_pairs(&shapes)
+        );
+
+        // reuses the cells from the last call
+        assert_eq!(
+            vec![(0, 1), (0, 3), (1, 3)],
+            hash.find_collider_pairs(&shapes)
+        );
+        assert!(hash.find_collider_pairs(&shapes[2..]).is_empty());
+    }
+
+    #[test]
+    fn test_spatial_hash_3d() {
+        let mut shapes = Vec::new();
+        for i in 0..6 {
+            for j in 0..6 {
+                let (x, y, z) = (i as f32 * 0.9, j as f32 * 1.1, (i + j) as f32 * 0.3);
+                shapes.push(Shape {
+                    bound: Aabb3::new(Point3::new(x, y, z), Point3::new(x + 1., y + 1., z + 1.)),
+                });
+            }
+        }
+        let mut hash = SpatialHash::new(0.7);
+        assert_eq!(
+            BruteForce.find_collider_pairs(&shapes),
+            hash.find_collider_pairs(&shapes)
+        );
+    }
+
+    fn bound2(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Shape<Aabb2<f32>> {
+        Shape {
+            bound: Aabb2::new(Point2::new(min_x, min_y), Point2::new(max_x, max_y)),
+        }
+    }
+}
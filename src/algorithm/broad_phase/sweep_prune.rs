@@ -3,6 +3,8 @@ pub use self::variance::Variance;
 use std::cmp::Ordering;
 
 use cgmath::num_traits::NumCast;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 use self::variance::{Variance2, Variance3};
 use crate::prelude::*;
@@ -24,6 +26,15 @@ pub type SweepAndPrune3<S, B> = SweepAndPrune<Variance3<S, B>>;
 /// Any shape pairs found by the base algorithm, will then do a bounding box intersection test,
 /// before adding to the resulting pairs list.
 ///
+/// The shapes list is sorted in place. When the same list is passed in every frame, it is still
+/// almost sorted from the last frame, and an insertion sort is used instead of a full sort, which
+/// is close to linear for small motions. A full sort is only done when the sweep axis changes.
+/// The sweep axis for the next frame is the axis with the largest variance of the bound centers.
+///
+/// With the `parallel` feature,
+/// [`par_find_collider_pairs`](#method.par_find_collider_pairs) splits the sweep over several
+/// threads.
+///
 /// # Type parameters:
 ///
 /// - `V`: Variance type used for computing what axis to use on the next iteration.
@@ -32,6 +43,7 @@ pub type SweepAndPrune3<S, B> = SweepAndPrune<Variance3<S, B>>;
 ///        implementing [Variance](trait.Variance.html).
 pub struct SweepAndPrune<V> {
     sweep_axis: usize,
+    sorted_axis: Option<usize>,
     variance: V,
 }
 
@@ -48,6 +60,7 @@ where
     pub fn with_sweep_axis(sweep_axis: usize) -> Self {
         Self {
             sweep_axis,
+            sorted_axis: None,
             variance: V::new(),
         }
     }
@@ -71,29 +84,39 @@ where
     /// ## Side effects:
     ///
     /// The shapes list might have been resorted. The indices in the return values will be for the
-    /// sorted list.
+    /// sorted list. Keep the list between calls, so the next sort can use the order from this
+    /// call.
     pub fn find_collider_pairs<A>(&mut self, shapes: &mut [A]) -> Vec<(usize, usize)>
     where
         A: HasBound,
         A::Bound: Bound + Discrete<A::Bound>,
         V: Variance<Bound = A::Bound>,
+    {
+        self.find_collider_pairs_by(shapes, |_, _| true)
+    }
+
+    /// Find all potentially colliding pairs of shapes, that are accepted by the given filter.
+    ///
+    /// The filter is called for the pairs with intersecting bounds, and rejected pairs are never
+    /// added to the result. The shapes list is sorted the same way as in
+    /// [`find_collider_pairs`](#method.find_collider_pairs).
+    pub fn find_collider_pairs_by<A, F>(
+        &mut self,
+        shapes: &mut [A],
+        mut filter: F,
+    ) -> Vec<(usize, usize)>
+    where
+        A: HasBound,
+        A::Bound: Bound + Discrete<A::Bound>,
+        V: Variance<Bound = A::Bound>,
+        F: FnMut(&A, &A) -> bool,
     {
         let mut pairs = Vec::default();
         if shapes.len() <= 1 {
             return pairs;
         }
 
-        shapes.sort_by(|a, b| {
-            let cmp_min = a.bound().min_extent()[self.sweep_axis]
-                .partial_cmp(&b.bound().min_extent()[self.sweep_axis]);
-            match cmp_min {
-                Some(Ordering::Equal) => a.bound().max_extent()[self.sweep_axis]
-                    .partial_cmp(&b.bound().max_extent()[self.sweep_axis])
-                    .unwrap_or(Ordering::Equal),
-                None => Ordering::Equal,
-                Some(order) => order,
-            }
-        });
+        self.sort(shapes);
 
         self.variance.clear();
         self.variance.add_bound(shapes[0].bound());
@@ -111,9 +134,10 @@ where
             });
 
             // all shapes in the active list are potential hits, do a real bound intersection test
-            // for those, and add to pairs if the bounds intersect.
+            // for those, and add to pairs if the bounds intersect and the filter accepts them.
             for active_index in &active {
-                if shapes[*active_index].bound().intersects(shape.bound()) {
+                let active_shape = &shapes[*active_index];
+                if active_shape.bound().intersects(shape.bound()) && filter(active_shape, shape) {
                     pairs.push((*active_index, shape_index));
                 }
             }
@@ -132,6 +156,101 @@ where
 
         pairs
     }
+
+    /// Find all potentially colliding pairs of shapes, splitting the sweep over several threads.
+    ///
+    /// The shapes are sorted in the same way as in
+    /// [`find_collider_pairs`](#method.find_collider_pairs), and then each shape is tested
+    /// against the shapes after it in the sorted list, up to the end of its extent along the
+    /// sweep axis, with the shapes split over the threads.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the same pairs as [`find_collider_pairs`](#method.find_collider_pairs), sorted, so
+    /// the order does not depend on how the work was split over the threads.
+    #[cfg(feature = "parallel")]
+    pub fn par_find_collider_pairs<A>(&mut self, shapes: &mut [A]) -> Vec<(usize, usize)>
+    where
+        A: HasBound + Sync,
+        A::Bound: Bound + Discrete<A::Bound>,
+        V: Variance<Bound = A::Bound>,
+    {
+        if shapes.len() <= 1 {
+            return Vec::default();
+        }
+
+        self.sort(shapes);
+
+        self.variance.clear();
+        for shape in shapes.iter() {
+            self.variance.add_bound(shape.bound());
+        }
+
+        let sweep_axis = self.sweep_axis;
+        let shapes = &*shapes;
+        let pairs = (0..shapes.len())
+            .into_par_iter()
+            .flat_map_iter(|left_index| {
+                let left = shapes[left_index].bound();
+                let end = left.max_extent()[sweep_axis];
+                shapes[(left_index + 1)..]
+                    .iter()
+                    .enumerate()
+                    .take_while(move |(_, right)| right.bound().min_extent()[sweep_axis] <= end)
+                    .filter(move |(_, right)| left.intersects(right.bound()))
+                    .map(move |(index, _)| (left_index, left_index + 1 + index))
+            })
+            .collect();
+
+        // compute sweep axis for the next iteration
+        let (axis, _) = self.variance
+            .compute_axis(NumCast::from(shapes.len()).unwrap());
+        self.sweep_axis = axis;
+
+        pairs
+    }
+
+    /// Sort the shapes along the sweep axis, using an insertion sort if they were sorted along
+    /// the same axis in the last call
+    fn sort<A>(&mut self, shapes: &mut [A])
+    where
+        A: HasBound,
+        A::Bound: Bound,
+    {
+        let sweep_axis = self.sweep_axis;
+        let compare = |a: &A, b: &A| {
+            let cmp_min =
+                a.bound().min_extent()[sweep_axis].partial_cmp(&b.bound().min_extent()[sweep_axis]);
+            match cmp_min {
+                Some(Ordering::Equal) => a.bound().max_extent()[sweep_axis]
+                    .partial_cmp(&b.bound().max_extent()[sweep_axis])
+                    .unwrap_or(Ordering::Equal),
+                None => Ordering::Equal,
+                Some(order) => order,
+            }
+        };
+        if self.sorted_axis == Some(sweep_axis) {
+            insertion_sort(shapes, compare);
+        } else {
+            shapes.sort_by(compare);
+            self.sorted_axis = Some(sweep_axis);
+        }
+    }
+}
+
+/// Sort the almost sorted list by moving each element back to its place, which is linear in the
+/// number of elements and the number of swaps
+fn insertion_sort<A, F>(list: &mut [A], compare: F)
+where
+    F: Fn(&A, &A) -> Ordering,
+{
+    for i in 1..list.len() {
+        let mut j = i;
+        while j > 0 && compare(&list[j - 1], &list[j]) == Ordering::Greater {
+            list.swap(j - 1, j);
+            j -= 1;
+        }
+    }
 }
 
 mod variance {
@@ -199,8 +318,8 @@ mod variance {
             let max_vec = bound.max_extent().to_vec();
             let sum = min_vec.add_element_wise(max_vec);
             let c = sum / (S::one() + S::one());
-            self.csum.add_element_wise(c);
-            self.csumsq.add_element_wise(c.mul_element_wise(c));
+            self.csum.add_assign_element_wise(c);
+            self.csumsq.add_assign_element_wise(c.mul_element_wise(c));
         }
 
         #[inline]
@@ -254,8 +373,8 @@ mod variance {
             let max_vec = bound.max_extent().to_vec();
             let sum = min_vec.add_element_wise(max_vec);
             let c = sum / (S::one() + S::one());
-            self.csum.add_element_wise(c);
-            self.csumsq.add_element_wise(c.mul_element_wise(c));
+            self.csum.add_assign_element_wise(c);
+            self.csumsq.add_assign_element_wise(c.mul_element_wise(c));
         }
 
         #[inline]
@@ -358,6 +477,44 @@ mod tests {
         assert_eq!((0, 1), potentials[0]);
     }
 
+    #[test]
+    fn incremental_sort() {
+        let mut shapes = vec![
+            coll(1, 0., 0., 2., 2.),
+            coll(2, 3., 0., 5., 2.),
+            coll(3, 6., 0., 8., 2.),
+            coll(4, 9., 0., 11., 2.),
+        ];
+        let mut sweep = SweepAndPrune2::new();
+        assert!(sweep.find_collider_pairs(&mut shapes).is_empty());
+        assert_eq!(0, sweep.get_sweep_axis());
+
+        // move the first shape past the second, and into the third
+        shapes[0].bound = bound(5.5, 0., 7.5, 2.);
+        let potentials = sweep.find_collider_pairs(&mut shapes);
+        let ids: Vec<u32> = shapes.iter().map(|s| s.id).collect();
+        assert_eq!(vec![2, 1, 3, 4], ids);
+        assert_eq!(vec![(1, 2)], potentials);
+    }
+
+    #[test]
+    fn sweep_axis_from_variance() {
+        let mut shapes = vec![
+            coll(1, 0., 0., 1., 1.),
+            coll(2, 0., 5., 1., 6.),
+            coll(3, 0.5, 10., 1.5, 11.),
+            coll(4, 0., 10.5, 1., 11.5),
+        ];
+        let mut sweep = SweepAndPrune2::new();
+        assert_eq!(vec![(2, 3)], sweep.find_collider_pairs(&mut shapes));
+        assert_eq!(1, sweep.get_sweep_axis());
+
+        // sorted along the new axis
+        assert_eq!(vec![(2, 3)], sweep.find_collider_pairs(&mut shapes));
+        let ids: Vec<u32> = shapes.iter().map(|s| s.id).collect();
+        assert_eq!(vec![1, 2, 3, 4], ids);
+    }
+
     // util
     fn coll(id: u32, min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> BroadCollisionInfo2 {
         BroadCollisionInfo2::new(id, bound(min_x, min_y, max_x, max_y))
@@ -0,0 +1,180 @@
+use super::FindPairs;
+use crate::prelude::*;
+
+/// Swept bound of a shape, with the index of the shape
+#[derive(Debug, Clone)]
+struct Swept<B> {
+    index: usize,
+    bound: B,
+}
+
+impl<B: Bound> HasBound for Swept<B> {
+    type Bound = B;
+
+    fn bound(&self) -> &B {
+        &self.bound
+    }
+}
+
+/// Broad phase collision detection for continuous collision detection, on the swept bounds of
+/// moving shapes.
+///
+/// Every shape is given the motion it makes this frame, and is tested with the bound covering
+/// both its start and end positions, see [`expanded_by`](../../trait.Aabb.html#method.expanded_by).
+/// Fast shapes that pass through each other during the frame are then found as pairs, and can be
+/// given to a time of impact test in the narrow phase, instead of tunneling through each other
+/// between two frames.
+///
+/// Uses one of the algorithms working on lists of shapes to find the pairs. The list of swept
+/// bounds is kept between calls, so algorithms that reorder it, like
+/// [`SweepAndPrune`](struct.SweepAndPrune.html), can use the order from the last call.
+///
+/// The swept bounds are computed again from the shapes and motions on every call, and are not
+/// stored by id, so this is not a [`BroadPhase`](trait.BroadPhase.html). To find swept pairs with
+/// a `BroadPhase`, update each object with its swept bound instead.
+///
+/// # Type parameters:
+///
+/// - `F`: Algorithm finding the pairs, [`BruteForce`](struct.BruteForce.html),
+///   [`SweepAndPrune`](struct.SweepAndPrune.html) or [`SpatialHash`](struct.SpatialHash.html)
+/// - `B`: Bounding box type
+#[derive(Debug)]
+pub struct SweptBroadPhase<F, B> {
+    find_pairs: F,
+    swept: Vec<Swept<B>>,
+}
+
+impl<F, B> SweptBroadPhase<F, B>
+where
+    F: FindPairs<B>,
+    B: Aabb + Bound,
+{
+    /// Create a new swept broad phase, using the given algorithm to find the pairs
+    pub fn new(find_pairs: F) -> Self {
+        Self {
+            find_pairs,
+            swept: Vec::default(),
+        }
+    }
+
+    /// Find all potentially colliding pairs of moving shapes.
+    ///
+    /// ## Parameters
+    ///
+    /// - `shapes`: Shapes at their start positions
+    /// - `motions`: Motion of each shape during the frame, must have the same length as the
+    ///   shapes list
+    ///
+    /// ## Returns
+    ///
+    /// Returns tuples with indices into the shapes list, of all potentially colliding pairs.
+    /// The first value in the tuple will always be first in the list, and the tuples are sorted.
+    pub fn find_collider_pairs<A>(
+        &mut self,
+        shapes: &[A],
+        motions: &[B::Diff],
+    ) -> Vec<(usize, usize)>
+    where
+        A: HasBound<Bound = B>,
+    {
+        assert_eq!(shapes.len(), motions.len());
+        if self.swept.len() == shapes.len() {
+            for swept in &mut self.swept {
+                swept.bound = shapes[swept.index]
+                    .bound()
+                    .expanded_by(&motions[swept.index]);
+            }
+        } else {
+            self.swept = shapes
+                .iter()
+                .zip(motions)
+                .enumerate()
+                .map(|(index, (shape, motion))| Swept {
+                    index,
+                    bound: shape.bound().expanded_by(motion),
+                })
+                .collect();
+        }
+
+        let mut pairs = self
+            .find_pairs
+            .find_pairs(&mut self.swept)
+            .into_iter()
+            .map(|(left, right)| {
+                let (left, right) = (self.swept[left].index, self.swept[right].index);
+                (left.min(right), left.max(right))
+            })
+            .collect::<Vec<_>>();
+        pairs.sort_unstable();
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Point2, Vector2};
+
+    use super::*;
+    use crate::algorithm::broad_phase::{BruteForce, SweepAndPrune2};
+    use crate::Aabb2;
+
+    #[derive(Debug, Clone)]
+    struct Shape {
+        bound: Aabb2<f32>,
+    }
+
+    impl HasBound for Shape {
+        type Bound = Aabb2<f32>;
+
+        fn bound(&self) -> &Aabb2<f32> {
+            &self.bound
+        }
+    }
+
+    fn shape(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Shape {
+        Shape {
+            bound: Aabb2::new(Point2::new(min_x, min_y), Point2::new(max_x, max_y)),
+        }
+    }
+
+    #[test]
+    fn test_swept_tunneling() {
+        let shapes = vec![
+            // thin wall
+            shape(10., -5., 10.5, 5.),
+            // bullet on one side of the wall, moving past it
+            shape(0., 0., 0.2, 0.2),
+            // slow shape, that does not reach the wall
+            shape(0., 3., 1., 4.),
+        ];
+        let motions = vec![
+            Vector2::new(0., 0.),
+            Vector2::new(20., 0.),
+            Vector2::new(1., 0.),
+        ];
+        assert!(BruteForce.find_collider_pairs(&shapes).is_empty());
+
+        let mut broad_phase = SweptBroadPhase::new(BruteForce);
+        assert_eq!(
+            vec![(0, 1)],
+            broad_phase.find_collider_pairs(&shapes, &motions)
+        );
+
+        // the same shapes, sweeping back
+        let motions = vec![
+            Vector2::new(0., 0.),
+            Vector2::new(-20., 0.),
+            Vector2::new(10., 0.),
+        ];
+        let mut broad_phase = SweptBroadPhase::new(SweepAndPrune2::new());
+        assert_eq!(
+            vec![(0, 2)],
+            broad_phase.find_collider_pairs(&shapes, &motions)
+        );
+        // the list of swept bounds is reused
+        assert_eq!(
+            vec![(0, 2)],
+            broad_phase.find_collider_pairs(&shapes, &motions)
+        );
+    }
+}
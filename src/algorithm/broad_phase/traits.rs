@@ -0,0 +1,159 @@
+//! Common interface of the broad phase structures.
+//!
+//! [`BroadPhase`](trait.BroadPhase.html) is implemented by the structures that keep their objects
+//! between frames, and can add, move and remove them by id:
+//!
+//! - [`ListBroadPhase`](struct.ListBroadPhase.html), over the algorithms working on lists of
+//!   shapes, [`BruteForce`](struct.BruteForce.html), [`SweepAndPrune`](struct.SweepAndPrune.html)
+//!   and [`SpatialHash`](struct.SpatialHash.html), through [`FindPairs`](trait.FindPairs.html)
+//! - [`TreeBroadPhase`](struct.TreeBroadPhase.html), over the
+//!   [DBVT](../../dbvt/struct.DynamicBoundingVolumeTree.html)
+//! - [`OrthtreeBroadPhase`](struct.OrthtreeBroadPhase.html), over a
+//!   [Quadtree or Octree](../../orthtree/struct.Orthtree.html)
+//!
+//! [`SweptBroadPhase`](struct.SweptBroadPhase.html) does not implement it, because the bounds it
+//! tests are computed each frame from the shapes and their motions, instead of being stored for
+//! an id. The same pairs are found by a `BroadPhase` given the swept bounds, see
+//! [`Aabb::expanded_by`](../../trait.Aabb.html#method.expanded_by). The
+//! [`Bvh`](../../bvh/struct.Bvh.html) does not implement it either, because it is built once from
+//! a fixed list of values, and can not add or remove them.
+
+use cgmath::BaseFloat;
+use cgmath::prelude::*;
+
+use super::{BruteForce, SpatialHash, SweepAndPrune, Variance};
+use crate::prelude::*;
+
+/// Broad phase collision detection over a set of bounded objects, identified by ids.
+///
+/// Implemented by all broad phase structures, so they can be swapped without changing the code
+/// using them. The objects are added, moved and removed with their ids, and pairs and query
+/// results are reported with the same ids.
+///
+/// # Type parameters:
+///
+/// - `ID`: Id of an object, usually an index or a handle into the storage of the user
+pub trait BroadPhase<ID> {
+    /// Bounding volume type
+    type Bound;
+
+    /// Add an object with the given id and bound. If the id is already used, the object with
+    /// that id is updated instead.
+    fn insert(&mut self, id: ID, bound: Self::Bound);
+
+    /// Remove the object with the given id, returns false if there is no such object
+    fn remove(&mut self, id: ID) -> bool;
+
+    /// Set a new bound for the object with the given id, returns false if there is no such
+    /// object
+    fn update(&mut self, id: ID, bound: Self::Bound) -> bool;
+
+    /// Get the number of objects
+    fn len(&self) -> usize;
+
+    /// Return true if there are no objects
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Find all pairs of objects with intersecting bounds. Each pair is returned once, in no
+    /// particular order.
+    fn compute_pairs(&mut self) -> Vec<(ID, ID)> {
+        self.compute_filtered_pairs(|_, _| true)
+    }
+
+    /// Find all pairs of objects with intersecting bounds, that are accepted by the given filter.
+    ///
+    /// Used to reject pairs that can never interact, for example by their
+    /// [`CollisionGroups`](struct.CollisionGroups.html), before they reach the narrow phase. The
+    /// filter is applied during the pair search, so rejected pairs are never collected.
+    fn compute_filtered_pairs<F>(&mut self, filter: F) -> Vec<(ID, ID)>
+    where
+        F: FnMut(&ID, &ID) -> bool;
+
+    /// Find all objects with bounds intersecting the given target, anything the bounds can be
+    /// tested against with [`Discrete`](../../trait.Discrete.html)
+    fn query<R>(&self, target: &R) -> Vec<ID>
+    where
+        Self::Bound: Discrete<R>;
+
+    /// Find all objects with bounds intersecting the given region
+    fn query_region(&self, region: &Self::Bound) -> Vec<ID>
+    where
+        Self::Bound: Discrete<Self::Bound>,
+    {
+        self.query(region)
+    }
+
+    /// Find all objects with bounds hit by the given ray
+    fn query_ray<R>(&self, ray: &R) -> Vec<ID>
+    where
+        Self::Bound: Discrete<R>,
+    {
+        self.query(ray)
+    }
+}
+
+/// Broad phase algorithm finding all potentially colliding pairs in a list of shapes.
+///
+/// Used by [`ListBroadPhase`](struct.ListBroadPhase.html) to implement
+/// [`BroadPhase`](trait.BroadPhase.html) for the algorithms working on lists of shapes.
+pub trait FindPairs<B> {
+    /// Find all potentially colliding pairs of shapes, as indices into the shapes list. The
+    /// shapes list may be reordered.
+    fn find_pairs<A>(&mut self, shapes: &mut [A]) -> Vec<(usize, usize)>
+    where
+        A: HasBound<Bound = B>,
+    {
+        self.find_pairs_by(shapes, |_, _| true)
+    }
+
+    /// Find all potentially colliding pairs of shapes that are accepted by the given filter, as
+    /// indices into the shapes list. The shapes list may be reordered.
+    fn find_pairs_by<A, F>(&mut self, shapes: &mut [A], filter: F) -> Vec<(usize, usize)>
+    where
+        A: HasBound<Bound = B>,
+        F: FnMut(&A, &A) -> bool;
+}
+
+impl<B> FindPairs<B> for BruteForce
+where
+    B: Bound + Discrete<B>,
+{
+    fn find_pairs_by<A, F>(&mut self, shapes: &mut [A], filter: F) -> Vec<(usize, usize)>
+    where
+        A: HasBound<Bound = B>,
+        F: FnMut(&A, &A) -> bool,
+    {
+        self.find_collider_pairs_by(shapes, filter)
+    }
+}
+
+impl<B, V> FindPairs<B> for SweepAndPrune<V>
+where
+    B: Bound + Discrete<B>,
+    V: Variance<Bound = B>,
+{
+    fn find_pairs_by<A, F>(&mut self, shapes: &mut [A], filter: F) -> Vec<(usize, usize)>
+    where
+        A: HasBound<Bound = B>,
+        F: FnMut(&A, &A) -> bool,
+    {
+        self.find_collider_pairs_by(shapes, filter)
+    }
+}
+
+impl<S, B> FindPairs<B> for SpatialHash<S>
+where
+    S: BaseFloat,
+    B: Bound + Discrete<B>,
+    B::Point: EuclideanSpace<Scalar = S>,
+{
+    fn find_pairs_by<A, F>(&mut self, shapes: &mut [A], filter: F) -> Vec<(usize, usize)>
+    where
+        A: HasBound<Bound = B>,
+        F: FnMut(&A, &A) -> bool,
+    {
+        self.find_collider_pairs_by(shapes, filter)
+    }
+}
@@ -0,0 +1,7 @@
+//! Convex hull computation
+
+pub use self::monotone_chain::monotone_chain;
+pub use self::quickhull3::quickhull3;
+
+mod monotone_chain;
+mod quickhull3;
@@ -0,0 +1,156 @@
+use std::cmp::Ordering;
+
+use cgmath::{BaseFloat, Point2};
+
+use crate::primitive::ConvexPolygon;
+
+/// Compute the 2D convex hull of the given points, using Andrew's monotone chain algorithm.
+///
+/// Runs in `O(n log n)`. Duplicate and collinear points are removed from the hull.
+///
+/// ## Parameters
+///
+/// - `points`: Points to compute the hull for, in any order
+///
+/// ## Returns
+///
+/// Returns a convex polygon with the hull vertices in CCW order, or `None` if the points do not
+/// span an area (less than 3 unique points, or all points collinear).
+pub fn monotone_chain<S>(points: &[Point2<S>]) -> Option<ConvexPolygon<S>>
+where
+    S: BaseFloat,
+{
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+    });
+    points.dedup();
+    if points.len() < 3 {
+        return None;
+    }
+
+    let mut hull: Vec<Point2<S>> = Vec::with_capacity(points.len() + 1);
+
+    // lower hull
+    for p in &points {
+        while hull.len() >= 2 && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= S::zero()
+        {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+
+    // upper hull
+    let lower_len = hull.len() + 1;
+    for p in points.iter().rev().skip(1) {
+        while hull.len() >= lower_len
+            && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= S::zero()
+        {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+
+    // last point is the same as the first
+    hull.pop();
+
+    if hull.len() < 3 {
+        None
+    } else {
+        Some(ConvexPolygon::new(hull))
+    }
+}
+
+/// Z component of the cross product `(a - o) x (b - o)`, positive if `o -> a -> b` is a CCW turn
+#[inline]
+fn cross<S>(o: &Point2<S>, a: &Point2<S>, b: &Point2<S>) -> S
+where
+    S: BaseFloat,
+{
+    (a - o).perp_dot(b - o)
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Point2;
+
+    use super::*;
+
+    #[test]
+    fn test_hull_square_with_interior() {
+        let points = vec![
+            Point2::new(0., 0.),
+            Point2::new(1., 1.),
+            Point2::new(2., 2.),
+            Point2::new(2., 0.),
+            Point2::new(0.5, 1.5),
+            Point2::new(0., 2.),
+        ];
+        let hull = monotone_chain(&points).unwrap();
+        assert_eq!(
+            vec![
+                Point2::new(0., 0.),
+                Point2::new(2., 0.),
+                Point2::new(2., 2.),
+                Point2::new(0., 2.),
+            ],
+            hull.vertices()
+        );
+    }
+
+    #[test]
+    fn test_hull_removes_collinear_and_duplicates() {
+        let points = vec![
+            Point2::new(0., 0.),
+            Point2::new(1., 0.),
+            Point2::new(2., 0.),
+            Point2::new(2., 0.),
+            Point2::new(1., 1.),
+            Point2::new(0., 0.),
+        ];
+        let hull = monotone_chain(&points).unwrap();
+        assert_eq!(
+            vec![
+                Point2::new(0., 0.),
+                Point2::new(2., 0.),
+                Point2::new(1., 1.),
+            ],
+            hull.vertices()
+        );
+    }
+
+    #[test]
+    fn test_hull_is_ccw() {
+        let points = vec![
+            Point2::new(3., 1.),
+            Point2::new(-2., 4.),
+            Point2::new(0., -3.),
+            Point2::new(1., 1.),
+            Point2::new(-4., -1.),
+            Point2::new(2., 5.),
+        ];
+        let hull = monotone_chain(&points).unwrap();
+        let n = hull.vertices().len();
+        assert_eq!(5, n);
+        for i in 0..n {
+            let o = hull.vertices()[i];
+            let a = hull.vertices()[(i + 1) % n];
+            let b = hull.vertices()[(i + 2) % n];
+            assert!(cross(&o, &a, &b) > 0.);
+        }
+    }
+
+    #[test]
+    fn test_hull_degenerate() {
+        assert!(monotone_chain::<f32>(&[]).is_none());
+        assert!(monotone_chain(&[Point2::new(0., 0.), Point2::new(1., 1.)]).is_none());
+        let collinear = [
+            Point2::new(0., 0.),
+            Point2::new(1., 1.),
+            Point2::new(2., 2.),
+        ];
+        assert!(monotone_chain(&collinear).is_none());
+    }
+}
@@ -0,0 +1,385 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::primitive::ConvexPolyhedron;
+
+/// Triangular face of the hull under construction
+#[derive(Debug)]
+struct HullFace<S> {
+    vertices: [usize; 3],
+    normal: Vector3<S>,
+    offset: S,
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl<S> HullFace<S>
+where
+    S: BaseFloat,
+{
+    /// Create a face with the given vertices in CCW order, seen from outside the hull
+    fn new(points: &[Point3<S>], vertices: [usize; 3]) -> Self {
+        let a = points[vertices[0]];
+        let normal = (points[vertices[1]] - a)
+            .cross(points[vertices[2]] - a)
+            .normalize();
+        Self {
+            vertices,
+            normal,
+            offset: normal.dot(a.to_vec()),
+            outside: Vec::default(),
+            alive: true,
+        }
+    }
+
+    /// Signed distance from the face plane, positive on the outside of the hull
+    fn distance(&self, point: &Point3<S>) -> S {
+        self.normal.dot(point.to_vec()) - self.offset
+    }
+
+    /// Directed edges of the face, in CCW order
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+/// Compute the 3D convex hull of the given points, using the Quickhull algorithm.
+///
+/// Runs in `O(n log n)` on average, and `O(n^2)` in the worst case, when most points are on the
+/// hull. Each new hull point only visits the faces it can see and their neighbours, found through
+/// the face adjacency. Duplicate and interior points are not part of the hull, and
+/// faces are triangulated, so coplanar hull faces are split into several triangles. The resulting
+/// polyhedron has full face and half edge information.
+///
+/// ## Parameters
+///
+/// - `points`: Points to compute the hull for, in any order
+///
+/// ## Returns
+///
+/// Returns a convex polyhedron with the hull vertices and faces, or `None` if the points do not
+/// span a volume (less than 4 unique points, or all points coplanar).
+pub fn quickhull3<S>(points: &[Point3<S>]) -> Option<ConvexPolyhedron<S>>
+where
+    S: BaseFloat,
+{
+    if points.len() < 4 {
+        return None;
+    }
+
+    // tolerance relative to the size of the input, used for all plane tests
+    let max_coordinate = points.iter().fold(Vector3::zero(), |m: Vector3<S>, p| {
+        Vector3::new(m.x.max(p.x.abs()), m.y.max(p.y.abs()), m.z.max(p.z.abs()))
+    });
+    let three = S::one() + S::one() + S::one();
+    let epsilon =
+        three * S::default_epsilon() * (max_coordinate.x + max_coordinate.y + max_coordinate.z);
+
+    let mut faces = initial_simplex(points, epsilon)?;
+
+    // assign all points to the first face they are outside of
+    for (i, p) in points.iter().enumerate() {
+        if let Some(face) = faces.iter_mut().find(|f| f.distance(p) > epsilon) {
+            face.outside.push(i);
+        }
+    }
+
+    // the face on the left of each directed edge, the face on the other side of an edge (a, b)
+    // is the face of (b, a)
+    let mut edge_faces = HashMap::new();
+    for (i, face) in faces.iter().enumerate() {
+        for edge in face.edges().iter() {
+            edge_faces.insert(*edge, i);
+        }
+    }
+
+    let mut pending: Vec<usize> = (0..faces.len()).collect();
+    while let Some(face_index) = pending.pop() {
+        if !faces[face_index].alive || faces[face_index].outside.is_empty() {
+            continue;
+        }
+        // furthest point from the face
+        let eye = *faces[face_index]
+            .outside
+            .iter()
+            .max_by(|a, b| {
+                let da = faces[face_index].distance(&points[**a]);
+                let db = faces[face_index].distance(&points[**b]);
+                da.partial_cmp(&db).unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+        let eye_point = points[eye];
+
+        // the faces that can see the eye point form a connected region around this face, so they
+        // are found by searching the neighbours of visible faces, and the edges to neighbours that
+        // can not see the eye point form the horizon
+        let mut visible = vec![face_index];
+        faces[face_index].alive = false;
+        let mut horizon = Vec::default();
+        let mut next = 0;
+        while next < visible.len() {
+            let edges = faces[visible[next]].edges();
+            next += 1;
+            for &(a, b) in edges.iter() {
+                match edge_faces.get(&(b, a)) {
+                    Some(&neighbour) if !faces[neighbour].alive => {}
+                    Some(&neighbour) if faces[neighbour].distance(&eye_point) > epsilon => {
+                        faces[neighbour].alive = false;
+                        visible.push(neighbour);
+                    }
+                    _ => horizon.push((a, b)),
+                }
+            }
+        }
+
+        // connect the horizon to the eye point
+        let mut orphans = Vec::default();
+        for i in &visible {
+            orphans.append(&mut faces[*i].outside);
+        }
+        let first_new = faces.len();
+        for (a, b) in horizon {
+            for edge in [(a, b), (b, eye), (eye, a)].iter() {
+                edge_faces.insert(*edge, faces.len());
+            }
+            faces.push(HullFace::new(points, [a, b, eye]));
+        }
+
+        for i in orphans {
+            if i == eye {
+                continue;
+            }
+            if let Some(face) = faces[first_new..]
+                .iter_mut()
+                .find(|f| f.distance(&points[i]) > epsilon)
+            {
+                face.outside.push(i);
+            }
+        }
+        pending.extend((first_new..faces.len()).filter(|i| !faces[*i].outside.is_empty()));
+    }
+
+    // compact the vertex list to the vertices that are used by the hull
+    let mut index_map = HashMap::new();
+    let mut vertices = Vec::default();
+    let mut hull_faces = Vec::default();
+    for face in faces.iter().filter(|f| f.alive) {
+        let mut mapped = [0; 3];
+        for (m, v) in mapped.iter_mut().zip(face.vertices.iter()) {
+            *m = *index_map.entry(*v).or_insert_with(|| {
+                vertices.push(points[*v]);
+                vertices.len() - 1
+            });
+        }
+        hull_faces.push((mapped[0], mapped[1], mapped[2]));
+    }
+
+    Some(ConvexPolyhedron::new_with_faces(vertices, hull_faces))
+}
+
+/// Find a tetrahedron with a non zero volume from the given points, and return its faces
+fn initial_simplex<S>(points: &[Point3<S>], epsilon: S) -> Option<Vec<HullFace<S>>>
+where
+    S: BaseFloat,
+{
+    // the two points furthest apart among the extreme points along each axis
+    let mut extremes = Vec::with_capacity(6);
+    for axis in 0..3 {
+        let (min, max) = (1..points.len()).fold((0, 0), |(min, max), i| {
+            (
+                if points[i][axis] < points[min][axis] {
+                    i
+                } else {
+                    min
+                },
+                if points[i][axis] > points[max][axis] {
+                    i
+                } else {
+                    max
+                },
+            )
+        });
+        extremes.push(min);
+        extremes.push(max);
+    }
+    let mut a = 0;
+    let mut b = 0;
+    let mut best = S::zero();
+    for i in &extremes {
+        for j in &extremes {
+            let d = (points[*i] - points[*j]).magnitude2();
+            if d > best {
+                best = d;
+                a = *i;
+                b = *j;
+            }
+        }
+    }
+    if best.sqrt() <= epsilon {
+        return None;
+    }
+
+    // the point furthest from the line through a and b
+    let ab = points[b] - points[a];
+    let (c, area) = furthest(points, |p| ab.cross(p - points[a]).magnitude())?;
+    if area <= epsilon * ab.magnitude() {
+        return None;
+    }
+
+    // the point furthest from the plane through a, b and c
+    let normal = ab.cross(points[c] - points[a]).normalize();
+    let (d, distance) = furthest(points, |p| normal.dot(p - points[a]).abs())?;
+    if distance <= epsilon {
+        return None;
+    }
+
+    // orient the faces so that d is behind the base
+    let (b, c) = if normal.dot(points[d] - points[a]) > S::zero() {
+        (c, b)
+    } else {
+        (b, c)
+    };
+    Some(vec![
+        HullFace::new(points, [a, b, c]),
+        HullFace::new(points, [a, d, b]),
+        HullFace::new(points, [b, d, c]),
+        HullFace::new(points, [c, d, a]),
+    ])
+}
+
+/// Find the point with the largest value of the given function
+fn furthest<S, F>(points: &[Point3<S>], f: F) -> Option<(usize, S)>
+where
+    S: BaseFloat,
+    F: Fn(Point3<S>) -> S,
+{
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i, f(*p)))
+        .max_by(|(_, l), (_, r)| l.partial_cmp(r).unwrap_or(Ordering::Equal))
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Decomposed, Point3, Quaternion, Vector3};
+    use approx::assert_ulps_eq;
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::Ray3;
+    use crate::prelude::*;
+
+    fn cube_points() -> Vec<Point3<f32>> {
+        let mut points = Vec::default();
+        for &x in &[-1., 1.] {
+            for &y in &[-1., 1.] {
+                for &z in &[-1., 1.] {
+                    points.push(Point3::new(x, y, z));
+                }
+            }
+        }
+        points
+    }
+
+    fn assert_convex_hull(hull: &ConvexPolyhedron<f32>, points: &[Point3<f32>]) {
+        for (a, b, c) in hull.faces_iter() {
+            let normal = (b - a).cross(c - a).normalize();
+            for p in points {
+                assert!(normal.dot(p - a) <= 1e-5, "{:?} outside face", p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hull_cube() {
+        let mut points = cube_points();
+        points.push(Point3::new(0., 0., 0.));
+        points.push(Point3::new(0.5, -0.2, 0.7));
+        points.push(Point3::new(1., 1., 1.));
+        let hull = quickhull3(&points).unwrap();
+        assert_eq!(12, hull.faces_iter().count());
+        assert_convex_hull(&hull, &points);
+        let t = Decomposed::<Vector3<f32>, Quaternion<f32>>::one();
+        assert_eq!(
+            Point3::new(1., 1., 1.),
+            hull.support_point(&Vector3::new(1., 1., 1.), &t)
+        );
+        assert_eq!(
+            Point3::new(-1., 1., -1.),
+            hull.support_point(&Vector3::new(-1., 1., -1.), &t)
+        );
+    }
+
+    #[test]
+    fn test_hull_sphere_points() {
+        let mut points = Vec::default();
+        for i in 0..20 {
+            for j in 0..10 {
+                let theta = i as f32 * 0.314;
+                let phi = j as f32 * 0.314 + 0.1;
+                points.push(Point3::new(
+                    phi.sin() * theta.cos() * 2.,
+                    phi.cos() * 2.,
+                    phi.sin() * theta.sin() * 2.,
+                ));
+                points.push(Point3::new(
+                    phi.sin() * theta.cos(),
+                    phi.cos(),
+                    phi.sin() * theta.sin(),
+                ));
+            }
+        }
+        let hull = quickhull3(&points).unwrap();
+        assert_convex_hull(&hull, &points);
+        // closed triangle mesh: F = 2V - 4
+        let faces = hull.faces_iter().count();
+        let vertices: HashSet<_> = hull
+            .faces_iter()
+            .flat_map(|(a, b, c)| vec![*a, *b, *c])
+            .map(|p| (p.x.to_bits(), p.y.to_bits(), p.z.to_bits()))
+            .collect();
+        assert_eq!(2 * vertices.len() - 4, faces);
+        // inner points are never part of the hull
+        for p in hull.faces_iter().flat_map(|(a, b, c)| vec![*a, *b, *c]) {
+            assert!(p.to_vec().magnitude() > 1.5);
+        }
+    }
+
+    #[test]
+    fn test_hull_ray() {
+        let hull = quickhull3(&cube_points()).unwrap();
+        let ray = Ray3::new(Point3::new(-5., 0.5, 0.2), Vector3::new(1., 0., 0.));
+        assert_ulps_eq!(
+            Point3::new(-1., 0.5, 0.2),
+            hull.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_hull_degenerate() {
+        assert!(quickhull3::<f32>(&[]).is_none());
+        let coplanar = [
+            Point3::new(0., 0., 0.),
+            Point3::new(1., 0., 0.),
+            Point3::new(0., 1., 0.),
+            Point3::new(1., 1., 0.),
+            Point3::new(0.5, 0.2, 0.),
+        ];
+        assert!(quickhull3(&coplanar).is_none());
+        let collinear = [
+            Point3::new(0., 0., 0.),
+            Point3::new(1., 1., 1.),
+            Point3::new(2., 2., 2.),
+            Point3::new(3., 3., 3.),
+        ];
+        assert!(quickhull3(&collinear).is_none());
+        let duplicates = [Point3::new(1., 2., 3.); 5];
+        assert!(quickhull3(&duplicates).is_none());
+    }
+}
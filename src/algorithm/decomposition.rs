@@ -0,0 +1,282 @@
+//! Approximate convex decomposition of triangle meshes
+
+use std::cmp::Ordering;
+
+use cgmath::{BaseFloat, Decomposed, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::algorithm::convexhull::quickhull3;
+use crate::primitive::{Compound3, ConvexPolyhedron, Primitive3};
+
+/// Split a closed triangle mesh into a set of convex pieces.
+///
+/// Uses a simple hierarchical approach: the convex hull of the mesh is computed, and if the mesh
+/// is too far from its hull, the mesh is cut in two by an axis aligned plane through its most
+/// concave point, and the halves are decomposed recursively. Of the three candidate planes, the
+/// one where the halves are closest to their hulls is used. The pieces are the convex hulls of
+/// the parts of the mesh, so their union covers the mesh, but may cover a bit more than the
+/// mesh where the pieces are not perfectly convex.
+///
+/// ## Parameters
+///
+/// - `vertices`: Vertices of the mesh
+/// - `indices`: Vertex indices of each triangle of the mesh, in CCW order seen from the outside
+/// - `concavity`: Largest allowed distance from the mesh surface to the hull of a piece
+/// - `max_depth`: Largest number of times the mesh is cut recursively, so there are at most
+///   `2^max_depth` pieces
+///
+/// ## Returns
+///
+/// Returns a compound shape with one `ConvexPolyhedron` per piece, all with identity transforms.
+/// Parts of the mesh that do not span a volume are skipped.
+pub fn convex_decomposition<S>(
+    vertices: &[Point3<S>],
+    indices: &[[usize; 3]],
+    concavity: S,
+    max_depth: u32,
+) -> Compound3<S>
+where
+    S: BaseFloat,
+{
+    let soup: Vec<Vec<Point3<S>>> = indices
+        .iter()
+        .map(|triangle| triangle.iter().map(|&i| vertices[i]).collect())
+        .collect();
+    let mut pieces = Vec::default();
+    decompose(soup, concavity, max_depth, &mut pieces);
+    Compound3::new(
+        pieces
+            .into_iter()
+            .map(|hull| (Primitive3::ConvexPolyhedron(hull), Decomposed::one()))
+            .collect(),
+    )
+}
+
+/// Recursively split the given polygon soup, and add the hulls of the pieces
+fn decompose<S>(
+    soup: Vec<Vec<Point3<S>>>,
+    concavity: S,
+    depth: u32,
+    pieces: &mut Vec<ConvexPolyhedron<S>>,
+) where
+    S: BaseFloat,
+{
+    let hull = match hull(&soup) {
+        Some(hull) => hull,
+        None => return,
+    };
+    let (deepest, point) = measure(&hull, &soup);
+    if deepest <= concavity || depth == 0 {
+        pieces.push(hull);
+        return;
+    }
+
+    // try cutting along each axis through the most concave point
+    let best = (0..3)
+        .filter_map(|axis| {
+            let mut normal = Vector3::zero();
+            normal[axis] = S::one();
+            let offset = normal.dot(point.to_vec());
+            let below = split(&soup, normal, offset);
+            let above = split(&soup, -normal, -offset);
+            if below.is_empty() || above.is_empty() {
+                return None;
+            }
+            let score = score(&below) + score(&above);
+            Some((score, below, above))
+        })
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    match best {
+        Some((_, below, above)) => {
+            decompose(below, concavity, depth - 1, pieces);
+            decompose(above, concavity, depth - 1, pieces);
+        }
+        None => pieces.push(hull),
+    }
+}
+
+/// Convex hull of all vertices of the soup
+fn hull<S>(soup: &[Vec<Point3<S>>]) -> Option<ConvexPolyhedron<S>>
+where
+    S: BaseFloat,
+{
+    let points: Vec<Point3<S>> = soup.iter().flat_map(|polygon| polygon.clone()).collect();
+    quickhull3(&points)
+}
+
+/// Concavity of the hull of the soup, or zero if the soup has no volume
+fn score<S>(soup: &[Vec<Point3<S>>]) -> S
+where
+    S: BaseFloat,
+{
+    hull(soup).map_or(S::zero(), |hull| measure(&hull, soup).0)
+}
+
+/// Find the point of the soup that is furthest from the hull, and its distance to the hull. The
+/// distance is measured from the vertices and centroids of the polygons, along the outward
+/// normal of the polygon, so points on the hull surface have no concavity.
+fn measure<S>(hull: &ConvexPolyhedron<S>, soup: &[Vec<Point3<S>>]) -> (S, Point3<S>)
+where
+    S: BaseFloat,
+{
+    // faces are in CCW order seen from the outside of the hull
+    let planes: Vec<(Vector3<S>, S)> = hull
+        .faces_iter()
+        .map(|(a, b, c)| {
+            let normal = (b - a).cross(c - a).normalize();
+            (normal, normal.dot(a.to_vec()))
+        })
+        .collect();
+    let distance = |p: &Point3<S>, direction: Vector3<S>| {
+        planes
+            .iter()
+            .filter(|&&(normal, _)| normal.dot(direction) > S::zero())
+            .map(|&(normal, offset)| (offset - normal.dot(p.to_vec())) / normal.dot(direction))
+            .fold(S::infinity(), S::min)
+            .max(S::zero())
+    };
+    let mut deepest = (S::neg_infinity(), Point3::origin());
+    for polygon in soup {
+        // clipping can leave polygons without area
+        let normal = newell_normal(polygon);
+        if normal.magnitude2().is_zero() {
+            continue;
+        }
+        let normal = normal.normalize();
+        let centroid = Point3::centroid(polygon);
+        for p in polygon.iter().chain(Some(&centroid)) {
+            let d = distance(p, normal);
+            if d > deepest.0 {
+                deepest = (d, *p);
+            }
+        }
+    }
+    deepest
+}
+
+/// Unnormalized normal of a polygon with vertices in CCW order, using Newell's method
+fn newell_normal<S>(polygon: &[Point3<S>]) -> Vector3<S>
+where
+    S: BaseFloat,
+{
+    let mut normal = Vector3::zero();
+    for (i, current) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        normal.x += (current.y - next.y) * (current.z + next.z);
+        normal.y += (current.z - next.z) * (current.x + next.x);
+        normal.z += (current.x - next.x) * (current.y + next.y);
+    }
+    normal
+}
+
+/// Clip all polygons of the soup to the half space `normal.dot(p) <= offset`
+fn split<S>(soup: &[Vec<Point3<S>>], normal: Vector3<S>, offset: S) -> Vec<Vec<Point3<S>>>
+where
+    S: BaseFloat,
+{
+    soup.iter()
+        .filter(|polygon| {
+            // polygons on the plane only belong to the side they face away from
+            !polygon.iter().all(|p| normal.dot(p.to_vec()) == offset)
+                || newell_normal(polygon).dot(normal) > S::zero()
+        })
+        .map(|polygon| clip(polygon, normal, offset))
+        .filter(|polygon| polygon.len() >= 3)
+        .collect()
+}
+
+/// Clip a convex polygon to the half space `normal.dot(p) <= offset`
+fn clip<S>(polygon: &[Point3<S>], normal: Vector3<S>, offset: S) -> Vec<Point3<S>>
+where
+    S: BaseFloat,
+{
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    let distance = |p: &Point3<S>| normal.dot(p.to_vec()) - offset;
+    for (i, current) in polygon.iter().enumerate() {
+        let next = &polygon[(i + 1) % polygon.len()];
+        let (dc, dn) = (distance(current), distance(next));
+        if dc <= S::zero() {
+            clipped.push(*current);
+        }
+        if (dc < S::zero() && dn > S::zero()) || (dc > S::zero() && dn < S::zero()) {
+            clipped.push(current + (next - current) * (dc / (dc - dn)));
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Point3, Vector3};
+
+    use super::*;
+    use crate::{Aabb3, Ray3};
+    use crate::prelude::*;
+
+    #[test]
+    fn test_decomposition_convex() {
+        let (vertices, indices) = prism(&[(0., 0.), (2., 0.), (2., 2.), (0., 2.)]);
+        let compound = convex_decomposition(&vertices, &indices, 0.01, 4);
+        assert_eq!(1, compound.children.len());
+        assert_eq!(
+            Aabb3::new(Point3::new(0., 0., 0.), Point3::new(2., 2., 1.)),
+            compound.compute_bound()
+        );
+    }
+
+    #[test]
+    fn test_decomposition_l_shape() {
+        let (vertices, indices) =
+            prism(&[(0., 0.), (3., 0.), (3., 1.), (1., 1.), (1., 3.), (0., 3.)]);
+        let compound = convex_decomposition(&vertices, &indices, 0.01, 4);
+        assert_eq!(2, compound.children.len());
+        let notch = Point3::new(2., 2., 0.5);
+        for (primitive, _) in &compound.children {
+            let bound: Aabb3<f32> = primitive.compute_bound();
+            assert!(!bound.contains(&notch));
+        }
+        assert_eq!(
+            Aabb3::new(Point3::new(0., 0., 0.), Point3::new(3., 3., 1.)),
+            compound.compute_bound()
+        );
+
+        // through the notch, and through the arm
+        let ray = Ray3::new(Point3::new(-1., 2., 0.5), Vector3::new(1., 0., 0.));
+        assert_eq!(Some(Point3::new(0., 2., 0.5)), compound.intersection(&ray));
+        let ray = Ray3::new(Point3::new(2., 2., 5.), Vector3::new(0., 0., -1.));
+        assert!(!compound.intersects(&ray));
+        let ray = Ray3::new(Point3::new(2., 0.5, 5.), Vector3::new(0., 0., -1.));
+        assert!(compound.intersects(&ray));
+    }
+
+    #[test]
+    fn test_decomposition_max_depth() {
+        let (vertices, indices) =
+            prism(&[(0., 0.), (3., 0.), (3., 1.), (1., 1.), (1., 3.), (0., 3.)]);
+        let compound = convex_decomposition(&vertices, &indices, 0.01, 0);
+        assert_eq!(1, compound.children.len());
+    }
+
+    // util
+    /// Closed mesh of a polygon in the XY plane, extruded from z = 0 to z = 1. The polygon must be
+    /// star shaped around its first vertex.
+    fn prism(outline: &[(f32, f32)]) -> (Vec<Point3<f32>>, Vec<[usize; 3]>) {
+        let n = outline.len();
+        let mut vertices = Vec::default();
+        for z in &[0., 1.] {
+            vertices.extend(outline.iter().map(|&(x, y)| Point3::new(x, y, *z)));
+        }
+        let mut indices = Vec::default();
+        for i in 1..n - 1 {
+            indices.push([0, i + 1, i]);
+            indices.push([n, n + i, n + i + 1]);
+        }
+        for i in 0..n {
+            let j = (i + 1) % n;
+            indices.push([i, j, n + j]);
+            indices.push([i, n + j, n + i]);
+        }
+        (vertices, indices)
+    }
+}
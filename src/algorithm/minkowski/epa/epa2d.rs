@@ -8,9 +8,14 @@ use approx::assert_ulps_ne;
 use super::*;
 use crate::{CollisionStrategy, Contact};
 use crate::prelude::*;
-use crate::primitive::util::triple_product;
 
 /// EPA algorithm implementation for 2D. Only to be used in [`GJK`](struct.GJK.html).
+///
+/// Expands the triangle from GJK into a polygon, inserting one support point at a time into the
+/// edge closest to the origin, until that edge is on the boundary of the Minkowski difference.
+/// The resulting contact has the normal of that edge, pointing from the left shape towards the
+/// right shape, the distance to that edge as the penetration depth, and the deepest point on the
+/// left shape as the contact point.
 #[derive(Debug)]
 pub struct EPA2<S> {
     m: marker::PhantomData<S>,
@@ -18,6 +23,25 @@ pub struct EPA2<S> {
     max_iterations: u32,
 }
 
+impl<S> EPA2<S>
+where
+    S: BaseFloat,
+{
+    /// Stop expanding the polytope when a new support point is less than the given distance
+    /// beyond the closest edge
+    pub fn with_tolerance(mut self, tolerance: S) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Stop expanding the polytope after the given number of iterations, and use the closest
+    /// edge found so far
+    pub fn with_max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+
 impl<S> EPA for EPA2<S>
 where
     S: BaseFloat,
@@ -137,14 +161,28 @@ where
     if simplex.len() < 3 {
         None
     } else {
+        // the outward normals are computed from the winding of the polytope, and not from the
+        // direction to the origin, because the origin can be on an edge
+        let ccw = (0..simplex.len())
+            .map(|i| {
+                let a = simplex[i].v;
+                let b = simplex[(i + 1) % simplex.len()].v;
+                a.perp_dot(b)
+            })
+            .fold(S::zero(), |sum, area| sum + area)
+            > S::zero();
         let mut edge = Edge::new(Vector2::zero(), S::infinity(), 0);
         for i in 0..simplex.len() {
             let j = if i + 1 == simplex.len() { 0 } else { i + 1 };
             let a = simplex[i].v;
             let b = simplex[j].v;
             let e = b - a;
-            let oa = a;
-            let n = triple_product(&e, &oa, &e).normalize();
+            let n = if ccw {
+                Vector2::new(e.y, -e.x)
+            } else {
+                Vector2::new(-e.y, e.x)
+            }
+            .normalize();
             let d = n.dot(a);
             if d < edge.distance {
                 edge = Edge::new(n, d, j);
@@ -11,6 +11,11 @@ use crate::prelude::*;
 use crate::primitive::util::barycentric_vector;
 
 /// EPA algorithm implementation for 3D. Only to be used in [`GJK`](struct.GJK.html).
+///
+/// Expands the tetrahedron from GJK into a polytope, until the face closest to the origin is on
+/// the boundary of the Minkowski difference. The resulting contact has the normal of that face,
+/// pointing from the left shape towards the right shape, the distance to that face as the
+/// penetration depth, and the deepest point on the left shape as the contact point.
 #[derive(Debug)]
 pub struct EPA3<S> {
     m: marker::PhantomData<S>,
@@ -18,6 +23,25 @@ pub struct EPA3<S> {
     max_iterations: u32,
 }
 
+impl<S> EPA3<S>
+where
+    S: BaseFloat,
+{
+    /// Stop expanding the polytope when a new support point is less than the given distance
+    /// beyond the closest face
+    pub fn with_tolerance(mut self, tolerance: S) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Stop expanding the polytope after the given number of iterations, and use the closest
+    /// face found so far
+    pub fn with_max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
+
 impl<S> EPA for EPA3<S>
 where
     S: BaseFloat,
@@ -87,7 +111,7 @@ where
 {
     Some(Contact::new_with_point(
         CollisionStrategy::FullResolution,
-        face.normal, // negate ?
+        face.normal,
         face.distance,
         point(polytope, face),
     ))
@@ -1,5 +1,4 @@
-//! GJK distance/collision detection algorithm. For now only have implementation of collision
-//! detection, not distance computation.
+//! GJK distance/collision detection algorithm.
 
 pub use self::simplex::SimplexProcessor;
 
@@ -10,11 +9,14 @@ use cgmath::BaseFloat;
 use cgmath::prelude::*;
 use cgmath::num_traits::NumCast;
 use cgmath::UlpsEq;
+use smallvec::SmallVec;
 
 use self::simplex::{Simplex, SimplexProcessor2, SimplexProcessor3};
 use crate::{CollisionStrategy, Contact};
 use crate::algorithm::minkowski::{EPA2, EPA3, SupportPoint, EPA};
 use crate::prelude::*;
+use crate::primitive::util::segment_parameter;
+use crate::primitive::WithMargin;
 use approx::ulps_eq;
 
 mod simplex;
@@ -29,6 +31,144 @@ pub type GJK2<S> = GJK<SimplexProcessor2<S>, EPA2<S>, S>;
 /// GJK algorithm for 3D, see [GJK](struct.GJK.html) for more information.
 pub type GJK3<S> = GJK<SimplexProcessor3<S>, EPA3<S>, S>;
 
+/// Result of a distance query on two separated primitives.
+///
+/// # Type parameters
+///
+/// - `P`: cgmath point type
+#[derive(Debug, Clone)]
+pub struct Separation<P: EuclideanSpace> {
+    /// The smallest distance between the primitives
+    pub distance: P::Scalar,
+
+    /// The point on the left primitive closest to the right primitive, in world space
+    pub left_point: P,
+
+    /// The point on the right primitive closest to the left primitive, in world space
+    pub right_point: P,
+}
+
+/// Per pair state for warm starting GJK queries between frames, see
+/// [`GJK::intersect_cached`](struct.GJK.html#method.intersect_cached).
+///
+/// The cache keeps the support points of the final simplex of a query in the local space of each
+/// primitive, and the next query for the same pair moves them with the primitives to their new
+/// transforms, and starts from that simplex. When the last query found a separating axis, the
+/// cache keeps that direction instead, and the next query starts from it. Without either, a query
+/// starts from the direction between the origins of the primitives.
+///
+/// # Type parameters
+///
+/// - `P`: cgmath point type
+#[derive(Debug, Clone)]
+pub struct GJKCache<P: EuclideanSpace> {
+    direction: Option<P::Diff>,
+    simplex: SmallVec<[(P, P); 4]>,
+    iterations: u32,
+}
+
+impl<P> GJKCache<P>
+where
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+    P::Diff: Array<Element = P::Scalar> + UlpsEq,
+{
+    /// Create a new empty cache, the first query will start from the direction between the
+    /// origins of the primitives
+    pub fn new() -> Self {
+        Self {
+            direction: None,
+            simplex: SmallVec::new(),
+            iterations: 0,
+        }
+    }
+
+    /// Clear the cache, for example when one of the primitives was teleported
+    pub fn clear(&mut self) {
+        self.direction = None;
+        self.simplex.clear();
+    }
+
+    /// The number of iterations the last query needed
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    fn start_direction<TL, TR>(&self, left_transform: &TL, right_transform: &TR) -> P::Diff
+    where
+        TL: Transform<P>,
+        TR: Transform<P>,
+    {
+        if let Some(d) = self.direction {
+            if !ulps_eq!(d, P::Diff::zero()) {
+                return d;
+            }
+        }
+        let d = right_transform.transform_point(P::origin())
+            - left_transform.transform_point(P::origin());
+        if ulps_eq!(d, P::Diff::zero()) {
+            P::Diff::from_value(P::Scalar::one())
+        } else {
+            d
+        }
+    }
+
+    /// Keep the support points of the given simplex, in the local space of each primitive
+    fn store_simplex<TL, TR>(
+        &mut self,
+        simplex: &Simplex<P>,
+        left_transform: &TL,
+        right_transform: &TR,
+    ) where
+        TL: Transform<P>,
+        TR: Transform<P>,
+    {
+        self.simplex.clear();
+        if let (Some(left), Some(right)) = (
+            left_transform.inverse_transform(),
+            right_transform.inverse_transform(),
+        ) {
+            self.simplex.extend(simplex.iter().map(|point| {
+                (
+                    left.transform_point(point.sup_a),
+                    right.transform_point(point.sup_b),
+                )
+            }));
+        }
+    }
+
+    /// The simplex of the last query, moved with the primitives to the given transforms
+    fn moved_simplex<TL, TR>(&self, left_transform: &TL, right_transform: &TR) -> Simplex<P>
+    where
+        TL: Transform<P>,
+        TR: Transform<P>,
+    {
+        self.simplex
+            .iter()
+            .map(|&(left, right)| {
+                let sup_a = left_transform.transform_point(left);
+                let sup_b = right_transform.transform_point(right);
+                SupportPoint {
+                    v: sup_a - sup_b,
+                    sup_a,
+                    sup_b,
+                }
+            })
+            .collect()
+    }
+}
+
+impl<P> Default for GJKCache<P>
+where
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+    P::Diff: Array<Element = P::Scalar> + UlpsEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Gilbert-Johnson-Keerthi narrow phase collision detection algorithm.
 ///
 /// # Type parameters:
@@ -40,6 +180,18 @@ pub type GJK3<S> = GJK<SimplexProcessor3<S>, EPA3<S>, S>;
 ///        [`EPA2`](struct.EPA2.html) or
 ///        [`EPA3`](struct.EPA3.html)
 ///
+/// # Settings
+///
+/// The iteration limits and tolerances can be changed with the `with_*` methods, e.g.
+/// `GJK3::new().with_max_iterations(20).with_distance_tolerance(1e-4)`, and the EPA settings with
+/// [`with_epa`](#method.with_epa).
+///
+/// # Margins
+///
+/// With a margin, see [`with_margin`](#method.with_margin), the primitives are treated as cores,
+/// grown by the margin in all directions. Distance queries and shallow contacts are computed from
+/// the closest points of the cores, and EPA is only used when the cores overlap, which keeps the
+/// contacts stable for thin shapes, like flat boxes and triangles, where EPA breaks down.
 #[derive(Debug)]
 pub struct GJK<SP, E, S> {
     simplex_processor: SP,
@@ -47,6 +199,7 @@ pub struct GJK<SP, E, S> {
     distance_tolerance: S,
     continuous_tolerance: S,
     max_iterations: u32,
+    margin: S,
 }
 
 impl<SP, E, S> GJK<SP, E, S>
@@ -64,6 +217,7 @@ where
             distance_tolerance: NumCast::from(GJK_DISTANCE_TOLERANCE).unwrap(),
             continuous_tolerance: NumCast::from(GJK_CONTINUOUS_TOLERANCE).unwrap(),
             max_iterations: MAX_ITERATIONS,
+            margin: S::zero(),
         }
     }
 
@@ -80,9 +234,68 @@ where
             distance_tolerance,
             continuous_tolerance,
             max_iterations,
+            margin: S::zero(),
         }
     }
 
+    /// Use the given number of iterations as the limit for building the simplex. Queries that
+    /// reach the limit report no collision, or the best distance found so far.
+    pub fn with_max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Use the given tolerance for distance queries. They stop when the distance improves by less
+    /// than the tolerance, relative to the squared distance when that is larger than one.
+    ///
+    /// The default is tuned for `f32` and shapes of around unit size; larger worlds, or `f64`
+    /// scalars, may need a different value.
+    pub fn with_distance_tolerance(mut self, distance_tolerance: S) -> Self {
+        self.distance_tolerance = distance_tolerance;
+        self
+    }
+
+    /// Use the given tolerance for time of impact queries, as the squared distance at which the
+    /// primitives are considered touching
+    pub fn with_continuous_tolerance(mut self, continuous_tolerance: S) -> Self {
+        self.continuous_tolerance = continuous_tolerance;
+        self
+    }
+
+    /// Use the given EPA implementation for contacts of overlapping primitives, to change its
+    /// tolerance and iteration limit
+    pub fn with_epa(mut self, epa: E) -> Self {
+        self.epa = epa;
+        self
+    }
+
+    /// Get the iteration limit for building the simplex
+    pub fn max_iterations(&self) -> u32 {
+        self.max_iterations
+    }
+
+    /// Get the tolerance for distance queries
+    pub fn distance_tolerance(&self) -> S {
+        self.distance_tolerance
+    }
+
+    /// Get the tolerance for time of impact queries
+    pub fn continuous_tolerance(&self) -> S {
+        self.continuous_tolerance
+    }
+
+    /// Use the given margin around both primitives in all queries. The margin is added to the
+    /// primitives, so the cores should be shrunk by the margin to keep the original size.
+    pub fn with_margin(mut self, margin: S) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Get the margin around the primitives
+    pub fn margin(&self) -> S {
+        self.margin
+    }
+
     /// Do intersection test on the given primitives
     ///
     /// ## Parameters:
@@ -114,28 +327,98 @@ where
         TL: Transform<P>,
         TR: Transform<P>,
     {
-        let left_pos = left_transform.transform_point(P::origin());
-        let right_pos = right_transform.transform_point(P::origin());
-        let mut d = right_pos - left_pos;
-        if ulps_eq!(d, P::Diff::zero()) {
-            d = P::Diff::from_value(S::one());
+        self.intersect_cached(
+            left,
+            left_transform,
+            right,
+            right_transform,
+            &mut GJKCache::new(),
+        )
+    }
+
+    /// Do intersection test on the given primitives, starting from the simplex or search
+    /// direction in the given cache, and store the final simplex or search direction in the cache.
+    ///
+    /// Keep a cache for each pair of primitives, and pass it to the query for that pair in every
+    /// frame. The direction found by a query that finds no collision is a separating axis, and
+    /// when the primitives only move a little between frames, it usually stays separating, so
+    /// the next query is done after a single iteration. For colliding primitives, the simplex
+    /// around the origin is moved with the primitives, and when it still encloses the origin,
+    /// the next query is also done after a single iteration.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `left`: left primitive
+    /// - `left_transform`: model-to-world-transform for the left primitive
+    /// - `right`: right primitive,
+    /// - `right_transform`: model-to-world-transform for the right primitive
+    /// - `cache`: per pair state, updated by the query
+    ///
+    /// ## Returns:
+    ///
+    /// Will return a simplex if a collision was detected, see [`intersect`](#method.intersect).
+    pub fn intersect_cached<P, PL, PR, TL, TR>(
+        &self,
+        left: &PL,
+        left_transform: &TL,
+        right: &PR,
+        right_transform: &TR,
+        cache: &mut GJKCache<P>,
+    ) -> Option<Simplex<P>>
+    where
+        P: EuclideanSpace<Scalar = S>,
+        PL: Primitive<Point = P>,
+        PR: Primitive<Point = P>,
+        SP: SimplexProcessor<Point = P>,
+        P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+        TL: Transform<P>,
+        TR: Transform<P>,
+    {
+        let left = &WithMargin::new(left, self.margin);
+        let right = &WithMargin::new(right, self.margin);
+        cache.iterations = 1;
+        let zero = P::Diff::zero();
+        let mut simplex = cache.moved_simplex(left_transform, right_transform);
+        let mut d = zero;
+        if !simplex.is_empty() {
+            // the origin is on the side of the closest feature of the moved simplex that the
+            // search direction points to, like for the simplex of the last iteration
+            let closest = self.simplex_processor
+                .get_closest_point_to_origin(&mut simplex);
+            if ulps_eq!(closest, zero) && simplex.len() == P::Diff::len() + 1 {
+                // the moved simplex still encloses the origin
+                return Some(simplex);
+            }
+            d = closest.neg();
         }
-        let a = SupportPoint::from_minkowski(left, left_transform, right, right_transform, &d);
-        if a.v.dot(d) <= S::zero() {
-            return None;
+        if ulps_eq!(d, zero) {
+            // no cached simplex, or the origin is on the boundary of the moved simplex
+            simplex.clear();
+            let start = cache.start_direction(left_transform, right_transform);
+            let a =
+                SupportPoint::from_minkowski(left, left_transform, right, right_transform, &start);
+            if a.v.dot(start) <= S::zero() {
+                cache.direction = Some(start);
+                cache.simplex.clear();
+                return None;
+            }
+            simplex.push(a);
+            d = start.neg();
         }
-        let mut simplex = Simplex::new();
-        simplex.push(a);
-        d = d.neg();
         for _ in 0..self.max_iterations {
+            cache.iterations += 1;
             let a = SupportPoint::from_minkowski(left, left_transform, right, right_transform, &d);
             if a.v.dot(d) <= S::zero() {
+                cache.direction = Some(d);
+                cache.simplex.clear();
                 return None;
             } else {
                 simplex.push(a);
                 if self.simplex_processor
                     .reduce_to_closest_feature(&mut simplex, &mut d)
                 {
+                    cache.direction = Some(a.v);
+                    cache.store_simplex(&simplex, left_transform, right_transform);
                     return Some(simplex);
                 }
             }
@@ -175,6 +458,9 @@ where
         TL: Transform<P> + TranslationInterpolate<S>,
         TR: Transform<P> + TranslationInterpolate<S>,
     {
+        let left = &WithMargin::new(left, self.margin);
+        let right = &WithMargin::new(right, self.margin);
+
         // build the ray, A.velocity - B.velocity is the ray direction
         let left_lin_vel = left_transform.end.transform_point(P::origin())
             - left_transform.start.transform_point(P::origin());
@@ -282,38 +568,212 @@ where
         TL: Transform<P>,
         TR: Transform<P>,
     {
-        let zero = P::Diff::zero();
-        let right_pos = right_transform.transform_point(P::origin());
-        let left_pos = left_transform.transform_point(P::origin());
-        let mut simplex = Simplex::new();
-        let mut d = right_pos - left_pos;
-        if ulps_eq!(d, P::Diff::zero()) {
-            d = P::Diff::from_value(S::one());
+        self.separation(left, left_transform, right, right_transform)
+            .map(|separation| separation.distance)
+    }
+
+    /// Compute the closest points on the given primitives.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `left`: left primitive
+    /// - `left_transform`: model-to-world-transform for the left primitive
+    /// - `right`: right primitive,
+    /// - `right_transform`: model-to-world-transform for the right primitive
+    ///
+    /// ## Returns:
+    ///
+    /// Will optionally return the point on the left primitive closest to the right primitive, and
+    /// the point on the right primitive closest to the left primitive, in world space. Will return
+    /// None, if the objects are colliding.
+    pub fn closest_points<P, PL, PR, TL, TR>(
+        &self,
+        left: &PL,
+        left_transform: &TL,
+        right: &PR,
+        right_transform: &TR,
+    ) -> Option<(P, P)>
+    where
+        P: EuclideanSpace<Scalar = S>,
+        PL: Primitive<Point = P>,
+        PR: Primitive<Point = P>,
+        SP: SimplexProcessor<Point = P>,
+        P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+        TL: Transform<P>,
+        TR: Transform<P>,
+    {
+        self.separation(left, left_transform, right, right_transform)
+            .map(|separation| (separation.left_point, separation.right_point))
+    }
+
+    /// Compute the distance between the given primitives, and the closest points on them.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `left`: left primitive
+    /// - `left_transform`: model-to-world-transform for the left primitive
+    /// - `right`: right primitive,
+    /// - `right_transform`: model-to-world-transform for the right primitive
+    ///
+    /// ## Returns:
+    ///
+    /// Will optionally return the distance between the objects, together with the closest point
+    /// on each object. The closest point on the left object is computed from the support points
+    /// of the final simplex, so for shapes with parallel features, it is one of many possible
+    /// points. The closest point on the right object is at the distance from it, along the
+    /// separating direction found by GJK.
+    /// Will return None, if the objects are colliding.
+    pub fn separation<P, PL, PR, TL, TR>(
+        &self,
+        left: &PL,
+        left_transform: &TL,
+        right: &PR,
+        right_transform: &TR,
+    ) -> Option<Separation<P>>
+    where
+        P: EuclideanSpace<Scalar = S>,
+        PL: Primitive<Point = P>,
+        PR: Primitive<Point = P>,
+        SP: SimplexProcessor<Point = P>,
+        P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+        TL: Transform<P>,
+        TR: Transform<P>,
+    {
+        self.separation_cached(
+            left,
+            left_transform,
+            right,
+            right_transform,
+            &mut GJKCache::new(),
+        )
+    }
+
+    /// Compute the distance between the given primitives, and the closest points on them,
+    /// starting from the simplex in the given cache, and store the final simplex in the cache.
+    ///
+    /// The final simplex of the last query for the same pair of primitives holds the closest
+    /// feature of the Minkowski difference, and when the primitives only move a little, the
+    /// simplex moved with them stays close to the closest feature for this query. See
+    /// [`intersect_cached`](#method.intersect_cached) for how to keep the cache.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `left`: left primitive
+    /// - `left_transform`: model-to-world-transform for the left primitive
+    /// - `right`: right primitive,
+    /// - `right_transform`: model-to-world-transform for the right primitive
+    /// - `cache`: per pair state, updated by the query
+    ///
+    /// ## Returns:
+    ///
+    /// Will optionally return the separation, see [`separation`](#method.separation).
+    pub fn separation_cached<P, PL, PR, TL, TR>(
+        &self,
+        left: &PL,
+        left_transform: &TL,
+        right: &PR,
+        right_transform: &TR,
+        cache: &mut GJKCache<P>,
+    ) -> Option<Separation<P>>
+    where
+        P: EuclideanSpace<Scalar = S>,
+        PL: Primitive<Point = P>,
+        PR: Primitive<Point = P>,
+        SP: SimplexProcessor<Point = P>,
+        P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+        TL: Transform<P>,
+        TR: Transform<P>,
+    {
+        let separation =
+            self.core_separation_cached(left, left_transform, right, right_transform, cache)?;
+        if self.margin.is_zero() {
+            return Some(separation);
         }
-        for d in &[d, d.neg()] {
-            simplex.push(SupportPoint::from_minkowski(
-                left,
-                left_transform,
-                right,
-                right_transform,
-                d,
-            ));
+        let margins = self.margin + self.margin;
+        if separation.distance - margins <= self.distance_tolerance {
+            return None;
+        }
+        let normal = (separation.right_point - separation.left_point) / separation.distance;
+        Some(Separation {
+            distance: separation.distance - margins,
+            left_point: separation.left_point + normal * self.margin,
+            right_point: separation.right_point - normal * self.margin,
+        })
+    }
+
+    /// Separation of the primitives without the margins, see [`separation`](#method.separation)
+    fn core_separation_cached<P, PL, PR, TL, TR>(
+        &self,
+        left: &PL,
+        left_transform: &TL,
+        right: &PR,
+        right_transform: &TR,
+        cache: &mut GJKCache<P>,
+    ) -> Option<Separation<P>>
+    where
+        P: EuclideanSpace<Scalar = S>,
+        PL: Primitive<Point = P>,
+        PR: Primitive<Point = P>,
+        SP: SimplexProcessor<Point = P>,
+        P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+        TL: Transform<P>,
+        TR: Transform<P>,
+    {
+        let zero = P::Diff::zero();
+        let mut simplex = cache.moved_simplex(left_transform, right_transform);
+        if simplex.is_empty() {
+            let d = cache.start_direction(left_transform, right_transform);
+            for d in &[d, d.neg()] {
+                simplex.push(SupportPoint::from_minkowski(
+                    left,
+                    left_transform,
+                    right,
+                    right_transform,
+                    d,
+                ));
+            }
         }
+        cache.iterations = 0;
+        let mut closest: Option<(Simplex<P>, P::Diff)> = None;
         for _ in 0..self.max_iterations {
+            cache.iterations += 1;
             let d = self.simplex_processor
                 .get_closest_point_to_origin(&mut simplex);
-            if ulps_eq!(d, zero) {
+            // a closest point within rounding error of the origin means touching or overlapping
+            if ulps_eq!(d, zero) || d.magnitude() <= self.distance_tolerance {
+                cache.store_simplex(&simplex, left_transform, right_transform);
                 return None;
             }
-            let d = d.neg();
-            let p = SupportPoint::from_minkowski(left, left_transform, right, right_transform, &d);
-            let dp = p.v.dot(d);
-            let d0 = simplex[0].v.dot(d);
-            if dp - d0 < self.distance_tolerance {
-                return Some(d.magnitude());
+            // rounding errors can make the simplex cycle without getting any closer, or even move
+            // it away from the origin, so stop with the closest simplex found so far
+            if let Some((previous, previous_d)) = &closest {
+                if d.magnitude2() >= previous_d.magnitude2() {
+                    cache.direction = Some(previous_d.neg());
+                    cache.store_simplex(previous, left_transform, right_transform);
+                    return Some(simplex_separation(previous, *previous_d));
+                }
             }
+            cache.direction = Some(d.neg());
+            let p = SupportPoint::from_minkowski(
+                left,
+                left_transform,
+                right,
+                right_transform,
+                &d.neg(),
+            );
+            // compare with the closest point, not with a vertex of the simplex, which is further
+            // away when the closest point is clamped to the end of an edge, and scale the
+            // tolerance with the distance, to stay above the rounding error for large distances
+            let dp = p.v.dot(d.neg());
+            let d0 = -d.magnitude2();
+            if dp - d0 < self.distance_tolerance * d.magnitude2().max(S::one()) {
+                cache.store_simplex(&simplex, left_transform, right_transform);
+                return Some(simplex_separation(&simplex, d));
+            }
+            closest = Some((simplex.clone(), d));
             simplex.push(p);
         }
+        cache.store_simplex(&simplex, left_transform, right_transform);
         None
     }
 
@@ -330,14 +790,20 @@ where
     ) -> Option<Contact<P>>
     where
         P: EuclideanSpace<Scalar = S>,
+        P::Diff: InnerSpace,
         PL: Primitive<Point = P>,
         PR: Primitive<Point = P>,
         TL: Transform<P>,
         TR: Transform<P>,
         SP: SimplexProcessor<Point = P>,
     {
-        self.epa
-            .process(&mut simplex, left, left_transform, right, right_transform)
+        self.epa.process(
+            &mut simplex,
+            &WithMargin::new(left, self.margin),
+            left_transform,
+            &WithMargin::new(right, self.margin),
+            right_transform,
+        )
     }
 
     /// Do intersection testing on the given primitives, and return the contact manifold.
@@ -364,6 +830,81 @@ where
         right: &PR,
         right_transform: &TR,
     ) -> Option<Contact<P>>
+    where
+        P: EuclideanSpace<Scalar = S>,
+        P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+        PL: Primitive<Point = P>,
+        PR: Primitive<Point = P>,
+        TL: Transform<P>,
+        TR: Transform<P>,
+        SP: SimplexProcessor<Point = P>,
+    {
+        self.intersection_scratch(
+            strategy,
+            left,
+            left_transform,
+            right,
+            right_transform,
+            &mut Vec::default(),
+        )
+    }
+
+    /// Do intersection testing on the given pairs of primitives, found by a broad phase, and
+    /// collect the contacts.
+    ///
+    /// The scratch space used by EPA is shared between the pairs, and the output is cleared and
+    /// reused, so keeping the contact list between frames avoids most allocations.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `strategy`: strategy to use, see [`intersection`](#method.intersection)
+    /// - `shapes`: primitive + model-to-world-transform for each shape
+    /// - `pairs`: indices into the shapes list of the pairs to test, as returned by the broad
+    ///   phase algorithms
+    /// - `contacts`: output list, will be cleared, and then get the indices and the contact for
+    ///   each colliding pair, in the order of the pairs. The first shape of a pair is the left
+    ///   primitive of the contact.
+    pub fn intersection_batch<P, PR, T>(
+        &self,
+        strategy: &CollisionStrategy,
+        shapes: &[(PR, T)],
+        pairs: &[(usize, usize)],
+        contacts: &mut Vec<(usize, usize, Contact<P>)>,
+    ) where
+        P: EuclideanSpace<Scalar = S>,
+        P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+        PR: Primitive<Point = P>,
+        T: Transform<P>,
+        SP: SimplexProcessor<Point = P>,
+    {
+        contacts.clear();
+        let mut scratch = Vec::default();
+        for &(left_index, right_index) in pairs {
+            let (ref left, ref left_transform) = shapes[left_index];
+            let (ref right, ref right_transform) = shapes[right_index];
+            if let Some(contact) = self.intersection_scratch(
+                strategy,
+                left,
+                left_transform,
+                right,
+                right_transform,
+                &mut scratch,
+            ) {
+                contacts.push((left_index, right_index, contact));
+            }
+        }
+    }
+
+    /// Do intersection testing on the given primitives, using the given scratch space for EPA
+    fn intersection_scratch<P, PL, PR, TL, TR>(
+        &self,
+        strategy: &CollisionStrategy,
+        left: &PL,
+        left_transform: &TL,
+        right: &PR,
+        right_transform: &TR,
+        scratch: &mut Vec<SupportPoint<P>>,
+    ) -> Option<Contact<P>>
     where
         P: EuclideanSpace<Scalar = S>,
         P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
@@ -374,16 +915,42 @@ where
         SP: SimplexProcessor<Point = P>,
     {
         use CollisionStrategy::*;
+        if !self.margin.is_zero() {
+            // when only the margins overlap, the closest points of the cores give the contact
+            let margins = self.margin + self.margin;
+            if let Some(separation) = self.core_separation_cached(
+                left,
+                left_transform,
+                right,
+                right_transform,
+                &mut GJKCache::new(),
+            ) {
+                if separation.distance > margins {
+                    return None;
+                }
+                return Some(match *strategy {
+                    CollisionOnly => Contact::new(CollisionOnly),
+                    FullResolution => {
+                        let normal =
+                            (separation.right_point - separation.left_point) / separation.distance;
+                        Contact::new_with_point(
+                            FullResolution,
+                            normal,
+                            margins - separation.distance,
+                            separation.left_point + normal * self.margin,
+                        )
+                    }
+                });
+            }
+        }
         self.intersect(left, left_transform, right, right_transform)
             .and_then(|simplex| match *strategy {
                 CollisionOnly => Some(Contact::new(CollisionOnly)),
-                FullResolution => self.get_contact_manifold(
-                    &mut simplex.into_vec(),
-                    left,
-                    left_transform,
-                    right,
-                    right_transform,
-                ),
+                FullResolution => {
+                    scratch.clear();
+                    scratch.extend(simplex);
+                    self.get_contact_manifold(scratch, left, left_transform, right, right_transform)
+                }
             })
     }
 
@@ -585,6 +1152,82 @@ where
     }
 }
 
+/// Separation of the primitives from the simplex reduced to the feature closest to the origin,
+/// and the closest point on that feature.
+///
+/// The barycentric coordinates of the closest point are ill conditioned for sliver simplices, so
+/// only the left point is taken from them, and the right point is placed along the direction to
+/// the closest point, which is accurate.
+fn simplex_separation<P>(simplex: &Simplex<P>, closest: P::Diff) -> Separation<P>
+where
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+    P::Diff: InnerSpace + Neg<Output = P::Diff>,
+{
+    let (left_point, _) = witness_points(simplex, closest);
+    Separation {
+        distance: closest.magnitude(),
+        left_point,
+        right_point: left_point + closest.neg(),
+    }
+}
+
+/// Compute the closest points on the two primitives, from the simplex reduced to the feature
+/// closest to the origin, and the closest point on that feature.
+///
+/// The closest point is written as a convex combination of the simplex vertices, and the same
+/// weights are applied to the support points of the primitives.
+fn witness_points<P>(simplex: &Simplex<P>, closest: P::Diff) -> (P, P)
+where
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+    P::Diff: InnerSpace,
+{
+    let weights = match simplex.len() {
+        1 => vec![P::Scalar::one()],
+        2 => segment_weights(simplex[0].v, simplex[1].v, closest),
+        _ => {
+            let a = simplex[0].v;
+            let v0 = simplex[1].v - a;
+            let v1 = simplex[2].v - a;
+            let v2 = closest - a;
+            let d00 = v0.dot(v0);
+            let d01 = v0.dot(v1);
+            let d11 = v1.dot(v1);
+            let d20 = v2.dot(v0);
+            let d21 = v2.dot(v1);
+            let denom = d00 * d11 - d01 * d01;
+            if denom.is_zero() {
+                let mut weights = segment_weights(simplex[0].v, simplex[1].v, closest);
+                weights.push(P::Scalar::zero());
+                weights
+            } else {
+                let v = (d11 * d20 - d01 * d21) / denom;
+                let w = (d00 * d21 - d01 * d20) / denom;
+                vec![P::Scalar::one() - v - w, v, w]
+            }
+        }
+    };
+    let mut left = P::Diff::zero();
+    let mut right = P::Diff::zero();
+    for (weight, point) in weights.iter().zip(simplex.iter()) {
+        left = left + point.sup_a.to_vec() * *weight;
+        right = right + point.sup_b.to_vec() * *weight;
+    }
+    (P::from_vec(left), P::from_vec(right))
+}
+
+/// Weights of the end points of the segment from `a` to `b` for the point on the segment closest
+/// to `p`
+fn segment_weights<S, V>(a: V, b: V, p: V) -> Vec<S>
+where
+    S: BaseFloat,
+    V: InnerSpace<Scalar = S>,
+{
+    let t = segment_parameter(a, b, p);
+    vec![S::one() - t, t]
+}
+
 #[cfg(test)]
 mod tests {
     use cgmath::{Basis2, Decomposed, Point2, Point3, Quaternion, Rad, Rotation2, Rotation3,
@@ -691,6 +1334,38 @@ mod tests {
         assert_eq!(Point2::new(10., 1.), contact.contact_point);
     }
 
+    #[test]
+    fn test_gjk_circle_hit() {
+        let shape = Circle::new(1.);
+        let left_transform = transform(0., 0., 0.);
+        let right_transform = transform(0.5, 0.5, 0.);
+        let gjk = GJK2::new();
+        let contact = gjk
+            .intersection(
+                &CollisionStrategy::FullResolution,
+                &shape,
+                &left_transform,
+                &shape,
+                &right_transform,
+            )
+            .unwrap();
+        let offset = Vector2::new(0.5, 0.5);
+        assert_ulps_eq!(2. - offset.magnitude(), contact.penetration_depth, epsilon = 1e-3);
+        assert_ulps_eq!(
+            -offset.normalize() * contact.penetration_depth,
+            contact.minimum_translation(),
+            epsilon = 1e-2
+        );
+        assert_ulps_eq!(1., contact.contact_point.to_vec().magnitude(), epsilon = 1e-3);
+        assert_ulps_eq!(1., (contact.right_point() - offset).to_vec().magnitude(), epsilon = 1e-3);
+
+        // swapping the shapes swaps the points
+        let flipped = contact.flipped();
+        assert_ulps_eq!(-contact.normal, flipped.normal);
+        assert_ulps_eq!(contact.right_point(), flipped.left_point());
+        assert_ulps_eq!(contact.left_point(), flipped.right_point(), epsilon = 1e-6);
+    }
+
     #[test]
     fn test_gjk_3d_hit() {
         let left = Cuboid::new(10., 10., 10.);
@@ -714,6 +1389,76 @@ mod tests {
         assert_ulps_eq!(Point3::new(10., 1., 5.), contact.contact_point);
     }
 
+    #[test]
+    fn test_gjk_3d_hit_aligned() {
+        let left = Cuboid::new(10., 10., 10.);
+        let left_transform = transform_3d(15., 0., 0., 0.);
+        let right = Cuboid::new(10., 10., 10.);
+        let right_transform = transform_3d(7., 0., 0., 0.);
+        let gjk = GJK3::new();
+        let contact = gjk
+            .intersection(
+                &CollisionStrategy::FullResolution,
+                &left,
+                &left_transform,
+                &right,
+                &right_transform,
+            )
+            .unwrap();
+        assert_eq!(Vector3::new(-1., 0., 0.), contact.normal);
+        assert_eq!(2., contact.penetration_depth);
+        assert_ulps_eq!(10., contact.contact_point.x);
+    }
+
+    #[test]
+    fn test_gjk_sphere_hit() {
+        let shape = Sphere::new(1.);
+        let left_transform = transform_3d(0., 0., 0., 0.);
+        let right_transform = transform_3d(0.2, 1., -0.8, 0.);
+        let gjk = GJK3::new();
+        let contact = gjk
+            .intersection(
+                &CollisionStrategy::FullResolution,
+                &shape,
+                &left_transform,
+                &shape,
+                &right_transform,
+            )
+            .unwrap();
+        let offset = Vector3::new(0.2, 1., -0.8);
+        assert_ulps_eq!(2. - offset.magnitude(), contact.penetration_depth, epsilon = 1e-2);
+        assert_ulps_eq!(1., contact.normal.dot(offset.normalize()), epsilon = 1e-2);
+    }
+
+    #[test]
+    fn test_gjk_settings() {
+        let shape = Sphere::new(1.);
+        let left_transform = transform_3d(0., 0., 0., 0.);
+        let right_transform = transform_3d(0.2, 1., -0.8, 0.);
+        let gjk = GJK3::new()
+            .with_distance_tolerance(1e-4)
+            .with_epa(EPA3::new().with_tolerance(1e-6).with_max_iterations(200));
+        assert_eq!(1e-4, gjk.distance_tolerance());
+        assert_eq!(MAX_ITERATIONS, gjk.max_iterations());
+        let contact = gjk
+            .intersection(
+                &CollisionStrategy::FullResolution,
+                &shape,
+                &left_transform,
+                &shape,
+                &right_transform,
+            )
+            .unwrap();
+        let offset = Vector3::new(0.2, 1., -0.8);
+        assert_ulps_eq!(2. - offset.magnitude(), contact.penetration_depth, epsilon = 1e-3);
+
+        // too few iterations to enclose the origin
+        let gjk = GJK3::new().with_max_iterations(1);
+        assert!(gjk
+            .intersect(&shape, &left_transform, &shape, &right_transform)
+            .is_none());
+    }
+
     #[test]
     fn test_gjk_distance_2d() {
         let left = Rectangle::new(10., 10.);
@@ -753,6 +1498,310 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gjk_margin() {
+        let gjk = GJK3::new().with_margin(0.1);
+        let cuboid = Cuboid::new(2., 2., 2.);
+        let left_transform = transform_3d(0., 0., 0., 0.);
+
+        let right_transform = transform_3d(3., 0., 0., 0.);
+        let separation = gjk
+            .separation(&cuboid, &left_transform, &cuboid, &right_transform)
+            .unwrap();
+        assert_ulps_eq!(0.8, separation.distance, epsilon = 1e-5);
+        assert_ulps_eq!(1.1, separation.left_point.x, epsilon = 1e-5);
+        assert_ulps_eq!(1.9, separation.right_point.x, epsilon = 1e-5);
+
+        // only the margins overlap
+        let right_transform = transform_3d(2.1, 0., 0., 0.);
+        assert!(gjk
+            .distance(&cuboid, &left_transform, &cuboid, &right_transform)
+            .is_none());
+        let contact = gjk
+            .intersection(
+                &CollisionStrategy::FullResolution,
+                &cuboid,
+                &left_transform,
+                &cuboid,
+                &right_transform,
+            )
+            .unwrap();
+        assert_ulps_eq!(Vector3::unit_x(), contact.normal, epsilon = 1e-5);
+        assert_ulps_eq!(0.1, contact.penetration_depth, epsilon = 1e-5);
+        assert_ulps_eq!(1.1, contact.contact_point.x, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_gjk_margin_thin() {
+        let gjk = GJK3::new().with_margin(0.05);
+        let quad = Quad::new(2., 2.);
+        let left_transform = transform_3d(0., 0., 0., 0.);
+
+        // parallel quads, resting on each other
+        let right_transform = transform_3d(0.5, 0., 0.05, 0.);
+        let contact = gjk
+            .intersection(
+                &CollisionStrategy::FullResolution,
+                &quad,
+                &left_transform,
+                &quad,
+                &right_transform,
+            )
+            .unwrap();
+        assert_ulps_eq!(Vector3::unit_z(), contact.normal, epsilon = 1e-5);
+        assert_ulps_eq!(0.05, contact.penetration_depth, epsilon = 1e-5);
+
+        // crossing quads, the cores overlap
+        let right_transform = Decomposed {
+            disp: Vector3::new(0., 0., 0.5),
+            rot: Quaternion::from_angle_x(Rad(std::f32::consts::FRAC_PI_2)),
+            scale: 1.,
+        };
+        let contact = gjk
+            .intersection(
+                &CollisionStrategy::FullResolution,
+                &quad,
+                &left_transform,
+                &quad,
+                &right_transform,
+            )
+            .unwrap();
+        assert_ulps_eq!(0.6, contact.penetration_depth, epsilon = 1e-3);
+        assert_ulps_eq!(1., contact.normal.magnitude(), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_gjk_closest_points() {
+        let left = Circle::new(1.);
+        let left_transform = transform(0., 0., 0.);
+        let right = Rectangle::new(2., 2.);
+        let right_transform = transform(3., 4., 0.3);
+        let gjk = GJK2::new();
+        let (left_point, right_point) = gjk
+            .closest_points(&left, &left_transform, &right, &right_transform)
+            .unwrap();
+        assert_ulps_eq!(1., left_point.to_vec().magnitude(), epsilon = 1e-5);
+        assert_ulps_eq!(
+            gjk.distance(&left, &left_transform, &right, &right_transform)
+                .unwrap(),
+            left_point.distance(right_point),
+            epsilon = 1e-5
+        );
+
+        // intersects
+        let right_transform = transform(1., 1., 0.3);
+        assert!(gjk
+            .closest_points(&left, &left_transform, &right, &right_transform)
+            .is_none());
+    }
+
+    #[test]
+    fn test_gjk_separation_2d() {
+        let left = Rectangle::new(10., 10.);
+        let left_transform = transform(15., 0., 0.);
+        let right = Rectangle::new(10., 10.);
+        let right_transform = transform(0., 3., 0.);
+        let gjk = GJK2::new();
+        let separation = gjk
+            .separation(&left, &left_transform, &right, &right_transform)
+            .unwrap();
+        assert_eq!(5., separation.distance);
+        assert_eq!(10., separation.left_point.x);
+        assert_eq!(5., separation.right_point.x);
+        assert_eq!(separation.left_point.y, separation.right_point.y);
+        assert!(separation.left_point.y >= -2. && separation.left_point.y <= 5.);
+
+        let right_transform = transform(7., 2., 0.);
+        assert!(
+            gjk.separation(&left, &left_transform, &right, &right_transform)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_gjk_separation_3d() {
+        let left = Sphere::new(1.);
+        let left_transform = transform_3d(0., 0., 0., 0.);
+        let right = Cuboid::new(2., 2., 2.);
+        let right_transform = transform_3d(3., 4., 0., 0.3);
+        let gjk = GJK3::new();
+        let separation = gjk
+            .separation(&left, &left_transform, &right, &right_transform)
+            .unwrap();
+        assert_ulps_eq!(
+            separation.left_point.distance(separation.right_point),
+            separation.distance,
+            epsilon = 1e-5
+        );
+        assert_ulps_eq!(1., separation.left_point.to_vec().magnitude(), epsilon = 1e-5);
+
+        let right = Sphere::new(1.);
+        let right_transform = transform_3d(3., 4., 0., 0.);
+        let separation = gjk
+            .separation(&left, &left_transform, &right, &right_transform)
+            .unwrap();
+        assert_ulps_eq!(3., separation.distance, epsilon = 1e-3);
+        assert_ulps_eq!(Point3::new(0.6, 0.8, 0.), separation.left_point, epsilon = 1e-3);
+        assert_ulps_eq!(Point3::new(2.4, 3.2, 0.), separation.right_point, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_gjk_cache() {
+        let left = Cuboid::new(2., 2., 2.);
+        let right = Cuboid::new(2., 2., 2.);
+        let gjk = GJK3::new();
+        let mut cache = GJKCache::new();
+
+        // separated, the separating axis of the last frame is still separating
+        let left_transform = transform_3d(0., 0., 0., 0.3);
+        for i in 0..5 {
+            let right_transform = transform_3d(3. + i as f32 * 0.01, 1., 0.5, 0.);
+            assert!(gjk
+                .intersect_cached(&left, &left_transform, &right, &right_transform, &mut cache)
+                .is_none());
+            if i > 0 {
+                assert_eq!(1, cache.iterations());
+            }
+        }
+
+        // colliding, the warm query needs no more iterations than a cold one
+        let right_transform = transform_3d(1.5, 1., 0.5, 0.);
+        assert!(gjk
+            .intersect_cached(&left, &left_transform, &right, &right_transform, &mut cache)
+            .is_some());
+        let right_transform = transform_3d(1.49, 1.01, 0.5, 0.);
+        let mut cold = GJKCache::new();
+        assert!(gjk
+            .intersect_cached(&left, &left_transform, &right, &right_transform, &mut cold)
+            .is_some());
+        assert!(gjk
+            .intersect_cached(&left, &left_transform, &right, &right_transform, &mut cache)
+            .is_some());
+        assert!(cache.iterations() <= cold.iterations());
+
+        // distance queries give the same result with and without the cache
+        let mut cache = GJKCache::new();
+        for i in 0..3 {
+            let right_transform = transform_3d(4., 1. + i as f32 * 0.1, 0.5, 0.);
+            let warm = gjk
+                .separation_cached(&left, &left_transform, &right, &right_transform, &mut cache)
+                .unwrap();
+            let expected = gjk
+                .separation(&left, &left_transform, &right, &right_transform)
+                .unwrap();
+            assert_ulps_eq!(expected.distance, warm.distance, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_gjk_cache_overlapping() {
+        // the simplex around the origin is moved with the primitives, and still encloses it
+        let left = Cuboid::new(2., 2., 2.);
+        let right = Sphere::new(1.);
+        let gjk = GJK3::new();
+        let left_transform = transform_3d(0., 0., 0., 0.3);
+        let mut cache = GJKCache::new();
+        let right_transform = transform_3d(1.5, 0.5, 0.2, 0.);
+        assert!(gjk
+            .intersect_cached(&left, &left_transform, &right, &right_transform, &mut cache)
+            .is_some());
+        let first = cache.iterations();
+        assert!(first > 1);
+        for i in 1..5 {
+            let left_transform = transform_3d(0., 0., 0., 0.3 + i as f32 * 0.01);
+            let right_transform = transform_3d(1.5 - i as f32 * 0.01, 0.5, 0.2, 0.);
+            let simplex = gjk
+                .intersect_cached(&left, &left_transform, &right, &right_transform, &mut cache)
+                .unwrap();
+            assert_eq!(1, cache.iterations());
+            assert_eq!(4, simplex.len());
+            // the moved simplex can be used for the contact like a new one
+            let contact = gjk
+                .intersection(
+                    &CollisionStrategy::FullResolution,
+                    &left,
+                    &left_transform,
+                    &right,
+                    &right_transform,
+                )
+                .unwrap();
+            let mut simplex = simplex.into_vec();
+            let warm = gjk
+                .get_contact_manifold(
+                    &mut simplex,
+                    &left,
+                    &left_transform,
+                    &right,
+                    &right_transform,
+                )
+                .unwrap();
+            assert_ulps_eq!(contact.normal, warm.normal, epsilon = 1e-3);
+            assert_ulps_eq!(contact.penetration_depth, warm.penetration_depth, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_gjk_cache_separation_iterations() {
+        let left = Cuboid::new(2., 2., 2.);
+        let right = Sphere::new(1.);
+        let gjk = GJK3::new();
+        let left_transform = transform_3d(0., 0., 0., 0.3);
+        let mut cache = GJKCache::new();
+        let right_transform = transform_3d(3., 2., 0.5, 0.);
+        gjk.separation_cached(&left, &left_transform, &right, &right_transform, &mut cache)
+            .unwrap();
+        let first = cache.iterations();
+
+        let right_transform = transform_3d(3.01, 2., 0.49, 0.);
+        let warm = gjk
+            .separation_cached(&left, &left_transform, &right, &right_transform, &mut cache)
+            .unwrap();
+        let mut cold = GJKCache::new();
+        let expected = gjk
+            .separation_cached(&left, &left_transform, &right, &right_transform, &mut cold)
+            .unwrap();
+        assert!(cache.iterations() < first);
+        assert!(cache.iterations() < cold.iterations());
+        assert_ulps_eq!(expected.distance, warm.distance, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_gjk_intersection_batch() {
+        let gjk = GJK2::new();
+        let shapes = vec![
+            (Circle::new(1.), transform(0., 0., 0.)),
+            (Circle::new(1.), transform(1.5, 0., 0.)),
+            (Circle::new(1.), transform(10., 0., 0.)),
+            (Circle::new(1.), transform(10., 1., 0.)),
+        ];
+        let pairs = [(0, 1), (0, 2), (1, 2), (2, 3)];
+        let mut contacts = Vec::default();
+        gjk.intersection_batch(
+            &CollisionStrategy::FullResolution,
+            &shapes,
+            &pairs,
+            &mut contacts,
+        );
+        assert_eq!(2, contacts.len());
+        let (left, right, ref contact) = contacts[0];
+        assert_eq!((0, 1), (left, right));
+        assert_ulps_eq!(0.5, contact.penetration_depth, epsilon = 1e-2);
+        assert_ulps_eq!(Vector2::new(1., 0.), contact.normal, epsilon = 1e-2);
+        let (left, right, ref contact) = contacts[1];
+        assert_eq!((2, 3), (left, right));
+        assert_ulps_eq!(1., contact.penetration_depth, epsilon = 1e-2);
+        assert_ulps_eq!(Vector2::new(0., 1.), contact.normal, epsilon = 1e-2);
+
+        // the output is cleared before reuse
+        gjk.intersection_batch(
+            &CollisionStrategy::CollisionOnly,
+            &shapes,
+            &pairs[1..3],
+            &mut contacts,
+        );
+        assert!(contacts.is_empty());
+    }
+
     #[test]
     fn test_gjk_time_of_impact_2d() {
         let left = Rectangle::new(10., 20.);
@@ -6,7 +6,7 @@ use cgmath::prelude::*;
 use approx::ulps_eq;
 
 use super::{Simplex, SimplexProcessor};
-use crate::primitive::util::{get_closest_point_on_edge, triple_product};
+use crate::primitive::util::{closest_point_on_segment, triple_product};
 
 /// Simplex processor implementation for 2D. Only to be used in [`GJK`](struct.GJK.html).
 #[derive(Debug)]
@@ -69,22 +69,41 @@ where
     /// Get the closest point on the simplex to the origin.
     ///
     /// Make simplex only retain the closest feature to the origin.
+    ///
+    /// Unlike the intersection test, the distance computation can not assume that the origin is
+    /// on the side of the newest point, so all edges of a triangle are tested.
     fn get_closest_point_to_origin(&self, simplex: &mut Simplex<Point2<S>>) -> Vector2<S> {
-        let mut d = Vector2::zero();
-
-        // reduce simplex to the closest feature to the origin
-        // if check_origin return true, the origin is inside the simplex, so return the zero vector
-        // if not, the simplex will be the closest edge to the origin, and d the normal of the edge
-        // in the direction of the origin
-        if self.reduce_to_closest_feature(simplex, &mut d) {
-            return d;
+        if simplex.len() == 3 {
+            let a = simplex[2].v;
+            let b = simplex[1].v;
+            let c = simplex[0].v;
+            let abo = (b - a).perp_dot(a.neg());
+            let bco = (c - b).perp_dot(b.neg());
+            let cao = (a - c).perp_dot(c.neg());
+            let inside = (abo >= S::zero() && bco >= S::zero() && cao >= S::zero())
+                || (abo <= S::zero() && bco <= S::zero() && cao <= S::zero());
+            if inside {
+                return Vector2::zero();
+            }
+            // keep the edge with the closest point, the vertex opposite to it is removed
+            let (remove, _) = [(0, a, b), (2, b, c), (1, c, a)]
+                .iter()
+                .map(|&(i, start, end)| {
+                    let p = closest_point_on_segment(start, end, Vector2::zero());
+                    (i, p.magnitude2())
+                })
+                .fold(
+                    (0, S::infinity()),
+                    |min, (i, d)| if d < min.1 { (i, d) } else { min },
+                );
+            simplex.remove(remove);
         }
 
         // compute closest point to origin on the simplex (which is now an edge)
         if simplex.len() == 1 {
             simplex[0].v
         } else {
-            get_closest_point_on_edge(&simplex[1].v, &simplex[0].v, &Vector2::zero())
+            closest_point_on_segment(simplex[1].v, simplex[0].v, Vector2::zero())
         }
     }
 
@@ -193,6 +212,48 @@ mod tests {
         assert_ulps_eq!(Vector2::new(0., 10.), p);
     }
 
+    #[test]
+    fn test_closest_point_to_origin_edge_vertex() {
+        let processor = SimplexProcessor2::new();
+        let mut simplex = smallvec![sup(3., 4.), sup(10., 4.)];
+        let p = processor.get_closest_point_to_origin(&mut simplex);
+        assert_eq!(2, simplex.len());
+        assert_ulps_eq!(Vector2::new(3., 4.), p);
+    }
+
+    #[test]
+    fn test_closest_point_to_origin_triangle_vertex() {
+        let processor = SimplexProcessor2::new();
+        let mut simplex = smallvec![sup(2., 1.), sup(5., 1.), sup(2., 6.)];
+        let p = processor.get_closest_point_to_origin(&mut simplex);
+        assert_eq!(2, simplex.len());
+        assert!(simplex.iter().any(|s| s.v == Vector2::new(2., 1.)));
+        assert_ulps_eq!(Vector2::new(2., 1.), p);
+    }
+
+    #[test]
+    fn test_closest_point_to_origin_triangle_each_edge() {
+        // the origin beyond each edge in turn, also the edge without the newest point
+        let processor = SimplexProcessor2::new();
+        for &(offset, removed) in &[
+            (Vector2::new(0., -2.), Vector2::new(0., 3.)),
+            (Vector2::new(2.5, 2.), Vector2::new(-3., -1.)),
+            (Vector2::new(-2.5, 2.), Vector2::new(3., -1.)),
+        ] {
+            let mut simplex: Simplex<_> = [(3., -1.), (-3., -1.), (0., 3.)]
+                .iter()
+                .map(|&(x, y)| sup(x - offset.x, y - offset.y))
+                .collect();
+            let p = processor.get_closest_point_to_origin(&mut simplex);
+            assert_eq!(2, simplex.len());
+            assert!(simplex.iter().all(|s| s.v != removed - offset));
+            // the closest point is on the kept edge, and perpendicular to it
+            let edge = simplex[1].v - simplex[0].v;
+            assert_ulps_eq!(0., edge.dot(p), epsilon = 1e-5);
+            assert!(p.magnitude() < offset.magnitude());
+        }
+    }
+
     fn sup(x: f32, y: f32) -> SupportPoint<Point2<f32>> {
         let mut s = SupportPoint::new();
         s.v = Vector2::new(x, y);
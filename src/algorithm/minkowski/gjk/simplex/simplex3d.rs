@@ -3,11 +3,10 @@ use std::ops::Neg;
 
 use cgmath::{BaseFloat, Point3, Vector3};
 use cgmath::prelude::*;
-use cgmath::num_traits::cast;
 use approx::ulps_eq;
 
 use super::{Simplex, SimplexProcessor};
-use crate::primitive::util::{barycentric_vector, get_closest_point_on_edge};
+use crate::primitive::util::{closest_point_on_segment, closest_point_on_triangle};
 
 /// Simplex processor implementation for 3D. Only to be used in [`GJK`](struct.GJK.html).
 #[derive(Debug)]
@@ -41,7 +40,7 @@ where
 
             // origin outside plane ABC, remove D and check side
             // need to check both edges
-            if abc.dot(ao) > S::zero() {
+            if is_outside(&abc, &ao, &ab) {
                 simplex.remove(0);
                 check_side(&abc, &ab, &ac, &ao, simplex, v, true, false);
             } else {
@@ -49,14 +48,14 @@ where
                 let acd = ac.cross(ad);
                 // origin outside plane ACD, remove B and check side
                 // no need to test first edge, since that region is also over ABC
-                if acd.dot(ao) > S::zero() {
+                if is_outside(&acd, &ao, &ac) {
                     simplex.remove(2);
                     check_side(&acd, &ac, &ad, &ao, simplex, v, true, true);
                 } else {
                     let adb = ad.cross(ab);
                     // origin outside plane ADB, remove C and check side
                     // no need to test edges, since those regions are covered in earlier tests
-                    if adb.dot(ao) > S::zero() {
+                    if is_outside(&adb, &ao, &ad) {
                         // [b, d, a]
                         simplex.remove(1);
                         simplex.swap(0, 1);
@@ -89,8 +88,12 @@ where
             let ab = b - a;
 
             *v = cross_aba(&ab, &ao);
-            if ulps_eq!(*v, Vector3::zero()) {
-                v.x = cast(0.1).unwrap();
+            // origin is on the line through the edge, search in any direction perpendicular to it
+            if !is_outside(&v.normalize(), &ao, &ab) {
+                *v = ab.cross(Vector3::unit_x());
+                if ulps_eq!(*v, Vector3::zero()) {
+                    *v = ab.cross(Vector3::unit_y());
+                }
             }
         }
         // 0-1 points
@@ -100,24 +103,62 @@ where
     /// Get the closest point on the simplex to the origin.
     ///
     /// Make simplex only retain the closest feature to the origin.
+    ///
+    /// Unlike the intersection test, the distance computation can not assume that the origin is
+    /// on the side of the newest point, so all faces of a tetrahedron, and all edges and vertices
+    /// of a triangle, are tested.
     fn get_closest_point_to_origin(&self, simplex: &mut Simplex<Point3<S>>) -> Vector3<S> {
-        let mut d = Vector3::zero();
-
-        // reduce simplex to the closest feature to the origin
-        // if check_origin return true, the origin is inside the simplex, so return the zero vector
-        // if not, the simplex will be the closest face or edge to the origin, and d the normal of
-        // the feature in the direction of the origin
-        if self.reduce_to_closest_feature(simplex, &mut d) {
-            return d;
-        }
-
-        if simplex.len() == 1 {
-            simplex[0].v
-        } else if simplex.len() == 2 {
-            get_closest_point_on_edge(&simplex[1].v, &simplex[0].v, &Vector3::zero())
-        } else {
-            get_closest_point_on_face(&simplex[2].v, &simplex[1].v, &simplex[0].v, &d)
-        }
+        let keep = match simplex.len() {
+            4 => {
+                let points = [simplex[0].v, simplex[1].v, simplex[2].v, simplex[3].v];
+                let mut closest: Option<(Vector3<S>, [bool; 4])> = None;
+                for &(i, j, k, opposite) in
+                    &[(0, 1, 2, 3), (0, 1, 3, 2), (0, 2, 3, 1), (1, 2, 3, 0)]
+                {
+                    let (a, b, c) = (points[i], points[j], points[k]);
+                    let normal = (b - a).cross(c - a);
+                    // only faces with the origin on the outside can hold the closest point, faces
+                    // of a flat tetrahedron are always tested
+                    if normal.dot(-a) * normal.dot(points[opposite] - a) > S::zero() {
+                        continue;
+                    }
+                    let (p, face_keep) = closest_point_on_triangle(a, b, c, Vector3::zero());
+                    let closer = match closest {
+                        Some((q, _)) => p.magnitude2() < q.magnitude2(),
+                        None => true,
+                    };
+                    if closer {
+                        let mut keep = [false; 4];
+                        keep[i] = face_keep[0];
+                        keep[j] = face_keep[1];
+                        keep[k] = face_keep[2];
+                        closest = Some((p, keep));
+                    }
+                }
+                match closest {
+                    Some((p, keep)) => (p, keep),
+                    // origin is inside the tetrahedron
+                    None => return Vector3::zero(),
+                }
+            }
+            3 => {
+                let (a, b, c) = (simplex[0].v, simplex[1].v, simplex[2].v);
+                let (p, keep) = closest_point_on_triangle(a, b, c, Vector3::zero());
+                (p, [keep[0], keep[1], keep[2], false])
+            }
+            2 => {
+                return closest_point_on_segment(simplex[1].v, simplex[0].v, Vector3::zero());
+            }
+            1 => return simplex[0].v,
+            _ => return Vector3::zero(),
+        };
+        let (p, keep) = keep;
+        let mut i = 0;
+        simplex.retain(|_| {
+            i += 1;
+            keep[i - 1]
+        });
+        p
     }
 
     fn new() -> Self {
@@ -127,45 +168,24 @@ where
     }
 }
 
+/// Relative tolerance used for deciding which side of a feature the origin is on
 #[inline]
-fn get_closest_point_on_face<S>(
-    a: &Vector3<S>,
-    b: &Vector3<S>,
-    c: &Vector3<S>,
-    normal: &Vector3<S>,
-) -> Vector3<S>
+fn tolerance<S>() -> S
 where
     S: BaseFloat,
 {
-    use crate::{Continuous, Plane, Ray3};
-    let ap = Point3::from_vec(*a);
-    let bp = Point3::from_vec(*b);
-    let cp = Point3::from_vec(*c);
-    let ray = Ray3::new(Point3::origin(), -*normal);
-    // unwrapping is safe, because the degenerate face will have been removed by the outer algorithm
-    let plane = Plane::from_points(ap, bp, cp).unwrap();
-    match plane.intersection(&ray) {
-        Some(point) => {
-            let (u, v, w) = barycentric_vector(point.to_vec(), *a, *b, *c);
-            assert!(
-                in_range(u) && in_range(v) && in_range(w),
-                "Simplex processing failed to deduce that this simplex {:?} is an edge case",
-                [a, b, c]
-            );
-
-            point.to_vec()
-        }
-
-        _ => Vector3::zero(),
-    }
+    S::default_epsilon().sqrt()
 }
 
+/// Check if the origin is outside the plane or edge with the given outward normal, where `edge`
+/// is an edge of the feature, used for scale. If the origin is on the plane or edge, within the
+/// precision of the computation, it is not outside.
 #[inline]
-fn in_range<S>(v: S) -> bool
+fn is_outside<S>(normal: &Vector3<S>, ao: &Vector3<S>, edge: &Vector3<S>) -> bool
 where
     S: BaseFloat,
 {
-    v >= S::zero() && v <= S::one()
+    normal.dot(*ao) > tolerance::<S>() * normal.magnitude() * edge.magnitude()
 }
 
 #[inline]
@@ -189,25 +209,35 @@ fn check_side<S>(
 ) where
     S: BaseFloat,
 {
-    let ab_perp = ab.cross(*abc);
+    let outside_ab = !ignore_ab && is_outside(&ab.cross(*abc), ao, ab);
+    let outside_ac = is_outside(&abc.cross(*ac), ao, ac);
 
     // origin outside AB, remove C and v = edge normal towards origin
-    if !ignore_ab && ab_perp.dot(*ao) > S::zero() {
+    if outside_ab && ab.dot(*ao) > S::zero() {
         simplex.remove(0);
         *v = cross_aba(ab, ao);
         return;
     }
 
-    let ac_perp = abc.cross(*ac);
-
     // origin outside AC, remove B and v = edge normal towards origin
-    if ac_perp.dot(*ao) > S::zero() {
+    if outside_ac && ac.dot(*ao) > S::zero() {
         simplex.remove(1);
         *v = cross_aba(ac, ao);
         return;
-        // origin above triangle, set v = surface normal towards origin
     }
 
+    // origin outside the triangle, beyond A, so only A is kept and v = direction to origin.
+    // This can not happen in the intersection test, but can in the distance computation.
+    if outside_ab || outside_ac {
+        let a = simplex[simplex.len() - 1];
+        simplex.clear();
+        simplex.push(a);
+        *v = *ao;
+        return;
+    }
+
+    // origin above triangle, set v = surface normal towards origin
+
     if above || abc.dot(*ao) > S::zero() {
         // [c, b, a]
         *v = *abc;
@@ -251,6 +281,15 @@ mod tests {
         assert_ulps_eq!(0., v.z);
     }
 
+    #[test]
+    fn test_check_side_outside_a() {
+        let mut simplex = smallvec![sup(1., 5., 0.), sup(5., 1., 0.), sup(1., 1., 0.)];
+        let v = test_check_side(&mut simplex, false, false);
+        assert_eq!(1, simplex.len());
+        assert_eq!(Vector3::new(1., 1., 0.), simplex[0].v); // only A should be in the simplex
+        assert_eq!(Vector3::new(-1., -1., 0.), v);
+    }
+
     #[test]
     fn test_check_side_above() {
         let mut simplex = smallvec![sup(5., -10., -1.), sup(-4., -10., -1.), sup(0., 5., -1.)];
@@ -300,6 +339,16 @@ mod tests {
         assert_eq!(Vector3::new(0., 810., 0.), v);
     }
 
+    #[test]
+    fn test_check_origin_line_through_origin() {
+        let mut simplex = smallvec![sup(-2., 0., 0.), sup(3., 0., 0.)];
+        let (hit, v) = test_check_origin(&mut simplex);
+        assert!(!hit);
+        assert_eq!(2, simplex.len());
+        assert_eq!(0., v.x);
+        assert!(v.magnitude2() > 0.);
+    }
+
     #[test]
     fn test_check_origin_triangle() {
         let mut simplex = smallvec![sup(5., -10., -1.), sup(-4., -10., -1.), sup(0., 5., -1.)];
@@ -371,6 +420,90 @@ mod tests {
         assert_eq!(4, simplex.len());
     }
 
+    #[test]
+    fn test_closest_point_to_origin_edge() {
+        let mut simplex = smallvec![sup(-2., 1., 1.), sup(2., 1., 1.)];
+        let p = test_closest_point(&mut simplex);
+        assert_eq!(2, simplex.len());
+        assert_ulps_eq!(Vector3::new(0., 1., 1.), p);
+
+        let mut simplex = smallvec![sup(1., 1., 1.), sup(3., 1., 1.)];
+        let p = test_closest_point(&mut simplex);
+        assert_ulps_eq!(Vector3::new(1., 1., 1.), p);
+    }
+
+    #[test]
+    fn test_closest_point_to_origin_triangle_regions() {
+        // vertex region
+        let mut simplex = smallvec![sup(1., 1., 1.), sup(4., 1., 1.), sup(1., 4., 1.)];
+        let p = test_closest_point(&mut simplex);
+        assert_eq!(1, simplex.len());
+        assert_eq!(Vector3::new(1., 1., 1.), simplex[0].v);
+        assert_ulps_eq!(Vector3::new(1., 1., 1.), p);
+
+        // edge region, of the edge without the newest point
+        let mut simplex = smallvec![sup(-2., 1., 1.), sup(2., 1., 1.), sup(0., 4., 1.)];
+        let p = test_closest_point(&mut simplex);
+        assert_eq!(2, simplex.len());
+        assert_eq!(Vector3::new(-2., 1., 1.), simplex[0].v);
+        assert_eq!(Vector3::new(2., 1., 1.), simplex[1].v);
+        assert_ulps_eq!(Vector3::new(0., 1., 1.), p);
+
+        // face region
+        let mut simplex = smallvec![sup(-2., -1., 1.), sup(2., -1., 1.), sup(0., 3., 1.)];
+        let p = test_closest_point(&mut simplex);
+        assert_eq!(3, simplex.len());
+        assert_ulps_eq!(Vector3::new(0., 0., 1.), p);
+    }
+
+    #[test]
+    fn test_closest_point_to_origin_tetrahedron_regions() {
+        // the tetrahedron has a face at z = 1 and its apex at z = 4, the origin is below the face
+        let tetrahedron = |dx: f32, dy: f32| -> Simplex<Point3<f32>> {
+            smallvec![
+                sup(-2. + dx, -1. + dy, 1.),
+                sup(2. + dx, -1. + dy, 1.),
+                sup(dx, 3. + dy, 1.),
+                sup(dx, dy, 4.)
+            ]
+        };
+
+        // face region, of the face without the newest point
+        let mut simplex = tetrahedron(0., 0.);
+        let p = test_closest_point(&mut simplex);
+        assert_eq!(3, simplex.len());
+        assert!(simplex.iter().all(|s| s.v.z == 1.));
+        assert_ulps_eq!(Vector3::new(0., 0., 1.), p);
+
+        // edge region
+        let mut simplex = tetrahedron(0., 2.);
+        let p = test_closest_point(&mut simplex);
+        assert_eq!(2, simplex.len());
+        assert_eq!(Vector3::new(-2., 1., 1.), simplex[0].v);
+        assert_eq!(Vector3::new(2., 1., 1.), simplex[1].v);
+        assert_ulps_eq!(Vector3::new(0., 1., 1.), p);
+
+        // vertex region
+        let mut simplex = tetrahedron(3., 2.);
+        let p = test_closest_point(&mut simplex);
+        assert_eq!(1, simplex.len());
+        assert_eq!(Vector3::new(1., 1., 1.), simplex[0].v);
+        assert_ulps_eq!(Vector3::new(1., 1., 1.), p);
+
+        // inside
+        let mut simplex = tetrahedron(0., 0.);
+        for s in simplex.iter_mut() {
+            s.v.z -= 2.;
+        }
+        let p = test_closest_point(&mut simplex);
+        assert_eq!(4, simplex.len());
+        assert_eq!(Vector3::zero(), p);
+    }
+
+    fn test_closest_point(simplex: &mut Simplex<Point3<f32>>) -> Vector3<f32> {
+        SimplexProcessor3::new().get_closest_point_to_origin(simplex)
+    }
+
     fn test_check_origin(simplex: &mut Simplex<Point3<f32>>) -> (bool, Vector3<f32>) {
         let mut v = Vector3::zero();
         let b = SimplexProcessor3::new().reduce_to_closest_feature(simplex, &mut v);
@@ -1,7 +1,8 @@
 //! Algorithms using the Minkowski Sum/Difference
 
 pub use self::epa::{EPA2, EPA3, EPA};
-pub use self::gjk::{GJK2, GJK3, SimplexProcessor, GJK};
+pub use self::gjk::{GJKCache, Separation, GJK2, GJK3, SimplexProcessor, GJK};
+pub use self::mpr::MPR3;
 
 use std::ops::{Neg, Sub};
 
@@ -10,6 +11,7 @@ use crate::prelude::*;
 
 mod epa;
 mod gjk;
+mod mpr;
 
 /// Minkowski Sum/Difference support point
 #[derive(Clone, Debug, Copy)]
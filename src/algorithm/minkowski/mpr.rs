@@ -0,0 +1,463 @@
+//! Minkowski Portal Refinement collision detection algorithm.
+
+use std::marker;
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+use cgmath::num_traits::NumCast;
+
+use super::SupportPoint;
+use crate::{CollisionStrategy, Contact};
+use crate::prelude::*;
+use crate::primitive::util::barycentric_vector;
+
+const MAX_ITERATIONS: u32 = 100;
+const MPR_TOLERANCE: f32 = 0.00001;
+
+/// Minkowski Portal Refinement (MPR), also known as XenoCollide, narrow phase collision detection
+/// algorithm for 3D.
+///
+/// An alternative to [`GJK3`](type.GJK3.html) and [`EPA3`](struct.EPA3.html). MPR casts a ray
+/// from a point inside the Minkowski difference of the primitives, the difference of their
+/// origins in world space, towards the origin, and finds the triangle (the portal) on the
+/// boundary of the Minkowski difference where the ray exits. The primitives intersect if the
+/// origin is on the inside of the portal.
+///
+/// MPR only works with triangles, so it has no degenerate simplex cases like GJK, and it is
+/// robust for touching and barely intersecting primitives. The contact it returns is not the
+/// minimum penetration, like the contact from EPA, but the penetration along the line between the
+/// origins of the primitives, which is a good approximation for shallow contacts. The normal
+/// points from the left primitive towards the right primitive, like for EPA.
+///
+/// The origins of the primitives must be inside the primitives in model space, which is the case
+/// for all the primitives in this crate.
+#[derive(Debug)]
+pub struct MPR3<S> {
+    m: marker::PhantomData<S>,
+    tolerance: S,
+    max_iterations: u32,
+}
+
+impl<S> MPR3<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new MPR algorithm implementation
+    pub fn new() -> Self {
+        Self::new_with_settings(NumCast::from(MPR_TOLERANCE).unwrap(), MAX_ITERATIONS)
+    }
+
+    /// Create a new MPR algorithm implementation with the given tolerance settings
+    pub fn new_with_settings(tolerance: S, max_iterations: u32) -> Self {
+        Self {
+            m: marker::PhantomData,
+            tolerance,
+            max_iterations,
+        }
+    }
+
+    /// Do intersection test on the given primitives
+    ///
+    /// ## Parameters:
+    ///
+    /// - `left`: left primitive
+    /// - `left_transform`: model-to-world-transform for the left primitive
+    /// - `right`: right primitive,
+    /// - `right_transform`: model-to-world-transform for the right primitive
+    ///
+    /// ## Returns:
+    ///
+    /// Will return true if the primitives intersect or touch.
+    pub fn intersect<PL, PR, TL, TR>(
+        &self,
+        left: &PL,
+        left_transform: &TL,
+        right: &PR,
+        right_transform: &TR,
+    ) -> bool
+    where
+        PL: Primitive<Point = Point3<S>>,
+        PR: Primitive<Point = Point3<S>>,
+        TL: Transform<Point3<S>>,
+        TR: Transform<Point3<S>>,
+    {
+        let minkowski = Minkowski {
+            left,
+            left_transform,
+            right,
+            right_transform,
+        };
+        match self.discover_portal(&minkowski) {
+            Discovery::Miss => false,
+            Discovery::Segment(..) => true,
+            Discovery::Portal(portal) => self.refine_portal(&minkowski, portal, false).is_some(),
+        }
+    }
+
+    /// Do intersection testing on the given primitives, and return the contact manifold.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `strategy`: strategy to use, if `CollisionOnly` it will only return a boolean result,
+    ///   otherwise, the portal will be refined until it is on the boundary of the Minkowski
+    ///   difference, to compute the contact point.
+    /// - `left`: left primitive
+    /// - `left_transform`: model-to-world-transform for the left primitive
+    /// - `right`: right primitive,
+    /// - `right_transform`: model-to-world-transform for the right primitive
+    ///
+    /// ## Returns:
+    ///
+    /// Will optionally return a `Contact` if a collision was detected. In `CollisionOnly` mode,
+    /// this contact will only be a boolean result. For `FullResolution` mode, the contact will
+    /// contain a full manifold (collision normal, penetration depth and contact point on the left
+    /// primitive).
+    pub fn intersection<PL, PR, TL, TR>(
+        &self,
+        strategy: &CollisionStrategy,
+        left: &PL,
+        left_transform: &TL,
+        right: &PR,
+        right_transform: &TR,
+    ) -> Option<Contact<Point3<S>>>
+    where
+        PL: Primitive<Point = Point3<S>>,
+        PR: Primitive<Point = Point3<S>>,
+        TL: Transform<Point3<S>>,
+        TR: Transform<Point3<S>>,
+    {
+        use CollisionStrategy::*;
+        let minkowski = Minkowski {
+            left,
+            left_transform,
+            right,
+            right_transform,
+        };
+        let full = *strategy == FullResolution;
+        let contact = match self.discover_portal(&minkowski) {
+            Discovery::Miss => return None,
+            Discovery::Segment(v0, v1) => {
+                // the origin is on the segment between the interior point and the support point
+                let normal = (v1.v - v0.v).normalize();
+                Contact::new_with_point(FullResolution, normal, v1.v.dot(normal), v1.sup_a)
+            }
+            Discovery::Portal(portal) => self.refine_portal(&minkowski, portal, full)?,
+        };
+        if full {
+            Some(contact)
+        } else {
+            Some(Contact::new(CollisionOnly))
+        }
+    }
+
+    /// Find a portal, a triangle of support points that the ray from the interior point towards
+    /// the origin passes through.
+    fn discover_portal<PL, PR, TL, TR>(&self, minkowski: &Minkowski<PL, PR, TL, TR>) -> Discovery<S>
+    where
+        PL: Primitive<Point = Point3<S>>,
+        PR: Primitive<Point = Point3<S>>,
+        TL: Transform<Point3<S>>,
+        TR: Transform<Point3<S>>,
+    {
+        let origin = minkowski.left_transform.transform_point(Point3::origin());
+        let right_origin = minkowski.right_transform.transform_point(Point3::origin());
+        let mut v0 = SupportPoint {
+            v: origin - right_origin,
+            sup_a: origin,
+            sup_b: right_origin,
+        };
+        if v0.v.magnitude2() <= S::default_epsilon() {
+            // the origins coincide, move the interior point a little off the origin
+            v0.v = Vector3::new(self.tolerance, S::zero(), S::zero());
+        }
+
+        let mut n = -v0.v;
+        let v1 = minkowski.support(&n);
+        if v1.v.dot(n) <= S::zero() {
+            return Discovery::Miss;
+        }
+
+        n = v1.v.cross(v0.v);
+        if n.magnitude2() <= S::default_epsilon() * v1.v.magnitude2() * v0.v.magnitude2() {
+            // the origin is on the ray from the interior point to the support point, and the
+            // support point is beyond the origin
+            return Discovery::Segment(v0, v1);
+        }
+
+        let mut v2 = minkowski.support(&n);
+        if v2.v.dot(n) <= S::zero() {
+            return Discovery::Miss;
+        }
+
+        let mut v1 = v1;
+        n = (v1.v - v0.v).cross(v2.v - v0.v);
+        if n.dot(v0.v) > S::zero() {
+            ::std::mem::swap(&mut v1, &mut v2);
+            n = -n;
+        }
+
+        for _ in 0..self.max_iterations {
+            let v3 = minkowski.support(&n);
+            if v3.v.dot(n) <= S::zero() {
+                return Discovery::Miss;
+            }
+            if v1.v.cross(v3.v).dot(v0.v) < S::zero() {
+                // the origin is outside the plane through v0, v1 and v3, replace v2
+                v2 = v3;
+                n = (v1.v - v0.v).cross(v3.v - v0.v);
+            } else if v3.v.cross(v2.v).dot(v0.v) < S::zero() {
+                // the origin is outside the plane through v0, v3 and v2, replace v1
+                v1 = v3;
+                n = (v3.v - v0.v).cross(v2.v - v0.v);
+            } else {
+                return Discovery::Portal([v0, v1, v2, v3]);
+            }
+        }
+        Discovery::Miss
+    }
+
+    /// Move the portal towards the boundary of the Minkowski difference, until the origin is known
+    /// to be inside or outside the boundary. If `full` is set, keep refining until the portal is
+    /// on the boundary, and compute the contact.
+    fn refine_portal<PL, PR, TL, TR>(
+        &self,
+        minkowski: &Minkowski<PL, PR, TL, TR>,
+        portal: [SupportPoint<Point3<S>>; 4],
+        full: bool,
+    ) -> Option<Contact<Point3<S>>>
+    where
+        PL: Primitive<Point = Point3<S>>,
+        PR: Primitive<Point = Point3<S>>,
+        TL: Transform<Point3<S>>,
+        TR: Transform<Point3<S>>,
+    {
+        let [v0, mut v1, mut v2, mut v3] = portal;
+        let mut hit = false;
+        for _ in 0..self.max_iterations {
+            let n = (v2.v - v1.v).cross(v3.v - v1.v);
+            if n.magnitude2() <= S::default_epsilon() * S::default_epsilon() {
+                // degenerate portal, the refinement has converged
+                break;
+            }
+            let n = n.normalize();
+            if !hit && n.dot(v1.v) >= S::zero() {
+                // the origin is inside the portal
+                hit = true;
+                if !full {
+                    break;
+                }
+            }
+            let v4 = minkowski.support(&n);
+            if !hit && v4.v.dot(n) < S::zero() {
+                // the origin is outside the support plane
+                return None;
+            }
+            if (v4.v - v3.v).dot(n) <= self.tolerance {
+                // the portal is on the boundary
+                break;
+            }
+            // choose the new portal, the one of the three triangles through v4 that the ray from
+            // v0 to the origin passes through
+            let c = v4.v.cross(v0.v);
+            if v1.v.dot(c) > S::zero() {
+                if v2.v.dot(c) > S::zero() {
+                    v1 = v4;
+                } else {
+                    v3 = v4;
+                }
+            } else if v3.v.dot(c) > S::zero() {
+                v2 = v4;
+            } else {
+                v1 = v4;
+            }
+        }
+        if !hit {
+            let n = (v2.v - v1.v).cross(v3.v - v1.v);
+            if n.dot(v1.v) < S::zero() {
+                return None;
+            }
+        }
+        Some(contact(&v1, &v2, &v3))
+    }
+}
+
+impl<S> Default for MPR3<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build the contact from the final portal. The normal is the portal normal, and the contact
+/// point is the projection of the origin on the portal, interpolated on the left primitive.
+fn contact<S>(
+    v1: &SupportPoint<Point3<S>>,
+    v2: &SupportPoint<Point3<S>>,
+    v3: &SupportPoint<Point3<S>>,
+) -> Contact<Point3<S>>
+where
+    S: BaseFloat,
+{
+    let n = (v2.v - v1.v).cross(v3.v - v1.v).normalize();
+    let depth = n.dot(v1.v);
+    let (u, v, w) = barycentric_vector(n * depth, v1.v, v2.v, v3.v);
+    let point = v1.sup_a * u + v2.sup_a.to_vec() * v + v3.sup_a.to_vec() * w;
+    Contact::new_with_point(CollisionStrategy::FullResolution, n, depth, point)
+}
+
+/// Result of the portal discovery phase
+enum Discovery<S>
+where
+    S: BaseFloat,
+{
+    /// The primitives are separated
+    Miss,
+    /// The origin is on the segment between the interior point and the first support point
+    Segment(SupportPoint<Point3<S>>, SupportPoint<Point3<S>>),
+    /// The interior point followed by the three points of the portal
+    Portal([SupportPoint<Point3<S>>; 4]),
+}
+
+/// The Minkowski difference of two transformed primitives
+struct Minkowski<'a, PL: 'a, PR: 'a, TL: 'a, TR: 'a> {
+    left: &'a PL,
+    left_transform: &'a TL,
+    right: &'a PR,
+    right_transform: &'a TR,
+}
+
+impl<'a, S, PL, PR, TL, TR> Minkowski<'a, PL, PR, TL, TR>
+where
+    S: BaseFloat,
+    PL: Primitive<Point = Point3<S>>,
+    PR: Primitive<Point = Point3<S>>,
+    TL: Transform<Point3<S>>,
+    TR: Transform<Point3<S>>,
+{
+    fn support(&self, direction: &Vector3<S>) -> SupportPoint<Point3<S>> {
+        SupportPoint::from_minkowski(
+            self.left,
+            self.left_transform,
+            self.right,
+            self.right_transform,
+            direction,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK3;
+    use crate::primitive::*;
+
+    fn transform(
+        x: f32,
+        y: f32,
+        z: f32,
+        angle_z: f32,
+    ) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            disp: Vector3::new(x, y, z),
+            rot: Quaternion::from_angle_z(Rad(angle_z)),
+            scale: 1.,
+        }
+    }
+
+    #[test]
+    fn test_mpr_miss() {
+        let left = Cuboid::new(10., 10., 10.);
+        let right = Sphere::new(5.);
+        let mpr = MPR3::new();
+        assert!(!mpr.intersect(
+            &left,
+            &transform(15., 0., 0., 0.),
+            &right,
+            &transform(4., 0., 0., 0.)
+        ));
+        assert!(!mpr.intersect(
+            &left,
+            &transform(0., 0., 0., 0.3),
+            &left,
+            &transform(11., 11., 0., 0.)
+        ));
+        assert!(mpr
+            .intersection(
+                &CollisionStrategy::FullResolution,
+                &left,
+                &transform(15., 0., 0., 0.),
+                &right,
+                &transform(4., 0., 0., 0.),
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_mpr_hit() {
+        let left = Cuboid::new(10., 10., 10.);
+        let right = Sphere::new(5.);
+        let mpr = MPR3::new();
+        let left_transform = transform(15., 0., 0., 0.);
+        let right_transform = transform(7., 0., 0., 0.);
+        assert!(mpr.intersect(&left, &left_transform, &right, &right_transform));
+        let contact = mpr.intersection(
+            &CollisionStrategy::FullResolution,
+            &left,
+            &left_transform,
+            &right,
+            &right_transform,
+        ).unwrap();
+        assert_ulps_eq!(Vector3::new(-1., 0., 0.), contact.normal, epsilon = 1e-4);
+        assert_ulps_eq!(2., contact.penetration_depth, epsilon = 1e-4);
+        assert_ulps_eq!(10., contact.contact_point.x, epsilon = 1e-4);
+
+        let contact = mpr.intersection(
+            &CollisionStrategy::CollisionOnly,
+            &left,
+            &left_transform,
+            &right,
+            &right_transform,
+        ).unwrap();
+        assert_eq!(CollisionStrategy::CollisionOnly, contact.strategy);
+    }
+
+    #[test]
+    fn test_mpr_same_origin() {
+        let shape = Cuboid::new(2., 2., 2.);
+        let t = transform(0., 0., 0., 0.);
+        let mpr = MPR3::new();
+        assert!(mpr.intersect(&shape, &t, &shape, &t));
+        assert!(mpr
+            .intersection(&CollisionStrategy::FullResolution, &shape, &t, &shape, &t)
+            .is_some());
+    }
+
+    #[test]
+    fn test_mpr_matches_gjk() {
+        let left = Cuboid::new(2., 2., 2.);
+        let right = Sphere::new(1.);
+        let mpr = MPR3::new();
+        let gjk = GJK3::new();
+        for &(x, y, z, angle) in &[
+            (1.5, 0., 0., 0.3),
+            (0., 2.5, 0.5, 0.),
+            (1.2, 1.2, 0., 0.2),
+            (2.5, 1., 0., 0.),
+            (0.5, -1.9, 0., 0.7),
+        ] {
+            let left_transform = transform(0., 0., 0., angle);
+            let right_transform = transform(x, y, z, 0.);
+            let expected = gjk.intersect(&left, &left_transform, &right, &right_transform)
+                .is_some();
+            assert_eq!(
+                expected,
+                mpr.intersect(&left, &left_transform, &right, &right_transform)
+            );
+        }
+    }
+}
@@ -1,4 +1,9 @@
 //! Collision detection algorithms
 
 pub mod minkowski;
+pub mod analytic;
 pub mod broad_phase;
+pub mod convexhull;
+pub mod decomposition;
+pub mod sat;
+pub mod shape_cast;
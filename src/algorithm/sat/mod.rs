@@ -0,0 +1,15 @@
+//! Separating axis test for convex shapes
+//!
+//! An alternative to [`GJK`](../minkowski/struct.GJK.html) and EPA for polygons and polyhedra,
+//! that tests the face normals of the shapes, and in 3D the cross products of their edges, as
+//! candidate separating axes. Besides the penetration axis and depth, it finds the features of
+//! the shapes that define the axis, which gives more stable contacts for boxy shapes.
+//!
+//! The features are used to build contact manifolds with several contact points, by clipping the
+//! incident edge or face of one shape against the reference edge or face of the other shape.
+
+pub use self::sat2::{polygon_circle, polygon_manifold, polygon_polygon, Overlap2, ReferenceShape};
+pub use self::sat3::{polyhedron_manifold, polyhedron_polyhedron, Feature3, Overlap3};
+
+mod sat2;
+mod sat3;
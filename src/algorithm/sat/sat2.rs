@@ -0,0 +1,557 @@
+use cgmath::{BaseFloat, Point2, Vector2};
+use cgmath::prelude::*;
+
+use crate::{ContactFeature, ContactId, Manifold, ManifoldPoint};
+use crate::primitive::{Circle, ConvexPolygon};
+
+/// Which of the two shapes the reference edge of an [`Overlap2`](struct.Overlap2.html) belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceShape {
+    /// The reference edge is on the left shape
+    Left,
+    /// The reference edge is on the right shape
+    Right,
+}
+
+/// Overlap of two 2D shapes, found by the separating axis test.
+///
+/// Edge `i` of a polygon is the edge from vertex `i` to vertex `i + 1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overlap2<S> {
+    /// Axis of least penetration, in world space, pointing from the left shape towards the right
+    /// shape. This follows the same convention as the normal of a
+    /// [`Contact`](../../struct.Contact.html).
+    pub axis: Vector2<S>,
+
+    /// Penetration depth along the axis
+    pub depth: S,
+
+    /// The shape the reference edge belongs to
+    pub reference: ReferenceShape,
+
+    /// Index of the reference edge, whose outward normal is the axis (negated if the reference
+    /// shape is the right shape). `None` if the axis is not an edge normal, which happens when
+    /// a circle overlaps a polygon vertex.
+    pub reference_edge: Option<usize>,
+
+    /// Index of the incident edge, the edge on the other shape with the normal most opposite to
+    /// the normal of the reference edge. `None` if the other shape is a circle.
+    pub incident_edge: Option<usize>,
+}
+
+/// Separating axis test between two convex polygons.
+///
+/// ## Parameters:
+///
+/// - `left`: left polygon, with vertices in CCW order
+/// - `left_transform`: model-to-world-transform for the left polygon
+/// - `right`: right polygon, with vertices in CCW order
+/// - `right_transform`: model-to-world-transform for the right polygon
+///
+/// ## Returns:
+///
+/// Will return the overlap if the polygons intersect, or None if a separating axis was found.
+/// Touching polygons overlap with zero depth. The reference edge is on the left polygon, unless
+/// the right polygon has an edge with noticeably less penetration.
+pub fn polygon_polygon<S, TL, TR>(
+    left: &ConvexPolygon<S>,
+    left_transform: &TL,
+    right: &ConvexPolygon<S>,
+    right_transform: &TR,
+) -> Option<Overlap2<S>>
+where
+    S: BaseFloat,
+    TL: Transform<Point2<S>>,
+    TR: Transform<Point2<S>>,
+{
+    let left = world_vertices(left, left_transform);
+    let right = world_vertices(right, right_transform);
+    let (left_edge, left_separation) = max_separation(&left, &right)?;
+    let (right_edge, right_separation) = max_separation(&right, &left)?;
+
+    // prefer the left polygon, so the reference edge does not flip between frames when the
+    // separations are almost equal
+    let tolerance = S::default_epsilon().sqrt();
+    let (reference, edge, separation, reference_vertices, incident_vertices) =
+        if right_separation > left_separation + tolerance {
+            (
+                ReferenceShape::Right,
+                right_edge,
+                right_separation,
+                &right,
+                &left,
+            )
+        } else {
+            (
+                ReferenceShape::Left,
+                left_edge,
+                left_separation,
+                &left,
+                &right,
+            )
+        };
+    let normal = edge_normal(reference_vertices, edge);
+    let incident_edge = (0..incident_vertices.len())
+        .map(|i| (i, edge_normal(incident_vertices, i).dot(normal)))
+        .fold(
+            (0, S::infinity()),
+            |min, (i, d)| if d < min.1 { (i, d) } else { min },
+        )
+        .0;
+    Some(Overlap2 {
+        axis: match reference {
+            ReferenceShape::Left => normal,
+            ReferenceShape::Right => -normal,
+        },
+        depth: -separation,
+        reference,
+        reference_edge: Some(edge),
+        incident_edge: Some(incident_edge),
+    })
+}
+
+/// Separating axis test between a convex polygon and a circle.
+///
+/// ## Parameters:
+///
+/// - `left`: left polygon, with vertices in CCW order
+/// - `left_transform`: model-to-world-transform for the polygon
+/// - `right`: right circle
+/// - `right_transform`: model-to-world-transform for the circle
+///
+/// ## Returns:
+///
+/// Will return the overlap if the shapes intersect, or None if a separating axis was found. The
+/// reference edge is always on the polygon, and there is no incident edge.
+pub fn polygon_circle<S, TL, TR>(
+    left: &ConvexPolygon<S>,
+    left_transform: &TL,
+    right: &Circle<S>,
+    right_transform: &TR,
+) -> Option<Overlap2<S>>
+where
+    S: BaseFloat,
+    TL: Transform<Point2<S>>,
+    TR: Transform<Point2<S>>,
+{
+    let vertices = world_vertices(left, left_transform);
+    let center = right_transform.transform_point(Point2::origin());
+    let radius = right_transform
+        .transform_vector(Vector2::new(right.radius, S::zero()))
+        .magnitude();
+
+    let mut edge = 0;
+    let mut separation = S::neg_infinity();
+    for i in 0..vertices.len() {
+        let s = edge_normal(&vertices, i).dot(center - vertices[i]);
+        if s > radius {
+            return None;
+        }
+        if s > separation {
+            edge = i;
+            separation = s;
+        }
+    }
+    if vertices.is_empty() {
+        return None;
+    }
+
+    let overlap = |axis, depth, reference_edge| {
+        Some(Overlap2 {
+            axis,
+            depth,
+            reference: ReferenceShape::Left,
+            reference_edge,
+            incident_edge: None,
+        })
+    };
+
+    // the center is outside the polygon, and may be in the region of one of the end vertices of
+    // the edge, in which case the axis is the direction from that vertex to the center
+    if separation > S::zero() {
+        let start = vertices[edge];
+        let end = vertices[(edge + 1) % vertices.len()];
+        let vertex = if (center - start).dot(end - start) < S::zero() {
+            Some(start)
+        } else if (center - end).dot(start - end) < S::zero() {
+            Some(end)
+        } else {
+            None
+        };
+        if let Some(vertex) = vertex {
+            let d = center - vertex;
+            let distance = d.magnitude();
+            if distance > radius {
+                return None;
+            }
+            return overlap(d / distance, radius - distance, None);
+        }
+    }
+    overlap(
+        edge_normal(&vertices, edge),
+        radius - separation,
+        Some(edge),
+    )
+}
+
+/// Contact manifold for two convex polygons.
+///
+/// Finds the reference and incident edges with
+/// [`polygon_polygon`](fn.polygon_polygon.html), clips the incident edge against the side planes
+/// of the reference edge, and keeps the clipped points behind the reference edge as the contact
+/// points, which gives at most two points. The id of a contact point is the reference edge and an
+/// incident vertex, or a reference vertex and the incident edge for clipped points.
+///
+/// ## Parameters:
+///
+/// - `left`: left polygon, with vertices in CCW order
+/// - `left_transform`: model-to-world-transform for the left polygon
+/// - `right`: right polygon, with vertices in CCW order
+/// - `right_transform`: model-to-world-transform for the right polygon
+///
+/// ## Returns:
+///
+/// Will return the manifold if the polygons intersect, or None if a separating axis was found.
+pub fn polygon_manifold<S, TL, TR>(
+    left: &ConvexPolygon<S>,
+    left_transform: &TL,
+    right: &ConvexPolygon<S>,
+    right_transform: &TR,
+) -> Option<Manifold<Point2<S>>>
+where
+    S: BaseFloat,
+    TL: Transform<Point2<S>>,
+    TR: Transform<Point2<S>>,
+{
+    let overlap = polygon_polygon(left, left_transform, right, right_transform)?;
+    let left = world_vertices(left, left_transform);
+    let right = world_vertices(right, right_transform);
+    let (reference, incident) = match overlap.reference {
+        ReferenceShape::Left => (&left, &right),
+        ReferenceShape::Right => (&right, &left),
+    };
+    let edge = overlap.reference_edge?;
+    let incident_edge = overlap.incident_edge?;
+
+    let end_index = (edge + 1) % reference.len();
+    let start = reference[edge];
+    let end = reference[end_index];
+    let normal = edge_normal(reference, edge);
+    let tangent = (end - start).normalize();
+
+    // the ids have the reference feature on the left until the end
+    let reference_edge = ContactFeature::edge(edge, end_index);
+    let incident_end = (incident_edge + 1) % incident.len();
+    let mut points = vec![
+        (
+            incident[incident_edge],
+            ContactId::new(reference_edge, ContactFeature::Vertex(incident_edge)),
+        ),
+        (
+            incident[incident_end],
+            ContactId::new(reference_edge, ContactFeature::Vertex(incident_end)),
+        ),
+    ];
+    let incident_side = ContactFeature::edge(incident_edge, incident_end);
+    for &(side, offset, vertex) in &[
+        (-tangent, -tangent.dot(start.to_vec()), edge),
+        (tangent, tangent.dot(end.to_vec()), end_index),
+    ] {
+        let id = ContactId::new(ContactFeature::Vertex(vertex), incident_side);
+        points = clip_segment(&points, side, offset, id);
+    }
+
+    let tolerance = S::default_epsilon().sqrt();
+    let points = points
+        .into_iter()
+        .filter_map(|(p, id)| {
+            let depth = -normal.dot(p - start);
+            if depth < -tolerance {
+                return None;
+            }
+            let depth = depth.max(S::zero());
+            // the contact points are on the left polygon
+            let (point, id) = match overlap.reference {
+                ReferenceShape::Left => (p + normal * depth, id),
+                ReferenceShape::Right => (p, id.flipped()),
+            };
+            Some(ManifoldPoint { point, depth, id })
+        })
+        .collect();
+    Some(Manifold {
+        normal: overlap.axis,
+        points,
+    })
+}
+
+/// Clip a segment against a plane, keeping the part of the segment where
+/// `normal.dot(p) <= offset`. A clipped end point gets the given id.
+fn clip_segment<S>(
+    segment: &[(Point2<S>, ContactId)],
+    normal: Vector2<S>,
+    offset: S,
+    id: ContactId,
+) -> Vec<(Point2<S>, ContactId)>
+where
+    S: BaseFloat,
+{
+    if segment.len() < 2 {
+        return segment.to_vec();
+    }
+    let (a, b) = (segment[0], segment[1]);
+    let (da, db) = (
+        normal.dot(a.0.to_vec()) - offset,
+        normal.dot(b.0.to_vec()) - offset,
+    );
+    let clipped = (a.0 + (b.0 - a.0) * (da / (da - db)), id);
+    match (da <= S::zero(), db <= S::zero()) {
+        (true, true) => vec![a, b],
+        (false, false) => vec![],
+        (true, false) => vec![a, clipped],
+        (false, true) => vec![clipped, b],
+    }
+}
+
+/// Vertices of the polygon in world space
+fn world_vertices<S, T>(polygon: &ConvexPolygon<S>, transform: &T) -> Vec<Point2<S>>
+where
+    S: BaseFloat,
+    T: Transform<Point2<S>>,
+{
+    polygon
+        .vertices()
+        .iter()
+        .map(|v| transform.transform_point(*v))
+        .collect()
+}
+
+/// Outward unit normal of the given edge of a polygon with CCW vertices
+fn edge_normal<S>(vertices: &[Point2<S>], edge: usize) -> Vector2<S>
+where
+    S: BaseFloat,
+{
+    let e = vertices[(edge + 1) % vertices.len()] - vertices[edge];
+    Vector2::new(e.y, -e.x).normalize()
+}
+
+/// Find the edge of the reference polygon with the largest separation from the other polygon.
+/// The separation of an edge is the distance from the edge to the vertex of the other polygon
+/// furthest behind it, negative if the polygons overlap along the edge normal.
+///
+/// Returns None if a separating edge was found.
+fn max_separation<S>(reference: &[Point2<S>], other: &[Point2<S>]) -> Option<(usize, S)>
+where
+    S: BaseFloat,
+{
+    if reference.is_empty() || other.is_empty() {
+        return None;
+    }
+    let mut best = (0, S::neg_infinity());
+    for i in 0..reference.len() {
+        let normal = edge_normal(reference, i);
+        let separation = other
+            .iter()
+            .map(|v| normal.dot(*v - reference[i]))
+            .fold(S::infinity(), S::min);
+        if separation > S::zero() {
+            return None;
+        }
+        if separation > best.1 {
+            best = (i, separation);
+        }
+    }
+    Some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Basis2, Decomposed, Point2, Rad, Rotation2, Vector2};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_polygon_polygon() {
+        let left = square();
+        let right = square();
+        let overlap = polygon_polygon(
+            &left,
+            &transform(0., 0., 0.),
+            &right,
+            &transform(1.5, 0.2, 0.),
+        )
+        .unwrap();
+        assert_ulps_eq!(Vector2::new(1., 0.), overlap.axis);
+        assert_ulps_eq!(0.5, overlap.depth);
+        assert_eq!(ReferenceShape::Left, overlap.reference);
+        assert_eq!(Some(1), overlap.reference_edge);
+        assert_eq!(Some(3), overlap.incident_edge);
+
+        assert!(polygon_polygon(
+            &left,
+            &transform(0., 0., 0.),
+            &right,
+            &transform(2.1, 0., 0.)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_polygon_polygon_rotated() {
+        // the corner of the rotated right square pokes into the top edge of the left square
+        let left = square();
+        let right = square();
+        let d = 1. + 2f32.sqrt() - 0.25;
+        let overlap = polygon_polygon(
+            &left,
+            &transform(0., 0., 0.),
+            &right,
+            &transform(0., d, std::f32::consts::FRAC_PI_4),
+        )
+        .unwrap();
+        assert_ulps_eq!(Vector2::new(0., 1.), overlap.axis);
+        assert_ulps_eq!(0.25, overlap.depth, epsilon = 1e-5);
+        assert_eq!(ReferenceShape::Left, overlap.reference);
+        assert_eq!(Some(2), overlap.reference_edge);
+
+        // swapped, the reference edge is still the flat edge, now on the right shape
+        let overlap = polygon_polygon(
+            &right,
+            &transform(0., d, std::f32::consts::FRAC_PI_4),
+            &left,
+            &transform(0., 0., 0.),
+        )
+        .unwrap();
+        assert_ulps_eq!(Vector2::new(0., -1.), overlap.axis);
+        assert_eq!(ReferenceShape::Right, overlap.reference);
+        assert_eq!(Some(2), overlap.reference_edge);
+    }
+
+    #[test]
+    fn test_polygon_circle() {
+        let left = square();
+        let circle = Circle::new(0.5);
+
+        // face region
+        let overlap = polygon_circle(
+            &left,
+            &transform(0., 0., 0.),
+            &circle,
+            &transform(0.2, 1.3, 0.),
+        )
+        .unwrap();
+        assert_ulps_eq!(Vector2::new(0., 1.), overlap.axis);
+        assert_ulps_eq!(0.2, overlap.depth, epsilon = 1e-6);
+        assert_eq!(Some(2), overlap.reference_edge);
+        assert_eq!(None, overlap.incident_edge);
+
+        // vertex region
+        let overlap = polygon_circle(
+            &left,
+            &transform(0., 0., 0.),
+            &circle,
+            &transform(1.3, 1.4, 0.),
+        )
+        .unwrap();
+        assert_ulps_eq!(Vector2::new(0.6, 0.8), overlap.axis, epsilon = 1e-6);
+        assert_ulps_eq!(0., overlap.depth, epsilon = 1e-6);
+        assert_eq!(None, overlap.reference_edge);
+        assert!(polygon_circle(
+            &left,
+            &transform(0., 0., 0.),
+            &circle,
+            &transform(1.4, 1.4, 0.)
+        )
+        .is_none());
+
+        // center inside the polygon
+        let overlap = polygon_circle(
+            &left,
+            &transform(0., 0., 0.),
+            &circle,
+            &transform(-0.8, 0., 0.),
+        )
+        .unwrap();
+        assert_ulps_eq!(Vector2::new(-1., 0.), overlap.axis);
+        assert_ulps_eq!(0.7, overlap.depth, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_polygon_manifold() {
+        // the right square rests on the top edge of the left square, overlapping half of it
+        let left = square();
+        let right = square();
+        let manifold = polygon_manifold(
+            &left,
+            &transform(0., 0., 0.),
+            &right,
+            &transform(1., 1.9, 0.),
+        )
+        .unwrap();
+        assert_ulps_eq!(Vector2::new(0., 1.), manifold.normal);
+        assert_eq!(2, manifold.points.len());
+        for p in &manifold.points {
+            assert_ulps_eq!(0.1, p.depth, epsilon = 1e-6);
+            assert_ulps_eq!(1., p.point.y, epsilon = 1e-6);
+        }
+        let mut xs: Vec<_> = manifold.points.iter().map(|p| p.point.x).collect();
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_ulps_eq!(0., xs[0], epsilon = 1e-6);
+        assert_ulps_eq!(1., xs[1], epsilon = 1e-6);
+
+        // one point is a vertex of the right square, the other is clipped at a left vertex
+        let ids: Vec<_> = manifold.points.iter().map(|p| p.id).collect();
+        assert!(ids.contains(&ContactId::new(
+            ContactFeature::Edge(2, 3),
+            ContactFeature::Vertex(0)
+        )));
+        assert!(ids.contains(&ContactId::new(
+            ContactFeature::Vertex(2),
+            ContactFeature::Edge(0, 1)
+        )));
+
+        // the ids stay the same when the square moves a little
+        let moved = polygon_manifold(
+            &left,
+            &transform(0., 0., 0.),
+            &right,
+            &transform(1.1, 1.85, 0.),
+        )
+        .unwrap();
+        assert!(moved.points.iter().all(|p| ids.contains(&p.id)));
+
+        // a corner poking into the edge gives a single point
+        let d = 1. + 2f32.sqrt() - 0.25;
+        let manifold = polygon_manifold(
+            &left,
+            &transform(0., 0., 0.),
+            &right,
+            &transform(0., d, std::f32::consts::FRAC_PI_4),
+        )
+        .unwrap();
+        assert_eq!(1, manifold.points.len());
+        assert_ulps_eq!(0.25, manifold.max_depth(), epsilon = 1e-5);
+        assert_ulps_eq!(
+            Point2::new(0., 1.),
+            manifold.points[0].point,
+            epsilon = 1e-5
+        );
+    }
+
+    fn square() -> ConvexPolygon<f32> {
+        ConvexPolygon::new(vec![
+            Point2::new(-1., -1.),
+            Point2::new(1., -1.),
+            Point2::new(1., 1.),
+            Point2::new(-1., 1.),
+        ])
+    }
+
+    fn transform(x: f32, y: f32, angle: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            disp: Vector2::new(x, y),
+            rot: Rotation2::from_angle(Rad(angle)),
+            scale: 1.,
+        }
+    }
+}
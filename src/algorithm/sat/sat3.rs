@@ -0,0 +1,720 @@
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{ContactFeature, ContactId, Manifold, ManifoldPoint};
+use crate::primitive::ConvexPolyhedron;
+use crate::primitive::util::closest_points_on_segments;
+
+/// The features of two polyhedra that define the axis of an
+/// [`Overlap3`](struct.Overlap3.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature3 {
+    /// The axis is the normal of the face with the given index on the left polyhedron
+    LeftFace(usize),
+    /// The axis is the negated normal of the face with the given index on the right polyhedron
+    RightFace(usize),
+    /// The axis is the cross product of the edge with the first index on the left polyhedron,
+    /// and the edge with the second index on the right polyhedron. The indices are half edge
+    /// indices.
+    Edges(usize, usize),
+}
+
+/// Overlap of two polyhedra, found by the separating axis test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overlap3<S> {
+    /// Axis of least penetration, in world space, pointing from the left polyhedron towards the
+    /// right polyhedron. This follows the same convention as the normal of a
+    /// [`Contact`](../../struct.Contact.html).
+    pub axis: Vector3<S>,
+
+    /// Penetration depth along the axis
+    pub depth: S,
+
+    /// The features the axis comes from
+    pub feature: Feature3,
+}
+
+/// Separating axis test between two convex polyhedra.
+///
+/// Tests the face normals of both polyhedra, and the cross products of pairs of edges. Only edge
+/// pairs that build a face on the Minkowski difference are tested, which is checked by testing
+/// if the arcs of the edges on the Gauss map of the polyhedra intersect. Edges between coplanar
+/// faces are never tested.
+///
+/// ## Parameters:
+///
+/// - `left`: left polyhedron
+/// - `left_transform`: model-to-world-transform for the left polyhedron
+/// - `right`: right polyhedron
+/// - `right_transform`: model-to-world-transform for the right polyhedron
+///
+/// ## Returns:
+///
+/// Will return the overlap if the polyhedra intersect, or None if a separating axis was found.
+/// Touching polyhedra overlap with zero depth. Face axes are preferred over edge axes, unless the
+/// edge axis has noticeably less penetration, because face contacts are more stable.
+///
+/// ## Panics
+///
+/// If any of the polyhedra was created without faces.
+pub fn polyhedron_polyhedron<S, TL, TR>(
+    left: &ConvexPolyhedron<S>,
+    left_transform: &TL,
+    right: &ConvexPolyhedron<S>,
+    right_transform: &TR,
+) -> Option<Overlap3<S>>
+where
+    S: BaseFloat,
+    TL: Transform<Point3<S>>,
+    TR: Transform<Point3<S>>,
+{
+    let left = WorldPolyhedron::new(left, left_transform);
+    let right = WorldPolyhedron::new(right, right_transform);
+
+    let (left_face, left_separation) = max_face_separation(&left, &right)?;
+    let (right_face, right_separation) = max_face_separation(&right, &left)?;
+    let edge = max_edge_separation(&left, &right)?;
+
+    let tolerance = S::default_epsilon().sqrt();
+    let face = if right_separation > left_separation + tolerance {
+        Overlap3 {
+            axis: -right.face_normals[right_face].0,
+            depth: -right_separation,
+            feature: Feature3::RightFace(right_face),
+        }
+    } else {
+        Overlap3 {
+            axis: left.face_normals[left_face].0,
+            depth: -left_separation,
+            feature: Feature3::LeftFace(left_face),
+        }
+    };
+    match edge {
+        Some(edge) if edge.depth + tolerance < face.depth => Some(edge),
+        _ => Some(face),
+    }
+}
+
+/// Contact manifold for two convex polyhedra.
+///
+/// Finds the axis of least penetration with
+/// [`polyhedron_polyhedron`](fn.polyhedron_polyhedron.html). For a face axis, the side of the
+/// other polyhedron that is most anti-parallel to that face, the incident side, is clipped
+/// against the side planes of the reference face (Sutherland-Hodgman), and the clipped points
+/// behind the reference face are the contact points. If there are more than four, the four that
+/// span the largest area are kept, starting with the deepest point. For an edge axis, the
+/// manifold has a single point, the closest point on the left edge to the right edge.
+///
+/// The id of a contact point is the reference face and an incident vertex, a reference edge and an
+/// incident edge for points clipped by the side planes, a reference vertex and the incident face
+/// for points clipped by two side planes, or the two edges for an edge axis.
+///
+/// ## Parameters:
+///
+/// - `left`: left polyhedron
+/// - `left_transform`: model-to-world-transform for the left polyhedron
+/// - `right`: right polyhedron
+/// - `right_transform`: model-to-world-transform for the right polyhedron
+///
+/// ## Returns:
+///
+/// Will return the manifold if the polyhedra intersect, or None if a separating axis was found.
+///
+/// ## Panics
+///
+/// If any of the polyhedra was created without faces.
+pub fn polyhedron_manifold<S, TL, TR>(
+    left: &ConvexPolyhedron<S>,
+    left_transform: &TL,
+    right: &ConvexPolyhedron<S>,
+    right_transform: &TR,
+) -> Option<Manifold<Point3<S>>>
+where
+    S: BaseFloat,
+    TL: Transform<Point3<S>>,
+    TR: Transform<Point3<S>>,
+{
+    let overlap = polyhedron_polyhedron(left, left_transform, right, right_transform)?;
+    let points = match overlap.feature {
+        Feature3::LeftFace(face) => {
+            let points = clip_incident_side(left, left_transform, face, right, right_transform);
+            // move the points from the right polyhedron to the reference face on the left
+            points
+                .into_iter()
+                .map(|p| ManifoldPoint {
+                    point: p.point + overlap.axis * p.depth,
+                    depth: p.depth,
+                    id: p.id,
+                })
+                .collect()
+        }
+        Feature3::RightFace(face) => {
+            clip_incident_side(right, right_transform, face, left, left_transform)
+                .into_iter()
+                .map(|p| ManifoldPoint {
+                    id: p.id.flipped(),
+                    ..p
+                })
+                .collect()
+        }
+        Feature3::Edges(left_edge, right_edge) => {
+            let (p1, q1) = left.edge_segment(left_edge);
+            let (p2, q2) = right.edge_segment(right_edge);
+            let (point, _) = closest_points_on_segments(
+                left_transform.transform_point(p1),
+                left_transform.transform_point(q1),
+                right_transform.transform_point(p2),
+                right_transform.transform_point(q2),
+            );
+            let (a, b) = left.edge_vertices(left_edge);
+            let (c, d) = right.edge_vertices(right_edge);
+            vec![ManifoldPoint {
+                point,
+                depth: overlap.depth,
+                id: ContactId::new(ContactFeature::edge(a, b), ContactFeature::edge(c, d)),
+            }]
+        }
+    };
+    Some(Manifold {
+        normal: overlap.axis,
+        points: reduce(points),
+    })
+}
+
+/// Clip the incident side of the incident polyhedron against the side planes of the given face of
+/// the reference polyhedron. Returns the points of the incident polyhedron behind the reference
+/// face, in world space, with the reference feature on the left of the ids.
+fn clip_incident_side<S, TR, TI>(
+    reference: &ConvexPolyhedron<S>,
+    reference_transform: &TR,
+    face: usize,
+    incident: &ConvexPolyhedron<S>,
+    incident_transform: &TI,
+) -> Vec<ManifoldPoint<Point3<S>>>
+where
+    S: BaseFloat,
+    TR: Transform<Point3<S>>,
+    TI: Transform<Point3<S>>,
+{
+    let reference_indices = reference.facet(face);
+    let reference_side: Vec<_> = reference_indices
+        .iter()
+        .map(|&i| reference_transform.transform_point(reference.vertex_position(i)))
+        .collect();
+    let normal = (reference_side[1] - reference_side[0])
+        .cross(reference_side[2] - reference_side[0])
+        .normalize();
+
+    let incident_face = incident
+        .face_normals()
+        .map(|(n, _)| incident_transform.transform_vector(n).dot(normal))
+        .enumerate()
+        .fold(
+            (0, S::infinity()),
+            |min, (i, d)| if d < min.1 { (i, d) } else { min },
+        )
+        .0;
+    let incident_indices = incident.facet(incident_face);
+    let n = incident_indices.len();
+    let mut points: Vec<_> = (0..n)
+        .map(|k| {
+            let vertex = incident_indices[k];
+            ClipVertex {
+                point: incident_transform.transform_point(incident.vertex_position(vertex)),
+                id: ContactId::new(ContactFeature::Face(face), ContactFeature::Vertex(vertex)),
+                next_edge: ClipEdge::Incident(vertex, incident_indices[(k + 1) % n]),
+            }
+        })
+        .collect();
+
+    for i in 0..reference_side.len() {
+        let j = (i + 1) % reference_side.len();
+        let start = reference_side[i];
+        let side = (reference_side[j] - start).cross(normal);
+        points = clip(
+            &points,
+            side,
+            side.dot(start.to_vec()),
+            (reference_indices[i], reference_indices[j]),
+            incident_face,
+        );
+    }
+
+    let tolerance = S::default_epsilon().sqrt();
+    points
+        .into_iter()
+        .filter_map(|p| {
+            let depth = -normal.dot(p.point - reference_side[0]);
+            if depth >= -tolerance {
+                Some(ManifoldPoint {
+                    point: p.point,
+                    depth: depth.max(S::zero()),
+                    id: p.id,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The line a clipped edge of the incident side lies on
+#[derive(Debug, Clone, Copy)]
+enum ClipEdge {
+    /// Edge between the given vertices of the incident polyhedron
+    Incident(usize, usize),
+    /// Side plane through the edge between the given vertices of the reference polyhedron
+    Reference(usize, usize),
+}
+
+/// A vertex of the incident side while clipping
+#[derive(Debug, Clone, Copy)]
+struct ClipVertex<S> {
+    point: Point3<S>,
+    id: ContactId,
+    /// The line the edge to the next vertex lies on
+    next_edge: ClipEdge,
+}
+
+/// Sutherland-Hodgman clipping of a convex polygon against the side plane through the given
+/// reference edge, keeping the part of the polygon where `normal.dot(p) <= offset`
+fn clip<S>(
+    polygon: &[ClipVertex<S>],
+    normal: Vector3<S>,
+    offset: S,
+    reference_edge: (usize, usize),
+    incident_face: usize,
+) -> Vec<ClipVertex<S>>
+where
+    S: BaseFloat,
+{
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    let distance = |p: &ClipVertex<S>| normal.dot(p.point.to_vec()) - offset;
+    let (a, b) = reference_edge;
+    for i in 0..polygon.len() {
+        let previous = polygon[(i + polygon.len() - 1) % polygon.len()];
+        let current = polygon[i];
+        let (dp, dc) = (distance(&previous), distance(&current));
+        if (dp <= S::zero()) != (dc <= S::zero()) {
+            let id = match previous.next_edge {
+                ClipEdge::Incident(c, d) => {
+                    ContactId::new(ContactFeature::edge(a, b), ContactFeature::edge(c, d))
+                }
+                // the point is on two side planes, which meet at a vertex if they are neighbours
+                ClipEdge::Reference(c, d) => {
+                    let reference = if c == a || c == b {
+                        ContactFeature::Vertex(c)
+                    } else if d == a || d == b {
+                        ContactFeature::Vertex(d)
+                    } else {
+                        ContactFeature::edge(a, b)
+                    };
+                    ContactId::new(reference, ContactFeature::Face(incident_face))
+                }
+            };
+            // leaving the kept part, the polygon continues along the side plane
+            let next_edge = if dp <= S::zero() {
+                ClipEdge::Reference(a, b)
+            } else {
+                previous.next_edge
+            };
+            clipped.push(ClipVertex {
+                point: previous.point + (current.point - previous.point) * (dp / (dp - dc)),
+                id,
+                next_edge,
+            });
+        }
+        if dc <= S::zero() {
+            clipped.push(current);
+        }
+    }
+    clipped
+}
+
+/// Reduce the contact points to at most four: the deepest point, the point furthest from it, the
+/// point that makes the largest triangle with those two, and the point furthest outside that
+/// triangle.
+fn reduce<S>(mut points: Vec<ManifoldPoint<Point3<S>>>) -> Vec<ManifoldPoint<Point3<S>>>
+where
+    S: BaseFloat,
+{
+    if points.len() <= 4 {
+        return points;
+    }
+    let a = take_max(&mut points, |p| p.depth);
+    let b = take_max(&mut points, |p| (p.point - a.point).magnitude2());
+    let ab = b.point - a.point;
+    let c = take_max(&mut points, |p| ab.cross(p.point - a.point).magnitude2());
+    let normal = ab.cross(c.point - a.point).normalize();
+    let edges = [(a.point, b.point), (b.point, c.point), (c.point, a.point)];
+    let d = take_max(&mut points, |p| {
+        edges
+            .iter()
+            .map(|&(start, end)| (end - start).cross(normal).normalize().dot(p.point - start))
+            .fold(S::neg_infinity(), S::max)
+    });
+    vec![a, b, c, d]
+}
+
+/// Remove and return the point with the highest score
+fn take_max<S, F>(points: &mut Vec<ManifoldPoint<Point3<S>>>, score: F) -> ManifoldPoint<Point3<S>>
+where
+    S: BaseFloat,
+    F: Fn(&ManifoldPoint<Point3<S>>) -> S,
+{
+    let mut best = (0, S::neg_infinity());
+    for (i, s) in points.iter().map(score).enumerate() {
+        if s > best.1 {
+            best = (i, s);
+        }
+    }
+    points.swap_remove(best.0)
+}
+
+/// The features of a polyhedron, transformed to world space
+struct WorldPolyhedron<S> {
+    vertices: Vec<Point3<S>>,
+    face_normals: Vec<(Vector3<S>, Point3<S>)>,
+    edges: Vec<WorldEdge<S>>,
+    centroid: Point3<S>,
+}
+
+/// An edge of a polyhedron in world space, with the normals of the faces on both sides
+struct WorldEdge<S> {
+    index: usize,
+    start: Point3<S>,
+    direction: Vector3<S>,
+    normals: (Vector3<S>, Vector3<S>),
+}
+
+impl<S> WorldPolyhedron<S>
+where
+    S: BaseFloat,
+{
+    fn new<T>(polyhedron: &ConvexPolyhedron<S>, transform: &T) -> Self
+    where
+        T: Transform<Point3<S>>,
+    {
+        let normal = |n: Vector3<S>| transform.transform_vector(n).normalize();
+        let vertices: Vec<_> = polyhedron
+            .vertex_positions()
+            .map(|p| transform.transform_point(*p))
+            .collect();
+        let centroid = Point3::centroid(&vertices);
+        Self {
+            face_normals: polyhedron
+                .face_normals()
+                .map(|(n, p)| (normal(n), transform.transform_point(p)))
+                .collect(),
+            edges: polyhedron
+                .edge_features()
+                .map(|(index, start, end, a, b)| {
+                    let start = transform.transform_point(start);
+                    WorldEdge {
+                        index,
+                        start,
+                        direction: transform.transform_point(end) - start,
+                        normals: (normal(a), normal(b)),
+                    }
+                })
+                .collect(),
+            vertices,
+            centroid,
+        }
+    }
+
+    /// Smallest projection of the vertices on the given axis
+    fn min_projection(&self, axis: Vector3<S>) -> S {
+        self.vertices
+            .iter()
+            .map(|v| axis.dot(v.to_vec()))
+            .fold(S::infinity(), S::min)
+    }
+}
+
+/// Find the face of the reference polyhedron with the largest separation from the other
+/// polyhedron. Returns None if a separating face was found.
+fn max_face_separation<S>(
+    reference: &WorldPolyhedron<S>,
+    other: &WorldPolyhedron<S>,
+) -> Option<(usize, S)>
+where
+    S: BaseFloat,
+{
+    let mut best = None;
+    for (i, &(normal, point)) in reference.face_normals.iter().enumerate() {
+        let separation = other.min_projection(normal) - normal.dot(point.to_vec());
+        if separation > S::zero() {
+            return None;
+        }
+        best = match best {
+            Some((_, s)) if s >= separation => best,
+            _ => Some((i, separation)),
+        };
+    }
+    best
+}
+
+/// Find the pair of edges with the largest separation, of the pairs that build a face on the
+/// Minkowski difference. Returns None if a separating axis was found, and no overlap if no edge
+/// pair builds a face.
+fn max_edge_separation<S>(
+    left: &WorldPolyhedron<S>,
+    right: &WorldPolyhedron<S>,
+) -> Option<Option<Overlap3<S>>>
+where
+    S: BaseFloat,
+{
+    let mut best: Option<Overlap3<S>> = None;
+    for l in &left.edges {
+        for r in &right.edges {
+            if !is_minkowski_face(l.normals.0, l.normals.1, -r.normals.0, -r.normals.1) {
+                continue;
+            }
+            let axis = l.direction.cross(r.direction);
+            // parallel edges, the axis is covered by the face axes
+            if axis.magnitude2() <= S::default_epsilon() * l.direction.magnitude2() {
+                continue;
+            }
+            let mut axis = axis.normalize();
+            if axis.dot(l.start - left.centroid) < S::zero() {
+                axis = -axis;
+            }
+            let separation = axis.dot(r.start - l.start);
+            if separation > S::zero() {
+                return None;
+            }
+            match best {
+                Some(ref best) if best.depth <= -separation => (),
+                _ => {
+                    best = Some(Overlap3 {
+                        axis,
+                        depth: -separation,
+                        feature: Feature3::Edges(l.index, r.index),
+                    })
+                }
+            }
+        }
+    }
+    Some(best)
+}
+
+/// Check if the arcs `ab` and `cd` on the unit sphere intersect, where `a` and `b` are the
+/// normals of the faces next to an edge of the left polyhedron, and `c` and `d` are the negated
+/// normals of the faces next to an edge of the right polyhedron. If they intersect, the edges
+/// build a face on the Minkowski difference.
+fn is_minkowski_face<S>(a: Vector3<S>, b: Vector3<S>, c: Vector3<S>, d: Vector3<S>) -> bool
+where
+    S: BaseFloat,
+{
+    let b_x_a = b.cross(a);
+    let d_x_c = d.cross(c);
+    let cba = c.dot(b_x_a);
+    let dba = d.dot(b_x_a);
+    let adc = a.dot(d_x_c);
+    let bdc = b.dot(d_x_c);
+    cba * dba < S::zero() && adc * bdc < S::zero() && cba * bdc > S::zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Decomposed, Point3, Quaternion, Rad, Vector3};
+    use cgmath::prelude::*;
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::convexhull::quickhull3;
+
+    #[test]
+    fn test_face_overlap() {
+        let shape = cube();
+        let overlap = polyhedron_polyhedron(
+            &shape,
+            &transform(0., 0., 0.),
+            &shape,
+            &transform(1.5, 0.3, 0.2),
+        ).unwrap();
+        assert_ulps_eq!(Vector3::new(1., 0., 0.), overlap.axis);
+        assert_ulps_eq!(0.5, overlap.depth);
+        match overlap.feature {
+            Feature3::LeftFace(_) => (),
+            feature => panic!("expected a face of the left cube, got {:?}", feature),
+        }
+
+        assert!(
+            polyhedron_polyhedron(
+                &shape,
+                &transform(0., 0., 0.),
+                &shape,
+                &transform(2.1, 0., 0.),
+            ).is_none()
+        );
+    }
+
+    #[test]
+    fn test_edge_overlap() {
+        // two cubes rotated 45 degrees around different axes, touching edge to edge
+        let shape = cube();
+        let left_transform = Decomposed {
+            disp: Vector3::zero(),
+            rot: Quaternion::from_angle_x(Rad(std::f32::consts::FRAC_PI_4)),
+            scale: 1.,
+        };
+        let d = 2. * 2f32.sqrt() - 0.1;
+        let right_transform = Decomposed {
+            disp: Vector3::new(0., d, 0.),
+            rot: Quaternion::from_angle_z(Rad(std::f32::consts::FRAC_PI_4)),
+            scale: 1.,
+        };
+        let overlap =
+            polyhedron_polyhedron(&shape, &left_transform, &shape, &right_transform).unwrap();
+        assert_ulps_eq!(Vector3::new(0., 1., 0.), overlap.axis, epsilon = 1e-5);
+        assert_ulps_eq!(0.1, overlap.depth, epsilon = 1e-5);
+        match overlap.feature {
+            Feature3::Edges(..) => (),
+            feature => panic!("expected an edge pair, got {:?}", feature),
+        }
+
+        let right_transform = Decomposed {
+            disp: Vector3::new(0., d + 0.2, 0.),
+            ..right_transform
+        };
+        assert!(polyhedron_polyhedron(&shape, &left_transform, &shape, &right_transform).is_none());
+    }
+
+    #[test]
+    fn test_right_face_overlap() {
+        // the corner of the rotated left cube pokes into the bottom face of the right cube
+        let shape = cube();
+        let left_transform = Decomposed {
+            disp: Vector3::zero(),
+            rot: Quaternion::from_axis_angle(Vector3::new(1., 0., 1.).normalize(), Rad(0.6)),
+            scale: 1.,
+        };
+        let top = shape
+            .vertex_positions()
+            .map(|p| left_transform.transform_point(*p).y)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let overlap = polyhedron_polyhedron(
+            &shape,
+            &left_transform,
+            &shape,
+            &transform(0., top + 0.9, 0.),
+        ).unwrap();
+        assert_ulps_eq!(Vector3::new(0., 1., 0.), overlap.axis, epsilon = 1e-5);
+        assert_ulps_eq!(0.1, overlap.depth, epsilon = 1e-5);
+        match overlap.feature {
+            Feature3::RightFace(_) => (),
+            feature => panic!("expected a face of the right cube, got {:?}", feature),
+        }
+    }
+
+    #[test]
+    fn test_manifold_face() {
+        // the right cube rests on the top face of the left cube, overlapping a quarter of it
+        let shape = cube();
+        let manifold = polyhedron_manifold(
+            &shape,
+            &transform(0., 0., 0.),
+            &shape,
+            &transform(1., 1.9, 1.),
+        )
+        .unwrap();
+        assert_ulps_eq!(Vector3::new(0., 1., 0.), manifold.normal);
+        assert_eq!(4, manifold.points.len());
+        for p in &manifold.points {
+            assert_ulps_eq!(0.1, p.depth, epsilon = 1e-5);
+            assert_ulps_eq!(1., p.point.y, epsilon = 1e-5);
+            assert!(p.point.x > -1e-5 && p.point.x < 1. + 1e-5);
+            assert!(p.point.z > -1e-5 && p.point.z < 1. + 1e-5);
+        }
+
+        // one corner of the right cube is inside the top face, one corner of the top face is
+        // inside the right cube, and the other two points are where the edges cross
+        let ids: Vec<_> = manifold.points.iter().map(|p| p.id).collect();
+        let kinds: Vec<_> = ids
+            .iter()
+            .map(|id| (kind(id.left), kind(id.right)))
+            .collect();
+        let count = |k| kinds.iter().filter(|&&other| other == k).count();
+        assert_eq!(1, count(("face", "vertex")));
+        assert_eq!(1, count(("vertex", "face")));
+        assert_eq!(2, count(("edge", "edge")));
+
+        // the ids stay the same when the cube moves a little
+        let moved = polyhedron_manifold(
+            &shape,
+            &transform(0., 0., 0.),
+            &shape,
+            &transform(1.1, 1.85, 0.95),
+        )
+        .unwrap();
+        assert_eq!(4, moved.points.len());
+        assert!(moved.points.iter().all(|p| ids.contains(&p.id)));
+
+        // rotated around the normal, the clipped side is an octagon, reduced to four points
+        let right_transform = Decomposed {
+            disp: Vector3::new(0., 1.9, 0.),
+            rot: Quaternion::from_angle_y(Rad(std::f32::consts::FRAC_PI_4)),
+            scale: 1.,
+        };
+        let manifold =
+            polyhedron_manifold(&shape, &transform(0., 0., 0.), &shape, &right_transform).unwrap();
+        assert_eq!(4, manifold.points.len());
+        assert_ulps_eq!(0.1, manifold.max_depth(), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_manifold_edges() {
+        let shape = cube();
+        let left_transform = Decomposed {
+            disp: Vector3::zero(),
+            rot: Quaternion::from_angle_x(Rad(std::f32::consts::FRAC_PI_4)),
+            scale: 1.,
+        };
+        let right_transform = Decomposed {
+            disp: Vector3::new(0., 2. * 2f32.sqrt() - 0.1, 0.),
+            rot: Quaternion::from_angle_z(Rad(std::f32::consts::FRAC_PI_4)),
+            scale: 1.,
+        };
+        let manifold =
+            polyhedron_manifold(&shape, &left_transform, &shape, &right_transform).unwrap();
+        assert_eq!(1, manifold.points.len());
+        assert_ulps_eq!(0.1, manifold.points[0].depth, epsilon = 1e-5);
+        assert_ulps_eq!(
+            Point3::new(0., 2f32.sqrt(), 0.),
+            manifold.points[0].point,
+            epsilon = 1e-5
+        );
+        let id = manifold.points[0].id;
+        assert_eq!(("edge", "edge"), (kind(id.left), kind(id.right)));
+    }
+
+    fn kind(feature: ContactFeature) -> &'static str {
+        match feature {
+            ContactFeature::Vertex(_) => "vertex",
+            ContactFeature::Edge(..) => "edge",
+            ContactFeature::Face(_) => "face",
+        }
+    }
+
+    fn cube() -> ConvexPolyhedron<f32> {
+        let mut points = Vec::new();
+        for &x in &[-1., 1.] {
+            for &y in &[-1., 1.] {
+                for &z in &[-1., 1.] {
+                    points.push(Point3::new(x, y, z));
+                }
+            }
+        }
+        quickhull3(&points).unwrap()
+    }
+
+    fn transform(x: f32, y: f32, z: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            disp: Vector3::new(x, y, z),
+            rot: Quaternion::one(),
+            scale: 1.,
+        }
+    }
+}
@@ -0,0 +1,1355 @@
+//! Shape casts, sweeping convex primitives along a motion against another primitive
+
+use std::f64::consts::FRAC_PI_4;
+use std::ops::Neg;
+
+use cgmath::{BaseFloat, Basis2, Decomposed, Point2, Point3, Quaternion, Rad, UlpsEq, Vector2,
+             Vector3};
+use cgmath::prelude::*;
+use cgmath::num_traits::NumCast;
+
+use crate::CollisionStrategy;
+use crate::algorithm::minkowski::{Separation, SimplexProcessor, EPA, GJK, GJK2};
+use crate::prelude::*;
+use crate::primitive::ConvexPolygon;
+use crate::primitive::util::{closest_point_on_segment, closest_point_on_triangle};
+
+const MAX_ITERATIONS: u32 = 64;
+const SHAPE_CAST_TOLERANCE: f32 = 0.0001;
+
+/// First contact of a primitive swept along a displacement, see [`shape_cast`](fn.shape_cast.html)
+/// and [`time_of_impact`](fn.time_of_impact.html)
+///
+/// # Type parameters
+///
+/// - `P`: cgmath point type
+#[derive(Debug, Clone)]
+pub struct ShapeCastHit<P: EuclideanSpace> {
+    /// Fraction of the displacement or motion travelled before the primitives touch, in range
+    /// 0.0..1.0
+    pub time_of_impact: P::Scalar,
+
+    /// The contact point in world space, on the moving (left) primitive at the time of impact
+    pub point: P,
+
+    /// The contact normal, pointing from the moving (left) primitive towards the other primitive
+    pub normal: P::Diff,
+}
+
+/// Sweep the left primitive along a linear displacement against the right primitive, and find
+/// the first time they touch.
+///
+/// Uses conservative advancement on the GJK distance: the left primitive is moved forward by
+/// the distance between the primitives, divided by the speed at which it approaches the right
+/// primitive along the line between the closest points. The separating plane through the closest
+/// points guarantees that the primitives can not touch before that, so the cast can not tunnel
+/// through thin primitives, no matter how long the displacement is.
+///
+/// ## Parameters:
+///
+/// - `gjk`: GJK implementation used for the distance queries
+/// - `left`: the moving primitive
+/// - `left_transform`: model-to-world-transform for the left primitive at the start of the cast
+/// - `displacement`: the translation of the left primitive over the cast, in world space
+/// - `right`: the static primitive
+/// - `right_transform`: model-to-world-transform for the right primitive
+///
+/// ## Returns:
+///
+/// The time of impact, contact point and normal of the first contact, or None if the primitives
+/// do not touch during the cast. If the primitives already overlap at the start, the time of
+/// impact is zero, and the normal and point come from EPA.
+pub fn shape_cast<SP, E, S, P, PL, PR, TL, TR>(
+    gjk: &GJK<SP, E, S>,
+    left: &PL,
+    left_transform: &TL,
+    displacement: P::Diff,
+    right: &PR,
+    right_transform: &TR,
+) -> Option<ShapeCastHit<P>>
+where
+    S: BaseFloat,
+    SP: SimplexProcessor<Point = P>,
+    E: EPA<Point = P>,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+    PL: Primitive<Point = P>,
+    PR: Primitive<Point = P>,
+    TL: Transform<P>,
+    TR: Transform<P>,
+{
+    let tolerance: S = NumCast::from(SHAPE_CAST_TOLERANCE).unwrap();
+    let mut time = S::zero();
+    let mut normal = None;
+    let mut last = None;
+    for _ in 0..MAX_ITERATIONS {
+        // move the right primitive backwards instead of the left primitive forwards, so any
+        // transform type can be used
+        let right_moved = Translated {
+            primitive: right,
+            offset: displacement * -time,
+        };
+        let separation = match gjk.separation(left, left_transform, &right_moved, right_transform) {
+            Some(separation) => separation,
+            None if time.is_zero() => {
+                return initial_overlap(
+                    gjk,
+                    left,
+                    left_transform,
+                    right,
+                    right_transform,
+                    displacement,
+                )
+            }
+            // the GJK distance may be slightly too large for curved primitives, so the last step
+            // can go too far, report the last separated position
+            None => return last,
+        };
+        let direction = separation.right_point - separation.left_point;
+        if separation.distance > tolerance || normal.is_none() {
+            normal = Some(direction / separation.distance);
+        }
+        let n = normal.unwrap();
+        let hit = ShapeCastHit {
+            time_of_impact: time,
+            point: separation.left_point + displacement * time,
+            normal: n,
+        };
+        if separation.distance <= tolerance {
+            return Some(hit);
+        }
+        last = Some(hit);
+        let speed = displacement.dot(n);
+        if speed <= S::zero() {
+            // moving away from the right primitive
+            return None;
+        }
+        time += (separation.distance - tolerance / (S::one() + S::one())) / speed;
+        if time > S::one() {
+            return None;
+        }
+    }
+    None
+}
+
+fn initial_overlap<SP, E, S, P, PL, PR, TL, TR>(
+    gjk: &GJK<SP, E, S>,
+    left: &PL,
+    left_transform: &TL,
+    right: &PR,
+    right_transform: &TR,
+    direction: P::Diff,
+) -> Option<ShapeCastHit<P>>
+where
+    S: BaseFloat,
+    SP: SimplexProcessor<Point = P>,
+    E: EPA<Point = P>,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+    PL: Primitive<Point = P>,
+    PR: Primitive<Point = P>,
+    TL: Transform<P>,
+    TR: Transform<P>,
+{
+    // EPA can only fail for degenerate cases, fall back to the direction of motion
+    let hit = match gjk.intersection(
+        &CollisionStrategy::FullResolution,
+        left,
+        left_transform,
+        right,
+        right_transform,
+    ) {
+        Some(contact) => ShapeCastHit {
+            time_of_impact: S::zero(),
+            point: contact.contact_point,
+            normal: contact.normal,
+        },
+        None => ShapeCastHit {
+            time_of_impact: S::zero(),
+            point: left_transform.transform_point(P::origin()),
+            normal: direction.normalize(),
+        },
+    };
+    Some(hit)
+}
+
+/// Sweep a sphere along a linear displacement against a triangle, and find the first time they
+/// touch.
+///
+/// Solves for the first time the sphere touches the inside of the triangle, then for each vertex
+/// and each edge, and keeps the earliest hit. The triangle is two sided. This is the building
+/// block for moving characters, modelled as spheres or as ellipsoids scaled to spheres, against
+/// triangle meshes.
+///
+/// ## Parameters:
+///
+/// - `center`: center of the sphere at the start of the cast, in world space
+/// - `radius`: radius of the sphere
+/// - `displacement`: the translation of the sphere over the cast, in world space
+/// - `triangle`: the vertices of the triangle, in world space
+///
+/// ## Returns:
+///
+/// The time of impact, contact point and normal of the first contact, or None if the sphere does
+/// not touch the triangle during the cast. If the sphere already overlaps the triangle at the
+/// start, the time of impact is zero, and the normal points towards the closest point on the
+/// triangle.
+pub fn sphere_triangle_cast<S>(
+    center: Point3<S>,
+    radius: S,
+    displacement: Vector3<S>,
+    triangle: &[Point3<S>; 3],
+) -> Option<ShapeCastHit<Point3<S>>>
+where
+    S: BaseFloat,
+{
+    let [a, b, c] = *triangle;
+    let two = S::one() + S::one();
+    let hit = |time: S, point: Point3<S>| {
+        let normal = (point - (center + displacement * time)).normalize();
+        Some(ShapeCastHit {
+            time_of_impact: time,
+            point,
+            normal,
+        })
+    };
+
+    // normal in the winding direction of the triangle
+    let m = (b - a).cross(c - a);
+
+    let (closest, _) = closest_point_on_triangle(a, b, c, center);
+    let distance2 = (closest - center).magnitude2();
+    if distance2 <= radius * radius {
+        let normal = if distance2.is_zero() {
+            if m.dot(displacement) < S::zero() {
+                -m.normalize()
+            } else {
+                m.normalize()
+            }
+        } else {
+            (closest - center).normalize()
+        };
+        return Some(ShapeCastHit {
+            time_of_impact: S::zero(),
+            point: center + normal * radius,
+            normal,
+        });
+    }
+
+    // the inside of the triangle, with the normal facing the sphere
+    if !m.magnitude2().is_zero() {
+        let n = m.normalize();
+        let n = if n.dot(center - a) < S::zero() { -n } else { n };
+        let speed = -n.dot(displacement);
+        let distance = n.dot(center - a);
+        if distance > radius && speed > S::zero() {
+            let time = (distance - radius) / speed;
+            if time > S::one() {
+                return None;
+            }
+            let point = center + displacement * time - n * radius;
+            let inside = [(a, b), (b, c), (c, a)]
+                .iter()
+                .all(|&(start, end)| (end - start).cross(point - start).dot(m) >= S::zero());
+            if inside {
+                return hit(time, point);
+            }
+        }
+    }
+
+    // otherwise the sphere touches a vertex or an edge first
+    let mut first: Option<(S, Point3<S>)> = None;
+    let mut earliest = S::one();
+    let v2 = displacement.magnitude2();
+    for &p in &[a, b, c] {
+        let d = center - p;
+        let root = lowest_root(
+            v2,
+            two * displacement.dot(d),
+            d.magnitude2() - radius * radius,
+            earliest,
+        );
+        if let Some(time) = root {
+            earliest = time;
+            first = Some((time, p));
+        }
+    }
+    for &(start, end) in &[(a, b), (b, c), (c, a)] {
+        let edge = end - start;
+        let base = start - center;
+        let e2 = edge.magnitude2();
+        if e2.is_zero() {
+            continue;
+        }
+        let ev = edge.dot(displacement);
+        let eb = edge.dot(base);
+        let root = lowest_root(
+            ev * ev - e2 * v2,
+            two * (e2 * displacement.dot(base) - ev * eb),
+            e2 * (radius * radius - base.magnitude2()) + eb * eb,
+            earliest,
+        );
+        if let Some(time) = root {
+            // the hit is only on the edge if it is between the end points
+            let f = (ev * time - eb) / e2;
+            if f >= S::zero() && f <= S::one() {
+                earliest = time;
+                first = Some((time, start + edge * f));
+            }
+        }
+    }
+    first.and_then(|(time, point)| hit(time, point))
+}
+
+/// Lowest root of `a * t^2 + b * t + c` in range `0..=max`
+fn lowest_root<S>(a: S, b: S, c: S, max: S) -> Option<S>
+where
+    S: BaseFloat,
+{
+    if a.abs() <= S::default_epsilon() {
+        return None;
+    }
+    let determinant = b * b - (S::one() + S::one() + S::one() + S::one()) * a * c;
+    if determinant < S::zero() {
+        return None;
+    }
+    let sqrt = determinant.sqrt();
+    let two_a = (S::one() + S::one()) * a;
+    let (r1, r2) = ((-b - sqrt) / two_a, (-b + sqrt) / two_a);
+    let (r1, r2) = if r1 > r2 { (r2, r1) } else { (r1, r2) };
+    if r1 >= S::zero() && r1 <= max {
+        Some(r1)
+    } else if r2 >= S::zero() && r2 <= max {
+        Some(r2)
+    } else {
+        None
+    }
+}
+
+/// Rigid motion of a primitive over the time interval 0.0..1.0 of a
+/// [`time_of_impact`](fn.time_of_impact.html) query.
+///
+/// # Type parameters
+///
+/// - `P`: cgmath point type
+pub trait RigidMotion<P: EuclideanSpace> {
+    /// Transform type of the primitive
+    type Transform: Transform<P>;
+
+    /// Model-to-world-transform of the primitive at the given time
+    fn transform_at(&self, time: P::Scalar) -> Self::Transform;
+
+    /// Linear velocity of the origin of the primitive, in world space
+    fn linear_velocity(&self) -> P::Diff;
+
+    /// Angular speed of the primitive about its origin, in radians
+    fn angular_speed(&self) -> P::Scalar;
+}
+
+/// Motion with constant linear and angular velocity in 2D, see
+/// [`RigidMotion`](trait.RigidMotion.html)
+#[derive(Debug, Clone)]
+pub struct Motion2<S: BaseFloat> {
+    /// Model-to-world-transform at the start of the motion
+    pub start: Decomposed<Vector2<S>, Basis2<S>>,
+
+    /// Translation of the origin over the motion
+    pub linear: Vector2<S>,
+
+    /// Rotation about the origin over the motion, counter clockwise
+    pub angular: Rad<S>,
+}
+
+impl<S> Motion2<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new motion
+    pub fn new(
+        start: Decomposed<Vector2<S>, Basis2<S>>,
+        linear: Vector2<S>,
+        angular: Rad<S>,
+    ) -> Self {
+        Self {
+            start,
+            linear,
+            angular,
+        }
+    }
+}
+
+impl<S> RigidMotion<Point2<S>> for Motion2<S>
+where
+    S: BaseFloat,
+{
+    type Transform = Decomposed<Vector2<S>, Basis2<S>>;
+
+    fn transform_at(&self, time: S) -> Self::Transform {
+        Decomposed {
+            disp: self.start.disp + self.linear * time,
+            rot: Basis2::from_angle(self.angular * time) * self.start.rot,
+            scale: self.start.scale,
+        }
+    }
+
+    fn linear_velocity(&self) -> Vector2<S> {
+        self.linear
+    }
+
+    fn angular_speed(&self) -> S {
+        self.angular.0.abs()
+    }
+}
+
+/// Motion with constant linear and angular velocity in 3D, see
+/// [`RigidMotion`](trait.RigidMotion.html)
+#[derive(Debug, Clone)]
+pub struct Motion3<S: BaseFloat> {
+    /// Model-to-world-transform at the start of the motion
+    pub start: Decomposed<Vector3<S>, Quaternion<S>>,
+
+    /// Translation of the origin over the motion
+    pub linear: Vector3<S>,
+
+    /// Rotation about the origin over the motion, as the rotation axis in world space scaled by
+    /// the angle in radians
+    pub angular: Vector3<S>,
+}
+
+impl<S> Motion3<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new motion
+    pub fn new(
+        start: Decomposed<Vector3<S>, Quaternion<S>>,
+        linear: Vector3<S>,
+        angular: Vector3<S>,
+    ) -> Self {
+        Self {
+            start,
+            linear,
+            angular,
+        }
+    }
+}
+
+impl<S> RigidMotion<Point3<S>> for Motion3<S>
+where
+    S: BaseFloat,
+{
+    type Transform = Decomposed<Vector3<S>, Quaternion<S>>;
+
+    fn transform_at(&self, time: S) -> Self::Transform {
+        let angle = self.angular.magnitude();
+        let rot = if angle.is_zero() {
+            self.start.rot
+        } else {
+            Quaternion::from_axis_angle(self.angular / angle, Rad(angle * time)) * self.start.rot
+        };
+        Decomposed {
+            disp: self.start.disp + self.linear * time,
+            rot,
+            scale: self.start.scale,
+        }
+    }
+
+    fn linear_velocity(&self) -> Vector3<S> {
+        self.linear
+    }
+
+    fn angular_speed(&self) -> S {
+        self.angular.magnitude()
+    }
+}
+
+/// Find the first time two primitives touch, when both move with the given rigid motions.
+///
+/// Uses conservative advancement on the GJK distance, like [`shape_cast`](fn.shape_cast.html),
+/// but the approach speed along the line between the closest points is bounded by the relative
+/// linear velocity plus the angular speed of each primitive times its bounding radius around its
+/// origin. Spinning primitives can therefore not tunnel through each other, but the number of
+/// iterations grows with the angular speed. When very fast spinning primitives use up the
+/// maximum number of iterations, the last time reached is reported as the time of impact, which
+/// is before the real contact, so the primitives still do not tunnel.
+///
+/// ## Parameters:
+///
+/// - `gjk`: GJK implementation used for the distance queries
+/// - `left`: left primitive
+/// - `left_motion`: motion of the left primitive
+/// - `right`: right primitive
+/// - `right_motion`: motion of the right primitive
+///
+/// ## Returns:
+///
+/// The time of impact, contact point and normal of the first contact, or None if the primitives
+/// do not touch during the motions. If the primitives already overlap at the start, the time of
+/// impact is zero, and the normal and point come from EPA. If the iterations run out, the hit is
+/// at the last time reached, with the closest points at that time.
+pub fn time_of_impact<SP, E, S, P, PL, PR, ML, MR>(
+    gjk: &GJK<SP, E, S>,
+    left: &PL,
+    left_motion: &ML,
+    right: &PR,
+    right_motion: &MR,
+) -> Option<ShapeCastHit<P>>
+where
+    S: BaseFloat,
+    SP: SimplexProcessor<Point = P>,
+    E: EPA<Point = P>,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S> + UlpsEq,
+    PL: Primitive<Point = P>,
+    PR: Primitive<Point = P>,
+    ML: RigidMotion<P>,
+    MR: RigidMotion<P>,
+{
+    let tolerance: S = NumCast::from(SHAPE_CAST_TOLERANCE).unwrap();
+    let linear = left_motion.linear_velocity() - right_motion.linear_velocity();
+    let angular = left_motion.angular_speed()
+        * bounding_radius(left, &left_motion.transform_at(S::zero()))
+        + right_motion.angular_speed()
+            * bounding_radius(right, &right_motion.transform_at(S::zero()));
+    let mut time = S::zero();
+    let mut last = None;
+    for _ in 0..MAX_ITERATIONS {
+        let left_transform = left_motion.transform_at(time);
+        let right_transform = right_motion.transform_at(time);
+        let separation = match gjk.separation(left, &left_transform, right, &right_transform) {
+            Some(separation) => separation,
+            None if time.is_zero() => {
+                return initial_overlap(gjk, left, &left_transform, right, &right_transform, linear)
+            }
+            // the last step went slightly too far, report the last separated position
+            None => return last,
+        };
+        let normal = match last {
+            Some(ShapeCastHit { normal, .. }) if separation.distance <= tolerance => normal,
+            _ => (separation.right_point - separation.left_point) / separation.distance,
+        };
+        let hit = ShapeCastHit {
+            time_of_impact: time,
+            point: separation.left_point,
+            normal,
+        };
+        if separation.distance <= tolerance {
+            return Some(hit);
+        }
+        last = Some(hit);
+        let speed = linear.dot(normal) + angular;
+        if speed <= S::zero() {
+            return None;
+        }
+        time += (separation.distance - tolerance / (S::one() + S::one())) / speed;
+        if time > S::one() {
+            return None;
+        }
+    }
+    // out of iterations, the primitives are still separated at the last time, but may touch
+    // right after it
+    last
+}
+
+/// Find the first time two convex polygons come within the target separation, when both move
+/// with the given rigid motions.
+///
+/// Uses bilateral advancement, as in Box2D: at the current time, the closest features of the
+/// polygons define a separating axis, either a face normal of one of the polygons or the
+/// direction between two vertices. The separation along that axis is a function of time, and
+/// its first root at the target separation is found by mixing bisection and secant steps. If
+/// other vertices cross the target separation first, the end of the interval is pushed back to
+/// the first root for those vertices. When the axis holds until the end of the interval, the
+/// polygons advance to it, and the next axis is built there. The separation along an axis is
+/// only tracked reliably for small rotations, so the intervals are limited to an eighth of a
+/// turn of the polygons, and fast spinning polygons need an iteration for each.
+///
+/// Unlike the conservative advancement in [`time_of_impact`](fn.time_of_impact.html), the time
+/// is exact up to the tolerance.
+///
+/// ## Parameters:
+///
+/// - `gjk`: GJK implementation used for the distance queries
+/// - `left`: left polygon, with vertices in CCW order
+/// - `left_motion`: motion of the left polygon
+/// - `right`: right polygon, with vertices in CCW order
+/// - `right_motion`: motion of the right polygon
+/// - `target`: the separation to stop at. It is at least a small tolerance, so the polygons do
+///   not touch at the time of impact, and the next distance query has a well defined normal.
+///
+/// ## Returns:
+///
+/// The time of impact, contact point and normal of the first time the polygons come within the
+/// target separation, or None if they stay further apart during the motions. If they are already
+/// closer at the start, the time of impact is zero, and the normal and point come from EPA if
+/// the polygons overlap.
+pub fn polygon_time_of_impact<S>(
+    gjk: &GJK2<S>,
+    left: &ConvexPolygon<S>,
+    left_motion: &Motion2<S>,
+    right: &ConvexPolygon<S>,
+    right_motion: &Motion2<S>,
+    target: S,
+) -> Option<ShapeCastHit<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    let slop: S = NumCast::from(4. * SHAPE_CAST_TOLERANCE).unwrap();
+    let tolerance: S = NumCast::from(SHAPE_CAST_TOLERANCE).unwrap();
+    let target = target.max(slop);
+    let sweep = PolygonSweep {
+        left,
+        left_motion,
+        right,
+        right_motion,
+    };
+    // the separation along an axis is only reliable while the polygons rotate a little, so
+    // faster rotations are split into several intervals
+    let max_rotation: S = NumCast::from(FRAC_PI_4).unwrap();
+    let rotation = left_motion.angular.0.abs() + right_motion.angular.0.abs();
+    let interval = if rotation > max_rotation {
+        max_rotation / rotation
+    } else {
+        S::one()
+    };
+    let mut time = S::zero();
+    for _ in 0..MAX_ITERATIONS {
+        let left_transform = left_motion.transform_at(time);
+        let right_transform = right_motion.transform_at(time);
+        let separation = match gjk.separation(left, &left_transform, right, &right_transform) {
+            Some(separation) => separation,
+            None => {
+                let mut hit = initial_overlap(
+                    gjk,
+                    left,
+                    &left_transform,
+                    right,
+                    &right_transform,
+                    left_motion.linear - right_motion.linear,
+                )?;
+                hit.time_of_impact = time;
+                return Some(hit);
+            }
+        };
+        if separation.distance <= target + tolerance {
+            return Some(ShapeCastHit {
+                time_of_impact: time,
+                point: separation.left_point,
+                normal: (separation.right_point - separation.left_point) / separation.distance,
+            });
+        }
+
+        let axis = sweep.axis(time, &separation);
+        let mut end = (time + interval).min(S::one());
+        let mut advanced = false;
+        // each push back fixes another pair of vertices, so the number of pairs is the limit
+        for _ in 0..(left.vertices().len() + right.vertices().len()) {
+            let (end_separation, left_index, right_index) = sweep.min_separation(&axis, end);
+            // the axis separates the polygons until the end of the interval
+            if end_separation > target + tolerance {
+                if end < S::one() {
+                    time = end;
+                    advanced = true;
+                    break;
+                }
+                return None;
+            }
+            // the polygons are at the target separation at the end of the interval, advance
+            if end_separation > target - tolerance {
+                time = end;
+                advanced = true;
+                break;
+            }
+            // already at the target separation, or the root finder can not make progress
+            let start_separation = sweep.separation(&axis, left_index, right_index, time);
+            if start_separation <= target + tolerance {
+                return Some(sweep.hit(gjk, time));
+            }
+            end = sweep.find_root(
+                &axis,
+                (left_index, right_index),
+                (time, start_separation),
+                (end, end_separation),
+                target,
+                tolerance,
+            );
+        }
+        if !advanced {
+            return Some(sweep.hit(gjk, time));
+        }
+    }
+    None
+}
+
+/// Axis used by [`polygon_time_of_impact`](fn.polygon_time_of_impact.html) to measure the
+/// separation of the polygons over time
+enum SweepAxis<S> {
+    /// Fixed direction in world space, between the closest vertices of the polygons
+    Points(Vector2<S>),
+    /// Normal of the edge with the given index on the left polygon
+    LeftFace(usize),
+    /// Normal of the edge with the given index on the right polygon
+    RightFace(usize),
+}
+
+/// Two polygons with their motions
+struct PolygonSweep<'a, S: BaseFloat> {
+    left: &'a ConvexPolygon<S>,
+    left_motion: &'a Motion2<S>,
+    right: &'a ConvexPolygon<S>,
+    right_motion: &'a Motion2<S>,
+}
+
+impl<'a, S> PolygonSweep<'a, S>
+where
+    S: BaseFloat,
+{
+    fn left_vertex(&self, index: usize, time: S) -> Point2<S> {
+        self.left_motion
+            .transform_at(time)
+            .transform_point(self.left.vertices()[index])
+    }
+
+    fn right_vertex(&self, index: usize, time: S) -> Point2<S> {
+        self.right_motion
+            .transform_at(time)
+            .transform_point(self.right.vertices()[index])
+    }
+
+    /// Start point and outward normal of an edge in world space
+    fn face(
+        &self,
+        polygon: &ConvexPolygon<S>,
+        motion: &Motion2<S>,
+        edge: usize,
+        time: S,
+    ) -> (Point2<S>, Vector2<S>) {
+        let transform = motion.transform_at(time);
+        let vertices = polygon.vertices();
+        let start = transform.transform_point(vertices[edge]);
+        let end = transform.transform_point(vertices[(edge + 1) % vertices.len()]);
+        let d = end - start;
+        (start, Vector2::new(d.y, -d.x).normalize())
+    }
+
+    /// Build the axis from the closest points of the polygons at the given time. If a closest
+    /// point is inside an edge, the axis is the normal of that edge.
+    fn axis(&self, time: S, separation: &Separation<Point2<S>>) -> SweepAxis<S> {
+        let tolerance: S = NumCast::from(SHAPE_CAST_TOLERANCE).unwrap();
+        let edge = |polygon: &ConvexPolygon<S>, motion: &Motion2<S>, point: Point2<S>| {
+            let transform = motion.transform_at(time);
+            let n = polygon.vertices().len();
+            (0..n).find(|&i| {
+                let start = transform.transform_point(polygon.vertices()[i]);
+                let end = transform.transform_point(polygon.vertices()[(i + 1) % n]);
+                let on_vertex = (point - start).magnitude() <= tolerance
+                    || (point - end).magnitude() <= tolerance;
+                !on_vertex
+                    && (closest_point_on_segment(start, end, point) - point).magnitude()
+                        <= tolerance
+            })
+        };
+        if let Some(i) = edge(self.left, self.left_motion, separation.left_point) {
+            return SweepAxis::LeftFace(i);
+        }
+        if let Some(i) = edge(self.right, self.right_motion, separation.right_point) {
+            return SweepAxis::RightFace(i);
+        }
+        SweepAxis::Points((separation.right_point - separation.left_point).normalize())
+    }
+
+    /// The vertices of each polygon deepest along the axis at the given time, and their
+    /// separation
+    fn min_separation(&self, axis: &SweepAxis<S>, time: S) -> (S, usize, usize) {
+        let deepest = |vertex: &dyn Fn(usize) -> Point2<S>, count: usize, direction: Vector2<S>| {
+            (0..count)
+                .map(|i| (i, vertex(i).to_vec().dot(direction)))
+                .fold((0, S::neg_infinity()), |best, (i, d)| {
+                    if d > best.1 {
+                        (i, d)
+                    } else {
+                        best
+                    }
+                })
+                .0
+        };
+        let left_count = self.left.vertices().len();
+        let right_count = self.right.vertices().len();
+        let (left_index, right_index) = match *axis {
+            SweepAxis::Points(direction) => (
+                deepest(&|i| self.left_vertex(i, time), left_count, direction),
+                deepest(&|i| self.right_vertex(i, time), right_count, -direction),
+            ),
+            SweepAxis::LeftFace(edge) => {
+                let (_, normal) = self.face(self.left, self.left_motion, edge, time);
+                (
+                    edge,
+                    deepest(&|i| self.right_vertex(i, time), right_count, -normal),
+                )
+            }
+            SweepAxis::RightFace(edge) => {
+                let (_, normal) = self.face(self.right, self.right_motion, edge, time);
+                (
+                    deepest(&|i| self.left_vertex(i, time), left_count, -normal),
+                    edge,
+                )
+            }
+        };
+        (
+            self.separation(axis, left_index, right_index, time),
+            left_index,
+            right_index,
+        )
+    }
+
+    /// Separation of the given vertices along the axis at the given time
+    fn separation(&self, axis: &SweepAxis<S>, left_index: usize, right_index: usize, time: S) -> S {
+        match *axis {
+            SweepAxis::Points(direction) => {
+                (self.right_vertex(right_index, time) - self.left_vertex(left_index, time))
+                    .dot(direction)
+            }
+            SweepAxis::LeftFace(edge) => {
+                let (start, normal) = self.face(self.left, self.left_motion, edge, time);
+                (self.right_vertex(right_index, time) - start).dot(normal)
+            }
+            SweepAxis::RightFace(edge) => {
+                let (start, normal) = self.face(self.right, self.right_motion, edge, time);
+                (self.left_vertex(left_index, time) - start).dot(normal)
+            }
+        }
+    }
+
+    /// Find the time in the interval where the separation of the given vertices is the target,
+    /// alternating secant and bisection steps
+    fn find_root(
+        &self,
+        axis: &SweepAxis<S>,
+        (left_index, right_index): (usize, usize),
+        (mut a1, mut s1): (S, S),
+        (mut a2, mut s2): (S, S),
+        target: S,
+        tolerance: S,
+    ) -> S {
+        let two = S::one() + S::one();
+        for i in 0..MAX_ITERATIONS {
+            let t = if i % 2 == 0 {
+                a1 + (target - s1) * (a2 - a1) / (s2 - s1)
+            } else {
+                (a1 + a2) / two
+            };
+            let s = self.separation(axis, left_index, right_index, t);
+            if (s - target).abs() < tolerance {
+                return t;
+            }
+            if s > target {
+                a1 = t;
+                s1 = s;
+            } else {
+                a2 = t;
+                s2 = s;
+            }
+        }
+        a2
+    }
+
+    /// The contact at the given time
+    fn hit(&self, gjk: &GJK2<S>, time: S) -> ShapeCastHit<Point2<S>> {
+        let left_transform = self.left_motion.transform_at(time);
+        let right_transform = self.right_motion.transform_at(time);
+        match gjk.separation(self.left, &left_transform, self.right, &right_transform) {
+            Some(separation) => ShapeCastHit {
+                time_of_impact: time,
+                point: separation.left_point,
+                normal: (separation.right_point - separation.left_point) / separation.distance,
+            },
+            None => {
+                let direction = self.left_motion.linear - self.right_motion.linear;
+                let mut hit = initial_overlap(
+                    gjk,
+                    self.left,
+                    &left_transform,
+                    self.right,
+                    &right_transform,
+                    direction,
+                ).unwrap();
+                hit.time_of_impact = time;
+                hit
+            }
+        }
+    }
+}
+
+/// Radius of a sphere around the origin of the primitive, that contains the whole primitive
+fn bounding_radius<S, P, PR, T>(primitive: &PR, transform: &T) -> S
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: Neg<Output = P::Diff> + InnerSpace + Zero + Array<Element = S>,
+    PR: Primitive<Point = P>,
+    T: Transform<P>,
+{
+    let origin = transform.transform_point(P::origin());
+    let mut extent = P::Diff::zero();
+    for i in 0..P::Diff::len() {
+        let mut axis = P::Diff::zero();
+        axis[i] = S::one();
+        let max = primitive.support_point(&axis, transform) - origin;
+        let min = primitive.support_point(&-axis, transform) - origin;
+        extent[i] = max[i].abs().max(min[i].abs());
+    }
+    extent.magnitude()
+}
+
+/// Primitive moved by an offset in world space
+struct Translated<'a, P: 'a, V> {
+    primitive: &'a P,
+    offset: V,
+}
+
+impl<'a, P> Primitive for Translated<'a, P, <P::Point as EuclideanSpace>::Diff>
+where
+    P: Primitive,
+{
+    type Point = P::Point;
+
+    fn support_point<T>(
+        &self,
+        direction: &<Self::Point as EuclideanSpace>::Diff,
+        transform: &T,
+    ) -> Self::Point
+    where
+        T: Transform<Self::Point>,
+    {
+        self.primitive.support_point(direction, transform) + self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Basis2, Decomposed, Euler, Point2, Point3, Quaternion, Rad, Rotation2, Vector2,
+                 Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::{GJK2, GJK3};
+    use crate::primitive::*;
+
+    fn transform(x: f32, y: f32, angle: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            disp: Vector2::new(x, y),
+            rot: Rotation2::from_angle(Rad(angle)),
+            scale: 1.,
+        }
+    }
+
+    fn transform_3d(x: f32, y: f32, z: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            disp: Vector3::new(x, y, z),
+            rot: Quaternion::one(),
+            scale: 1.,
+        }
+    }
+
+    #[test]
+    fn test_shape_cast_2d() {
+        let gjk = GJK2::new();
+        let left = Circle::new(1.);
+        let right = Rectangle::new(2., 10.);
+        let hit = shape_cast(
+            &gjk,
+            &left,
+            &transform(0., 0., 0.),
+            Vector2::new(10., 0.),
+            &right,
+            &transform(8., 1., 0.),
+        )
+        .unwrap();
+        assert_ulps_eq!(0.6, hit.time_of_impact, epsilon = 1e-4);
+        assert_ulps_eq!(Vector2::new(1., 0.), hit.normal, epsilon = 1e-2);
+        assert_ulps_eq!(Point2::new(7., 0.), hit.point, epsilon = 1e-2);
+
+        // too short
+        assert!(shape_cast(
+            &gjk,
+            &left,
+            &transform(0., 0., 0.),
+            Vector2::new(5., 0.),
+            &right,
+            &transform(8., 1., 0.),
+        )
+        .is_none());
+
+        // passes by
+        assert!(shape_cast(
+            &gjk,
+            &left,
+            &transform(0., 0., 0.),
+            Vector2::new(10., 10.),
+            &right,
+            &transform(8., -5., 0.),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_shape_cast_thin() {
+        // a bullet does not tunnel through a thin wall, no matter how fast it is
+        let gjk = GJK3::new();
+        let left = Sphere::new(0.1);
+        let right = Cuboid::new(0.01, 10., 10.);
+        let hit = shape_cast(
+            &gjk,
+            &left,
+            &transform_3d(-500., 0., 0.),
+            Vector3::new(1000., 0., 0.),
+            &right,
+            &transform_3d(0., 0., 0.),
+        )
+        .unwrap();
+        assert_ulps_eq!(0.49989, hit.time_of_impact, epsilon = 1e-5);
+        assert_ulps_eq!(Vector3::new(1., 0., 0.), hit.normal, epsilon = 1e-2);
+        assert_ulps_eq!(Point3::new(-0.005, 0., 0.), hit.point, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn test_shape_cast_curved_overshoot() {
+        // the GJK distance between a sphere and a rotated cuboid may be slightly too large, which
+        // must not make the cast step past the contact and miss it
+        let gjk = GJK3::new();
+        let left = Sphere::new(0.5);
+        let right = Cuboid::new(1., 2., 3.);
+        for i in 0..300 {
+            let i = i as f32;
+            let right_transform = Decomposed {
+                disp: Vector3::zero(),
+                rot: Quaternion::from(Euler::new(Rad(i * 0.37), Rad(i * 0.71), Rad(i * 1.13))),
+                scale: 1.,
+            };
+            let (theta, phi) = (i * 2.39, i * 0.53);
+            let start =
+                Vector3::new(phi.sin() * theta.cos(), phi.sin() * theta.sin(), phi.cos()) * 5.;
+            let hit = shape_cast(
+                &gjk,
+                &left,
+                &transform_3d(start.x, start.y, start.z),
+                -start * 2.,
+                &right,
+                &right_transform,
+            )
+            .unwrap();
+            assert!(hit.time_of_impact > 0. && hit.time_of_impact < 0.5);
+        }
+    }
+
+    #[test]
+    fn test_shape_cast_initial_overlap() {
+        let gjk = GJK3::new();
+        let shape = Cuboid::new(2., 2., 2.);
+        let hit = shape_cast(
+            &gjk,
+            &shape,
+            &transform_3d(0., 0., 0.),
+            Vector3::new(1., 0., 0.),
+            &shape,
+            &transform_3d(1.5, 0., 0.),
+        )
+        .unwrap();
+        assert_eq!(0., hit.time_of_impact);
+        assert_ulps_eq!(Vector3::new(1., 0., 0.), hit.normal, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_time_of_impact_fast_spin() {
+        // fast spinning bars take many small steps, and must not miss the contact when the
+        // iterations run out
+        let gjk = GJK2::new();
+        let steps = 2000;
+        for i in 0..100 {
+            let i = i as f32;
+            let left = Rectangle::new(6. + (i * 0.37).sin(), 0.2);
+            let right = Rectangle::new(1., 1. + (i * 0.53).cos().abs());
+            let left_motion = Motion2::new(
+                transform(0., 0., i * 0.3),
+                Vector2::new((i * 0.9).sin(), (i * 1.7).cos()),
+                Rad(10. * (i * 0.61).sin()),
+            );
+            let right_motion = Motion2::new(transform(0., 4., i * 0.5), Vector2::zero(), Rad(0.));
+            let first = (0..=steps).map(|k| k as f32 / steps as f32).find(|&t| {
+                gjk.intersect(
+                    &left,
+                    &left_motion.transform_at(t),
+                    &right,
+                    &right_motion.transform_at(t),
+                )
+                .is_some()
+            });
+            let hit = time_of_impact(&gjk, &left, &left_motion, &right, &right_motion);
+            if let Some(t) = first {
+                let hit = hit.unwrap_or_else(|| panic!("case {} tunneled", i));
+                assert!(hit.time_of_impact <= t, "case {}", i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_time_of_impact_spinning_2d() {
+        // a spinning bar hits a box, that a linear cast of the bar would miss
+        let gjk = GJK2::new();
+        let left = Rectangle::new(10., 0.2);
+        let right = Rectangle::new(1., 1.);
+        let right_motion = Motion2::new(transform(0., 4., 0.), Vector2::zero(), Rad(0.));
+        let hit = time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(0., 0., 0.), Vector2::zero(), Rad::turn_div_4()),
+            &right,
+            &right_motion,
+        )
+        .unwrap();
+        // the bar touches the corner (0.5, 3.5) of the box
+        assert_ulps_eq!(0.89167, hit.time_of_impact, epsilon = 1e-3);
+        assert_ulps_eq!(Point2::new(0.5, 3.5), hit.point, epsilon = 1e-2);
+
+        // not spinning far enough
+        assert!(time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(0., 0., 0.), Vector2::zero(), Rad::turn_div_6()),
+            &right,
+            &right_motion,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_time_of_impact_linear() {
+        let gjk = GJK2::new();
+        let left = Circle::new(1.);
+        let right = Rectangle::new(2., 10.);
+        let hit = time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(0., 0., 0.), Vector2::new(10., 0.), Rad(0.)),
+            &right,
+            &Motion2::new(transform(8., 1., 0.), Vector2::zero(), Rad(-1.)),
+        )
+        .unwrap();
+        // the lower half of the rectangle swings towards the circle
+        assert!(hit.time_of_impact < 0.6);
+        assert!(hit.time_of_impact > 0.4);
+
+        let hit = time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(0., 0., 0.), Vector2::new(10., 0.), Rad(0.)),
+            &right,
+            &Motion2::new(transform(8., 1., 0.), Vector2::zero(), Rad(0.)),
+        )
+        .unwrap();
+        assert_ulps_eq!(0.6, hit.time_of_impact, epsilon = 1e-4);
+        assert_ulps_eq!(Vector2::new(1., 0.), hit.normal, epsilon = 1e-2);
+    }
+
+    fn rectangle(width: f32, height: f32) -> ConvexPolygon<f32> {
+        let (x, y) = (width / 2., height / 2.);
+        ConvexPolygon::new(vec![
+            Point2::new(-x, -y),
+            Point2::new(x, -y),
+            Point2::new(x, y),
+            Point2::new(-x, y),
+        ])
+    }
+
+    #[test]
+    fn test_polygon_time_of_impact() {
+        let gjk = GJK2::new();
+        let left = rectangle(2., 2.);
+        let right = rectangle(2., 10.);
+        let right_motion = Motion2::new(transform(5., 1., 0.), Vector2::zero(), Rad(0.));
+        let hit = polygon_time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(0., 0., 0.), Vector2::new(10., 0.), Rad(0.)),
+            &right,
+            &right_motion,
+            0.,
+        )
+        .unwrap();
+        assert_ulps_eq!(0.3, hit.time_of_impact, epsilon = 1e-4);
+        assert_ulps_eq!(Vector2::new(1., 0.), hit.normal, epsilon = 1e-3);
+        assert_ulps_eq!(4., hit.point.x, epsilon = 1e-3);
+
+        // stop at a larger separation
+        let hit = polygon_time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(0., 0., 0.), Vector2::new(10., 0.), Rad(0.)),
+            &right,
+            &right_motion,
+            0.5,
+        )
+        .unwrap();
+        assert_ulps_eq!(0.25, hit.time_of_impact, epsilon = 1e-4);
+
+        // moving away, and overlapping at the start
+        assert!(polygon_time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(0., 0., 0.), Vector2::new(-10., 0.), Rad(0.)),
+            &right,
+            &right_motion,
+            0.,
+        )
+        .is_none());
+        let hit = polygon_time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(4.5, 0., 0.), Vector2::new(-10., 0.), Rad(0.)),
+            &right,
+            &right_motion,
+            0.,
+        )
+        .unwrap();
+        assert_eq!(0., hit.time_of_impact);
+    }
+
+    #[test]
+    fn test_polygon_time_of_impact_spinning() {
+        // the same spinning bar as for the conservative advancement
+        let gjk = GJK2::new();
+        let left = rectangle(10., 0.2);
+        let right = rectangle(1., 1.);
+        let right_motion = Motion2::new(transform(0., 4., 0.), Vector2::zero(), Rad(0.));
+        let hit = polygon_time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(0., 0., 0.), Vector2::zero(), Rad::turn_div_4()),
+            &right,
+            &right_motion,
+            0.,
+        )
+        .unwrap();
+        assert_ulps_eq!(0.89167, hit.time_of_impact, epsilon = 1e-3);
+        assert_ulps_eq!(Point2::new(0.5, 3.5), hit.point, epsilon = 1e-2);
+
+        assert!(polygon_time_of_impact(
+            &gjk,
+            &left,
+            &Motion2::new(transform(0., 0., 0.), Vector2::zero(), Rad::turn_div_6()),
+            &right,
+            &right_motion,
+            0.,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_polygon_time_of_impact_fast_spin() {
+        // compare with the first contact found by stepping through the motions
+        let gjk = GJK2::new();
+        let target = 0.01;
+        let steps = 4000;
+        for i in 0..200 {
+            let i = i as f32;
+            let left = rectangle(2. + (i * 0.37).sin(), 0.5 + (i * 0.53).cos().abs());
+            let right = rectangle(1. + (i * 0.71).cos().abs(), 3.);
+            let left_motion = Motion2::new(
+                transform(-4., (i * 0.9).sin(), i * 0.3),
+                Vector2::new(8., (i * 1.7).cos() * 2.),
+                Rad(8. * (i * 0.61).sin()),
+            );
+            let right_motion = Motion2::new(
+                transform(0., 0., i * 0.5),
+                Vector2::zero(),
+                Rad(-4. * (i * 1.3).cos()),
+            );
+            let distance = |t: f32| {
+                gjk.separation(
+                    &left,
+                    &left_motion.transform_at(t),
+                    &right,
+                    &right_motion.transform_at(t),
+                )
+                .map_or(0., |separation| separation.distance)
+            };
+            let first = (0..=steps)
+                .map(|k| k as f32 / steps as f32)
+                .find(|&t| distance(t) <= target);
+            let hit =
+                polygon_time_of_impact(&gjk, &left, &left_motion, &right, &right_motion, target);
+            match (hit, first) {
+                (Some(hit), Some(t)) => {
+                    let error = (hit.time_of_impact - t).abs();
+                    assert!(error <= 2. / steps as f32, "case {}", i);
+                }
+                // a contact between two steps
+                (Some(hit), None) => assert!(distance(hit.time_of_impact) <= target + 1e-3),
+                (None, Some(_)) => panic!("case {} tunneled", i),
+                (None, None) => (),
+            }
+        }
+    }
+
+    #[test]
+    fn test_time_of_impact_spinning_3d() {
+        let gjk = GJK3::new();
+        let left = Cuboid::new(10., 0.2, 0.2);
+        let right = Cuboid::new(1., 1., 1.);
+        let hit = time_of_impact(
+            &gjk,
+            &left,
+            &Motion3::new(
+                transform_3d(0., 0., 0.),
+                Vector3::zero(),
+                Vector3::unit_z() * Rad::<f32>::turn_div_4().0,
+            ),
+            &right,
+            &Motion3::new(transform_3d(0., 4., 0.), Vector3::zero(), Vector3::zero()),
+        )
+        .unwrap();
+        assert_ulps_eq!(0.89167, hit.time_of_impact, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_sphere_triangle_cast() {
+        let triangle = [
+            Point3::new(-1., 0., -1.),
+            Point3::new(1., 0., -1.),
+            Point3::new(0., 0., 1.),
+        ];
+        let cast = |center, displacement| sphere_triangle_cast(center, 1., displacement, &triangle);
+
+        // face
+        let hit = cast(Point3::new(0., 3., 0.), Vector3::new(0., -4., 0.)).unwrap();
+        assert_ulps_eq!(0.5, hit.time_of_impact);
+        assert_ulps_eq!(Point3::new(0., 0., 0.), hit.point);
+        assert_ulps_eq!(Vector3::new(0., -1., 0.), hit.normal);
+
+        // the back face is hit the same way
+        let hit = cast(Point3::new(0., -3., 0.), Vector3::new(0., 4., 0.)).unwrap();
+        assert_ulps_eq!(0.5, hit.time_of_impact);
+        assert_ulps_eq!(Vector3::new(0., 1., 0.), hit.normal);
+
+        // edge
+        let hit = cast(Point3::new(0., 0., -4.), Vector3::new(0., 0., 4.)).unwrap();
+        assert_ulps_eq!(0.5, hit.time_of_impact);
+        assert_ulps_eq!(Point3::new(0., 0., -1.), hit.point);
+        assert_ulps_eq!(Vector3::new(0., 0., 1.), hit.normal);
+
+        // vertex
+        let hit = cast(Point3::new(0., 0.6, 4.), Vector3::new(0., 0., -4.)).unwrap();
+        assert_ulps_eq!(0.55, hit.time_of_impact, epsilon = 1e-6);
+        assert_ulps_eq!(Point3::new(0., 0., 1.), hit.point);
+        assert_ulps_eq!(Vector3::new(0., -0.6, -0.8), hit.normal);
+
+        // too short, and passing by
+        assert!(cast(Point3::new(0., 3., 0.), Vector3::new(0., -1.5, 0.)).is_none());
+        assert!(cast(Point3::new(3., 3., 0.), Vector3::new(0., -6., 0.)).is_none());
+
+        // initial overlap
+        let hit = cast(Point3::new(0., 0.5, 0.), Vector3::new(1., 0., 0.)).unwrap();
+        assert_eq!(0., hit.time_of_impact);
+        assert_ulps_eq!(Vector3::new(0., -1., 0.), hit.normal);
+    }
+}
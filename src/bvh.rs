@@ -0,0 +1,338 @@
+//! [Static bounding volume hierarchy](struct.Bvh.html), built once over a list of bounded
+//! values, with the surface area heuristic.
+//!
+//! Where the [DBVT](../dbvt/struct.DynamicBoundingVolumeTree.html) is built for values that move,
+//! and changes a little at a time, this tree is built top down over all values at once, and can
+//! not be changed afterwards. That gives a tree of better quality, for level geometry and other
+//! values that do not move.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate cgmath;
+//! # extern crate collision;
+//!
+//! use cgmath::{Point2, Vector2};
+//! use collision::{Aabb2, Ray2};
+//! use collision::bvh::Bvh;
+//! use collision::prelude::*;
+//!
+//! struct Wall {
+//!     aabb: Aabb2<f32>,
+//! }
+//!
+//! impl HasBound for Wall {
+//!     type Bound = Aabb2<f32>;
+//!
+//!     fn bound(&self) -> &Aabb2<f32> {
+//!         &self.aabb
+//!     }
+//! }
+//!
+//! fn wall(minx: f32, miny: f32, maxx: f32, maxy: f32) -> Wall {
+//!     Wall {
+//!         aabb: Aabb2::new(Point2::new(minx, miny), Point2::new(maxx, maxy)),
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let walls = vec![
+//!         wall(0., 0., 10., 1.),
+//!         wall(0., 9., 10., 10.),
+//!         wall(0., 0., 1., 10.),
+//!         wall(9., 0., 10., 10.),
+//!     ];
+//!     let bvh = Bvh::new(&walls);
+//!
+//!     let ray = Ray2::new(Point2::new(5., 5.), Vector2::new(0., 1.));
+//!     assert_eq!(vec![1], bvh.query_ray(&ray));
+//!
+//!     let region = Aabb2::new(Point2::new(8., 8.), Point2::new(12., 12.));
+//!     let mut hits = bvh.query_aabb(&region);
+//!     hits.sort();
+//!     assert_eq!(vec![1, 3], hits);
+//! }
+//! ```
+
+use cgmath::BaseFloat;
+use cgmath::prelude::*;
+use cgmath::num_traits::NumCast;
+
+use crate::{Frustum, PlaneBound, Relation};
+use crate::prelude::*;
+
+const BINS: usize = 12;
+const DEFAULT_MAX_LEAF_SIZE: usize = 4;
+
+/// Static bounding volume hierarchy over a list of bounded values.
+///
+/// The tree is built top down, splitting each node where the surface area heuristic estimates
+/// the lowest cost for queries, using binning of the bound centers along each axis. A node is
+/// made a leaf when splitting does not lower the cost, and it holds at most the max leaf size
+/// values, or when all bound centers are at the same position.
+///
+/// The nodes are stored in depth first order, with each node storing the index of the node
+/// after its subtree. The queries walk the list, and skip to that index when a node is missed,
+/// so they need no stack.
+///
+/// Queries return indices into the list of values the tree was built from. Values can not be
+/// added or removed after the build, so the tree is not a
+/// [`BroadPhase`](../algorithm/broad_phase/trait.BroadPhase.html), rebuild it when the values
+/// change, or use a [`TreeBroadPhase`](../algorithm/broad_phase/struct.TreeBroadPhase.html).
+///
+/// # Type parameters:
+///
+/// - `B`: Bounding box type, [`Aabb2`](../struct.Aabb2.html) or [`Aabb3`](../struct.Aabb3.html)
+#[derive(Debug, Clone)]
+pub struct Bvh<B> {
+    nodes: Vec<Node<B>>,
+    values: Vec<(usize, B)>,
+}
+
+/// Node of the tree. The first child of a branch is the next node.
+#[derive(Debug, Clone)]
+struct Node<B> {
+    bound: B,
+    /// Index of the node after the subtree of this node
+    skip: usize,
+    /// Range in the values list, empty for branches
+    start: usize,
+    count: usize,
+}
+
+impl<S, B> Bvh<B>
+where
+    S: BaseFloat,
+    B: Aabb<Scalar = S> + Union<B, Output = B> + SurfaceArea<Scalar = S> + Copy,
+{
+    /// Build a tree over the bounds of the given values, with at most 4 values in each leaf
+    pub fn new<A>(values: &[A]) -> Self
+    where
+        A: HasBound<Bound = B>,
+    {
+        Self::with_max_leaf_size(values, DEFAULT_MAX_LEAF_SIZE)
+    }
+
+    /// Build a tree over the bounds of the given values, with at most the given number of values
+    /// in each leaf, unless the values can not be split
+    pub fn with_max_leaf_size<A>(values: &[A], max_leaf_size: usize) -> Self
+    where
+        A: HasBound<Bound = B>,
+    {
+        let mut bvh = Bvh {
+            nodes: Vec::with_capacity(2 * values.len()),
+            values: values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (index, *value.bound()))
+                .collect(),
+        };
+        if !bvh.values.is_empty() {
+            bvh.build(0, bvh.values.len(), max_leaf_size.max(1));
+        }
+        bvh
+    }
+
+    /// Return the number of values in the tree
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return true if the tree was built over an empty list
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Return the number of nodes in the tree
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the bound of all values, or None if the tree is empty
+    pub fn bound(&self) -> Option<&B> {
+        self.nodes.first().map(|node| &node.bound)
+    }
+
+    /// Find all values with bounds accepted by the given test. The test is also used on the
+    /// bounds of the nodes, and must accept a node if it accepts any value inside it.
+    ///
+    /// ### Returns
+    ///
+    /// Indices of the accepted values, in the list the tree was built from.
+    pub fn query<F>(&self, mut test: F) -> Vec<usize>
+    where
+        F: FnMut(&B) -> bool,
+    {
+        let mut results = Vec::default();
+        let mut i = 0;
+        while i < self.nodes.len() {
+            let node = &self.nodes[i];
+            if !test(&node.bound) {
+                i = node.skip;
+                continue;
+            }
+            for &(index, ref bound) in &self.values[node.start..node.start + node.count] {
+                if test(bound) {
+                    results.push(index);
+                }
+            }
+            i += 1;
+        }
+        results
+    }
+
+    /// Find all values with bounds hit by the given ray
+    pub fn query_ray<R>(&self, ray: &R) -> Vec<usize>
+    where
+        B: Discrete<R>,
+    {
+        self.query(|bound| bound.intersects(ray))
+    }
+
+    /// Find all values with bounds overlapping the given box
+    pub fn query_aabb(&self, aabb: &B) -> Vec<usize>
+    where
+        B: Discrete<B>,
+    {
+        self.query(|bound| bound.intersects(aabb))
+    }
+
+    /// Find all values with bounds inside or intersecting the given frustum, for visibility
+    /// culling. The values in nodes fully inside the frustum are returned without testing them.
+    ///
+    /// ### Returns
+    ///
+    /// Indices of the values, with their relation to the frustum, which is either `Relation::In`
+    /// or `Relation::Cross`.
+    pub fn query_frustum(&self, frustum: &Frustum<S>) -> Vec<(usize, Relation)>
+    where
+        B: PlaneBound<S>,
+    {
+        let mut results = Vec::default();
+        let mut i = 0;
+        while i < self.nodes.len() {
+            let node = &self.nodes[i];
+            match frustum.contains(&node.bound) {
+                Relation::Out => (),
+                Relation::In => {
+                    for inner in &self.nodes[i..node.skip] {
+                        for &(index, _) in &self.values[inner.start..inner.start + inner.count] {
+                            results.push((index, Relation::In));
+                        }
+                    }
+                }
+                Relation::Cross => {
+                    for &(index, ref bound) in &self.values[node.start..node.start + node.count] {
+                        let relation = frustum.contains(bound);
+                        if relation != Relation::Out {
+                            results.push((index, relation));
+                        }
+                    }
+                    i += 1;
+                    continue;
+                }
+            }
+            i = node.skip;
+        }
+        results
+    }
+
+    /// Build the subtree for the given range of values
+    fn build(&mut self, start: usize, end: usize, max_leaf_size: usize) {
+        let values = &mut self.values[start..end];
+        let bound = union(values.iter().map(|v| v.1)).unwrap();
+        let node_index = self.nodes.len();
+        self.nodes.push(Node {
+            bound,
+            skip: 0,
+            start,
+            count: end - start,
+        });
+        if let Some(middle) = split(values, &bound, max_leaf_size) {
+            self.nodes[node_index].count = 0;
+            self.build(start, start + middle, max_leaf_size);
+            self.build(start + middle, end, max_leaf_size);
+        }
+        self.nodes[node_index].skip = self.nodes.len();
+    }
+}
+
+/// Union of all the given bounds
+fn union<B, I>(bounds: I) -> Option<B>
+where
+    B: Union<B, Output = B>,
+    I: IntoIterator<Item = B>,
+{
+    bounds.into_iter().fold(None, |acc, b| match acc {
+        Some(acc) => Some(acc.union(&b)),
+        None => Some(b),
+    })
+}
+
+/// Sort the values into two groups at the split with the lowest surface area heuristic cost,
+/// and return the number of values in the first group, or None if the node should be a leaf
+fn split<S, B>(values: &mut [(usize, B)], bound: &B, max_leaf_size: usize) -> Option<usize>
+where
+    S: BaseFloat,
+    B: Aabb<Scalar = S> + Union<B, Output = B> + SurfaceArea<Scalar = S> + Copy,
+{
+    if values.len() <= 1 {
+        return None;
+    }
+    let centers = union(values.iter().map(|v| B::new(v.1.center(), v.1.center()))).unwrap();
+    let (min, max) = (centers.min(), centers.max());
+    let bins: S = NumCast::from(BINS).unwrap();
+    let bin = |axis: usize, bound: &B| {
+        let offset = (bound.center()[axis] - min[axis]) / (max[axis] - min[axis]) * bins;
+        offset.to_usize().unwrap_or(0).min(BINS - 1)
+    };
+
+    // lowest cost over all axes, as (cost, axis, last bin in the first group)
+    let mut best: Option<(S, usize, usize)> = None;
+    for axis in 0..B::Diff::len() {
+        if max[axis] <= min[axis] {
+            continue;
+        }
+        let mut bin_bounds: [Option<B>; BINS] = [None; BINS];
+        let mut bin_counts = [0usize; BINS];
+        for (_, bound) in values.iter() {
+            let i = bin(axis, bound);
+            bin_counts[i] += 1;
+            bin_bounds[i] = Some(bin_bounds[i].map_or(*bound, |b| b.union(bound)));
+        }
+        for last in 0..(BINS - 1) {
+            let left = union(bin_bounds[..=last].iter().filter_map(|b| *b));
+            let right = union(bin_bounds[(last + 1)..].iter().filter_map(|b| *b));
+            if let (Some(left), Some(right)) = (left, right) {
+                let left_count: usize = bin_counts[..=last].iter().sum();
+                let right_count = values.len() - left_count;
+                // the bound of a child is tested before its values
+                let cost = left.surface_area() * NumCast::from(left_count + 1).unwrap()
+                    + right.surface_area() * NumCast::from(right_count + 1).unwrap();
+                let better = match best {
+                    Some((best_cost, _, _)) => cost < best_cost,
+                    None => true,
+                };
+                if better {
+                    best = Some((cost, axis, last));
+                }
+            }
+        }
+    }
+
+    // all centers at the same position, so the values can not be told apart
+    let (cost, axis, last) = best?;
+    // the expected number of bound tests, relative to the area of the node
+    let leaf_cost = bound.surface_area() * NumCast::from(values.len()).unwrap();
+    if values.len() <= max_leaf_size && cost >= leaf_cost {
+        return None;
+    }
+    let mut middle = 0;
+    for i in 0..values.len() {
+        if bin(axis, &values[i].1) <= last {
+            values.swap(i, middle);
+            middle += 1;
+        }
+    }
+    Some(middle)
+}
@@ -1,5 +1,6 @@
 //! Collision contact manifold
 
+use cgmath::BaseFloat;
 use cgmath::prelude::*;
 
 /// Collision strategy to use for collisions.
@@ -19,6 +20,11 @@ pub enum CollisionStrategy {
 
 /// Contact manifold for a single collision contact point.
 ///
+/// The contact point is the point of the left shape deepest inside the right shape, and the point
+/// of the right shape deepest inside the left shape is `penetration_depth` back along the normal,
+/// see [`left_point`](struct.Contact.html#method.left_point) and
+/// [`right_point`](struct.Contact.html#method.right_point).
+///
 /// # Type parameters
 ///
 /// - `P`: cgmath point type
@@ -27,13 +33,15 @@ pub struct Contact<P: EuclideanSpace> {
     /// The collision strategy used for this contact.
     pub strategy: CollisionStrategy,
 
-    /// The collision normal. Only applicable if the collision strategy is not `CollisionOnly`
+    /// The collision normal, pointing from the left shape towards the right shape. Only applicable
+    /// if the collision strategy is not `CollisionOnly`
     pub normal: P::Diff,
 
     /// The penetration depth. Only applicable if the collision strategy is not `CollisionOnly`
     pub penetration_depth: P::Scalar,
 
-    /// The contact point. Only applicable if the collision strategy is not `CollisionOnly`
+    /// The contact point on the left shape, in world space. Only applicable if the collision
+    /// strategy is not `CollisionOnly`
     pub contact_point: P,
 
     /// The time of impact, only applicable for continuous collision detection, value is in
@@ -75,4 +83,131 @@ where
             time_of_impact: P::Scalar::zero(),
         }
     }
+
+    /// The minimum translation vector, the shortest translation of the left shape that separates
+    /// it from the right shape. Only applicable if the collision strategy is not `CollisionOnly`
+    pub fn minimum_translation(&self) -> P::Diff {
+        P::Diff::zero() - self.normal * self.penetration_depth
+    }
+
+    /// The point on the left shape deepest inside the right shape, in world space. Only
+    /// applicable if the collision strategy is not `CollisionOnly`
+    pub fn left_point(&self) -> P {
+        self.contact_point
+    }
+
+    /// The point on the right shape deepest inside the left shape, in world space. Only
+    /// applicable if the collision strategy is not `CollisionOnly`
+    pub fn right_point(&self) -> P {
+        self.contact_point - self.normal * self.penetration_depth
+    }
+
+    /// The same contact with the shapes swapped, the normal is reversed and the contact point
+    /// moves to the surface of the right shape
+    pub fn flipped(&self) -> Self {
+        if self.strategy == CollisionStrategy::CollisionOnly {
+            return self.clone();
+        }
+        let mut contact = Self::new_with_point(
+            self.strategy.clone(),
+            P::Diff::zero() - self.normal,
+            self.penetration_depth,
+            self.right_point(),
+        );
+        contact.time_of_impact = self.time_of_impact;
+        contact
+    }
+}
+
+/// Feature of a shape that generated a contact point, identified by the indices of its vertices
+/// or faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContactFeature {
+    /// The vertex with the given index
+    Vertex(usize),
+    /// The edge between the vertices with the given indices, smaller index first
+    Edge(usize, usize),
+    /// The face with the given index
+    Face(usize),
+}
+
+impl ContactFeature {
+    /// The edge between the given vertices, in any order
+    pub fn edge(a: usize, b: usize) -> Self {
+        ContactFeature::Edge(a.min(b), a.max(b))
+    }
+}
+
+/// Identifies a contact point by the features of both shapes that generated it.
+///
+/// The features stay the same while the shapes move a little, so physics engines can match the
+/// contact points of a manifold with the points of the previous frame, to warm start the solver
+/// and to anchor friction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContactId {
+    /// Feature of the left shape
+    pub left: ContactFeature,
+
+    /// Feature of the right shape
+    pub right: ContactFeature,
+}
+
+impl ContactId {
+    /// Create a new contact id
+    pub fn new(left: ContactFeature, right: ContactFeature) -> Self {
+        ContactId { left, right }
+    }
+
+    /// The same id with the shapes swapped
+    pub fn flipped(&self) -> Self {
+        ContactId::new(self.right, self.left)
+    }
+}
+
+/// A single point of a contact [`Manifold`](struct.Manifold.html).
+///
+/// # Type parameters
+///
+/// - `P`: cgmath point type
+#[derive(Debug, Clone)]
+pub struct ManifoldPoint<P: EuclideanSpace> {
+    /// The contact point on the left shape, in world space
+    pub point: P,
+
+    /// The penetration depth at the contact point, along the normal of the manifold
+    pub depth: P::Scalar,
+
+    /// The features of the shapes that generated the contact point
+    pub id: ContactId,
+}
+
+/// Contact manifold with several contact points, for shapes that touch along an edge or a face.
+///
+/// A single contact point, like the one in a [`Contact`](struct.Contact.html), lets a resting box
+/// rock around that point, while a manifold with a point in each corner of the touching area
+/// keeps it stable.
+///
+/// # Type parameters
+///
+/// - `P`: cgmath point type
+#[derive(Debug, Clone)]
+pub struct Manifold<P: EuclideanSpace> {
+    /// The collision normal, pointing from the left shape towards the right shape
+    pub normal: P::Diff,
+
+    /// The contact points, at most 2 in 2D and 4 in 3D
+    pub points: Vec<ManifoldPoint<P>>,
+}
+
+impl<S, P> Manifold<P>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+{
+    /// The largest penetration depth of the contact points, zero if there are no points
+    pub fn max_depth(&self) -> S {
+        self.points.iter().map(|p| p.depth).fold(S::zero(), S::max)
+    }
 }
@@ -91,13 +91,18 @@ pub use self::util::*;
 pub use self::visitor::*;
 pub use self::wrapped::TreeValueWrapped;
 
-use std::cmp::max;
+use std::cmp::{max, Ordering, Reverse};
+use std::collections::BinaryHeap;
 use std::fmt;
+use std::iter::FromIterator;
 
-use cgmath::num_traits::NumCast;
+use bit_set::BitSet;
+use cgmath::{Array, BaseFloat, EuclideanSpace, InnerSpace};
+use cgmath::num_traits::{NumCast, Zero};
 use rand;
 use rand::Rng;
 
+use crate::{Frustum, PlaneBound, Relation};
 use crate::prelude::*;
 
 mod wrapped;
@@ -107,12 +112,18 @@ mod util;
 const SURFACE_AREA_IMPROVEMENT_FOR_ROTATION: f32 = 0.3;
 const PERFORM_ROTATION_PERCENTAGE: u32 = 10;
 
+/// Margin type of a bounding volume, the vector type of its points
+type Margin<B> = <<B as Bound>::Point as EuclideanSpace>::Diff;
+
+/// Value index, fat bound and center of the fat bound of a leaf, used when building the tree
+type BuildLeaf<B> = (usize, B, <B as Bound>::Point);
+
 /// Trait that needs to be implemented for any value that is to be used in the
 /// [`DynamicBoundingVolumeTree`](struct.DynamicBoundingVolumeTree.html).
 ///
 pub trait TreeValue: Clone {
     /// Bounding volume type
-    type Bound;
+    type Bound: Bound;
 
     /// Return the bounding volume of the value
     fn bound(&self) -> &Self::Bound;
@@ -248,6 +259,7 @@ where
     updated_list: Vec<usize>,
     root_index: usize,
     refit_nodes: Vec<(u32, usize)>,
+    margin: Option<Margin<T::Bound>>,
 }
 
 impl<T> Default for DynamicBoundingVolumeTree<T>
@@ -263,6 +275,7 @@ where
             updated_list: Vec::default(),
             root_index: 0,
             refit_nodes: Vec::default(),
+            margin: None,
         }
     }
 }
@@ -342,6 +355,16 @@ where
         Default::default()
     }
 
+    /// Return the fattened bounding volume to store in the leaf for the given value.
+    ///
+    /// Uses the margin of the tree if one is set, otherwise the fat bound of the value.
+    fn fat_bound(&self, value: &T) -> T::Bound {
+        match self.margin {
+            Some(margin) => value.bound().with_margin(margin),
+            None => value.get_bound_with_margin(),
+        }
+    }
+
     /// Return the number of nodes in the tree.
     ///
     pub fn size(&self) -> usize {
@@ -430,6 +453,24 @@ where
         }
     }
 
+    /// Query the tree for all values with bounds overlapping the given box.
+    ///
+    /// Useful for area of effect queries, and for selecting all values inside a box.
+    ///
+    /// ### Returns
+    ///
+    /// Will return a list of the values with overlapping bounds.
+    ///
+    pub fn query_aabb(&self, aabb: &T::Bound) -> Vec<&T>
+    where
+        T::Bound: Discrete<T::Bound>,
+    {
+        self.query(&mut DiscreteVisitor::<T::Bound, T>::new(aabb))
+            .into_iter()
+            .map(|(value, _)| value)
+            .collect()
+    }
+
     /// Query the tree for all leafs that the given visitor accepts.
     ///
     /// Will do a depth first search of the tree and pass all bounding volumes on the way to the
@@ -516,6 +557,277 @@ where
         values
     }
 
+    /// Query the tree for all values with bounds inside or intersecting the given frustum, for
+    /// visibility culling.
+    ///
+    /// Unlike querying with a [`FrustumVisitor`](struct.FrustumVisitor.html), subtrees with
+    /// bounds fully inside the frustum are not tested any further, and all values in them are
+    /// returned as inside.
+    ///
+    /// ### Returns
+    ///
+    /// Will return a list of tuples of values and their relation to the frustum, which is either
+    /// `Relation::In` or `Relation::Cross`.
+    ///
+    pub fn query_frustum<S>(&self, frustum: &Frustum<S>) -> Vec<(&T, Relation)>
+    where
+        S: BaseFloat,
+        T::Bound: PlaneBound<S>,
+    {
+        let mut stack = vec![(self.root_index, Relation::Cross)];
+        let mut values = Vec::default();
+        while let Some((node_index, parent_relation)) = stack.pop() {
+            let bound = match self.nodes[node_index] {
+                Node::Leaf(ref leaf) => self.values[leaf.value].1.bound(),
+                Node::Branch(ref branch) => &branch.bound,
+                Node::Nil => continue,
+            };
+            let relation = match parent_relation {
+                Relation::In => Relation::In,
+                _ => frustum.contains(bound),
+            };
+            match (relation, &self.nodes[node_index]) {
+                (Relation::Out, _) | (_, Node::Nil) => (),
+                (_, Node::Leaf(leaf)) => values.push((&self.values[leaf.value].1, relation)),
+                (_, Node::Branch(branch)) => {
+                    stack.push((branch.left, relation));
+                    stack.push((branch.right, relation));
+                }
+            }
+        }
+        values
+    }
+
+    /// Find the value with the bound closest to the given point.
+    ///
+    /// See [`nearest_by`](struct.DynamicBoundingVolumeTree.html#method.nearest_by), using the
+    /// distance to the bound of each value.
+    ///
+    /// ### Returns
+    ///
+    /// Optionally returns the closest value, along with the distance from the point to its bound,
+    /// which is zero if the point is inside the bound. Returns `None` if the tree is empty.
+    ///
+    pub fn nearest<P>(&self, point: &P) -> Option<(&T, P::Scalar)>
+    where
+        P: EuclideanSpace,
+        P::Scalar: BaseFloat,
+        P::Diff: InnerSpace,
+        T::Bound: ClosestPoint<P>,
+    {
+        self.nearest_by(point, |value| bound_distance(value.bound(), point))
+    }
+
+    /// Find the value closest to the given point, using the given distance to each value.
+    ///
+    /// Will do a best first search of the tree, visiting the nodes in order of the distance from
+    /// the point to their bounds, and stop when no node left can have a value closer than the
+    /// closest found so far. The distance is only computed for values in the visited leafs.
+    ///
+    /// ### Parameters:
+    ///
+    /// - `point`: The point to find the closest value to.
+    /// - `distance`: Distance from the point to a value, for example to the shape of the value.
+    ///   Must never be less than the distance from the point to the bound of the value.
+    ///
+    /// ### Returns
+    ///
+    /// Optionally returns the closest value, along with its distance. Returns `None` if the tree
+    /// is empty.
+    ///
+    pub fn nearest_by<P, F>(&self, point: &P, distance: F) -> Option<(&T, P::Scalar)>
+    where
+        P: EuclideanSpace,
+        P::Scalar: BaseFloat,
+        P::Diff: InnerSpace,
+        T::Bound: ClosestPoint<P>,
+        F: FnMut(&T) -> P::Scalar,
+    {
+        self.k_nearest_by(point, 1, distance).pop()
+    }
+
+    /// Find the `k` values with bounds closest to the given point.
+    ///
+    /// See [`k_nearest_by`](struct.DynamicBoundingVolumeTree.html#method.k_nearest_by), using the
+    /// distance to the bound of each value.
+    ///
+    /// ### Returns
+    ///
+    /// Returns at most `k` values, along with the distances from the point to their bounds, sorted
+    /// by increasing distance.
+    ///
+    pub fn k_nearest<P>(&self, point: &P, k: usize) -> Vec<(&T, P::Scalar)>
+    where
+        P: EuclideanSpace,
+        P::Scalar: BaseFloat,
+        P::Diff: InnerSpace,
+        T::Bound: ClosestPoint<P>,
+    {
+        self.k_nearest_by(point, k, |value| bound_distance(value.bound(), point))
+    }
+
+    /// Find the `k` values closest to the given point, using the given distance to each value.
+    ///
+    /// Will do a best first search of the tree, visiting the nodes in order of the distance from
+    /// the point to their bounds, and stop when no node left can have a value closer than the
+    /// `k` closest found so far. The distance is only computed for values in the visited leafs.
+    ///
+    /// ### Parameters:
+    ///
+    /// - `point`: The point to find the closest values to.
+    /// - `k`: The number of values to find.
+    /// - `distance`: Distance from the point to a value, for example to the shape of the value.
+    ///   Must never be less than the distance from the point to the bound of the value.
+    ///
+    /// ### Returns
+    ///
+    /// Returns at most `k` values, along with their distances, sorted by increasing distance.
+    ///
+    pub fn k_nearest_by<P, F>(&self, point: &P, k: usize, mut distance: F) -> Vec<(&T, P::Scalar)>
+    where
+        P: EuclideanSpace,
+        P::Scalar: BaseFloat,
+        P::Diff: InnerSpace,
+        T::Bound: ClosestPoint<P>,
+        F: FnMut(&T) -> P::Scalar,
+    {
+        // closest values found so far, as value indices and distances, sorted by distance
+        let mut closest: Vec<(usize, P::Scalar)> = Vec::with_capacity(k);
+        let mut heap = BinaryHeap::new();
+        if let Node::Nil = self.nodes[self.root_index] {
+            return Vec::default();
+        }
+        if k > 0 {
+            heap.push(Candidate {
+                distance: bound_distance(get_bound(&self.nodes[self.root_index]), point),
+                node_index: self.root_index,
+            });
+        }
+        while let Some(candidate) = heap.pop() {
+            if closest.len() == k && candidate.distance >= closest[k - 1].1 {
+                break;
+            }
+            match self.nodes[candidate.node_index] {
+                Node::Leaf(ref leaf) => {
+                    let d = distance(&self.values[leaf.value].1);
+                    let position = closest
+                        .iter()
+                        .position(|&(_, other)| d < other)
+                        .unwrap_or(closest.len());
+                    if position < k {
+                        closest.truncate(k - 1);
+                        closest.insert(position, (leaf.value, d));
+                    }
+                }
+                Node::Branch(ref branch) => {
+                    for &child in &[branch.left, branch.right] {
+                        heap.push(Candidate {
+                            distance: bound_distance(get_bound(&self.nodes[child]), point),
+                            node_index: child,
+                        });
+                    }
+                }
+                Node::Nil => (),
+            }
+        }
+        closest
+            .into_iter()
+            .map(|(value_index, d)| (&self.values[value_index].1, d))
+            .collect()
+    }
+
+    /// Find all pairs of values in the tree with intersecting bounds.
+    ///
+    /// Will traverse the tree against itself, so only subtrees with intersecting bounding volumes
+    /// are tested against each other. The fat bounds are used for the internal nodes, and the
+    /// value bounds for the final leaf tests. Should be called after
+    /// [`do_refit`](struct.DynamicBoundingVolumeTree.html#method.do_refit), like the queries.
+    ///
+    /// ### Returns
+    ///
+    /// A list of tuples of indices into the values list, with the lower index first, sorted by
+    /// increasing left index.
+    ///
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)>
+    where
+        T::Bound: Discrete<T::Bound>,
+    {
+        self.overlapping_pairs_by(|_, _| true)
+    }
+
+    /// Find all pairs of values in the tree with intersecting bounds, that are accepted by the
+    /// given filter.
+    ///
+    /// Same as [`overlapping_pairs`](#method.overlapping_pairs), but the filter is called for
+    /// each pair of values with intersecting bounds, and rejected pairs are never added to the
+    /// result.
+    ///
+    /// ### Returns
+    ///
+    /// A list of tuples of indices into the values list, with the lower index first, sorted by
+    /// increasing left index.
+    ///
+    pub fn overlapping_pairs_by<F>(&self, mut filter: F) -> Vec<(usize, usize)>
+    where
+        T::Bound: Discrete<T::Bound>,
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut pairs = Vec::default();
+        let mut stack = vec![(self.root_index, self.root_index)];
+        while let Some((left_index, right_index)) = stack.pop() {
+            match (&self.nodes[left_index], &self.nodes[right_index]) {
+                (Node::Nil, _) | (_, Node::Nil) => (),
+
+                // pairs within a subtree are in the pairs of its children, or between them
+                (Node::Branch(branch), _) if left_index == right_index => {
+                    stack.push((branch.left, branch.left));
+                    stack.push((branch.right, branch.right));
+                    stack.push((branch.left, branch.right));
+                }
+
+                (Node::Leaf(left), Node::Leaf(right)) => {
+                    let (left_value, right_value) =
+                        (&self.values[left.value].1, &self.values[right.value].1);
+                    if left_index != right_index
+                        && left_value.bound().intersects(right_value.bound())
+                        && filter(left_value, right_value)
+                    {
+                        pairs.push((left.value.min(right.value), left.value.max(right.value)));
+                    }
+                }
+
+                (Node::Branch(left), Node::Leaf(right)) => {
+                    if left.bound.intersects(&right.bound) {
+                        stack.push((left.left, right_index));
+                        stack.push((left.right, right_index));
+                    }
+                }
+
+                (Node::Leaf(left), Node::Branch(right)) => {
+                    if left.bound.intersects(&right.bound) {
+                        stack.push((left_index, right.left));
+                        stack.push((left_index, right.right));
+                    }
+                }
+
+                // descend into the higher subtree
+                (Node::Branch(left), Node::Branch(right)) => {
+                    if left.bound.intersects(&right.bound) {
+                        if left.height >= right.height {
+                            stack.push((left.left, right_index));
+                            stack.push((left.right, right_index));
+                        } else {
+                            stack.push((left_index, right.left));
+                            stack.push((left_index, right.right));
+                        }
+                    }
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+
     /// Update a node in the tree with a new value.
     ///
     /// The node will be fed its node_index after updating in the tree, so there is no need to
@@ -566,11 +878,7 @@ where
             .filter_map(|&index| {
                 if let Node::Leaf(ref l) = self.nodes[index] {
                     if !l.bound.contains(self.values[l.value].1.bound()) {
-                        Some((
-                            index,
-                            l.parent,
-                            self.values[l.value].1.get_bound_with_margin(),
-                        ))
+                        Some((index, l.parent, self.fat_bound(&self.values[l.value].1)))
                     } else {
                         None
                     }
@@ -622,7 +930,7 @@ where
     /// The node index of the inserted value. This value should never change after insertion.
     ///
     pub fn insert(&mut self, value: T) -> usize {
-        let fat_bound = value.get_bound_with_margin();
+        let fat_bound = self.fat_bound(&value);
         let value_index = self.values.len();
         self.values.push((0, value));
 
@@ -843,6 +1151,86 @@ where
         }
     }
 
+    /// Refit the given leaves to the current bounds of their values, and tighten the bounds of
+    /// all their ancestors.
+    ///
+    /// Unlike [`update`](struct.DynamicBoundingVolumeTree.html#method.update), the leaves get new
+    /// fat bounds even if the values are still inside the old ones, so bounds shrink when values
+    /// do. No nodes are moved or rotated, which makes this much cheaper than removing and
+    /// inserting the values again, for values that move slowly. The tree keeps its structure, so
+    /// its quality will drop if the values move far from where they were inserted.
+    ///
+    /// Each ancestor is only recalculated once, after all its refitted children. The ancestors
+    /// are ordered by height in a heap, so the complexity is O(k * log k), where k is the number
+    /// of ancestors of the given leaves, which is at most m * log n for m leaves in a balanced
+    /// tree.
+    ///
+    /// ### Parameters
+    ///
+    /// - `node_indices`: node indices of the leaves to refit, other node indices are ignored.
+    ///
+    pub fn refit(&mut self, node_indices: &[usize]) {
+        let mut parents = BinaryHeap::default();
+        let mut queued = BitSet::with_capacity(self.nodes.len());
+        for &node_index in node_indices {
+            let (parent_index, fat_bound) = match self.nodes.get(node_index) {
+                Some(Node::Leaf(leaf)) => (leaf.parent, self.fat_bound(&self.values[leaf.value].1)),
+                _ => continue,
+            };
+            if let Node::Leaf(ref mut leaf) = self.nodes[node_index] {
+                leaf.bound = fat_bound;
+            }
+            if parent_index != 0 && queued.insert(parent_index) {
+                let parent_height = get_height(&self.nodes[parent_index]);
+                parents.push(Reverse((parent_height, parent_index)));
+            }
+        }
+
+        // the structure is not changed, so the heights are correct, and going from the lowest
+        // height up recalculates all children before their parents
+        while let Some(Reverse((height, node_index))) = parents.pop() {
+            if let Some((parent_index, _)) = self.recalculate_node(node_index) {
+                if parent_index != 0 && queued.insert(parent_index) {
+                    let parent_height = max(get_height(&self.nodes[parent_index]), height + 1);
+                    parents.push(Reverse((parent_height, parent_index)));
+                }
+            }
+        }
+    }
+
+    /// Recalculate the bounds and heights of all branches, and check every branch for rotation.
+    ///
+    /// [`do_refit`](struct.DynamicBoundingVolumeTree.html#method.do_refit) only checks a few of
+    /// the refitted branches for rotation, to spread the cost over many frames. In long running
+    /// simulations with many insertions and removals, the tree may still degrade over time. This
+    /// can be called periodically, or after loading many values, to restore the quality of the
+    /// tree.
+    ///
+    /// The branches are visited from the leaves up, so each rotation is done with correct bounds
+    /// for the children. Any nodes marked for refitting are refitted in the process.
+    ///
+    /// This method have complexity O(n), where n is the number of nodes in the tree.
+    ///
+    pub fn rebalance(&mut self) {
+        // all descendants of a node are before it in the reversed pre-order, and rotations only
+        // move nodes below the rotated node, so the order stays valid during the pass
+        let mut branches = Vec::default();
+        let mut stack = vec![self.root_index];
+        while let Some(node_index) = stack.pop() {
+            if let Node::Branch(ref branch) = self.nodes[node_index] {
+                branches.push(node_index);
+                stack.push(branch.left);
+                stack.push(branch.right);
+            }
+        }
+
+        for &node_index in branches.iter().rev() {
+            self.recalculate_node(node_index);
+            self.rotate(node_index);
+        }
+        self.refit_nodes.clear();
+    }
+
     /// Get two new node indices, where nodes can be inserted in the tree.
     ///
     fn next_free(&mut self) -> (usize, usize) {
@@ -1083,6 +1471,160 @@ where
     }
 }
 
+impl<T> DynamicBoundingVolumeTree<T>
+where
+    T: TreeValue,
+    T::Bound: Clone + Contains<T::Bound> + Union<T::Bound, Output = T::Bound> + SurfaceArea + Bound,
+{
+    /// Create a new tree, that uses the given margin for the fat bounds of all values.
+    ///
+    /// See [`set_margin`](struct.DynamicBoundingVolumeTree.html#method.set_margin).
+    ///
+    pub fn with_margin(margin: Margin<T::Bound>) -> Self {
+        let mut tree = Self::new();
+        tree.set_margin(margin);
+        tree
+    }
+
+    /// Set the margin used for the fat bounds of all values in the tree.
+    ///
+    /// The fat bound of a leaf is then the bound of its value extended by the margin, instead of
+    /// the bound given by
+    /// [`get_bound_with_margin`](trait.TreeValue.html#tymethod.get_bound_with_margin). A value
+    /// that moves less than the margin will stay inside its fat bound, and will not cause the
+    /// tree to be changed by [`update`](struct.DynamicBoundingVolumeTree.html#method.update).
+    /// A larger margin means fewer tree updates for moving values, but looser bounds, and thus
+    /// more false positives in queries.
+    ///
+    /// Only affects leaves that are inserted or refattened after the margin is set.
+    ///
+    pub fn set_margin(&mut self, margin: Margin<T::Bound>) {
+        self.margin = Some(margin);
+    }
+
+    /// Stop using the tree margin, and use the fat bounds given by the values instead.
+    ///
+    pub fn clear_margin(&mut self) {
+        self.margin = None;
+    }
+}
+
+impl<T> DynamicBoundingVolumeTree<T>
+where
+    T: TreeValue,
+    T::Bound: Clone + Contains<T::Bound> + Union<T::Bound, Output = T::Bound> + SurfaceArea + Bound,
+{
+    /// Replace all values in the tree with the given values, building a balanced tree top down.
+    ///
+    /// The values are split recursively at the median of the centers of their fat bounds, along
+    /// the axis where the centers are most spread out. This is O(n log n), and gives a much
+    /// better tree than inserting the values one by one, so it should be used to create the
+    /// tree for the initial set of values, especially for static scenes. The margin of the tree
+    /// is kept, and used for the fat bounds.
+    ///
+    /// The node indices of the values can be found with
+    /// [`values`](struct.DynamicBoundingVolumeTree.html#method.values), in the same order as
+    /// the values were given.
+    ///
+    pub fn build<I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.clear();
+        self.updated_list.clear();
+        self.values = values.into_iter().map(|value| (0, value)).collect();
+        if self.values.is_empty() {
+            return;
+        }
+
+        let mut leaves = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(value_index, (_, value))| {
+                let bound = self.fat_bound(value);
+                let center = bound.min_extent().midpoint(bound.max_extent());
+                (value_index, bound, center)
+            })
+            .collect::<Vec<_>>();
+        self.nodes.reserve(2 * leaves.len() - 1);
+        self.root_index = self.build_node(&mut leaves, 0);
+    }
+
+    /// Build the subtree for the given leaves, and return the index of its root node
+    fn build_node(&mut self, leaves: &mut [BuildLeaf<T::Bound>], parent: usize) -> usize {
+        let node_index = self.nodes.len();
+        if leaves.len() == 1 {
+            let (value_index, ref bound, _) = leaves[0];
+            self.nodes.push(Node::Leaf(Leaf {
+                parent,
+                value: value_index,
+                bound: bound.clone(),
+            }));
+            self.values[value_index].0 = node_index;
+            return node_index;
+        }
+
+        // the branch is filled in when both children are built
+        self.nodes.push(Node::Nil);
+
+        let (mut lower, mut upper) = (leaves[0].2, leaves[0].2);
+        for &(_, _, center) in leaves.iter() {
+            for i in 0..<T::Bound as Bound>::Point::len() {
+                if center[i] < lower[i] {
+                    lower[i] = center[i];
+                }
+                if center[i] > upper[i] {
+                    upper[i] = center[i];
+                }
+            }
+        }
+        let mut axis = 0;
+        for i in 1..<T::Bound as Bound>::Point::len() {
+            if upper[i] - lower[i] > upper[axis] - lower[axis] {
+                axis = i;
+            }
+        }
+
+        let middle = leaves.len() / 2;
+        leaves.select_nth_unstable_by(middle, |a, b| {
+            a.2[axis].partial_cmp(&b.2[axis]).unwrap_or(Ordering::Equal)
+        });
+        let (left_leaves, right_leaves) = leaves.split_at_mut(middle);
+        let left = self.build_node(left_leaves, node_index);
+        let right = self.build_node(right_leaves, node_index);
+
+        self.nodes[node_index] = Node::Branch(Branch {
+            parent,
+            left,
+            right,
+            height: 1 + max(
+                get_height(&self.nodes[left]),
+                get_height(&self.nodes[right]),
+            ),
+            bound: get_bound(&self.nodes[left]).union(get_bound(&self.nodes[right])),
+        });
+        node_index
+    }
+}
+
+impl<T> FromIterator<T> for DynamicBoundingVolumeTree<T>
+where
+    T: TreeValue,
+    T::Bound: Clone + Contains<T::Bound> + Union<T::Bound, Output = T::Bound> + SurfaceArea + Bound,
+{
+    /// Create a balanced tree from the given values, see
+    /// [`build`](struct.DynamicBoundingVolumeTree.html#method.build).
+    fn from_iter<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut tree = Self::new();
+        tree.build(values);
+        tree
+    }
+}
+
 enum Rotation {
     None,
     LeftRightLeft,
@@ -1281,6 +1823,52 @@ fn get_height<B>(node: &Node<B>) -> u32 {
     }
 }
 
+/// Node waiting to be visited in the nearest value search, ordered so the closest node is the
+/// greatest, for use in a max heap
+struct Candidate<S> {
+    distance: S,
+    node_index: usize,
+}
+
+impl<S: PartialOrd> PartialEq for Candidate<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: PartialOrd> Eq for Candidate<S> {}
+
+impl<S: PartialOrd> PartialOrd for Candidate<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: PartialOrd> Ord for Candidate<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Distance from the point to the bound, zero if the point is inside the bound
+fn bound_distance<B, P>(bound: &B, point: &P) -> P::Scalar
+where
+    B: ClosestPoint<P>,
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+    P::Diff: InnerSpace,
+{
+    let (closest, inside) = bound.closest_point(point);
+    if inside {
+        P::Scalar::zero()
+    } else {
+        (closest - *point).magnitude()
+    }
+}
+
 /// Get the bound of the node. Will panic if node is nil.
 ///
 #[inline]
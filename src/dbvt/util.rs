@@ -2,6 +2,7 @@
 //! [`DynamicBoundingVolumeTree`](struct.DynamicBoundingVolumeTree.html).
 //!
 
+use std::cmp::Ordering;
 use std::marker::PhantomData;
 
 use cgmath::BaseFloat;
@@ -71,6 +72,39 @@ where
     }
 }
 
+/// Visitor returning the distance along the ray to where it enters each bound, which is zero for
+/// bounds containing the ray origin
+struct RayEntryVisitor<'a, S, P, T>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+{
+    ray: &'a Ray<S, P, P::Diff>,
+    marker: PhantomData<T>,
+}
+
+impl<'a, S, P, T> Visitor for RayEntryVisitor<'a, S, P, T>
+where
+    S: BaseFloat,
+    T: TreeValue,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: VectorSpace<Scalar = S> + InnerSpace,
+    T::Bound: Continuous<Ray<S, P, P::Diff>, Result = P> + Contains<P>,
+{
+    type Bound = T::Bound;
+    type Result = S;
+
+    fn accept(&mut self, bound: &Self::Bound, _: bool) -> Option<S> {
+        if bound.contains(&self.ray.origin) {
+            // the ray starts inside the bound, so it enters it at the origin
+            return Some(S::zero());
+        }
+        bound
+            .intersection(self.ray)
+            .map(|point| (point - self.ray.origin).dot(self.ray.direction))
+    }
+}
+
 /// Query the given tree for the closest value that intersects the given ray.
 ///
 /// ### Parameters:
@@ -142,3 +176,73 @@ where
     let mut visitor = ContinuousVisitor::<_, T>::new(&ray);
     tree.query(&mut visitor)
 }
+
+/// Query the given tree for the closest value hit by the given ray, using the given test for
+/// where the ray hits the value itself.
+///
+/// The tree is used to find the values with bounds hit by the ray, which are then tested in the
+/// order the ray enters their bounds, starting with the bounds containing the ray origin. Testing
+/// stops when the ray enters the next bound after the closest hit found so far, so the test, which
+/// is usually a narrow phase test on the shape of the value, is only used on a few of the values.
+///
+/// ### Parameters:
+///
+/// - `tree`: DBVT to query.
+/// - `ray`: Ray to find the closest hit for.
+/// - `hit`: Test for the ray against a value, returning the point where the ray hits the value,
+///   or `None` if it misses. The point must be inside the bound of the value.
+///
+/// ### Returns
+///
+/// Optionally returns the value that had the closest hit with the ray, along with the hit point.
+///
+pub fn query_ray_closest_by<'a, S, T, P, F>(
+    tree: &'a DynamicBoundingVolumeTree<T>,
+    ray: Ray<S, P, P::Diff>,
+    mut hit: F,
+) -> Option<(&'a T, P)>
+where
+    S: BaseFloat,
+    T: TreeValue + 'a,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: VectorSpace<Scalar = S> + InnerSpace,
+    T::Bound: Clone
+        + Contains<T::Bound>
+        + SurfaceArea<Scalar = S>
+        + Union<T::Bound, Output = T::Bound>
+        + Continuous<Ray<S, P, P::Diff>, Result = P>
+        + Contains<P>,
+    F: FnMut(&T, &Ray<S, P, P::Diff>) -> Option<P>,
+{
+    let distance = |point: P| (point - ray.origin).dot(ray.direction);
+    let mut visitor = RayEntryVisitor::<S, P, T> {
+        ray: &ray,
+        marker: PhantomData,
+    };
+    let mut candidates = tree
+        .query(&mut visitor)
+        .into_iter()
+        .map(|(value, entry)| (entry, value))
+        .collect::<Vec<_>>();
+    candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    let mut closest: Option<(S, &'a T, P)> = None;
+    for (entry, value) in candidates {
+        if let Some((tmin, _, _)) = closest {
+            if entry > tmin {
+                break;
+            }
+        }
+        if let Some(point) = hit(value, &ray) {
+            let t = distance(point);
+            let closer = match closest {
+                Some((tmin, _, _)) => t < tmin,
+                None => true,
+            };
+            if closer {
+                closest = Some((t, value, point));
+            }
+        }
+    }
+    closest.map(|(_, value, point)| (value, point))
+}
@@ -93,6 +93,49 @@ where
     }
 }
 
+/// Visitor for finding the values whose bounds contain a point.
+///
+/// Will return () for bounds where the
+/// [`Contains`](../trait.Contains.html) implementation
+/// of bound.contains(self.point) is true.
+///
+#[derive(Debug)]
+pub struct PointVisitor<'a, P: 'a, T> {
+    point: &'a P,
+    marker: PhantomData<T>,
+}
+
+impl<'a, P: 'a, T> PointVisitor<'a, P, T>
+where
+    T: TreeValue,
+    T::Bound: Contains<P>,
+{
+    /// Create a new visitor that will do containment tests using the given point.
+    pub fn new(point: &'a P) -> Self {
+        Self {
+            point,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, P: 'a, T> Visitor for PointVisitor<'a, P, T>
+where
+    T: TreeValue,
+    T::Bound: Contains<P>,
+{
+    type Bound = T::Bound;
+    type Result = ();
+
+    fn accept(&mut self, bound: &Self::Bound, _: bool) -> Option<()> {
+        if bound.contains(self.point) {
+            Some(())
+        } else {
+            None
+        }
+    }
+}
+
 /// Visitor for doing frustum intersection testing on the DBVT.
 ///
 /// Will return the relation for intersections with the
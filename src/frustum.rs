@@ -1,11 +1,13 @@
 //! View frustum for visibility determination
 
-use crate::Plane;
+use crate::{Aabb3, Plane};
 use crate::bound::*;
+use crate::prelude::*;
+use crate::primitive::util::{get_bound, get_max_point};
 use cgmath::{Matrix, Matrix4};
 use cgmath::{Ortho, Perspective, PerspectiveFov};
 use cgmath::BaseFloat;
-use cgmath::Point3;
+use cgmath::{Point3, Transform, Vector3};
 
 /// View frustum, used for frustum culling
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -94,6 +96,23 @@ impl<S: BaseFloat> Frustum<S> {
                 max(cur, r)
             })
     }
+
+    /// Compute the corner points of the frustum, by intersecting the planes.
+    ///
+    /// Returns `None` if any three planes meeting in a corner do not intersect in a single point.
+    pub fn to_points(&self) -> Option<FrustumPoints<S>> {
+        let corner = |a: &Plane<S>, b: Plane<S>, c: Plane<S>| a.intersection(&(b, c));
+        Some(FrustumPoints {
+            near_top_left: corner(&self.near, self.top, self.left)?,
+            near_top_right: corner(&self.near, self.top, self.right)?,
+            near_bottom_left: corner(&self.near, self.bottom, self.left)?,
+            near_bottom_right: corner(&self.near, self.bottom, self.right)?,
+            far_top_left: corner(&self.far, self.top, self.left)?,
+            far_top_right: corner(&self.far, self.top, self.right)?,
+            far_bottom_left: corner(&self.far, self.bottom, self.left)?,
+            far_bottom_right: corner(&self.far, self.bottom, self.right)?,
+        })
+    }
 }
 
 /// View frustum corner points
@@ -118,6 +137,41 @@ pub struct FrustumPoints<S> {
     pub far_bottom_right: Point3<S>,
 }
 
+impl<S: Copy> FrustumPoints<S> {
+    /// Get all eight corner points
+    pub fn to_corners(&self) -> [Point3<S>; 8] {
+        [
+            self.near_top_left,
+            self.near_top_right,
+            self.near_bottom_left,
+            self.near_bottom_right,
+            self.far_top_left,
+            self.far_top_right,
+            self.far_bottom_left,
+            self.far_bottom_right,
+        ]
+    }
+}
+
+/// The frustum points form a convex volume, so they can be used as a primitive for GJK, to test
+/// the actual overlap between a frustum and other primitives.
+impl<S: BaseFloat> Primitive for FrustumPoints<S> {
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        get_max_point(self.to_corners().iter(), direction, transform)
+    }
+}
+
+impl<S: BaseFloat> ComputeBound<Aabb3<S>> for FrustumPoints<S> {
+    fn compute_bound(&self) -> Aabb3<S> {
+        get_bound(self.to_corners().iter())
+    }
+}
+
 /// Conversion trait for converting cgmath projection types into a view frustum
 pub trait Projection<S: BaseFloat>: Into<Matrix4<S>> {
     /// Create a view frustum
@@ -15,6 +15,8 @@ unused_qualifications)]
 extern crate bit_set;
 extern crate cgmath;
 extern crate rand;
+#[cfg(feature = "parallel")]
+extern crate rayon;
 
 #[cfg(feature = "serde")]
 #[macro_use]
@@ -36,7 +38,9 @@ pub use traits::*;
 pub use volume::*;
 
 pub mod prelude;
+pub mod bvh;
 pub mod dbvt;
+pub mod orthtree;
 pub mod primitive;
 pub mod algorithm;
 
@@ -0,0 +1,453 @@
+//! [Quadtree](type.Quadtree.html) and [Octree](type.Octree.html), spatial subdivision trees with
+//! fixed cells, and their [loose variant](struct.Orthtree.html#method.with_looseness).
+//!
+//! Each node covers a box in space, and when a node has too many values, it is split into a
+//! child for each corner of the box. Values are stored in the deepest node that contains them, so
+//! unlike the [DBVT](../dbvt/struct.DynamicBoundingVolumeTree.html), the shape of the tree only
+//! depends on where the values are, not on the order they were inserted in. This makes it simple
+//! and predictable, but values that straddle a split stay in the larger node above it.
+//!
+//! The values are the same as for the DBVT, see [`TreeValue`](../dbvt/trait.TreeValue.html), and
+//! the tree is queried with the same [visitors](../dbvt/trait.Visitor.html), for example
+//! [`PointVisitor`](../dbvt/struct.PointVisitor.html) for the values containing a point,
+//! [`DiscreteVisitor`](../dbvt/struct.DiscreteVisitor.html) for the values overlapping a box,
+//! [`ContinuousVisitor`](../dbvt/struct.ContinuousVisitor.html) for rays, and
+//! [`FrustumVisitor`](../dbvt/struct.FrustumVisitor.html) for view frustum culling.
+//!
+//! Compared to the DBVT, the trees are best for mostly static scenes inside a known region, where
+//! a simple structure, and the limits on depth and node size, matter more than tight bounds.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate cgmath;
+//! # extern crate collision;
+//!
+//! use cgmath::{Point2, Vector2};
+//! use collision::{Aabb2, Ray2};
+//! use collision::dbvt::{ContinuousVisitor, DiscreteVisitor, TreeValue};
+//! use collision::orthtree::Quadtree;
+//!
+//! #[derive(Debug, Clone)]
+//! struct Value {
+//!     pub id: u32,
+//!     pub aabb: Aabb2<f32>,
+//! }
+//!
+//! impl TreeValue for Value {
+//!     type Bound = Aabb2<f32>;
+//!
+//!     fn bound(&self) -> &Aabb2<f32> {
+//!         &self.aabb
+//!     }
+//!
+//!     fn get_bound_with_margin(&self) -> Aabb2<f32> {
+//!         self.aabb
+//!     }
+//! }
+//!
+//! fn aabb2(minx: f32, miny: f32, maxx: f32, maxy: f32) -> Aabb2<f32> {
+//!     Aabb2::new(Point2::new(minx, miny), Point2::new(maxx, maxy))
+//! }
+//!
+//! fn main() {
+//!     let mut tree = Quadtree::new(aabb2(0., 0., 100., 100.));
+//!     tree.insert(Value { id: 10, aabb: aabb2(5., 5., 10., 10.) });
+//!     let index = tree.insert(Value { id: 11, aabb: aabb2(21., 14., 23., 16.) });
+//!
+//!     let region = aabb2(0., 0., 20., 20.);
+//!     let results = tree.query(&mut DiscreteVisitor::<Aabb2<f32>, Value>::new(&region));
+//!     assert_eq!(1, results.len());
+//!     assert_eq!(10, results[0].0.id);
+//!
+//!     tree.update(index, Value { id: 11, aabb: aabb2(1., 1., 3., 3.) });
+//!     let ray = Ray2::new(Point2::new(0., 2.), Vector2::new(1., 0.));
+//!     let results = tree.query(&mut ContinuousVisitor::<Ray2<f32>, Value>::new(&ray));
+//!     assert_eq!(1, results.len());
+//!     assert_eq!(Point2::new(1., 2.), results[0].1);
+//! }
+//! ```
+
+use cgmath::BaseFloat;
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Aabb3};
+use crate::dbvt::{DiscreteVisitor, TreeValue, Visitor};
+use crate::prelude::*;
+
+const DEFAULT_MAX_DEPTH: u32 = 8;
+const DEFAULT_LEAF_CAPACITY: usize = 8;
+
+/// Quadtree, see [`Orthtree`](struct.Orthtree.html) for more information.
+pub type Quadtree<S, T> = Orthtree<Aabb2<S>, T>;
+
+/// Octree, see [`Orthtree`](struct.Orthtree.html) for more information.
+pub type Octree<S, T> = Orthtree<Aabb3<S>, T>;
+
+/// Spatial subdivision tree over an axis aligned box, splitting each node into a child for each
+/// corner of its box, four in 2D and eight in 3D.
+///
+/// A node is split when it holds more values than the leaf capacity, unless it is at the maximum
+/// depth. Values go to the child containing the center of their bound, if the child also
+/// contains the whole bound, otherwise they stay in the node. Values outside the box of the root
+/// are kept in the root.
+///
+/// In a loose tree, see [`with_looseness`](#method.with_looseness), the box of each node is grown
+/// around its cell, so the nodes overlap, and small values near the split planes can still move
+/// down into the children.
+///
+/// Values are referred to by the index returned from [`insert`](#method.insert), which stays the
+/// same until the value is removed.
+///
+/// # Type parameters:
+///
+/// - `B`: Bounding box type, [`Aabb2`](../struct.Aabb2.html) or [`Aabb3`](../struct.Aabb3.html)
+/// - `T`: A type that implements [`TreeValue`](../dbvt/trait.TreeValue.html), the fat bound is
+///   not used.
+#[derive(Debug)]
+pub struct Orthtree<B, T>
+where
+    B: Aabb,
+{
+    nodes: Vec<Node<B>>,
+    values: Vec<Option<(T, usize)>>,
+    free_list: Vec<usize>,
+    len: usize,
+    max_depth: u32,
+    leaf_capacity: usize,
+    looseness: B::Scalar,
+}
+
+/// Node covering a cell of the parent
+#[derive(Debug)]
+struct Node<B> {
+    /// Cell of the node
+    cell: B,
+    /// Box containing all values in the node, the cell grown by the looseness
+    loose: B,
+    depth: u32,
+    /// Index of the first child, the children are stored next to each other
+    children: Option<usize>,
+    /// Indices of the values stored in this node
+    values: Vec<usize>,
+}
+
+impl<S, B, T> Orthtree<B, T>
+where
+    S: BaseFloat,
+    B: Aabb<Scalar = S> + Copy + Contains<B>,
+    T: TreeValue<Bound = B>,
+{
+    /// Create a new tree covering the given box, with a maximum depth of 8, and a leaf capacity
+    /// of 8.
+    pub fn new(bound: B) -> Self {
+        Self {
+            nodes: vec![Node {
+                cell: bound,
+                loose: bound,
+                depth: 0,
+                children: None,
+                values: Vec::default(),
+            }],
+            values: Vec::default(),
+            free_list: Vec::default(),
+            len: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            leaf_capacity: DEFAULT_LEAF_CAPACITY,
+            looseness: S::one(),
+        }
+    }
+
+    /// Use the given maximum depth, the root is at depth zero. Only affects later splits.
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Use the given number of values a node can hold before it is split. Only affects later
+    /// splits.
+    pub fn with_leaf_capacity(mut self, leaf_capacity: usize) -> Self {
+        self.leaf_capacity = leaf_capacity;
+        self
+    }
+
+    /// Make the tree loose, with the box of each node the given factor larger than its cell,
+    /// around the same center. A factor of 2 is common, and lets every value move down to the
+    /// depth where the cells are about the size of the value. A factor of 1 is a regular tree.
+    ///
+    /// Should be set before any values are inserted.
+    pub fn with_looseness(mut self, looseness: S) -> Self {
+        self.looseness = looseness;
+        let cell = self.nodes[0].cell;
+        self.nodes[0].loose = self.loose(&cell);
+        self
+    }
+
+    /// Get the box covered by the root
+    pub fn bound(&self) -> &B {
+        &self.nodes[0].cell
+    }
+
+    /// Get the maximum depth
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    /// Get the number of values a node can hold before it is split
+    pub fn leaf_capacity(&self) -> usize {
+        self.leaf_capacity
+    }
+
+    /// Get the factor the node boxes are larger than their cells
+    pub fn looseness(&self) -> S {
+        self.looseness
+    }
+
+    /// Return the number of values in the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if there are no values in the tree
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the depth of the deepest node
+    pub fn depth(&self) -> u32 {
+        self.nodes.iter().map(|node| node.depth).max().unwrap_or(0)
+    }
+
+    /// Return the depth of the node holding the value with the given index
+    pub fn value_depth(&self, index: usize) -> Option<u32> {
+        match self.values.get(index) {
+            Some(&Some((_, node))) => Some(self.nodes[node].depth),
+            _ => None,
+        }
+    }
+
+    /// Get the value with the given index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values
+            .get(index)
+            .and_then(|value| value.as_ref())
+            .map(|(value, _)| value)
+    }
+
+    /// Iterate over the values in the tree, with their indices
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| value.as_ref().map(|(value, _)| (index, value)))
+    }
+
+    /// Remove all values, and the nodes below the root
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[0].children = None;
+        self.nodes[0].values.clear();
+        self.values.clear();
+        self.free_list.clear();
+        self.len = 0;
+    }
+
+    /// Insert a value in the tree.
+    ///
+    /// ### Returns
+    ///
+    /// The index of the value, used to update and remove it.
+    pub fn insert(&mut self, value: T) -> usize {
+        let index = match self.free_list.pop() {
+            Some(index) => {
+                self.values[index] = Some((value, 0));
+                index
+            }
+            None => {
+                self.values.push(Some((value, 0)));
+                self.values.len() - 1
+            }
+        };
+        self.len += 1;
+        self.place(0, index);
+        index
+    }
+
+    /// Remove the value with the given index from the tree. Nodes are not merged when they
+    /// become empty, they are reused if values move back into them.
+    ///
+    /// ### Returns
+    ///
+    /// The value, or None if there is no value with the given index.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let (value, node) = self.values.get_mut(index)?.take()?;
+        self.detach(node, index);
+        self.free_list.push(index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Replace the value with the given index, and move it to the node matching its new bound.
+    /// Does nothing if there is no value with the given index.
+    pub fn update(&mut self, index: usize, value: T) {
+        let node = match self.values.get(index) {
+            Some(&Some((_, node))) => node,
+            _ => return,
+        };
+        let bound = *value.bound();
+        self.values[index] = Some((value, node));
+        // stays in the same node if that is still the deepest node containing it
+        let contained = node == 0 || self.nodes[node].loose.contains(&bound);
+        if contained && self.child_for(node, &bound).is_none() {
+            return;
+        }
+        self.detach(node, index);
+        let start = if contained { node } else { 0 };
+        self.place(start, index);
+    }
+
+    /// Query the tree for all values that the given visitor accepts.
+    ///
+    /// The visitor is given the box of each node, and the bound of each value in the nodes it
+    /// accepts. Values outside the box of the root are always given to the visitor.
+    ///
+    /// ### Returns
+    ///
+    /// Will return a list of tuples of values accepted and the result returned by the visitor for
+    /// the acceptance test.
+    pub fn query<V>(&self, visitor: &mut V) -> Vec<(&T, V::Result)>
+    where
+        V: Visitor<Bound = B>,
+    {
+        self.query_for_indices(visitor)
+            .into_iter()
+            .map(|(index, result)| (self.get(index).unwrap(), result))
+            .collect()
+    }
+
+    /// Find all values with bounds overlapping the given box, for area queries and box selection
+    pub fn query_aabb(&self, aabb: &B) -> Vec<&T>
+    where
+        B: Discrete<B>,
+    {
+        self.query(&mut DiscreteVisitor::<B, T>::new(aabb))
+            .into_iter()
+            .map(|(value, _)| value)
+            .collect()
+    }
+
+    /// Query the tree for all values that the given visitor accepts, see
+    /// [`query`](#method.query).
+    ///
+    /// ### Returns
+    ///
+    /// Will return a list of tuples of value indices accepted and the result returned by the
+    /// visitor for the acceptance test.
+    pub fn query_for_indices<V>(&self, visitor: &mut V) -> Vec<(usize, V::Result)>
+    where
+        V: Visitor<Bound = B>,
+    {
+        let mut results = Vec::default();
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            // the root can hold values outside its box
+            if node_index != 0 && visitor.accept(&node.loose, false).is_none() {
+                continue;
+            }
+            for &index in &node.values {
+                let value = self.get(index).unwrap();
+                if let Some(result) = visitor.accept(value.bound(), true) {
+                    results.push((index, result));
+                }
+            }
+            if let Some(first) = node.children {
+                stack.extend(first..first + Self::child_count());
+            }
+        }
+        results
+    }
+
+    fn child_count() -> usize {
+        1 << B::Diff::len()
+    }
+
+    /// Cell grown by the looseness
+    fn loose(&self, cell: &B) -> B {
+        let two = S::one() + S::one();
+        cell.add_margin(cell.dim() * ((self.looseness - S::one()) / two))
+    }
+
+    /// Store the value in the deepest node below the given node that contains it
+    fn place(&mut self, mut node: usize, index: usize) {
+        let bound = *self.get(index).unwrap().bound();
+        while let Some(child) = self.child_for(node, &bound) {
+            node = child;
+        }
+        self.attach(node, index);
+        let full = self.nodes[node].children.is_none()
+            && self.nodes[node].values.len() > self.leaf_capacity
+            && self.nodes[node].depth < self.max_depth;
+        if full {
+            self.split(node);
+        }
+    }
+
+    fn attach(&mut self, node: usize, index: usize) {
+        self.nodes[node].values.push(index);
+        self.values[index].as_mut().unwrap().1 = node;
+    }
+
+    fn detach(&mut self, node: usize, index: usize) {
+        let values = &mut self.nodes[node].values;
+        if let Some(position) = values.iter().position(|&i| i == index) {
+            values.swap_remove(position);
+        }
+    }
+
+    /// The child of the given node that should hold a value with the given bound
+    fn child_for(&self, node: usize, bound: &B) -> Option<usize> {
+        let first = self.nodes[node].children?;
+        let center = self.nodes[node].cell.center();
+        let value_center = bound.center();
+        let offset = (0..B::Diff::len())
+            .filter(|&i| value_center[i] >= center[i])
+            .fold(0, |offset, i| offset | (1 << i));
+        let child = first + offset;
+        if self.nodes[child].loose.contains(bound) {
+            Some(child)
+        } else {
+            None
+        }
+    }
+
+    /// Create the children of the given node, and move the values that fit into them
+    fn split(&mut self, node: usize) {
+        let cell = self.nodes[node].cell;
+        let depth = self.nodes[node].depth + 1;
+        let (min, center, max) = (cell.min(), cell.center(), cell.max());
+        let first = self.nodes.len();
+        for offset in 0..Self::child_count() {
+            let (mut child_min, mut child_max) = (min, center);
+            for i in 0..B::Diff::len() {
+                if offset & (1 << i) != 0 {
+                    child_min[i] = center[i];
+                    child_max[i] = max[i];
+                }
+            }
+            let child_cell = B::new(child_min, child_max);
+            self.nodes.push(Node {
+                cell: child_cell,
+                loose: self.loose(&child_cell),
+                depth,
+                children: None,
+                values: Vec::default(),
+            });
+        }
+        self.nodes[node].children = Some(first);
+        let values = std::mem::take(&mut self.nodes[node].values);
+        for index in values {
+            self.place(node, index);
+        }
+    }
+}
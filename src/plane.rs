@@ -5,7 +5,7 @@ use cgmath::{AbsDiffEq, RelativeEq, UlpsEq};
 use cgmath::prelude::*;
 use approx::{ulps_eq, ulps_ne};
 
-use crate::Ray3;
+use crate::{Aabb3, Ray3, Sphere};
 use crate::prelude::*;
 
 /// A 3-dimensional plane formed from the equation: `A*x + B*y + C*z - D = 0`.
@@ -23,6 +23,16 @@ use crate::prelude::*;
 /// The `A*x + B*y + C*z - D = 0` form is preferred over the other common
 /// alternative, `A*x + B*y + C*z + D = 0`, because it tends to avoid
 /// superfluous negations (see _Real Time Collision Detection_, p. 55).
+///
+/// # Sides
+///
+/// The normal points to the free side of the plane, where
+/// [`PlaneBound::relate_plane`](trait.PlaneBound.html#tymethod.relate_plane) returns
+/// `Relation::In`, so the planes of a frustum point into it. The intersection tests against
+/// spheres, boxes and convex primitives treat the region behind the plane as solid, the same
+/// region as the [`HalfSpace`](primitive/struct.HalfSpace.html) with the same normal and offset.
+/// A shape intersects the plane unless it is completely `In`, and the intersection is the
+/// penetration distance of the shape behind the plane, along the normal.
 #[derive(Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Plane<S> {
@@ -105,6 +115,20 @@ impl<S: BaseFloat> Plane<S> {
             Some(Plane::new(self.n * denom, self.d * denom))
         }
     }
+
+    /// Signed distance from the plane to the given point, positive on the side the normal points
+    /// to. The plane must be normalized.
+    pub fn distance(&self, point: Point3<S>) -> S {
+        self.n.dot(point.to_vec()) - self.d
+    }
+}
+
+fn penetration<S: BaseFloat>(depth: S) -> Option<S> {
+    if depth < S::zero() {
+        None
+    } else {
+        Some(depth)
+    }
 }
 
 impl<S: AbsDiffEq> AbsDiffEq for Plane<S>
@@ -240,3 +264,65 @@ impl<S: BaseFloat> Discrete<(Plane<S>, Plane<S>)> for Plane<S> {
         ulps_ne!(denom.abs(), &S::zero())
     }
 }
+
+/// See the [plane sides](struct.Plane.html#sides). The plane must be normalized.
+impl<S: BaseFloat> Discrete<Sphere<S>> for Plane<S> {
+    fn intersects(&self, sphere: &Sphere<S>) -> bool {
+        sphere.relate_plane(*self) != Relation::In
+    }
+}
+
+/// Returns the penetration distance of the sphere behind the plane. The plane must be normalized.
+impl<S: BaseFloat> Continuous<Sphere<S>> for Plane<S> {
+    type Result = S;
+    fn intersection(&self, sphere: &Sphere<S>) -> Option<S> {
+        penetration(sphere.radius - self.distance(sphere.center))
+    }
+}
+
+/// See the [plane sides](struct.Plane.html#sides). The plane must be normalized.
+impl<S: BaseFloat> Discrete<Aabb3<S>> for Plane<S> {
+    fn intersects(&self, aabb: &Aabb3<S>) -> bool {
+        aabb.relate_plane(*self) != Relation::In
+    }
+}
+
+/// Returns the penetration distance of the box behind the plane. The plane must be normalized.
+impl<S: BaseFloat> Continuous<Aabb3<S>> for Plane<S> {
+    type Result = S;
+    fn intersection(&self, aabb: &Aabb3<S>) -> Option<S> {
+        let half = aabb.half_extents();
+        let extent = self.n.x.abs() * half.x + self.n.y.abs() * half.y + self.n.z.abs() * half.z;
+        penetration(extent - self.distance(aabb.center()))
+    }
+}
+
+/// Test against a convex primitive with a transform, see the
+/// [plane sides](struct.Plane.html#sides). The plane must be normalized, and is in world space.
+impl<'a, S, P, T> Discrete<(&'a P, &'a T)> for Plane<S>
+where
+    S: BaseFloat,
+    P: Primitive<Point = Point3<S>>,
+    T: Transform<Point3<S>>,
+{
+    fn intersects(&self, target: &(&'a P, &'a T)) -> bool {
+        self.intersection(target).is_some()
+    }
+}
+
+/// Returns the penetration distance of a convex primitive with a transform behind the plane. The
+/// plane must be normalized, and is in world space. Use
+/// [`HalfSpace::intersection_primitive`](primitive/struct.HalfSpace.html#method.intersection_primitive)
+/// for a full contact.
+impl<'a, S, P, T> Continuous<(&'a P, &'a T)> for Plane<S>
+where
+    S: BaseFloat,
+    P: Primitive<Point = Point3<S>>,
+    T: Transform<Point3<S>>,
+{
+    type Result = S;
+    fn intersection(&self, &(primitive, transform): &(&'a P, &'a T)) -> Option<S> {
+        let deepest = primitive.support_point(&-self.n, transform);
+        penetration(-self.distance(deepest))
+    }
+}
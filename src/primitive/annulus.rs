@@ -0,0 +1,242 @@
+//! Annulus primitive
+
+use cgmath::{BaseFloat, Point2, Rad, Vector2};
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Ray2};
+use crate::prelude::*;
+use crate::volume::Circle;
+
+/// Annulus primitive, the ring shaped region between two concentric circles.
+///
+/// The local origin is in the center of the circles. The annulus is not convex, so it does not
+/// implement `Primitive`, but ray and containment queries are handled analytically.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Annulus<S> {
+    /// Radius of the inner circle
+    pub inner_radius: S,
+    /// Radius of the outer circle
+    pub outer_radius: S,
+}
+
+impl<S> Annulus<S> {
+    /// Create a new annulus primitive
+    pub fn new(inner_radius: S, outer_radius: S) -> Self {
+        Self {
+            inner_radius,
+            outer_radius,
+        }
+    }
+}
+
+/// Get the entry and exit parameters of the ray with the circle of the given radius
+fn circle_interval<S>(r: &Ray2<S>, radius: S) -> Option<(S, S)>
+where
+    S: BaseFloat,
+{
+    let l = Vector2::new(-r.origin.x, -r.origin.y);
+    let tca = l.dot(r.direction);
+    let d2 = l.magnitude2() - tca * tca;
+    if d2 > radius * radius {
+        return None;
+    }
+    let thc = (radius * radius - d2).sqrt();
+    Some((tca - thc, tca + thc))
+}
+
+impl<S> ComputeBound<Aabb2<S>> for Annulus<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb2<S> {
+        Aabb2::new(
+            Point2::new(-self.outer_radius, -self.outer_radius),
+            Point2::new(self.outer_radius, self.outer_radius),
+        )
+    }
+}
+
+impl<S> ComputeBound<Circle<S>> for Annulus<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: self.outer_radius,
+        }
+    }
+}
+
+impl<S> Contains<Point2<S>> for Annulus<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        let d2 = point.to_vec().magnitude2();
+        d2 >= self.inner_radius * self.inner_radius && d2 <= self.outer_radius * self.outer_radius
+    }
+}
+
+impl<S> Sdf<Point2<S>> for Annulus<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn signed_distance(&self, point: &Point2<S>) -> S {
+        let d = point.to_vec().magnitude();
+        (d - self.outer_radius).max(self.inner_radius - d)
+    }
+}
+
+impl<S> Discrete<Ray2<S>> for Annulus<S>
+where
+    S: BaseFloat,
+{
+    /// Ray must be in object space
+    fn intersects(&self, r: &Ray2<S>) -> bool {
+        self.intersection(r).is_some()
+    }
+}
+
+impl<S> Continuous<Ray2<S>> for Annulus<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point2<S>;
+
+    /// Ray must be in object space. Will return the ray origin if it is inside the annulus, and
+    /// the point where the ray leaves the hole if the ray starts in the hole.
+    fn intersection(&self, r: &Ray2<S>) -> Option<Point2<S>> {
+        let (outer_in, outer_out) = circle_interval(r, self.outer_radius)?;
+        if outer_out < S::zero() {
+            return None;
+        }
+        let mut t = outer_in.max(S::zero());
+        if let Some((inner_in, inner_out)) = circle_interval(r, self.inner_radius) {
+            if t > inner_in && t < inner_out {
+                t = inner_out;
+            }
+        }
+        Some(r.origin + r.direction * t)
+    }
+}
+
+impl<S> Geometry2 for Annulus<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        Rad::<S>::turn_div_2().0
+            * (self.outer_radius * self.outer_radius - self.inner_radius * self.inner_radius)
+    }
+
+    fn perimeter(&self) -> S {
+        Rad::<S>::full_turn().0 * (self.outer_radius + self.inner_radius)
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        Point2::origin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Basis2, Decomposed, Point2, Rad, Rotation2, Vector2};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_annulus_contains() {
+        let annulus = Annulus::new(2., 4.);
+        assert!(annulus.contains(&Point2::new(3., 0.)));
+        assert!(annulus.contains(&Point2::new(0., -2.)));
+        assert!(annulus.contains(&Point2::new(0., 4.)));
+        assert!(!annulus.contains(&Point2::new(0., 0.)));
+        assert!(!annulus.contains(&Point2::new(1., 1.)));
+        assert!(!annulus.contains(&Point2::new(3., 3.)));
+    }
+
+    #[test]
+    fn test_annulus_signed_distance() {
+        let annulus = Annulus::new(2., 4.);
+        assert_ulps_eq!(-1., annulus.signed_distance(&Point2::new(3., 0.)));
+        assert_ulps_eq!(2., annulus.signed_distance(&Point2::new(0., 0.)));
+        assert_ulps_eq!(1., annulus.signed_distance(&Point2::new(0., -5.)));
+    }
+
+    #[test]
+    fn test_annulus_bound() {
+        let annulus = Annulus::new(2., 4.);
+        assert_eq!(
+            Aabb2::new(Point2::new(-4., -4.), Point2::new(4., 4.)),
+            annulus.compute_bound()
+        );
+    }
+
+    #[test]
+    fn test_annulus_ray_outside() {
+        let annulus = Annulus::new(2., 4.);
+        let ray = Ray2::new(Point2::new(-10., 0.), Vector2::new(1., 0.));
+        assert!(annulus.intersects(&ray));
+        assert_eq!(Some(Point2::new(-4., 0.)), annulus.intersection(&ray));
+        let ray = Ray2::new(Point2::new(-10., 5.), Vector2::new(1., 0.));
+        assert!(!annulus.intersects(&ray));
+        let ray = Ray2::new(Point2::new(-10., 0.), Vector2::new(-1., 0.));
+        assert!(!annulus.intersects(&ray));
+        assert_eq!(None, annulus.intersection(&ray));
+    }
+
+    #[test]
+    fn test_annulus_ray_inside_ring() {
+        let annulus = Annulus::new(2., 4.);
+        let ray = Ray2::new(Point2::new(-3., 0.), Vector2::new(1., 0.));
+        assert_eq!(Some(Point2::new(-3., 0.)), annulus.intersection(&ray));
+    }
+
+    #[test]
+    fn test_annulus_ray_inside_hole() {
+        let annulus = Annulus::new(2., 4.);
+        let ray = Ray2::new(Point2::new(0., 0.), Vector2::new(0., 1.));
+        assert!(annulus.intersects(&ray));
+        assert_eq!(Some(Point2::new(0., 2.)), annulus.intersection(&ray));
+    }
+
+    #[test]
+    fn test_annulus_ray_transformed() {
+        let annulus = Annulus::new(2., 4.);
+        let ray = Ray2::new(Point2::new(0., 0.), Vector2::new(1., 0.));
+        let t = transform(10., 0., std::f32::consts::PI / 3.);
+        assert_ulps_eq!(
+            Point2::new(6., 0.),
+            annulus.intersection_transformed(&ray, &t).unwrap(),
+            epsilon = 1e-5
+        );
+        let t = transform(10., 5., 0.);
+        assert!(!annulus.intersects_transformed(&ray, &t));
+    }
+
+    #[test]
+    fn test_annulus_geometry() {
+        let annulus = Annulus::new(1., 2.);
+        assert_ulps_eq!(3. * std::f32::consts::PI, annulus.area());
+        assert_ulps_eq!(6. * std::f32::consts::PI, annulus.perimeter());
+        assert_eq!(Point2::new(0., 0.), annulus.centroid());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+}
@@ -1,9 +1,9 @@
 use cgmath::{BaseFloat, Point3, Vector3};
 use cgmath::prelude::*;
 
-use crate::{Aabb3, Ray3};
+use crate::{Aabb3, Line3, Ray3};
 use crate::prelude::*;
-use crate::primitive::util::cylinder_ray_quadratic_solve;
+use crate::primitive::util::{closest_point_on_ball, cylinder_ray_quadratic_solve};
 use crate::volume::Sphere;
 
 /// Capsule primitive
@@ -36,6 +36,21 @@ where
     pub fn height(&self) -> S {
         self.half_height + self.half_height
     }
+
+    /// Get half height
+    pub fn half_height(&self) -> S {
+        self.half_height
+    }
+
+    /// Get the core line segment of the capsule, in object space.
+    ///
+    /// The capsule is the set of all points within `radius` of this segment.
+    pub fn segment(&self) -> Line3<S> {
+        Line3::new(
+            Point3::new(S::zero(), -self.half_height, S::zero()),
+            Point3::new(S::zero(), self.half_height, S::zero()),
+        )
+    }
 }
 
 impl<S> Primitive for Capsule<S>
@@ -80,6 +95,28 @@ where
     }
 }
 
+impl<S> Contains<Point3<S>> for Capsule<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        let y = point.y.max(-self.half_height).min(self.half_height);
+        point.distance2(Point3::new(S::zero(), y, S::zero())) <= self.radius * self.radius
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for Capsule<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        let y = point.y.max(-self.half_height).min(self.half_height);
+        closest_point_on_ball(Point3::new(S::zero(), y, S::zero()), self.radius, *point)
+    }
+}
+
 impl<S> Discrete<Ray3<S>> for Capsule<S>
 where
     S: BaseFloat,
@@ -215,6 +252,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_capsule_segment() {
+        let capsule = Capsule::new(2., 1.);
+        assert_eq!(2., capsule.half_height());
+        let segment = capsule.segment();
+        assert_eq!(Point3::new(0., -2., 0.), segment.origin);
+        assert_eq!(Point3::new(0., 2., 0.), segment.dest);
+        // every support point is within radius of the core segment
+        let direction = Vector3::new(0.5, -1., 0.).normalize();
+        let point = capsule.support_point(&direction, &transform(0., 0., 0., 0.));
+        assert_ulps_eq!(1., (point - segment.origin).magnitude());
+    }
+
     #[test]
     fn test_capsule_support_1() {
         let capsule = Capsule::new(2., 1.);
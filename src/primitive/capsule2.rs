@@ -0,0 +1,335 @@
+//! 2D capsule primitive
+
+use cgmath::{BaseFloat, Point2, Rad, Vector2};
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Line2, Ray2};
+use crate::prelude::*;
+use crate::primitive::util::closest_point_on_ball;
+use crate::volume::Circle;
+
+/// 2D capsule primitive, also known as a stadium.
+/// Capsule body is aligned with the Y axis, with local origin in the center of the capsule.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Capsule2<S> {
+    half_height: S,
+    radius: S,
+}
+
+impl<S> Capsule2<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new 2D capsule
+    pub fn new(half_height: S, radius: S) -> Self {
+        Self {
+            half_height,
+            radius,
+        }
+    }
+
+    /// Get radius
+    pub fn radius(&self) -> S {
+        self.radius
+    }
+
+    /// Get height
+    pub fn height(&self) -> S {
+        self.half_height + self.half_height
+    }
+
+    /// Get half height
+    pub fn half_height(&self) -> S {
+        self.half_height
+    }
+
+    /// Get the core line segment of the capsule, in object space.
+    ///
+    /// The capsule is the set of all points within `radius` of this segment.
+    pub fn segment(&self) -> Line2<S> {
+        Line2::new(
+            Point2::new(S::zero(), -self.half_height),
+            Point2::new(S::zero(), self.half_height),
+        )
+    }
+
+    /// Get the entry and exit parameters of the ray with the capsule.
+    ///
+    /// The capsule is convex, so the intervals of the core rectangle and the two end circles
+    /// overlap, and their union is the interval for the whole capsule.
+    fn ray_interval(&self, r: &Ray2<S>) -> Option<(S, S)> {
+        let intervals = [
+            rectangle_interval(r, self.radius, self.half_height),
+            circle_interval(r, Point2::new(S::zero(), self.half_height), self.radius),
+            circle_interval(r, Point2::new(S::zero(), -self.half_height), self.radius),
+        ];
+        intervals
+            .iter()
+            .filter_map(|i| *i)
+            .fold(None, |acc, (t_in, t_out)| match acc {
+                None => Some((t_in, t_out)),
+                Some((a_in, a_out)) => Some((a_in.min(t_in), a_out.max(t_out))),
+            })
+    }
+}
+
+fn circle_interval<S>(r: &Ray2<S>, center: Point2<S>, radius: S) -> Option<(S, S)>
+where
+    S: BaseFloat,
+{
+    let l = center - r.origin;
+    let tca = l.dot(r.direction);
+    let d2 = l.magnitude2() - tca * tca;
+    if d2 > radius * radius {
+        return None;
+    }
+    let thc = (radius * radius - d2).sqrt();
+    Some((tca - thc, tca + thc))
+}
+
+fn rectangle_interval<S>(r: &Ray2<S>, half_x: S, half_y: S) -> Option<(S, S)>
+where
+    S: BaseFloat,
+{
+    let mut t_in = S::neg_infinity();
+    let mut t_out = S::infinity();
+    for &(o, d, h) in &[
+        (r.origin.x, r.direction.x, half_x),
+        (r.origin.y, r.direction.y, half_y),
+    ] {
+        if d.is_zero() {
+            if o < -h || o > h {
+                return None;
+            }
+        } else {
+            let t1 = (-h - o) / d;
+            let t2 = (h - o) / d;
+            t_in = t_in.max(t1.min(t2));
+            t_out = t_out.min(t1.max(t2));
+        }
+    }
+    if t_in > t_out {
+        None
+    } else {
+        Some((t_in, t_out))
+    }
+}
+
+impl<S> Primitive for Capsule2<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point2<S>;
+
+    fn support_point<T>(&self, direction: &Vector2<S>, transform: &T) -> Point2<S>
+    where
+        T: Transform<Point2<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+
+        let mut result = Point2::origin();
+        result.y = direction.y.signum() * self.half_height;
+        transform.transform_point(result + direction.normalize_to(self.radius))
+    }
+}
+
+impl<S> ComputeBound<Aabb2<S>> for Capsule2<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb2<S> {
+        Aabb2::new(
+            Point2::new(-self.radius, -self.half_height - self.radius),
+            Point2::new(self.radius, self.half_height + self.radius),
+        )
+    }
+}
+
+impl<S> ComputeBound<Circle<S>> for Capsule2<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: self.half_height + self.radius,
+        }
+    }
+}
+
+impl<S> Contains<Point2<S>> for Capsule2<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        let y = point.y.max(-self.half_height).min(self.half_height);
+        point.distance2(Point2::new(S::zero(), y)) <= self.radius * self.radius
+    }
+}
+
+impl<S> ClosestPoint<Point2<S>> for Capsule2<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point2<S>) -> (Point2<S>, bool) {
+        let y = point.y.max(-self.half_height).min(self.half_height);
+        closest_point_on_ball(Point2::new(S::zero(), y), self.radius, *point)
+    }
+}
+
+impl<S> Discrete<Ray2<S>> for Capsule2<S>
+where
+    S: BaseFloat,
+{
+    fn intersects(&self, r: &Ray2<S>) -> bool {
+        match self.ray_interval(r) {
+            None => false,
+            Some((_, t_out)) => t_out >= S::zero(),
+        }
+    }
+}
+
+impl<S> Continuous<Ray2<S>> for Capsule2<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point2<S>;
+
+    fn intersection(&self, r: &Ray2<S>) -> Option<Point2<S>> {
+        let (t_in, t_out) = self.ray_interval(r)?;
+        if t_in >= S::zero() {
+            Some(r.origin + r.direction * t_in)
+        } else if t_out >= S::zero() {
+            Some(r.origin + r.direction * t_out)
+        } else {
+            None
+        }
+    }
+}
+
+impl<S> Geometry2 for Capsule2<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        self.height() * (self.radius + self.radius)
+            + Rad::<S>::turn_div_2().0 * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> S {
+        self.height() + self.height() + Rad::<S>::full_turn().0 * self.radius
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        Point2::origin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Basis2, Decomposed, Point2, Rad, Rotation2, Vector2};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_capsule2_support_side() {
+        let capsule = Capsule2::new(2., 1.);
+        let point = capsule.support_point(&Vector2::new(1., 0.), &transform(0., 0., 0.));
+        assert_ulps_eq!(Point2::new(1., 2.), point);
+    }
+
+    #[test]
+    fn test_capsule2_support_cap() {
+        let capsule = Capsule2::new(2., 1.);
+        let point = capsule.support_point(&Vector2::new(0., -1.), &transform(0., 0., 0.));
+        assert_ulps_eq!(Point2::new(0., -3.), point);
+    }
+
+    #[test]
+    fn test_capsule2_support_transformed() {
+        let capsule = Capsule2::new(2., 1.);
+        let t = transform(10., 0., -std::f32::consts::PI / 2.);
+        let point = capsule.support_point(&Vector2::new(1., 0.), &t);
+        assert_ulps_eq!(Point2::new(13., 0.), point, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_capsule2_bound() {
+        let capsule = Capsule2::new(2., 1.);
+        assert_eq!(bound(-1., -3., 1., 3.), capsule.compute_bound());
+    }
+
+    #[test]
+    fn test_capsule2_ray_discrete() {
+        let capsule = Capsule2::new(2., 1.);
+        let ray = Ray2::new(Point2::new(-5., 2.5), Vector2::new(1., 0.));
+        assert!(capsule.intersects(&ray));
+        let ray = Ray2::new(Point2::new(-5., 3.5), Vector2::new(1., 0.));
+        assert!(!capsule.intersects(&ray));
+        let ray = Ray2::new(Point2::new(-5., 0.), Vector2::new(-1., 0.));
+        assert!(!capsule.intersects(&ray));
+    }
+
+    #[test]
+    fn test_capsule2_ray_continuous_side() {
+        let capsule = Capsule2::new(2., 1.);
+        let ray = Ray2::new(Point2::new(-5., 1.), Vector2::new(1., 0.));
+        assert_eq!(Some(Point2::new(-1., 1.)), capsule.intersection(&ray));
+    }
+
+    #[test]
+    fn test_capsule2_ray_continuous_cap() {
+        let capsule = Capsule2::new(2., 1.);
+        let ray = Ray2::new(Point2::new(0., 10.), Vector2::new(0., -1.));
+        assert_eq!(Some(Point2::new(0., 3.)), capsule.intersection(&ray));
+        let ray = Ray2::new(Point2::new(0., -10.), Vector2::new(0., 1.));
+        assert_eq!(Some(Point2::new(0., -3.)), capsule.intersection(&ray));
+    }
+
+    #[test]
+    fn test_capsule2_ray_continuous_inside() {
+        let capsule = Capsule2::new(2., 1.);
+        let ray = Ray2::new(Point2::new(0., 0.), Vector2::new(0., 1.));
+        assert_eq!(Some(Point2::new(0., 3.)), capsule.intersection(&ray));
+    }
+
+    #[test]
+    fn test_capsule2_ray_continuous_transformed() {
+        let capsule = Capsule2::new(2., 1.);
+        let ray = Ray2::new(Point2::new(10., 0.), Vector2::new(-1., 0.));
+        let t = transform(0., 0., std::f32::consts::PI / 2.);
+        let p = capsule.intersection_transformed(&ray, &t).unwrap();
+        assert_ulps_eq!(Point2::new(3., 0.), p, epsilon = 1e-6);
+        let t = transform(0., 4., 0.);
+        assert_eq!(None, capsule.intersection_transformed(&ray, &t));
+    }
+
+    #[test]
+    fn test_capsule2_geometry() {
+        let capsule = Capsule2::new(2., 1.);
+        assert_ulps_eq!(8. + std::f32::consts::PI, capsule.area());
+        assert_ulps_eq!(8. + 2. * std::f32::consts::PI, capsule.perimeter());
+        assert_eq!(Point2::new(0., 0.), capsule.centroid());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+
+    fn bound(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Aabb2<f32> {
+        Aabb2::new(Point2::new(min_x, min_y), Point2::new(max_x, max_y))
+    }
+}
@@ -1,12 +1,16 @@
 //! Circle primitive
 
-use cgmath::{BaseFloat, Point2, Vector2};
+use cgmath::{BaseFloat, Point2, Rad, Vector2};
 use cgmath::prelude::*;
 
 use crate::{Aabb2, Ray2};
 use crate::prelude::*;
+use crate::primitive::util::closest_point_on_ball;
 
 /// Circle primitive
+///
+/// The support function assumes that the transform does not scale the circle non-uniformly. Use
+/// [`Scaled`](struct.Scaled.html) for non-uniformly scaled circles.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Circle<S> {
@@ -48,6 +52,38 @@ where
     }
 }
 
+impl<S> ComputeBound<crate::volume::Circle<S>> for Circle<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> crate::volume::Circle<S> {
+        crate::volume::Circle {
+            center: Point2::origin(),
+            radius: self.radius,
+        }
+    }
+}
+
+impl<S> Contains<Point2<S>> for Circle<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        point.to_vec().magnitude2() <= self.radius * self.radius
+    }
+}
+
+impl<S> ClosestPoint<Point2<S>> for Circle<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point2<S>) -> (Point2<S>, bool) {
+        closest_point_on_ball(Point2::origin(), self.radius, *point)
+    }
+}
+
 impl<S> Discrete<Ray2<S>> for Circle<S>
 where
     S: BaseFloat,
@@ -87,6 +123,25 @@ where
     }
 }
 
+impl<S> Geometry2 for Circle<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        Rad::<S>::turn_div_2().0 * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> S {
+        Rad::<S>::full_turn().0 * self.radius
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        Point2::origin()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std;
@@ -179,6 +234,14 @@ mod tests {
         assert_ulps_eq!(py, point.y);
     }
 
+    #[test]
+    fn test_circle_geometry() {
+        let circle = Circle::new(2.);
+        assert_ulps_eq!(4. * std::f32::consts::PI, circle.area());
+        assert_ulps_eq!(4. * std::f32::consts::PI, circle.perimeter());
+        assert_eq!(Point2::new(0., 0.), circle.centroid());
+    }
+
     // util
     fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
         Decomposed {
@@ -0,0 +1,349 @@
+//! Compound primitive
+
+use std::cmp::Ordering;
+
+use cgmath::{BaseFloat, Basis2, Decomposed, Quaternion, Vector2, Vector3};
+use cgmath::prelude::*;
+
+use crate::Ray;
+use crate::prelude::*;
+use crate::primitive::{Primitive2, Primitive3};
+
+/// Compound primitive, built from a set of child primitives, each with a local transform that
+/// places the child in the local space of the compound.
+///
+/// Can be used to build non-convex shapes from convex pieces. Bounding volumes and ray tests are
+/// computed over all children. For narrow phase tests, use the children with the
+/// `*_complex` functions on [`GJK`](../algorithm/minkowski/struct.GJK.html), which will test each
+/// child separately.
+///
+/// The `Primitive` implementation gives the support function of the convex hull of the
+/// children.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Compound<P, T> {
+    /// Child primitives, with their local-to-compound transforms
+    pub children: Vec<(P, T)>,
+}
+
+/// 2D compound primitive
+pub type Compound2<S> = Compound<Primitive2<S>, Decomposed<Vector2<S>, Basis2<S>>>;
+
+/// 3D compound primitive
+pub type Compound3<S> = Compound<Primitive3<S>, Decomposed<Vector3<S>, Quaternion<S>>>;
+
+impl<P, T> Compound<P, T> {
+    /// Create a new compound primitive from the given children
+    pub fn new(children: Vec<(P, T)>) -> Self {
+        Self { children }
+    }
+
+    /// Get the children of the compound, with their local transforms
+    pub fn children(&self) -> &[(P, T)] {
+        &self.children
+    }
+}
+
+impl<P, T> Primitive for Compound<P, T>
+where
+    P: Primitive,
+    <P::Point as EuclideanSpace>::Scalar: BaseFloat,
+    T: Transform<P::Point>,
+{
+    type Point = P::Point;
+
+    fn support_point<TT>(
+        &self,
+        direction: &<P::Point as EuclideanSpace>::Diff,
+        transform: &TT,
+    ) -> P::Point
+    where
+        TT: Transform<P::Point>,
+    {
+        let local_direction = transform.inverse_transform_vector(*direction).unwrap();
+        let p = self
+            .children
+            .iter()
+            .map(|(child, child_transform)| {
+                let p = child.support_point(&local_direction, child_transform);
+                (p, p.dot(local_direction))
+            })
+            .max_by(|l, r| l.1.partial_cmp(&r.1).unwrap_or(Ordering::Equal))
+            .map(|(p, _)| p)
+            .unwrap_or_else(P::Point::origin);
+        transform.transform_point(p)
+    }
+}
+
+impl<P, T, B> ComputeBound<B> for Compound<P, T>
+where
+    P: ComputeBound<B>,
+    B: Bound + Union<B, Output = B>,
+    T: Transform<B::Point>,
+{
+    fn compute_bound(&self) -> B {
+        self.children
+            .iter()
+            .map(|(child, child_transform)| child.compute_bound().transform_volume(child_transform))
+            .fold(None, |bound: Option<B>, child_bound| match bound {
+                None => Some(child_bound),
+                Some(bound) => Some(bound.union(&child_bound)),
+            })
+            .unwrap_or_else(B::empty)
+    }
+}
+
+impl<S, P, V, C, T> Discrete<Ray<S, P, V>> for Compound<C, T>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S, Diff = V>,
+    V: VectorSpace<Scalar = S>,
+    C: DiscreteTransformed<Ray<S, P, V>, Point = P>,
+    T: Transform<P>,
+{
+    /// Ray must be in object space of the compound
+    fn intersects(&self, ray: &Ray<S, P, V>) -> bool {
+        self.children
+            .iter()
+            .any(|(child, child_transform)| child.intersects_transformed(ray, child_transform))
+    }
+}
+
+impl<S, P, V, C, T> Continuous<Ray<S, P, V>> for Compound<C, T>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S, Diff = V>,
+    V: VectorSpace<Scalar = S> + InnerSpace,
+    C: ContinuousTransformed<Ray<S, P, V>, Point = P, Result = P>,
+    T: Transform<P>,
+{
+    type Result = P;
+
+    /// Ray must be in object space of the compound. Will return the closest intersection point
+    /// over all children.
+    fn intersection(&self, ray: &Ray<S, P, V>) -> Option<P> {
+        self.children
+            .iter()
+            .filter_map(|(child, child_transform)| {
+                child.intersection_transformed(ray, child_transform)
+            })
+            .fold(None, |closest: Option<P>, p| match closest {
+                Some(c) if (c - ray.origin).magnitude2() <= (p - ray.origin).magnitude2() => {
+                    Some(c)
+                }
+                _ => Some(p),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{
+        Basis2, Decomposed, Point2, Point3, Quaternion, Rad, Rotation2, Vector2, Vector3,
+    };
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::{Aabb2, Aabb3, CollisionStrategy, Ray2, Ray3};
+    use crate::algorithm::minkowski::{GJK2, GJK3};
+    use crate::primitive::{Circle, Cuboid, Rectangle, Sphere};
+
+    #[test]
+    fn test_compound_bound() {
+        let compound = l_shape();
+        assert_eq!(bound(-1., -1., 5., 5.), compound.compute_bound());
+    }
+
+    #[test]
+    fn test_compound_bound_empty() {
+        let compound: Compound2<f32> = Compound::new(vec![]);
+        let bound: Aabb2<f32> = compound.compute_bound();
+        assert_eq!(Aabb2::empty(), bound);
+    }
+
+    #[test]
+    fn test_compound_support() {
+        let compound = l_shape();
+        let t = transform(0., 0., 0.);
+        assert_eq!(
+            Point2::new(5., 1.),
+            compound.support_point(&Vector2::new(1., 0.1), &t)
+        );
+        assert_eq!(
+            Point2::new(1., 5.),
+            compound.support_point(&Vector2::new(0.1, 1.), &t)
+        );
+        let t = transform(10., 0., 0.);
+        assert_eq!(
+            Point2::new(9., -1.),
+            compound.support_point(&Vector2::new(-1., -1.), &t)
+        );
+    }
+
+    #[test]
+    fn test_compound_ray_discrete() {
+        let compound = l_shape();
+        // passes through the concave region
+        let ray = Ray2::new(Point2::new(3., 10.), Vector2::new(0., -1.));
+        assert!(compound.intersects(&ray));
+        let ray = Ray2::new(Point2::new(10., 3.), Vector2::new(-1., 1.).normalize());
+        assert!(!compound.intersects(&ray));
+        let t = transform(0., 0., 0.);
+        let ray = Ray2::new(Point2::new(10., 0.), Vector2::new(-1., 0.));
+        assert!(compound.intersects_transformed(&ray, &t));
+    }
+
+    #[test]
+    fn test_compound_ray_continuous() {
+        let compound = l_shape();
+        let ray = Ray2::new(Point2::new(3., 10.), Vector2::new(0., -1.));
+        assert_eq!(Some(Point2::new(3., 1.)), compound.intersection(&ray));
+        let ray = Ray2::new(Point2::new(0., 10.), Vector2::new(0., -1.));
+        assert_eq!(Some(Point2::new(0., 5.)), compound.intersection(&ray));
+        let t = transform(10., 0., 0.);
+        assert_eq!(None, compound.intersection_transformed(&ray, &t));
+    }
+
+    #[test]
+    fn test_compound_gjk() {
+        let compound = l_shape();
+        let circle = vec![(Primitive2::from(Circle::new(0.5)), transform(0., 0., 0.))];
+        let gjk = GJK2::new();
+        let strategy = CollisionStrategy::CollisionOnly;
+        // inside the concave region, but inside the convex hull
+        let circle_transform = transform(3., 3., 0.);
+        assert!(
+            gjk.intersection_complex(
+                &strategy,
+                compound.children(),
+                &transform(0., 0., 0.),
+                &circle,
+                &circle_transform,
+            ).is_none()
+        );
+        let circle_transform = transform(3., 1.2, 0.);
+        assert!(
+            gjk.intersection_complex(
+                &strategy,
+                compound.children(),
+                &transform(0., 0., 0.),
+                &circle,
+                &circle_transform,
+            ).is_some()
+        );
+    }
+
+    #[test]
+    fn test_compound3_bound() {
+        let compound = table();
+        assert_eq!(
+            Aabb3::new(Point3::new(-2., -1., -1.), Point3::new(2., 1.2, 1.)),
+            compound.compute_bound()
+        );
+    }
+
+    #[test]
+    fn test_compound3_support() {
+        let compound = table();
+        let t = transform3(0., 0., 0., 0.);
+        assert_eq!(
+            Point3::new(2., 1.2, 1.),
+            compound.support_point(&Vector3::new(1., 1., 1.), &t)
+        );
+        assert_eq!(
+            Point3::new(2., -1., -0.5),
+            compound.support_point(&Vector3::new(1., -1., -1.), &t)
+        );
+    }
+
+    #[test]
+    fn test_compound3_ray() {
+        let compound = table();
+        // under the table top, between the legs
+        let ray = Ray3::new(Point3::new(0., 0., -10.), Vector3::new(0., 0., 1.));
+        assert!(!compound.intersects(&ray));
+        let ray = Ray3::new(Point3::new(0., 10., 0.), Vector3::new(0., -1., 0.));
+        assert_ulps_eq!(
+            Point3::new(0., 1.2, 0.),
+            compound.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        let ray = Ray3::new(Point3::new(1.5, -10., 0.2), Vector3::new(0., 1., 0.));
+        assert_eq!(
+            Some(Point3::new(1.5, -1., 0.2)),
+            compound.intersection(&ray)
+        );
+    }
+
+    #[test]
+    fn test_compound3_gjk() {
+        let compound = table();
+        let sphere = vec![(
+            Primitive3::from(Sphere::new(0.4)),
+            transform3(0., 0., 0., 0.),
+        )];
+        let gjk = GJK3::new();
+        let strategy = CollisionStrategy::CollisionOnly;
+        // under the table top, inside the convex hull of the compound
+        assert!(
+            gjk.intersection_complex(
+                &strategy,
+                compound.children(),
+                &transform3(0., 0., 0., 0.),
+                &sphere,
+                &transform3(0., 0., 0., 0.),
+            ).is_none()
+        );
+        assert!(
+            gjk.intersection_complex(
+                &strategy,
+                compound.children(),
+                &transform3(0., 0., 0., 0.),
+                &sphere,
+                &transform3(1.1, 0., 0., 0.),
+            ).is_some()
+        );
+    }
+
+    // util
+    fn l_shape() -> Compound2<f32> {
+        Compound::new(vec![
+            (Rectangle::new(6., 2.).into(), transform(2., 0., 0.)),
+            (Rectangle::new(2., 6.).into(), transform(0., 2., 0.)),
+        ])
+    }
+
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+
+    /// Table top with two legs
+    fn table() -> Compound3<f32> {
+        Compound::new(vec![
+            (Cuboid::new(4., 0.4, 2.).into(), transform3(0., 1., 0., 0.)),
+            (Cuboid::new(1., 2., 1.).into(), transform3(-1.5, 0., 0., 0.)),
+            (Cuboid::new(1., 2., 1.).into(), transform3(1.5, 0., 0., 0.)),
+        ])
+    }
+
+    fn transform3(
+        dx: f32,
+        dy: f32,
+        dz: f32,
+        rot: f32,
+    ) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(rot)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+
+    fn bound(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Aabb2<f32> {
+        Aabb2::new(Point2::new(min_x, min_y), Point2::new(max_x, max_y))
+    }
+}
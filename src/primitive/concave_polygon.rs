@@ -0,0 +1,578 @@
+//! Simple, possibly concave, polygon primitive
+
+use std::cmp::Ordering;
+
+use cgmath::{BaseFloat, Decomposed, Point2, Vector2};
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Ray2};
+use crate::prelude::*;
+use crate::primitive::{Compound2, ConvexPolygon};
+use crate::primitive::util::{
+    cross_2d, get_bound, get_max_distance, polygon_area_centroid, polygon_perimeter,
+};
+use crate::volume::Circle;
+
+/// Simple polygon primitive, that may be concave.
+///
+/// The polygon is given as a single loop of vertices, without holes or self intersections. On
+/// creation, the polygon is triangulated using ear clipping, and the triangles are then merged
+/// into convex pieces using the Hertel-Mehlhorn algorithm, which will give at most four times the
+/// minimum number of convex pieces.
+///
+/// Bounding volumes, ray tests and point containment are computed over the whole polygon. The
+/// `Primitive` implementation gives the support function of the convex hull of the polygon. For
+/// narrow phase tests, use [`to_compound`](#method.to_compound) with the `*_complex` functions on
+/// [`GJK`](../algorithm/minkowski/struct.GJK.html), which will test each convex piece separately.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Polygon<S>
+where
+    S: BaseFloat,
+{
+    vertices: Vec<Point2<S>>,
+    pieces: Vec<ConvexPolygon<S>>,
+}
+
+impl<S> Polygon<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new polygon from a loop of vertices, in either winding order.
+    ///
+    /// Duplicate and collinear vertices are removed. Will return `None` if the loop is degenerate
+    /// or self intersecting.
+    pub fn new(vertices: Vec<Point2<S>>) -> Option<Self> {
+        let mut vertices = simplify(vertices);
+        if vertices.len() < 3 || !is_simple(&vertices) {
+            return None;
+        }
+        let area = signed_area(&vertices);
+        if area.is_zero() {
+            return None;
+        }
+        if area < S::zero() {
+            vertices.reverse();
+        }
+        let triangles = triangulate(&vertices)?;
+        let pieces = merge_convex(&vertices, triangles)
+            .into_iter()
+            .map(|piece| ConvexPolygon::new(piece.iter().map(|&i| vertices[i]).collect()))
+            .collect();
+        Some(Self { vertices, pieces })
+    }
+
+    /// Get the vertices of the polygon, in counter clockwise order
+    pub fn vertices(&self) -> &[Point2<S>] {
+        &self.vertices
+    }
+
+    /// Get the convex pieces of the polygon
+    pub fn pieces(&self) -> &[ConvexPolygon<S>] {
+        &self.pieces
+    }
+
+    /// Build a compound primitive from the convex pieces of the polygon, for use with the
+    /// `*_complex` functions on GJK
+    pub fn to_compound(&self) -> Compound2<S> {
+        Compound2::new(
+            self.pieces
+                .iter()
+                .map(|piece| (piece.clone().into(), Decomposed::one()))
+                .collect(),
+        )
+    }
+}
+
+fn signed_area<S>(vertices: &[Point2<S>]) -> S
+where
+    S: BaseFloat,
+{
+    let origin = vertices[0];
+    vertices
+        .windows(2)
+        .fold(S::zero(), |area, w| area + cross_2d(origin, w[0], w[1]))
+}
+
+/// Remove duplicate and collinear vertices from the loop
+fn simplify<S>(mut vertices: Vec<Point2<S>>) -> Vec<Point2<S>>
+where
+    S: BaseFloat,
+{
+    let mut changed = true;
+    while changed && vertices.len() >= 3 {
+        changed = false;
+        let n = vertices.len();
+        for i in 0..n {
+            let prev = vertices[(i + n - 1) % n];
+            let next = vertices[(i + 1) % n];
+            if vertices[i] == next || cross_2d(prev, vertices[i], next).is_zero() {
+                vertices.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+    vertices
+}
+
+/// Check that no two non adjacent edges of the loop touch
+fn is_simple<S>(vertices: &[Point2<S>]) -> bool
+where
+    S: BaseFloat,
+{
+    let n = vertices.len();
+    (0..n).all(|i| {
+        ((i + 2)..n).filter(|&j| (j + 1) % n != i).all(|j| {
+            !segments_touch(
+                vertices[i],
+                vertices[(i + 1) % n],
+                vertices[j],
+                vertices[(j + 1) % n],
+            )
+        })
+    })
+}
+
+fn segments_touch<S>(a: Point2<S>, b: Point2<S>, c: Point2<S>, d: Point2<S>) -> bool
+where
+    S: BaseFloat,
+{
+    let on_segment = |p: Point2<S>, q: Point2<S>, r: Point2<S>| {
+        r.x >= p.x.min(q.x) && r.x <= p.x.max(q.x) && r.y >= p.y.min(q.y) && r.y <= p.y.max(q.y)
+    };
+    let d1 = cross_2d(c, d, a);
+    let d2 = cross_2d(c, d, b);
+    let d3 = cross_2d(a, b, c);
+    let d4 = cross_2d(a, b, d);
+    if ((d1 > S::zero() && d2 < S::zero()) || (d1 < S::zero() && d2 > S::zero()))
+        && ((d3 > S::zero() && d4 < S::zero()) || (d3 < S::zero() && d4 > S::zero()))
+    {
+        return true;
+    }
+    (d1.is_zero() && on_segment(c, d, a))
+        || (d2.is_zero() && on_segment(c, d, b))
+        || (d3.is_zero() && on_segment(a, b, c))
+        || (d4.is_zero() && on_segment(a, b, d))
+}
+
+/// Check if p is inside or on the border of the counter clockwise triangle (a, b, c)
+fn in_triangle<S>(p: Point2<S>, a: Point2<S>, b: Point2<S>, c: Point2<S>) -> bool
+where
+    S: BaseFloat,
+{
+    cross_2d(a, b, p) >= S::zero()
+        && cross_2d(b, c, p) >= S::zero()
+        && cross_2d(c, a, p) >= S::zero()
+}
+
+/// Triangulate a counter clockwise simple polygon using ear clipping.
+fn triangulate<S>(vertices: &[Point2<S>]) -> Option<Vec<Vec<usize>>>
+where
+    S: BaseFloat,
+{
+    let mut remaining: Vec<usize> = (0..vertices.len()).collect();
+    let mut triangles = Vec::with_capacity(vertices.len() - 2);
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let (ia, ib, ic) = (
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            );
+            let (a, b, c) = (vertices[ia], vertices[ib], vertices[ic]);
+            cross_2d(a, b, c) > S::zero()
+                && remaining.iter().all(|&j| {
+                    let p = vertices[j];
+                    j == ia
+                        || j == ib
+                        || j == ic
+                        || p == a
+                        || p == b
+                        || p == c
+                        || !in_triangle(p, a, b, c)
+                })
+        })?;
+        triangles.push(vec![
+            remaining[(ear + n - 1) % n],
+            remaining[ear],
+            remaining[(ear + 1) % n],
+        ]);
+        remaining.remove(ear);
+    }
+    triangles.push(remaining);
+    Some(triangles)
+}
+
+/// Merge neighbouring pieces of a triangulation, as long as the result stays convex
+/// (Hertel-Mehlhorn).
+fn merge_convex<S>(vertices: &[Point2<S>], mut pieces: Vec<Vec<usize>>) -> Vec<Vec<usize>>
+where
+    S: BaseFloat,
+{
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'search: for i in 0..pieces.len() {
+            for j in (i + 1)..pieces.len() {
+                if let Some(piece) = merge_pair(&pieces[i], &pieces[j]) {
+                    if is_convex(vertices, &piece) {
+                        pieces[i] = piece;
+                        pieces.swap_remove(j);
+                        merged = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+    }
+    pieces
+}
+
+/// Merge two pieces that share an edge.
+fn merge_pair(a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+    let (n, m) = (a.len(), b.len());
+    let (i, j) = (0..n)
+        .filter_map(|i| {
+            let (from, to) = (a[i], a[(i + 1) % n]);
+            (0..m)
+                .find(|&j| b[j] == to && b[(j + 1) % m] == from)
+                .map(|j| (i, j))
+        })
+        .next()?;
+    // walk a from the end of the shared edge around to its start, then b around to its end
+    let piece: Vec<usize> = (1..=n)
+        .map(|k| a[(i + k) % n])
+        .chain((2..m).map(|k| b[(j + k) % m]))
+        .collect();
+    Some(piece)
+}
+
+/// Check if a counter clockwise piece is convex
+fn is_convex<S>(vertices: &[Point2<S>], piece: &[usize]) -> bool
+where
+    S: BaseFloat,
+{
+    let n = piece.len();
+    (0..n).all(|i| {
+        cross_2d(
+            vertices[piece[i]],
+            vertices[piece[(i + 1) % n]],
+            vertices[piece[(i + 2) % n]],
+        ) >= S::zero()
+    })
+}
+
+impl<S> Primitive for Polygon<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point2<S>;
+
+    fn support_point<T>(&self, direction: &Vector2<S>, transform: &T) -> Point2<S>
+    where
+        T: Transform<Point2<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let p = self
+            .vertices
+            .iter()
+            .max_by(|l, r| {
+                l.dot(direction)
+                    .partial_cmp(&r.dot(direction))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .cloned()
+            .unwrap_or_else(Point2::origin);
+        transform.transform_point(p)
+    }
+}
+
+impl<S> ComputeBound<Aabb2<S>> for Polygon<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb2<S> {
+        get_bound(self.vertices.iter())
+    }
+}
+
+impl<S> ComputeBound<Circle<S>> for Polygon<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: get_max_distance(self.vertices.iter()),
+        }
+    }
+}
+
+impl<S> Contains<Point2<S>> for Polygon<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        self.pieces.iter().any(|piece| {
+            let n = piece.vertices().len();
+            (0..n).all(|i| {
+                cross_2d(piece.vertices()[i], piece.vertices()[(i + 1) % n], *point) >= S::zero()
+            })
+        })
+    }
+}
+
+impl<S> Discrete<Ray2<S>> for Polygon<S>
+where
+    S: BaseFloat,
+{
+    /// Ray must be in object space
+    fn intersects(&self, ray: &Ray2<S>) -> bool {
+        self.pieces.iter().any(|piece| piece.intersects(ray))
+    }
+}
+
+impl<S> Continuous<Ray2<S>> for Polygon<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point2<S>;
+
+    /// Ray must be in object space. Will return the closest intersection point over all pieces.
+    fn intersection(&self, ray: &Ray2<S>) -> Option<Point2<S>> {
+        self.pieces
+            .iter()
+            .filter_map(|piece| piece.intersection(ray))
+            .fold(None, |closest: Option<Point2<S>>, p| match closest {
+                Some(c) if (c - ray.origin).magnitude2() <= (p - ray.origin).magnitude2() => {
+                    Some(c)
+                }
+                _ => Some(p),
+            })
+    }
+}
+
+impl<S> From<Polygon<S>> for Compound2<S>
+where
+    S: BaseFloat,
+{
+    fn from(polygon: Polygon<S>) -> Compound2<S> {
+        polygon.to_compound()
+    }
+}
+
+impl<S> Geometry2 for Polygon<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        polygon_area_centroid(&self.vertices).0
+    }
+
+    fn perimeter(&self) -> S {
+        polygon_perimeter(&self.vertices)
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        polygon_area_centroid(&self.vertices).1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Basis2, Decomposed, Point2, Rad, Rotation2, Vector2};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::CollisionStrategy;
+    use crate::algorithm::minkowski::GJK2;
+    use crate::primitive::{Circle, Primitive2};
+
+    #[test]
+    fn test_polygon_convex_input() {
+        let polygon = Polygon::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(2., 0.),
+            Point2::new(2., 2.),
+            Point2::new(0., 2.),
+        ])
+        .unwrap();
+        assert_eq!(1, polygon.pieces().len());
+        assert_eq!(4, polygon.pieces()[0].vertices().len());
+    }
+
+    #[test]
+    fn test_polygon_l_shape() {
+        let polygon = l_shape();
+        assert_eq!(2, polygon.pieces().len());
+        for piece in polygon.pieces() {
+            let n = piece.vertices().len();
+            for i in 0..n {
+                assert!(
+                    cross_2d(
+                        piece.vertices()[i],
+                        piece.vertices()[(i + 1) % n],
+                        piece.vertices()[(i + 2) % n]
+                    ) >= 0.
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_polygon_clockwise_input() {
+        let mut vertices = l_shape().vertices().to_vec();
+        vertices.reverse();
+        let polygon = Polygon::new(vertices).unwrap();
+        assert!(signed_area(polygon.vertices()) > 0.);
+        assert_eq!(2, polygon.pieces().len());
+    }
+
+    #[test]
+    fn test_polygon_removes_collinear() {
+        let polygon = Polygon::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(1., 0.),
+            Point2::new(2., 0.),
+            Point2::new(2., 2.),
+            Point2::new(2., 2.),
+            Point2::new(0., 2.),
+        ])
+        .unwrap();
+        assert_eq!(4, polygon.vertices().len());
+    }
+
+    #[test]
+    fn test_polygon_degenerate() {
+        assert!(Polygon::<f32>::new(vec![Point2::new(0., 0.), Point2::new(1., 0.)]).is_none());
+        assert!(Polygon::<f32>::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(1., 0.),
+            Point2::new(2., 0.),
+        ])
+        .is_none());
+    }
+
+    #[test]
+    fn test_polygon_self_intersecting() {
+        let polygon = Polygon::<f32>::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(2., 2.),
+            Point2::new(2., 0.),
+            Point2::new(0., 2.),
+        ]);
+        assert!(polygon.is_none());
+    }
+
+    #[test]
+    fn test_polygon_touching_edges() {
+        // the two lobes touch at (2, 2)
+        let polygon = Polygon::<f32>::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(4., 0.),
+            Point2::new(2., 2.),
+            Point2::new(4., 4.),
+            Point2::new(0., 4.),
+            Point2::new(2., 2.),
+        ]);
+        assert!(polygon.is_none());
+    }
+
+    #[test]
+    fn test_polygon_contains() {
+        let polygon = l_shape();
+        assert!(polygon.contains(&Point2::new(0.5, 0.5)));
+        assert!(polygon.contains(&Point2::new(3., 0.5)));
+        assert!(polygon.contains(&Point2::new(0.5, 3.)));
+        assert!(!polygon.contains(&Point2::new(3., 3.)));
+    }
+
+    #[test]
+    fn test_polygon_bound_and_support() {
+        let polygon = l_shape();
+        assert_eq!(
+            Aabb2::new(Point2::new(0., 0.), Point2::new(4., 4.)),
+            polygon.compute_bound()
+        );
+        let t = transform(0., 0., 0.);
+        assert_eq!(
+            Point2::new(4., 1.),
+            polygon.support_point(&Vector2::new(1., 0.1), &t)
+        );
+    }
+
+    #[test]
+    fn test_polygon_ray() {
+        let polygon = l_shape();
+        // through the concave region
+        let ray = Ray2::new(Point2::new(3., 10.), Vector2::new(0., -1.));
+        assert!(polygon.intersects(&ray));
+        assert_eq!(Some(Point2::new(3., 1.)), polygon.intersection(&ray));
+        let ray = Ray2::new(Point2::new(10., 3.), Vector2::new(-1., 1.).normalize());
+        assert!(!polygon.intersects(&ray));
+        assert_eq!(None, polygon.intersection(&ray));
+    }
+
+    #[test]
+    fn test_polygon_gjk() {
+        let polygon = l_shape().to_compound();
+        let circle = vec![(Primitive2::from(Circle::new(0.5)), transform(0., 0., 0.))];
+        let gjk = GJK2::new();
+        let strategy = CollisionStrategy::CollisionOnly;
+        // inside the concave region
+        assert!(gjk
+            .intersection_complex(
+                &strategy,
+                polygon.children(),
+                &transform(0., 0., 0.),
+                &circle,
+                &transform(3., 3., 0.),
+            )
+            .is_none());
+        assert!(gjk
+            .intersection_complex(
+                &strategy,
+                polygon.children(),
+                &transform(0., 0., 0.),
+                &circle,
+                &transform(3., 1.2, 0.),
+            )
+            .is_some());
+    }
+
+    #[test]
+    fn test_polygon_geometry() {
+        let polygon = l_shape();
+        assert_ulps_eq!(7., polygon.area());
+        assert_ulps_eq!(16., polygon.perimeter());
+        // two rectangles, 4x1 centered at (2, 0.5), and 1x3 centered at (0.5, 2.5)
+        let expected = Point2::new((4. * 2. + 3. * 0.5) / 7., (4. * 0.5 + 3. * 2.5) / 7.);
+        assert_ulps_eq!(expected, polygon.centroid(), epsilon = 1e-6);
+    }
+
+    // util
+    fn l_shape() -> Polygon<f32> {
+        Polygon::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(4., 0.),
+            Point2::new(4., 1.),
+            Point2::new(1., 1.),
+            Point2::new(1., 4.),
+            Point2::new(0., 4.),
+        ])
+        .unwrap()
+    }
+
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use cgmath::{BaseFloat, Point2, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, Ray3};
+use crate::prelude::*;
+use crate::primitive::util::closest_point_on_revolution;
+use crate::volume::Sphere;
+
+/// Cone primitive
+/// Cone body is aligned with the Y axis, with the apex at `half_height` on the positive Y axis,
+/// the base at `-half_height`, and local origin halfway between them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cone<S> {
+    half_height: S,
+    radius: S,
+}
+
+impl<S> Cone<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new cone
+    pub fn new(half_height: S, radius: S) -> Self {
+        Self {
+            half_height,
+            radius,
+        }
+    }
+
+    /// Get base radius
+    pub fn radius(&self) -> S {
+        self.radius
+    }
+
+    /// Get height
+    pub fn height(&self) -> S {
+        self.half_height + self.half_height
+    }
+
+    /// Get the apex of the cone, in object space
+    pub fn apex(&self) -> Point3<S> {
+        Point3::new(S::zero(), self.half_height, S::zero())
+    }
+}
+
+impl<S> Primitive for Cone<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+
+        let mut rim = Vector3::new(direction.x, S::zero(), direction.z);
+        if !rim.magnitude2().is_zero() {
+            rim = rim.normalize_to(self.radius);
+        }
+        let rim = Point3::new(rim.x, -self.half_height, rim.z);
+        let apex = self.apex();
+        let point = if apex.dot(direction) >= rim.dot(direction) {
+            apex
+        } else {
+            rim
+        };
+        transform.transform_point(point)
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for Cone<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        Aabb3::new(
+            Point3::new(-self.radius, -self.half_height, -self.radius),
+            Point3::new(self.radius, self.half_height, self.radius),
+        )
+    }
+}
+
+impl<S> ComputeBound<Sphere<S>> for Cone<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere {
+            center: Point3::origin(),
+            radius: ((self.radius * self.radius) + (self.half_height * self.half_height)).sqrt(),
+        }
+    }
+}
+
+impl<S> Contains<Point3<S>> for Cone<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        if point.y.abs() > self.half_height {
+            return false;
+        }
+        // the radius shrinks linearly from the base to the apex
+        let radius =
+            self.radius * (self.half_height - point.y) / (self.half_height + self.half_height);
+        point.x * point.x + point.z * point.z <= radius * radius
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for Cone<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        let profile = [
+            Point2::new(S::zero(), -self.half_height),
+            Point2::new(self.radius, -self.half_height),
+            Point2::new(S::zero(), self.half_height),
+        ];
+        (
+            closest_point_on_revolution(&profile, *point),
+            self.contains(point),
+        )
+    }
+}
+
+impl<S> Discrete<Ray3<S>> for Cone<S>
+where
+    S: BaseFloat,
+{
+    fn intersects(&self, r: &Ray3<S>) -> bool {
+        self.intersection(r).is_some()
+    }
+}
+
+impl<S> Continuous<Ray3<S>> for Cone<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point3<S>;
+
+    fn intersection(&self, r: &Ray3<S>) -> Option<Point3<S>> {
+        let two = S::one() + S::one();
+        let k = self.radius / self.height();
+        let k2 = k * k;
+
+        // the lateral surface is x^2 + z^2 = k^2 * (half_height - y)^2
+        let h = self.half_height - r.origin.y;
+        let a = r.direction.x * r.direction.x + r.direction.z * r.direction.z
+            - k2 * r.direction.y * r.direction.y;
+        let b = two
+            * (r.origin.x * r.direction.x + r.origin.z * r.direction.z + k2 * h * r.direction.y);
+        let c = r.origin.x * r.origin.x + r.origin.z * r.origin.z - k2 * h * h;
+
+        let mut candidates = [None; 3];
+        if a.is_zero() {
+            if !b.is_zero() {
+                candidates[0] = Some(-c / b);
+            }
+        } else {
+            let dr = b * b - two * two * a * c;
+            if dr >= S::zero() {
+                let drsqrt = dr.sqrt();
+                candidates[0] = Some((-b + drsqrt) / (two * a));
+                candidates[1] = Some((-b - drsqrt) / (two * a));
+            }
+        }
+        for t in candidates.iter_mut() {
+            if let Some(tc) = *t {
+                let y = r.origin.y + r.direction.y * tc;
+                if y > self.half_height || y < -self.half_height {
+                    *t = None;
+                }
+            }
+        }
+
+        // base
+        if !r.direction.y.is_zero() {
+            let tb = (-self.half_height - r.origin.y) / r.direction.y;
+            let p = r.origin + r.direction * tb;
+            if p.x * p.x + p.z * p.z <= self.radius * self.radius {
+                candidates[2] = Some(tb);
+            }
+        }
+
+        candidates
+            .iter()
+            .filter_map(|t| *t)
+            .filter(|t| *t >= S::zero())
+            .fold(None, |acc: Option<S>, t| match acc {
+                Some(a) if a <= t => Some(a),
+                _ => Some(t),
+            })
+            .map(|t| r.origin + r.direction * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK3;
+    use crate::primitive::Sphere as SpherePrimitive;
+
+    #[test]
+    fn test_cone_aabb() {
+        let cone = Cone::new(1., 1.);
+        assert_eq!(
+            Aabb3::new(Point3::new(-1., -1., -1.), Point3::new(1., 1., 1.)),
+            cone.compute_bound()
+        );
+    }
+
+    #[test]
+    fn test_cone_support_apex() {
+        let cone = Cone::new(1., 1.);
+        let transform = transform(0., 0., 0., 0.);
+        let point = cone.support_point(&Vector3::new(0., 1., 0.), &transform);
+        assert_ulps_eq!(Point3::new(0., 1., 0.), point);
+        let point = cone.support_point(&Vector3::new(0.1, 1., 0.), &transform);
+        assert_ulps_eq!(Point3::new(0., 1., 0.), point);
+    }
+
+    #[test]
+    fn test_cone_support_rim() {
+        let cone = Cone::new(1., 1.);
+        let transform = transform(0., 0., 0., 0.);
+        let point = cone.support_point(&Vector3::new(1., 0., 0.), &transform);
+        assert_ulps_eq!(Point3::new(1., -1., 0.), point);
+        let point = cone.support_point(&Vector3::new(0., -1., 0.), &transform);
+        assert_ulps_eq!(Point3::new(0., -1., 0.), point);
+    }
+
+    #[test]
+    fn test_cone_support_transformed() {
+        let cone = Cone::new(1., 1.);
+        let transform = transform(10., 0., 0., std::f32::consts::PI);
+        let point = cone.support_point(&Vector3::new(0., -1., 0.), &transform);
+        assert_ulps_eq!(Point3::new(10., -1., 0.), point, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_discrete() {
+        let cone = Cone::new(1., 1.);
+        let ray = Ray3::new(Point3::new(-3., 0., 0.), Vector3::new(1., 0., 0.));
+        assert!(cone.intersects(&ray));
+        let ray = Ray3::new(Point3::new(-3., 0., 0.), Vector3::new(-1., 0., 0.));
+        assert!(!cone.intersects(&ray));
+        let ray = Ray3::new(Point3::new(-3., 1.5, 0.), Vector3::new(1., 0., 0.));
+        assert!(!cone.intersects(&ray));
+    }
+
+    #[test]
+    fn test_continuous_side() {
+        let cone = Cone::new(1., 1.);
+        let ray = Ray3::new(Point3::new(-3., 0., 0.), Vector3::new(1., 0., 0.));
+        assert_ulps_eq!(Point3::new(-0.5, 0., 0.), cone.intersection(&ray).unwrap());
+        let ray = Ray3::new(Point3::new(0., 0.9, -3.), Vector3::new(0., 0., 1.));
+        assert_ulps_eq!(
+            Point3::new(0., 0.9, -0.05),
+            cone.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_continuous_apex_and_base() {
+        let cone = Cone::new(1., 1.);
+        let ray = Ray3::new(Point3::new(0., 3., 0.), Vector3::new(0., -1., 0.));
+        assert_ulps_eq!(Point3::new(0., 1., 0.), cone.intersection(&ray).unwrap());
+        let ray = Ray3::new(Point3::new(0.5, -3., 0.), Vector3::new(0., 1., 0.));
+        assert_ulps_eq!(Point3::new(0.5, -1., 0.), cone.intersection(&ray).unwrap());
+        let ray = Ray3::new(Point3::new(2., -3., 0.), Vector3::new(0., 1., 0.));
+        assert_eq!(None, cone.intersection(&ray));
+    }
+
+    #[test]
+    fn test_cone_gjk() {
+        let cone = Cone::new(1., 1.);
+        let sphere = SpherePrimitive::new(0.5);
+        let gjk = GJK3::new();
+        let t = transform(0., 0., 0., 0.);
+        assert!(gjk
+            .intersect(&cone, &t, &sphere, &transform(0., 1.4, 0., 0.))
+            .is_some());
+        assert!(gjk
+            .intersect(&cone, &t, &sphere, &transform(1., 1., 0., 0.))
+            .is_none());
+        assert!(gjk
+            .intersect(&cone, &t, &sphere, &transform(1.2, -1., 0., 0.))
+            .is_some());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(rot)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
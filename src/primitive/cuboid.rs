@@ -3,7 +3,7 @@ use cgmath::prelude::*;
 
 use crate::{Aabb3, Ray3};
 use crate::prelude::*;
-use crate::primitive::util::get_max_point;
+use crate::primitive::util::{closest_point_on_box, get_max_point};
 use crate::volume::Sphere;
 
 /// Cuboid primitive.
@@ -92,14 +92,36 @@ where
     S: BaseFloat,
 {
     fn compute_bound(&self) -> Sphere<S> {
-        let max = self.half_dim.x.max(self.half_dim.y).max(self.half_dim.z);
         Sphere {
             center: Point3::origin(),
-            radius: max,
+            radius: self.half_dim.magnitude(),
         }
     }
 }
 
+impl<S> Contains<Point3<S>> for Cuboid<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        point.x.abs() <= self.half_dim.x
+            && point.y.abs() <= self.half_dim.y
+            && point.z.abs() <= self.half_dim.z
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for Cuboid<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        let (closest, inside) = closest_point_on_box(point.to_vec(), self.half_dim);
+        (Point3::from_vec(closest), inside)
+    }
+}
+
 impl<S> Discrete<Ray3<S>> for Cuboid<S>
 where
     S: BaseFloat,
@@ -189,6 +211,26 @@ where
     }
 }
 
+impl<S> Contains<Point3<S>> for Cube<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        self.cuboid.contains(point)
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for Cube<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        self.cuboid.closest_point(point)
+    }
+}
+
 impl<S> Discrete<Ray3<S>> for Cube<S>
 where
     S: BaseFloat,
@@ -224,6 +266,26 @@ mod tests {
         assert_eq!(bound(-5., -5., -5., 5., 5., 5.), r.compute_bound())
     }
 
+    #[test]
+    fn test_rectangle_sphere_bound() {
+        let r = Cuboid::new(2., 4., 4.);
+        let sphere: Sphere<f32> = r.compute_bound();
+        assert_eq!(3., sphere.radius);
+        assert!(r.corners.iter().all(|c| sphere.contains(c)));
+    }
+
+    #[test]
+    fn test_rectangle_bound_transformed() {
+        let r = Cuboid::new(10., 10., 10.);
+        let t = transform(0., 1., 0., std::f32::consts::PI / 4.);
+        let aabb: Aabb3<f32> = r.compute_bound_transformed(&t);
+        let d = 5. * 2f32.sqrt();
+        assert_ulps_eq!(Point3::new(-d, 1. - d, -5.), aabb.min, epsilon = 1e-5);
+        assert_ulps_eq!(Point3::new(d, 1. + d, 5.), aabb.max, epsilon = 1e-5);
+        let sphere: Sphere<f32> = r.compute_bound_transformed(&t);
+        assert_ulps_eq!(Point3::new(0., 1., 0.), sphere.center);
+    }
+
     #[test]
     fn test_ray_discrete() {
         let cuboid = Cuboid::new(10., 10., 10.);
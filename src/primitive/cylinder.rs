@@ -1,9 +1,9 @@
-use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::{BaseFloat, Point2, Point3, Vector3};
 use cgmath::prelude::*;
 
 use crate::{Aabb3, Ray3};
 use crate::prelude::*;
-use crate::primitive::util::cylinder_ray_quadratic_solve;
+use crate::primitive::util::{closest_point_on_revolution, cylinder_ray_quadratic_solve};
 use crate::volume::Sphere;
 
 /// Cylinder primitive
@@ -97,13 +97,45 @@ where
     }
 }
 
+impl<S> Contains<Point3<S>> for Cylinder<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        point.y.abs() <= self.half_height
+            && point.x * point.x + point.z * point.z <= self.radius * self.radius
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for Cylinder<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        let profile = [
+            Point2::new(S::zero(), -self.half_height),
+            Point2::new(self.radius, -self.half_height),
+            Point2::new(self.radius, self.half_height),
+            Point2::new(S::zero(), self.half_height),
+        ];
+        (
+            closest_point_on_revolution(&profile, *point),
+            self.contains(point),
+        )
+    }
+}
+
 impl<S> Discrete<Ray3<S>> for Cylinder<S>
 where
     S: BaseFloat,
 {
     fn intersects(&self, r: &Ray3<S>) -> bool {
         if r.direction.x.is_zero() && r.direction.z.is_zero() {
-            if r.direction.y.is_zero() {
+            if r.direction.y.is_zero()
+                || r.origin.x * r.origin.x + r.origin.z * r.origin.z > self.radius * self.radius
+            {
                 return false;
             }
 
@@ -163,21 +195,23 @@ where
 
     fn intersection(&self, r: &Ray3<S>) -> Option<Point3<S>> {
         if r.direction.x.is_zero() && r.direction.z.is_zero() {
-            if r.direction.y.is_zero() {
+            if r.direction.y.is_zero()
+                || r.origin.x * r.origin.x + r.origin.z * r.origin.z > self.radius * self.radius
+            {
                 return None;
             }
 
             if r.origin.y >= self.half_height && r.direction.y < S::zero() {
-                return Some(Point3::new(S::zero(), self.half_height, S::zero()));
+                return Some(Point3::new(r.origin.x, self.half_height, r.origin.z));
             }
             if r.origin.y >= -self.half_height && r.direction.y < S::zero() {
-                return Some(Point3::new(S::zero(), -self.half_height, S::zero()));
+                return Some(Point3::new(r.origin.x, -self.half_height, r.origin.z));
             }
             if r.origin.y <= -self.half_height && r.direction.y > S::zero() {
-                return Some(Point3::new(S::zero(), -self.half_height, S::zero()));
+                return Some(Point3::new(r.origin.x, -self.half_height, r.origin.z));
             }
             if r.origin.y <= self.half_height && r.direction.y > S::zero() {
-                return Some(Point3::new(S::zero(), self.half_height, S::zero()));
+                return Some(Point3::new(r.origin.x, self.half_height, r.origin.z));
             }
 
             return None;
@@ -368,6 +402,26 @@ mod tests {
         assert_eq!(Some(Point3::new(0., 2., 0.)), cylinder.intersection(&ray));
     }
 
+    #[test]
+    fn test_discrete_axis_parallel() {
+        let cylinder = Cylinder::new(2., 1.);
+        let ray = Ray3::new(Point3::new(0.5, 3., 0.5), Vector3::new(0., -1., 0.));
+        assert!(cylinder.intersects(&ray));
+        let ray = Ray3::new(Point3::new(5., 3., 0.), Vector3::new(0., -1., 0.));
+        assert!(!cylinder.intersects(&ray));
+    }
+
+    #[test]
+    fn test_continuous_axis_parallel() {
+        let cylinder = Cylinder::new(2., 1.);
+        let ray = Ray3::new(Point3::new(0.5, 3., 0.5), Vector3::new(0., -1., 0.));
+        assert_eq!(Some(Point3::new(0.5, 2., 0.5)), cylinder.intersection(&ray));
+        let ray = Ray3::new(Point3::new(0.5, -3., 0.), Vector3::new(0., 1., 0.));
+        assert_eq!(Some(Point3::new(0.5, -2., 0.)), cylinder.intersection(&ray));
+        let ray = Ray3::new(Point3::new(5., 3., 0.), Vector3::new(0., -1., 0.));
+        assert_eq!(None, cylinder.intersection(&ray));
+    }
+
     // util
     fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
         Decomposed {
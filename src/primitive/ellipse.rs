@@ -0,0 +1,320 @@
+//! Ellipse primitive
+
+use cgmath::{BaseFloat, Point2, Rad, Vector2};
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Ray2};
+use crate::prelude::*;
+use crate::primitive::util::closest_point_on_ellipsoid;
+use crate::volume::Circle;
+
+/// Ellipse primitive
+///
+/// Axis aligned in its local space, with local origin in the center of the ellipse.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipse<S> {
+    /// Radius along the X axis
+    pub radius_x: S,
+    /// Radius along the Y axis
+    pub radius_y: S,
+}
+
+impl<S> Ellipse<S> {
+    /// Create a new ellipse primitive
+    pub fn new(radius_x: S, radius_y: S) -> Self {
+        Self { radius_x, radius_y }
+    }
+}
+
+impl<S> Ellipse<S>
+where
+    S: BaseFloat,
+{
+    /// Map a ray into the space where the ellipse is the unit circle, and return the two
+    /// intersection parameters along the ray, if any.
+    fn ray_solve(&self, r: &Ray2<S>) -> Option<(S, S)> {
+        let o = Vector2::new(r.origin.x / self.radius_x, r.origin.y / self.radius_y);
+        let d = Vector2::new(r.direction.x / self.radius_x, r.direction.y / self.radius_y);
+        let a = d.magnitude2();
+        if a.is_zero() {
+            return None;
+        }
+        let b = o.dot(d);
+        let c = o.magnitude2() - S::one();
+        let discr = b * b - a * c;
+        if discr < S::zero() {
+            return None;
+        }
+        let discr = discr.sqrt();
+        Some(((-b - discr) / a, (-b + discr) / a))
+    }
+}
+
+impl<S> Primitive for Ellipse<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point2<S>;
+
+    fn support_point<T>(&self, direction: &Vector2<S>, transform: &T) -> Point2<S>
+    where
+        T: Transform<Point2<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let rx2 = self.radius_x * self.radius_x;
+        let ry2 = self.radius_y * self.radius_y;
+        let denom = (rx2 * direction.x * direction.x + ry2 * direction.y * direction.y).sqrt();
+        let point = if denom.is_zero() {
+            Point2::origin()
+        } else {
+            Point2::new(rx2 * direction.x / denom, ry2 * direction.y / denom)
+        };
+        transform.transform_point(point)
+    }
+}
+
+impl<S> ComputeBound<Aabb2<S>> for Ellipse<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb2<S> {
+        Aabb2::new(
+            Point2::new(-self.radius_x, -self.radius_y),
+            Point2::new(self.radius_x, self.radius_y),
+        )
+    }
+}
+
+impl<S> ComputeBound<Circle<S>> for Ellipse<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: self.radius_x.max(self.radius_y),
+        }
+    }
+}
+
+impl<S> Contains<Point2<S>> for Ellipse<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        let x = point.x / self.radius_x;
+        let y = point.y / self.radius_y;
+        x * x + y * y <= S::one()
+    }
+}
+
+impl<S> ClosestPoint<Point2<S>> for Ellipse<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point2<S>) -> (Point2<S>, bool) {
+        let (closest, inside) =
+            closest_point_on_ellipsoid(point.to_vec(), Vector2::new(self.radius_x, self.radius_y));
+        (Point2::from_vec(closest), inside)
+    }
+}
+
+impl<S> Discrete<Ray2<S>> for Ellipse<S>
+where
+    S: BaseFloat,
+{
+    fn intersects(&self, r: &Ray2<S>) -> bool {
+        match self.ray_solve(r) {
+            None => false,
+            Some((_, t2)) => t2 >= S::zero(),
+        }
+    }
+}
+
+impl<S> Continuous<Ray2<S>> for Ellipse<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point2<S>;
+
+    fn intersection(&self, r: &Ray2<S>) -> Option<Point2<S>> {
+        let (t1, t2) = self.ray_solve(r)?;
+        if t1 >= S::zero() {
+            Some(r.origin + r.direction * t1)
+        } else if t2 >= S::zero() {
+            Some(r.origin + r.direction * t2)
+        } else {
+            None
+        }
+    }
+}
+
+impl<S> Geometry2 for Ellipse<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        Rad::<S>::turn_div_2().0 * self.radius_x * self.radius_y
+    }
+
+    /// Uses Ramanujan's second approximation, which is exact for circles
+    fn perimeter(&self) -> S {
+        let sum = self.radius_x + self.radius_y;
+        if sum.is_zero() {
+            return S::zero();
+        }
+        let h = ((self.radius_x - self.radius_y) / sum).powi(2);
+        let three = S::from(3.).unwrap();
+        let correction =
+            three * h / (S::from(10.).unwrap() + (S::from(4.).unwrap() - three * h).sqrt());
+        Rad::<S>::turn_div_2().0 * sum * (S::one() + correction)
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        Point2::origin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Basis2, Decomposed, Point2, Rad, Rotation2, Vector2};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_ellipse_support_axis() {
+        let ellipse = Ellipse::new(10., 5.);
+        let t = transform(0., 0., 0.);
+        assert_ulps_eq!(
+            Point2::new(10., 0.),
+            ellipse.support_point(&Vector2::new(1., 0.), &t)
+        );
+        assert_ulps_eq!(
+            Point2::new(0., -5.),
+            ellipse.support_point(&Vector2::new(0., -1.), &t)
+        );
+    }
+
+    #[test]
+    fn test_ellipse_support_diagonal() {
+        let ellipse = Ellipse::new(10., 5.);
+        let t = transform(0., 0., 0.);
+        let point = ellipse.support_point(&Vector2::new(1., 1.), &t);
+        // point must be on the ellipse, with the normal along the direction
+        assert_ulps_eq!(
+            1.,
+            point.x * point.x / 100. + point.y * point.y / 25.,
+            epsilon = 1e-6
+        );
+        assert_ulps_eq!(8.944272, point.x);
+        assert_ulps_eq!(2.236068, point.y);
+    }
+
+    #[test]
+    fn test_ellipse_support_transformed() {
+        let ellipse = Ellipse::new(10., 5.);
+        let t = transform(0., 10., std::f32::consts::PI / 2.);
+        let point = ellipse.support_point(&Vector2::new(1., 0.), &t);
+        assert_ulps_eq!(5., point.x);
+        assert_ulps_eq!(10., point.y, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_ellipse_bound() {
+        let ellipse = Ellipse::new(10., 5.);
+        assert_eq!(bound(-10., -5., 10., 5.), ellipse.compute_bound())
+    }
+
+    #[test]
+    fn test_ellipse_ray_discrete() {
+        let ellipse = Ellipse::new(10., 5.);
+        let ray = Ray2::new(Point2::new(25., 4.), Vector2::new(-1., 0.));
+        assert!(ellipse.intersects(&ray));
+        let ray = Ray2::new(Point2::new(25., 6.), Vector2::new(-1., 0.));
+        assert!(!ellipse.intersects(&ray));
+        let ray = Ray2::new(Point2::new(25., 0.), Vector2::new(1., 0.));
+        assert!(!ellipse.intersects(&ray));
+    }
+
+    #[test]
+    fn test_ellipse_ray_discrete_transformed() {
+        let ellipse = Ellipse::new(10., 5.);
+        let ray = Ray2::new(Point2::new(25., 0.), Vector2::new(-1., 0.));
+        let t = transform(0., 0., 0.);
+        assert!(ellipse.intersects_transformed(&ray, &t));
+        let t = transform(0., 6., 0.);
+        assert!(!ellipse.intersects_transformed(&ray, &t));
+    }
+
+    #[test]
+    fn test_ellipse_ray_continuous() {
+        let ellipse = Ellipse::new(10., 5.);
+        let ray = Ray2::new(Point2::new(25., 0.), Vector2::new(-1., 0.));
+        assert_ulps_eq!(
+            Point2::new(10., 0.),
+            ellipse.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        let ray = Ray2::new(Point2::new(0., 25.), Vector2::new(0., -1.));
+        assert_ulps_eq!(
+            Point2::new(0., 5.),
+            ellipse.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        let ray = Ray2::new(Point2::new(25., 6.), Vector2::new(-1., 0.));
+        assert_eq!(None, ellipse.intersection(&ray));
+    }
+
+    #[test]
+    fn test_ellipse_ray_continuous_inside() {
+        let ellipse = Ellipse::new(10., 5.);
+        let ray = Ray2::new(Point2::new(0., 0.), Vector2::new(1., 0.));
+        assert_ulps_eq!(
+            Point2::new(10., 0.),
+            ellipse.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_ellipse_ray_continuous_transformed() {
+        let ellipse = Ellipse::new(10., 5.);
+        let ray = Ray2::new(Point2::new(25., 0.), Vector2::new(-1., 0.));
+        let t = transform(0., 0., std::f32::consts::PI / 2.);
+        let p = ellipse.intersection_transformed(&ray, &t).unwrap();
+        assert_ulps_eq!(Point2::new(5., 0.), p, epsilon = 1e-5);
+        let t = transform(0., 6., 0.);
+        assert_eq!(None, ellipse.intersection_transformed(&ray, &t));
+    }
+
+    #[test]
+    fn test_ellipse_geometry() {
+        let ellipse = Ellipse::new(10., 5.);
+        assert_ulps_eq!(50. * std::f32::consts::PI, ellipse.area());
+        assert_ulps_eq!(48.442_206, ellipse.perimeter(), epsilon = 1e-3);
+        assert_eq!(Point2::new(0., 0.), ellipse.centroid());
+        let circle = Ellipse::new(2., 2.);
+        assert_ulps_eq!(4. * std::f32::consts::PI, circle.perimeter());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+
+    fn bound(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Aabb2<f32> {
+        Aabb2::new(Point2::new(min_x, min_y), Point2::new(max_x, max_y))
+    }
+}
@@ -0,0 +1,303 @@
+//! Ellipsoid primitive
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, Ray3};
+use crate::prelude::*;
+use crate::primitive::util::closest_point_on_ellipsoid;
+use crate::volume::Sphere;
+
+/// Ellipsoid primitive
+///
+/// Axis aligned in its local space, with local origin in the center of the ellipsoid.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipsoid<S> {
+    /// Radius along the X axis
+    pub radius_x: S,
+    /// Radius along the Y axis
+    pub radius_y: S,
+    /// Radius along the Z axis
+    pub radius_z: S,
+}
+
+impl<S> Ellipsoid<S> {
+    /// Create a new ellipsoid primitive
+    pub fn new(radius_x: S, radius_y: S, radius_z: S) -> Self {
+        Self {
+            radius_x,
+            radius_y,
+            radius_z,
+        }
+    }
+}
+
+impl<S> Ellipsoid<S>
+where
+    S: BaseFloat,
+{
+    /// Map a ray into the space where the ellipsoid is the unit sphere, and return the two
+    /// intersection parameters along the ray, if any.
+    fn ray_solve(&self, r: &Ray3<S>) -> Option<(S, S)> {
+        let o = Vector3::new(
+            r.origin.x / self.radius_x,
+            r.origin.y / self.radius_y,
+            r.origin.z / self.radius_z,
+        );
+        let d = Vector3::new(
+            r.direction.x / self.radius_x,
+            r.direction.y / self.radius_y,
+            r.direction.z / self.radius_z,
+        );
+        let a = d.magnitude2();
+        if a.is_zero() {
+            return None;
+        }
+        let b = o.dot(d);
+        let c = o.magnitude2() - S::one();
+        let discr = b * b - a * c;
+        if discr < S::zero() {
+            return None;
+        }
+        let discr = discr.sqrt();
+        Some(((-b - discr) / a, (-b + discr) / a))
+    }
+}
+
+impl<S> Primitive for Ellipsoid<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let scaled = Vector3::new(
+            direction.x * self.radius_x,
+            direction.y * self.radius_y,
+            direction.z * self.radius_z,
+        );
+        let point = if scaled.magnitude2().is_zero() {
+            Point3::origin()
+        } else {
+            let n = scaled.normalize();
+            Point3::new(
+                n.x * self.radius_x,
+                n.y * self.radius_y,
+                n.z * self.radius_z,
+            )
+        };
+        transform.transform_point(point)
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for Ellipsoid<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        Aabb3::new(
+            Point3::new(-self.radius_x, -self.radius_y, -self.radius_z),
+            Point3::new(self.radius_x, self.radius_y, self.radius_z),
+        )
+    }
+}
+
+impl<S> ComputeBound<Sphere<S>> for Ellipsoid<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere {
+            center: Point3::origin(),
+            radius: self.radius_x.max(self.radius_y).max(self.radius_z),
+        }
+    }
+}
+
+impl<S> Contains<Point3<S>> for Ellipsoid<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        let scaled = Vector3::new(
+            point.x / self.radius_x,
+            point.y / self.radius_y,
+            point.z / self.radius_z,
+        );
+        scaled.magnitude2() <= S::one()
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for Ellipsoid<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        let (closest, inside) = closest_point_on_ellipsoid(
+            point.to_vec(),
+            Vector3::new(self.radius_x, self.radius_y, self.radius_z),
+        );
+        (Point3::from_vec(closest), inside)
+    }
+}
+
+impl<S> Discrete<Ray3<S>> for Ellipsoid<S>
+where
+    S: BaseFloat,
+{
+    fn intersects(&self, r: &Ray3<S>) -> bool {
+        match self.ray_solve(r) {
+            None => false,
+            Some((_, t2)) => t2 >= S::zero(),
+        }
+    }
+}
+
+impl<S> Continuous<Ray3<S>> for Ellipsoid<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point3<S>;
+
+    fn intersection(&self, r: &Ray3<S>) -> Option<Point3<S>> {
+        let (t1, t2) = self.ray_solve(r)?;
+        if t1 >= S::zero() {
+            Some(r.origin + r.direction * t1)
+        } else if t2 >= S::zero() {
+            Some(r.origin + r.direction * t2)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK3;
+    use crate::primitive::Sphere as SpherePrimitive;
+
+    #[test]
+    fn test_ellipsoid_support_axis() {
+        let ellipsoid = Ellipsoid::new(3., 2., 1.);
+        let t = transform(0., 0., 0., 0.);
+        assert_ulps_eq!(
+            Point3::new(3., 0., 0.),
+            ellipsoid.support_point(&Vector3::new(1., 0., 0.), &t)
+        );
+        assert_ulps_eq!(
+            Point3::new(0., -2., 0.),
+            ellipsoid.support_point(&Vector3::new(0., -1., 0.), &t)
+        );
+        assert_ulps_eq!(
+            Point3::new(0., 0., 1.),
+            ellipsoid.support_point(&Vector3::new(0., 0., 1.), &t)
+        );
+    }
+
+    #[test]
+    fn test_ellipsoid_support_diagonal() {
+        let ellipsoid = Ellipsoid::new(3., 2., 1.);
+        let t = transform(0., 0., 0., 0.);
+        let direction = Vector3::new(1., 1., 1.);
+        let point = ellipsoid.support_point(&direction, &t);
+        // point must be on the ellipsoid, with the normal along the direction
+        assert_ulps_eq!(
+            1.,
+            point.x * point.x / 9. + point.y * point.y / 4. + point.z * point.z,
+            epsilon = 1e-6
+        );
+        let normal = Vector3::new(point.x / 9., point.y / 4., point.z).normalize();
+        assert_ulps_eq!(direction.normalize(), normal, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_ellipsoid_support_transformed() {
+        let ellipsoid = Ellipsoid::new(3., 2., 1.);
+        let t = transform(0., 10., 0., std::f32::consts::PI / 2.);
+        let point = ellipsoid.support_point(&Vector3::new(1., 0., 0.), &t);
+        assert_ulps_eq!(Point3::new(2., 10., 0.), point, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_ellipsoid_bound() {
+        let ellipsoid = Ellipsoid::new(3., 2., 1.);
+        assert_eq!(
+            Aabb3::new(Point3::new(-3., -2., -1.), Point3::new(3., 2., 1.)),
+            ellipsoid.compute_bound()
+        );
+        let sphere: Sphere<f32> = ellipsoid.compute_bound();
+        assert_ulps_eq!(3., sphere.radius);
+    }
+
+    #[test]
+    fn test_ellipsoid_ray_discrete() {
+        let ellipsoid = Ellipsoid::new(3., 2., 1.);
+        let ray = Ray3::new(Point3::new(10., 0., 0.5), Vector3::new(-1., 0., 0.));
+        assert!(ellipsoid.intersects(&ray));
+        let ray = Ray3::new(Point3::new(10., 0., 1.5), Vector3::new(-1., 0., 0.));
+        assert!(!ellipsoid.intersects(&ray));
+        let ray = Ray3::new(Point3::new(10., 0., 0.), Vector3::new(1., 0., 0.));
+        assert!(!ellipsoid.intersects(&ray));
+    }
+
+    #[test]
+    fn test_ellipsoid_ray_continuous() {
+        let ellipsoid = Ellipsoid::new(3., 2., 1.);
+        let ray = Ray3::new(Point3::new(10., 0., 0.), Vector3::new(-1., 0., 0.));
+        assert_ulps_eq!(
+            Point3::new(3., 0., 0.),
+            ellipsoid.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        let ray = Ray3::new(Point3::new(0., 0., -10.), Vector3::new(0., 0., 1.));
+        assert_ulps_eq!(
+            Point3::new(0., 0., -1.),
+            ellipsoid.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        let ray = Ray3::new(Point3::new(0., 0., 0.), Vector3::new(0., 1., 0.));
+        assert_ulps_eq!(
+            Point3::new(0., 2., 0.),
+            ellipsoid.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        let ray = Ray3::new(Point3::new(10., 0., 1.5), Vector3::new(-1., 0., 0.));
+        assert_eq!(None, ellipsoid.intersection(&ray));
+    }
+
+    #[test]
+    fn test_ellipsoid_gjk() {
+        let ellipsoid = Ellipsoid::new(3., 1., 1.);
+        let sphere = SpherePrimitive::new(1.);
+        let gjk = GJK3::new();
+        let t = transform(0., 0., 0., 0.);
+        assert!(gjk
+            .intersect(&ellipsoid, &t, &sphere, &transform(3.9, 0., 0., 0.))
+            .is_some());
+        assert!(gjk
+            .intersect(&ellipsoid, &t, &sphere, &transform(0., 2.1, 0., 0.))
+            .is_none());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(rot)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
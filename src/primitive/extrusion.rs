@@ -0,0 +1,348 @@
+//! Extrusion of 2D primitives into 3D
+
+use approx::ulps_eq;
+use cgmath::{BaseFloat, Basis2, Decomposed, Point2, Point3, Vector2, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Aabb3, Ray2, Ray3};
+use crate::prelude::*;
+use crate::volume::Sphere;
+
+/// Wrapper that extrudes a convex 2D primitive into a 3D prism.
+///
+/// The 2D primitive lies in the XY plane, and is extruded along the Z axis, from `-half_height`
+/// to `half_height`, so an extruded `Circle` is a cylinder along the Z axis, and an extruded
+/// `Rectangle` is a cuboid. The support function is the support function of the 2D primitive,
+/// combined with the extent along the Z axis.
+///
+/// Ray tests use the ray tests of the 2D primitive, which must be convex.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Extrusion<P, S> {
+    /// Wrapped 2D primitive
+    pub primitive: P,
+    /// Half of the height of the prism along the Z axis
+    pub half_height: S,
+}
+
+impl<P, S> Extrusion<P, S> {
+    /// Create a new extruded primitive
+    pub fn new(primitive: P, half_height: S) -> Self {
+        Self {
+            primitive,
+            half_height,
+        }
+    }
+}
+
+impl<P, S> Extrusion<P, S>
+where
+    S: BaseFloat,
+    P: Continuous<Ray2<S>, Result = Point2<S>> + ComputeBound<Aabb2<S>>,
+{
+    /// Find the first ray parameter inside the prism, if any
+    fn ray_parameter(&self, ray: &Ray3<S>) -> Option<S> {
+        // part of the ray between the caps
+        let (enter, exit) = if ray.direction.z.is_zero() {
+            if ray.origin.z.abs() > self.half_height {
+                return None;
+            }
+            (S::zero(), S::infinity())
+        } else {
+            let t1 = (-self.half_height - ray.origin.z) / ray.direction.z;
+            let t2 = (self.half_height - ray.origin.z) / ray.direction.z;
+            (t1.min(t2).max(S::zero()), t1.max(t2))
+        };
+        if enter > exit {
+            return None;
+        }
+
+        // part of the projected ray inside the 2D primitive, with a line along the X axis if the
+        // ray is parallel to the Z axis
+        let origin = Point2::new(
+            ray.origin.x + ray.direction.x * enter,
+            ray.origin.y + ray.direction.y * enter,
+        );
+        let planar = Vector2::new(ray.direction.x, ray.direction.y);
+        let speed = planar.magnitude();
+        let direction = if speed.is_zero() {
+            Vector2::unit_x()
+        } else {
+            planar / speed
+        };
+        let (inside_enter, inside_exit) = self.line_interval(origin, direction)?;
+        if speed.is_zero() {
+            return if inside_enter <= S::zero() && inside_exit >= S::zero() {
+                Some(enter)
+            } else {
+                None
+            };
+        }
+        let inside_enter = enter + inside_enter / speed;
+        let inside_exit = enter + inside_exit / speed;
+        let t = inside_enter.max(enter);
+        if t <= inside_exit.min(exit) {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    /// Find the distances along the line where it enters and exits the 2D primitive. The
+    /// direction must be normalized.
+    fn line_interval(&self, origin: Point2<S>, direction: Vector2<S>) -> Option<(S, S)> {
+        let bound: Aabb2<S> = self.primitive.compute_bound();
+        // start both rays outside of the bound, so they always hit from the outside
+        let reach = bound
+            .to_corners()
+            .iter()
+            .map(|corner| (corner - origin).magnitude())
+            .fold(S::zero(), S::max)
+            + S::one();
+        let distance = |p: Point2<S>| (p - origin).dot(direction);
+        let enter = self
+            .primitive
+            .intersection(&Ray2::new(origin - direction * reach, direction))?;
+        let exit = self
+            .primitive
+            .intersection(&Ray2::new(origin + direction * reach, -direction))?;
+        Some((distance(enter), distance(exit)))
+    }
+}
+
+impl<P, S> Primitive for Extrusion<P, S>
+where
+    S: BaseFloat,
+    P: Primitive<Point = Point2<S>>,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let planar = Vector2::new(direction.x, direction.y);
+        // along the Z axis any point on the cap is a support point, and the 2D primitive may not
+        // handle a zero direction
+        let planar = if ulps_eq!(planar, Vector2::zero()) {
+            Vector2::unit_x()
+        } else {
+            planar
+        };
+        let p = self
+            .primitive
+            .support_point(&planar, &Decomposed::<Vector2<S>, Basis2<S>>::one());
+        let z = if direction.z < S::zero() {
+            -self.half_height
+        } else {
+            self.half_height
+        };
+        transform.transform_point(Point3::new(p.x, p.y, z))
+    }
+}
+
+impl<P, S> ComputeBound<Aabb3<S>> for Extrusion<P, S>
+where
+    S: BaseFloat,
+    P: ComputeBound<Aabb2<S>>,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        let bound: Aabb2<S> = self.primitive.compute_bound();
+        Aabb3::new(
+            Point3::new(bound.min.x, bound.min.y, -self.half_height),
+            Point3::new(bound.max.x, bound.max.y, self.half_height),
+        )
+    }
+}
+
+impl<P, S> ComputeBound<Sphere<S>> for Extrusion<P, S>
+where
+    S: BaseFloat,
+    P: ComputeBound<Aabb2<S>>,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        let bound: Aabb3<S> = self.compute_bound();
+        Sphere {
+            center: bound.center(),
+            radius: bound.dim().magnitude() / (S::one() + S::one()),
+        }
+    }
+}
+
+impl<P, S> Discrete<Ray3<S>> for Extrusion<P, S>
+where
+    S: BaseFloat,
+    P: Continuous<Ray2<S>, Result = Point2<S>> + ComputeBound<Aabb2<S>>,
+{
+    fn intersects(&self, ray: &Ray3<S>) -> bool {
+        self.ray_parameter(ray).is_some()
+    }
+}
+
+impl<P, S> Continuous<Ray3<S>> for Extrusion<P, S>
+where
+    S: BaseFloat,
+    P: Continuous<Ray2<S>, Result = Point2<S>> + ComputeBound<Aabb2<S>>,
+{
+    type Result = Point3<S>;
+
+    fn intersection(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
+        self.ray_parameter(ray)
+            .map(|t| ray.origin + ray.direction * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK3;
+    use crate::primitive::{Circle, ConvexPolygon, Cuboid, Rectangle};
+
+    #[test]
+    fn test_extrusion_support() {
+        let prism = Extrusion::new(Rectangle::new(4., 2.), 3.);
+        let t = transform(0., 0., 0., 0.);
+        assert_eq!(
+            Point3::new(2., 1., 3.),
+            prism.support_point(&Vector3::new(1., 1., 1.), &t)
+        );
+        assert_eq!(
+            Point3::new(-2., 1., -3.),
+            prism.support_point(&Vector3::new(-1., 1., -1.), &t)
+        );
+        let cylinder = Extrusion::new(Circle::new(2.), 1.);
+        assert_ulps_eq!(
+            Point3::new(0., -2., 1.),
+            cylinder.support_point(&Vector3::new(0., -1., 0.5), &t)
+        );
+    }
+
+    #[test]
+    fn test_extrusion_support_transformed() {
+        let prism = Extrusion::new(Rectangle::new(4., 2.), 3.);
+        let t = transform(0., 10., 0., std::f32::consts::PI / 2.);
+        assert_ulps_eq!(
+            Point3::new(1., 12., 3.),
+            prism.support_point(&Vector3::new(1., 1., 1.), &t),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_extrusion_bound() {
+        let triangle = ConvexPolygon::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(2., 0.),
+            Point2::new(0., 4.),
+        ]);
+        let prism = Extrusion::new(triangle, 1.);
+        assert_eq!(
+            Aabb3::new(Point3::new(0., 0., -1.), Point3::new(2., 4., 1.)),
+            prism.compute_bound()
+        );
+        let sphere: Sphere<f32> = prism.compute_bound();
+        assert_eq!(Point3::new(1., 2., 0.), sphere.center);
+        assert_ulps_eq!(6f32.sqrt(), sphere.radius);
+    }
+
+    #[test]
+    fn test_extrusion_ray_side() {
+        let cylinder = Extrusion::new(Circle::new(2.), 1.);
+        let ray = Ray3::new(Point3::new(10., 0., 0.5), Vector3::new(-1., 0., 0.));
+        assert!(cylinder.intersects(&ray));
+        assert_ulps_eq!(
+            Point3::new(2., 0., 0.5),
+            cylinder.intersection(&ray).unwrap()
+        );
+        let ray = Ray3::new(Point3::new(10., 0., 1.5), Vector3::new(-1., 0., 0.));
+        assert!(!cylinder.intersects(&ray));
+        let ray = Ray3::new(Point3::new(10., 2.5, 0.), Vector3::new(-1., 0., 0.));
+        assert!(!cylinder.intersects(&ray));
+        let ray = Ray3::new(Point3::new(10., 0., 0.), Vector3::new(1., 0., 0.));
+        assert!(!cylinder.intersects(&ray));
+    }
+
+    #[test]
+    fn test_extrusion_ray_caps() {
+        let cylinder = Extrusion::new(Circle::new(2.), 1.);
+        let ray = Ray3::new(Point3::new(0.5, 0.5, 10.), Vector3::new(0., 0., -1.));
+        assert_ulps_eq!(
+            Point3::new(0.5, 0.5, 1.),
+            cylinder.intersection(&ray).unwrap()
+        );
+        let ray = Ray3::new(Point3::new(2.5, 0., 10.), Vector3::new(0., 0., -1.));
+        assert!(!cylinder.intersects(&ray));
+        // enters through the side, below the top cap
+        let ray = Ray3::new(
+            Point3::new(-4., 0., 2.),
+            Vector3::new(1., 0., -0.5).normalize(),
+        );
+        assert_ulps_eq!(
+            Point3::new(-2., 0., 1.),
+            cylinder.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+        // passes over the top cap
+        let ray = Ray3::new(
+            Point3::new(-4., 0., 2.),
+            Vector3::new(1., 0., -0.1).normalize(),
+        );
+        assert!(!cylinder.intersects(&ray));
+    }
+
+    #[test]
+    fn test_extrusion_ray_inside() {
+        let prism = Extrusion::new(Rectangle::new(4., 2.), 3.);
+        let ray = Ray3::new(
+            Point3::new(0.5, 0., 0.),
+            Vector3::new(1., 1., 0.).normalize(),
+        );
+        assert_eq!(Some(Point3::new(0.5, 0., 0.)), prism.intersection(&ray));
+    }
+
+    #[test]
+    fn test_extrusion_gjk() {
+        let prism = Extrusion::new(Rectangle::new(2., 2.), 1.);
+        let cuboid = Cuboid::new(2., 2., 2.);
+        let gjk = GJK3::new();
+        let t = transform(0., 0., 0., 0.);
+        assert!(gjk
+            .intersect(&prism, &t, &cuboid, &transform(1.9, 0.2, 0.1, 0.))
+            .is_some());
+        assert!(gjk
+            .intersect(&prism, &t, &cuboid, &transform(0.2, 0.1, 2.1, 0.))
+            .is_none());
+    }
+
+    #[test]
+    fn test_extrusion_gjk_stacked() {
+        let cylinder = Extrusion::new(Circle::new(1.), 1.);
+        let gjk = GJK3::new();
+        let t = transform(0., 0., 0., 0.);
+        assert!(gjk
+            .intersect(&cylinder, &t, &cylinder, &transform(0., 0., 3., 0.))
+            .is_none());
+        assert!(gjk
+            .intersect(&cylinder, &t, &cylinder, &transform(0., 0., 1.9, 0.))
+            .is_some());
+        assert_eq!(
+            Point3::new(1., 0., -1.),
+            cylinder.support_point(&Vector3::new(0., 0., -1.), &t)
+        );
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(rot)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
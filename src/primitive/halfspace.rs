@@ -0,0 +1,437 @@
+//! Half-plane and half-space primitives
+
+use std::ops::Neg;
+
+use cgmath::{BaseFloat, Point2, Point3, Vector2, Vector3};
+use cgmath::prelude::*;
+
+use crate::{CollisionStrategy, Contact, Ray, Ray2, Ray3};
+use crate::prelude::*;
+
+/// Half-plane primitive, the region on one side of an infinite line.
+///
+/// The region contains all points `x` where `normal.dot(x) <= offset`, so the normal points out
+/// of the region. Useful for infinite ground and walls, without having to fake them with very
+/// large rectangles, which destroy the efficiency of the broad phase.
+///
+/// Half-planes have no bounding volume and no support function, so they can not be used with
+/// GJK. Use [`intersects_primitive`](#method.intersects_primitive) and
+/// [`intersection_primitive`](#method.intersection_primitive) to test against convex primitives.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HalfPlane<S> {
+    /// Normal of the boundary, pointing out of the region. Must be normalized.
+    pub normal: Vector2<S>,
+    /// Distance from the origin to the boundary, along the normal
+    pub offset: S,
+}
+
+/// Half-space primitive, the region on one side of an infinite plane.
+///
+/// The region contains all points `x` where `normal.dot(x) <= offset`, so the normal points out
+/// of the region. Useful for infinite ground and walls, without having to fake them with very
+/// large cuboids, which destroy the efficiency of the broad phase.
+///
+/// Half-spaces have no bounding volume and no support function, so they can not be used with
+/// GJK. Use [`intersects_primitive`](#method.intersects_primitive) and
+/// [`intersection_primitive`](#method.intersection_primitive) to test against convex primitives.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HalfSpace<S> {
+    /// Normal of the boundary, pointing out of the region. Must be normalized.
+    pub normal: Vector3<S>,
+    /// Distance from the origin to the boundary, along the normal
+    pub offset: S,
+}
+
+impl<S> HalfPlane<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new half-plane, the normal will be normalized
+    pub fn new(normal: Vector2<S>, offset: S) -> Self {
+        Self {
+            normal: normal.normalize(),
+            offset,
+        }
+    }
+
+    /// Create a new half-plane with the boundary through the given point
+    pub fn from_point_normal(point: Point2<S>, normal: Vector2<S>) -> Self {
+        let normal = normal.normalize();
+        Self {
+            normal,
+            offset: normal.dot(point.to_vec()),
+        }
+    }
+
+    /// Test if a convex primitive intersects the half-plane. The half-plane is in world space.
+    pub fn intersects_primitive<P, T>(&self, primitive: &P, transform: &T) -> bool
+    where
+        P: Primitive<Point = Point2<S>>,
+        T: Transform<Point2<S>>,
+    {
+        intersects_primitive(self.normal, self.offset, primitive, transform)
+    }
+
+    /// Compute the contact between a convex primitive and the half-plane. The half-plane is in
+    /// world space, and is the first shape in the contact, so the normal of the contact is the
+    /// normal of the half-plane, and the contact point is on the boundary of the half-plane.
+    pub fn intersection_primitive<P, T>(
+        &self,
+        strategy: &CollisionStrategy,
+        primitive: &P,
+        transform: &T,
+    ) -> Option<Contact<Point2<S>>>
+    where
+        P: Primitive<Point = Point2<S>>,
+        T: Transform<Point2<S>>,
+    {
+        intersection_primitive(self.normal, self.offset, strategy, primitive, transform)
+    }
+}
+
+impl<S> HalfSpace<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new half-space, the normal will be normalized
+    pub fn new(normal: Vector3<S>, offset: S) -> Self {
+        Self {
+            normal: normal.normalize(),
+            offset,
+        }
+    }
+
+    /// Create a new half-space with the boundary through the given point
+    pub fn from_point_normal(point: Point3<S>, normal: Vector3<S>) -> Self {
+        let normal = normal.normalize();
+        Self {
+            normal,
+            offset: normal.dot(point.to_vec()),
+        }
+    }
+
+    /// Test if a convex primitive intersects the half-space. The half-space is in world space.
+    pub fn intersects_primitive<P, T>(&self, primitive: &P, transform: &T) -> bool
+    where
+        P: Primitive<Point = Point3<S>>,
+        T: Transform<Point3<S>>,
+    {
+        intersects_primitive(self.normal, self.offset, primitive, transform)
+    }
+
+    /// Compute the contact between a convex primitive and the half-space. The half-space is in
+    /// world space, and is the first shape in the contact, so the normal of the contact is the
+    /// normal of the half-space, and the contact point is on the boundary of the half-space.
+    pub fn intersection_primitive<P, T>(
+        &self,
+        strategy: &CollisionStrategy,
+        primitive: &P,
+        transform: &T,
+    ) -> Option<Contact<Point3<S>>>
+    where
+        P: Primitive<Point = Point3<S>>,
+        T: Transform<Point3<S>>,
+    {
+        intersection_primitive(self.normal, self.offset, strategy, primitive, transform)
+    }
+}
+
+fn intersects_primitive<S, P, T>(
+    normal: <P::Point as EuclideanSpace>::Diff,
+    offset: S,
+    primitive: &P,
+    transform: &T,
+) -> bool
+where
+    S: BaseFloat,
+    P: Primitive,
+    P::Point: EuclideanSpace<Scalar = S>,
+    <P::Point as EuclideanSpace>::Diff:
+        InnerSpace + Neg<Output = <P::Point as EuclideanSpace>::Diff>,
+    T: Transform<P::Point>,
+{
+    let deepest = primitive.support_point(&-normal, transform);
+    normal.dot(deepest.to_vec()) <= offset
+}
+
+fn intersection_primitive<S, P, T>(
+    normal: <P::Point as EuclideanSpace>::Diff,
+    offset: S,
+    strategy: &CollisionStrategy,
+    primitive: &P,
+    transform: &T,
+) -> Option<Contact<P::Point>>
+where
+    S: BaseFloat,
+    P: Primitive,
+    P::Point: EuclideanSpace<Scalar = S>,
+    <P::Point as EuclideanSpace>::Diff:
+        InnerSpace + Neg<Output = <P::Point as EuclideanSpace>::Diff>,
+    T: Transform<P::Point>,
+{
+    let deepest = primitive.support_point(&-normal, transform);
+    let depth = offset - normal.dot(deepest.to_vec());
+    if depth < S::zero() {
+        return None;
+    }
+    match *strategy {
+        CollisionStrategy::CollisionOnly => Some(Contact::new(CollisionStrategy::CollisionOnly)),
+        CollisionStrategy::FullResolution => Some(Contact::new_with_point(
+            CollisionStrategy::FullResolution,
+            normal,
+            depth,
+            deepest + normal * depth,
+        )),
+    }
+}
+
+fn ray_intersection<S, P>(normal: P::Diff, offset: S, ray: &Ray<S, P, P::Diff>) -> Option<P>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+{
+    let distance = normal.dot(ray.origin.to_vec()) - offset;
+    if distance <= S::zero() {
+        return Some(ray.origin);
+    }
+    let speed = normal.dot(ray.direction);
+    if speed >= S::zero() {
+        return None;
+    }
+    Some(ray.origin + ray.direction * (-distance / speed))
+}
+
+impl<S> Contains<Point2<S>> for HalfPlane<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        self.normal.dot(point.to_vec()) <= self.offset
+    }
+}
+
+impl<S> Sdf<Point2<S>> for HalfPlane<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn signed_distance(&self, point: &Point2<S>) -> S {
+        self.normal.dot(point.to_vec()) - self.offset
+    }
+}
+
+impl<S> Discrete<Ray2<S>> for HalfPlane<S>
+where
+    S: BaseFloat,
+{
+    /// Ray must be in object space
+    fn intersects(&self, ray: &Ray2<S>) -> bool {
+        ray_intersection(self.normal, self.offset, ray).is_some()
+    }
+}
+
+impl<S> Continuous<Ray2<S>> for HalfPlane<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point2<S>;
+
+    /// Ray must be in object space. Will return the ray origin if it is inside the half-plane.
+    fn intersection(&self, ray: &Ray2<S>) -> Option<Point2<S>> {
+        ray_intersection(self.normal, self.offset, ray)
+    }
+}
+
+impl<S> Contains<Point3<S>> for HalfSpace<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        self.normal.dot(point.to_vec()) <= self.offset
+    }
+}
+
+impl<S> Sdf<Point3<S>> for HalfSpace<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn signed_distance(&self, point: &Point3<S>) -> S {
+        self.normal.dot(point.to_vec()) - self.offset
+    }
+}
+
+impl<S> Discrete<Ray3<S>> for HalfSpace<S>
+where
+    S: BaseFloat,
+{
+    /// Ray must be in object space
+    fn intersects(&self, ray: &Ray3<S>) -> bool {
+        ray_intersection(self.normal, self.offset, ray).is_some()
+    }
+}
+
+impl<S> Continuous<Ray3<S>> for HalfSpace<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point3<S>;
+
+    /// Ray must be in object space. Will return the ray origin if it is inside the half-space.
+    fn intersection(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
+        ray_intersection(self.normal, self.offset, ray)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{
+        Basis2, Decomposed, Point2, Point3, Quaternion, Rad, Rotation2, Vector2, Vector3,
+    };
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::primitive::{Circle, Cuboid, Rectangle, Sphere};
+
+    #[test]
+    fn test_half_plane_contains() {
+        let ground = HalfPlane::new(Vector2::new(0., 1.), 2.);
+        assert!(ground.contains(&Point2::new(100., 1.)));
+        assert!(ground.contains(&Point2::new(-100., 2.)));
+        assert!(!ground.contains(&Point2::new(0., 2.5)));
+    }
+
+    #[test]
+    fn test_half_plane_from_point_normal() {
+        let wall = HalfPlane::from_point_normal(Point2::new(3., 5.), Vector2::new(-2., 0.));
+        assert_eq!(Vector2::new(-1., 0.), wall.normal);
+        assert_eq!(-3., wall.offset);
+        assert!(wall.contains(&Point2::new(4., 0.)));
+        assert!(!wall.contains(&Point2::new(2., 0.)));
+    }
+
+    #[test]
+    fn test_half_plane_ray() {
+        let ground = HalfPlane::new(Vector2::new(0., 1.), 0.);
+        let ray = Ray2::new(Point2::new(0., 10.), Vector2::new(1., -1.).normalize());
+        assert!(ground.intersects(&ray));
+        assert_ulps_eq!(
+            Point2::new(10., 0.),
+            ground.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+        let ray = Ray2::new(Point2::new(0., 10.), Vector2::new(1., 0.));
+        assert!(!ground.intersects(&ray));
+        assert_eq!(None, ground.intersection(&ray));
+        let ray = Ray2::new(Point2::new(0., -1.), Vector2::new(0., 1.));
+        assert_eq!(Some(Point2::new(0., -1.)), ground.intersection(&ray));
+    }
+
+    #[test]
+    fn test_half_plane_ray_transformed() {
+        let ground = HalfPlane::new(Vector2::new(0., 1.), 0.);
+        let ray = Ray2::new(Point2::new(0., 10.), Vector2::new(0., -1.));
+        let t = transform(0., 5., 0.);
+        assert_ulps_eq!(
+            Point2::new(0., 5.),
+            ground.intersection_transformed(&ray, &t).unwrap()
+        );
+        // flipped, so the region is above the boundary
+        let t = transform(0., 0., std::f32::consts::PI);
+        let ray = Ray2::new(Point2::new(0., -10.), Vector2::new(0., -1.));
+        assert!(!ground.intersects_transformed(&ray, &t));
+    }
+
+    #[test]
+    fn test_half_plane_primitive() {
+        let ground = HalfPlane::new(Vector2::new(0., 1.), 0.);
+        let circle = Circle::new(1.);
+        assert!(!ground.intersects_primitive(&circle, &transform(0., 1.5, 0.)));
+        assert!(ground.intersects_primitive(&circle, &transform(1000., 0.5, 0.)));
+        assert!(ground
+            .intersection_primitive(
+                &CollisionStrategy::FullResolution,
+                &circle,
+                &transform(0., 1.5, 0.)
+            )
+            .is_none());
+        let contact = ground
+            .intersection_primitive(
+                &CollisionStrategy::FullResolution,
+                &circle,
+                &transform(3., 0.75, 0.),
+            )
+            .unwrap();
+        assert_eq!(Vector2::new(0., 1.), contact.normal);
+        assert_ulps_eq!(0.25, contact.penetration_depth);
+        assert_ulps_eq!(Point2::new(3., 0.), contact.contact_point);
+    }
+
+    #[test]
+    fn test_half_plane_rotated_rectangle() {
+        let ground = HalfPlane::new(Vector2::new(0., 1.), 0.);
+        let rectangle = Rectangle::new(2., 2.);
+        // corner reaches down to 1.2 - sqrt(2)
+        let t = transform(0., 1.2, std::f32::consts::PI / 4.);
+        let contact = ground
+            .intersection_primitive(&CollisionStrategy::FullResolution, &rectangle, &t)
+            .unwrap();
+        assert_ulps_eq!(2f32.sqrt() - 1.2, contact.penetration_depth, epsilon = 1e-6);
+        let contact = ground
+            .intersection_primitive(&CollisionStrategy::CollisionOnly, &rectangle, &t)
+            .unwrap();
+        assert_eq!(CollisionStrategy::CollisionOnly, contact.strategy);
+    }
+
+    #[test]
+    fn test_half_space_ray() {
+        let ground = HalfSpace::new(Vector3::new(0., 1., 0.), 0.);
+        let ray = Ray3::new(Point3::new(1., 10., 2.), Vector3::new(0., -1., 0.));
+        assert!(ground.intersects(&ray));
+        assert_eq!(Some(Point3::new(1., 0., 2.)), ground.intersection(&ray));
+        let ray = Ray3::new(Point3::new(1., 10., 2.), Vector3::new(0., 1., 0.));
+        assert!(!ground.intersects(&ray));
+        assert!(ground.contains(&Point3::new(5., -1., 5.)));
+    }
+
+    #[test]
+    fn test_half_space_primitive() {
+        let ground =
+            HalfSpace::from_point_normal(Point3::new(0., 0., 0.), Vector3::new(0., 1., 0.));
+        let sphere = Sphere::new(1.);
+        let cuboid = Cuboid::new(2., 2., 2.);
+        let t = transform3(0., 0.5, 0.);
+        assert!(ground.intersects_primitive(&sphere, &t));
+        let contact = ground
+            .intersection_primitive(&CollisionStrategy::FullResolution, &cuboid, &t)
+            .unwrap();
+        assert_eq!(Vector3::new(0., 1., 0.), contact.normal);
+        assert_ulps_eq!(0.5, contact.penetration_depth);
+        assert_ulps_eq!(0., contact.contact_point.y);
+        assert!(!ground.intersects_primitive(&sphere, &transform3(0., 1.5, 0.)));
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+
+    fn transform3(dx: f32, dy: f32, dz: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::one(),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
@@ -0,0 +1,494 @@
+//! Height field primitive
+
+use std::cmp::Ordering;
+
+use cgmath::{BaseFloat, Point3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, CollisionStrategy, Contact, Ray3, Sphere};
+use crate::algorithm::minkowski::GJK3;
+use crate::prelude::*;
+use crate::primitive::util::{ray_triangle, MeshTriangle};
+
+/// Height field primitive, for terrain.
+///
+/// The height field is a regular grid of heights in the XZ plane, with the local origin at the
+/// first sample. Sample `(x, z)` is at `(x * cell_size, height, z * cell_size)`, and each grid
+/// cell is split into two triangles along the diagonal from `(x + 1, z)` to `(x, z + 1)`.
+///
+/// Ray tests walk the grid cells along the ray, and only test the triangles of the visited cells.
+/// Convex primitives can be tested against the triangles under their bounding box with
+/// [`intersects_primitive`](#method.intersects_primitive) and
+/// [`intersection_primitive`](#method.intersection_primitive).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HeightField<S> {
+    heights: Vec<S>,
+    columns: usize,
+    rows: usize,
+    cell_size: S,
+    min_height: S,
+    max_height: S,
+}
+
+impl<S> HeightField<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new height field.
+    ///
+    /// ## Parameters
+    ///
+    /// - `columns`: number of samples along the X axis
+    /// - `rows`: number of samples along the Z axis
+    /// - `heights`: the samples, row by row, so sample `(x, z)` is at index `z * columns + x`
+    /// - `cell_size`: distance between two neighbouring samples
+    ///
+    /// ## Panics
+    ///
+    /// If there are less than 2 columns or rows, or the number of heights does not match the grid
+    /// size.
+    pub fn new(columns: usize, rows: usize, heights: Vec<S>, cell_size: S) -> Self {
+        assert!(columns >= 2 && rows >= 2);
+        assert_eq!(columns * rows, heights.len());
+        let min_height = heights.iter().fold(S::infinity(), |m, h| m.min(*h));
+        let max_height = heights.iter().fold(S::neg_infinity(), |m, h| m.max(*h));
+        Self {
+            heights,
+            columns,
+            rows,
+            cell_size,
+            min_height,
+            max_height,
+        }
+    }
+
+    /// Get the number of samples along the X axis
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Get the number of samples along the Z axis
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Get the distance between two neighbouring samples
+    pub fn cell_size(&self) -> S {
+        self.cell_size
+    }
+
+    /// Get the height of the given sample
+    pub fn height(&self, x: usize, z: usize) -> S {
+        self.heights[z * self.columns + x]
+    }
+
+    /// Get the position of the given sample, in object space
+    pub fn point(&self, x: usize, z: usize) -> Point3<S> {
+        Point3::new(
+            S::from(x).unwrap() * self.cell_size,
+            self.height(x, z),
+            S::from(z).unwrap() * self.cell_size,
+        )
+    }
+
+    /// Get the two triangles of the given grid cell, in object space.
+    ///
+    /// The triangle with index `k` of cell `(x, z)` has the triangle index
+    /// `2 * (z * (columns - 1) + x) + k` in the results of the primitive queries.
+    pub fn cell_triangles(&self, x: usize, z: usize) -> [[Point3<S>; 3]; 2] {
+        let p00 = self.point(x, z);
+        let p10 = self.point(x + 1, z);
+        let p01 = self.point(x, z + 1);
+        let p11 = self.point(x + 1, z + 1);
+        [[p00, p01, p10], [p10, p01, p11]]
+    }
+
+    /// Check if a convex primitive intersects the height field.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `gjk`: GJK algorithm to use for the tests on each triangle
+    /// - `primitive`: the convex primitive
+    /// - `transform`: transform of the primitive, relative to the height field
+    pub fn intersects_primitive<P, T>(&self, gjk: &GJK3<S>, primitive: &P, transform: &T) -> bool
+    where
+        P: Primitive<Point = Point3<S>> + ComputeBound<Aabb3<S>>,
+        T: Transform<Point3<S>>,
+    {
+        let identity = T::one();
+        self.candidates(primitive, transform)
+            .into_iter()
+            .any(|(_, vertices)| {
+                gjk.intersect(&MeshTriangle { vertices }, &identity, primitive, transform)
+                    .is_some()
+            })
+    }
+
+    /// Compute the contacts between a convex primitive and the height field.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `gjk`: GJK algorithm to use for the tests on each triangle
+    /// - `strategy`: strategy to use for each triangle
+    /// - `primitive`: the convex primitive
+    /// - `transform`: transform of the primitive, relative to the height field
+    ///
+    /// ## Returns:
+    ///
+    /// A list of the intersecting triangle indices, with the contact for each triangle. The
+    /// triangle is the first shape in each contact.
+    pub fn intersection_primitive<P, T>(
+        &self,
+        gjk: &GJK3<S>,
+        strategy: &CollisionStrategy,
+        primitive: &P,
+        transform: &T,
+    ) -> Vec<(usize, Contact<Point3<S>>)>
+    where
+        P: Primitive<Point = Point3<S>> + ComputeBound<Aabb3<S>>,
+        T: Transform<Point3<S>>,
+    {
+        let identity = T::one();
+        self.candidates(primitive, transform)
+            .into_iter()
+            .filter_map(|(i, vertices)| {
+                gjk.intersection(
+                    strategy,
+                    &MeshTriangle { vertices },
+                    &identity,
+                    primitive,
+                    transform,
+                )
+                .map(|contact| (i, contact))
+            })
+            .collect()
+    }
+
+    /// Find the triangles in the grid cells under the bounding box of the primitive
+    fn candidates<P, T>(&self, primitive: &P, transform: &T) -> Vec<(usize, [Point3<S>; 3])>
+    where
+        P: ComputeBound<Aabb3<S>>,
+        T: Transform<Point3<S>>,
+    {
+        let bound: Aabb3<S> = primitive.compute_bound();
+        let bound = bound.transform_volume(transform);
+        if bound.min.y > self.max_height || bound.max.y < self.min_height {
+            return Vec::default();
+        }
+        let (x0, x1) = match self.cell_range(bound.min.x, bound.max.x, self.columns) {
+            Some(range) => range,
+            None => return Vec::default(),
+        };
+        let (z0, z1) = match self.cell_range(bound.min.z, bound.max.z, self.rows) {
+            Some(range) => range,
+            None => return Vec::default(),
+        };
+        let mut triangles = Vec::default();
+        for z in z0..=z1 {
+            for x in x0..=x1 {
+                let cell = z * (self.columns - 1) + x;
+                for (k, vertices) in self.cell_triangles(x, z).iter().enumerate() {
+                    triangles.push((cell * 2 + k, *vertices));
+                }
+            }
+        }
+        triangles
+    }
+
+    /// Get the range of cells overlapping the given interval along one axis, if any
+    fn cell_range(&self, min: S, max: S, samples: usize) -> Option<(usize, usize)> {
+        let last = samples - 2;
+        let extent = S::from(samples - 1).unwrap() * self.cell_size;
+        if max < S::zero() || min > extent {
+            return None;
+        }
+        let first = (min / self.cell_size).floor().max(S::zero());
+        let end = (max / self.cell_size).floor().max(S::zero());
+        Some((
+            first.to_usize().unwrap().min(last),
+            end.to_usize().unwrap().min(last),
+        ))
+    }
+
+    /// Walk the grid cells along the ray, and return the ray parameter of the first hit
+    fn ray_walk(&self, ray: &Ray3<S>) -> Option<S> {
+        let extent_x = S::from(self.columns - 1).unwrap() * self.cell_size;
+        let extent_z = S::from(self.rows - 1).unwrap() * self.cell_size;
+
+        // clip the ray against the grid in the XZ plane
+        let mut t_enter = S::zero();
+        let mut t_exit = S::infinity();
+        for &(origin, direction, extent) in &[
+            (ray.origin.x, ray.direction.x, extent_x),
+            (ray.origin.z, ray.direction.z, extent_z),
+        ] {
+            if direction.is_zero() {
+                if origin < S::zero() || origin > extent {
+                    return None;
+                }
+            } else {
+                let t1 = -origin / direction;
+                let t2 = (extent - origin) / direction;
+                t_enter = t_enter.max(t1.min(t2));
+                t_exit = t_exit.min(t1.max(t2));
+            }
+        }
+        if t_enter > t_exit {
+            return None;
+        }
+
+        // initialize the grid traversal at the entry point
+        let entry = ray.origin + ray.direction * t_enter;
+        let last_x = (self.columns - 2) as isize;
+        let last_z = (self.rows - 2) as isize;
+        let cell_of = |v: S, last: isize| {
+            ((v / self.cell_size).floor().to_isize().unwrap_or(0))
+                .max(0)
+                .min(last)
+        };
+        let mut x = cell_of(entry.x, last_x);
+        let mut z = cell_of(entry.z, last_z);
+        let step = |origin: S, direction: S, cell: isize| -> (isize, S, S) {
+            if direction > S::zero() {
+                let boundary = S::from(cell + 1).unwrap() * self.cell_size;
+                (
+                    1,
+                    (boundary - origin) / direction,
+                    self.cell_size / direction,
+                )
+            } else if direction < S::zero() {
+                let boundary = S::from(cell).unwrap() * self.cell_size;
+                (
+                    -1,
+                    (boundary - origin) / direction,
+                    -self.cell_size / direction,
+                )
+            } else {
+                (0, S::infinity(), S::infinity())
+            }
+        };
+        let (step_x, mut t_max_x, delta_x) = step(ray.origin.x, ray.direction.x, x);
+        let (step_z, mut t_max_z, delta_z) = step(ray.origin.z, ray.direction.z, z);
+        let cell_hit = |x: isize, z: isize| {
+            self.cell_triangles(x as usize, z as usize)
+                .iter()
+                .filter_map(|triangle| ray_triangle(ray, triangle))
+                .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        };
+
+        // a vertical ray only passes through the cell under its origin
+        if step_x == 0 && step_z == 0 {
+            return cell_hit(x, z);
+        }
+
+        loop {
+            let hit = cell_hit(x, z);
+            if hit.is_some() {
+                return hit;
+            }
+
+            if t_max_x < t_max_z {
+                if t_max_x > t_exit {
+                    return None;
+                }
+                x += step_x;
+                t_max_x += delta_x;
+            } else {
+                if t_max_z > t_exit {
+                    return None;
+                }
+                z += step_z;
+                t_max_z += delta_z;
+            }
+            if x < 0 || x > last_x || z < 0 || z > last_z {
+                return None;
+            }
+        }
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for HeightField<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        Aabb3::new(
+            Point3::new(S::zero(), self.min_height, S::zero()),
+            Point3::new(
+                S::from(self.columns - 1).unwrap() * self.cell_size,
+                self.max_height,
+                S::from(self.rows - 1).unwrap() * self.cell_size,
+            ),
+        )
+    }
+}
+
+/// Sphere around the bounding box, since the shape has no natural center
+impl<S> ComputeBound<Sphere<S>> for HeightField<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere::from(ComputeBound::<Aabb3<S>>::compute_bound(self))
+    }
+}
+
+impl<S> Discrete<Ray3<S>> for HeightField<S>
+where
+    S: BaseFloat,
+{
+    /// Ray must be in object space
+    fn intersects(&self, ray: &Ray3<S>) -> bool {
+        self.ray_walk(ray).is_some()
+    }
+}
+
+impl<S> Continuous<Ray3<S>> for HeightField<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point3<S>;
+
+    /// Ray must be in object space. Will return the first intersection point along the ray.
+    fn intersection(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
+        self.ray_walk(ray).map(|t| ray.origin + ray.direction * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::primitive::{Cuboid, Sphere};
+
+    /// 5x5 samples, flat at 0, with a peak of height 2 in the middle
+    fn terrain() -> HeightField<f32> {
+        let mut heights = vec![0.; 25];
+        heights[12] = 2.;
+        HeightField::new(5, 5, heights, 1.)
+    }
+
+    #[test]
+    fn test_heightfield_bound() {
+        let field = terrain();
+        assert_eq!(
+            Aabb3::new(Point3::new(0., 0., 0.), Point3::new(4., 2., 4.)),
+            field.compute_bound()
+        );
+        assert_eq!(2., field.height(2, 2));
+        assert_eq!(Point3::new(2., 2., 2.), field.point(2, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_heightfield_wrong_size() {
+        HeightField::new(5, 5, vec![0.; 24], 1.);
+    }
+
+    #[test]
+    fn test_heightfield_ray_down() {
+        let field = terrain();
+        let ray = Ray3::new(Point3::new(0.5, 10., 3.5), Vector3::new(0., -1., 0.));
+        assert!(field.intersects(&ray));
+        assert_ulps_eq!(Point3::new(0.5, 0., 3.5), field.intersection(&ray).unwrap());
+        // on the slope of the peak
+        let ray = Ray3::new(Point3::new(1.9, 10., 1.9), Vector3::new(0., -1., 0.));
+        assert_ulps_eq!(
+            Point3::new(1.9, 1.6, 1.9),
+            field.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+        let ray = Ray3::new(Point3::new(5.5, 10., 2.), Vector3::new(0., -1., 0.));
+        assert!(!field.intersects(&ray));
+    }
+
+    #[test]
+    fn test_heightfield_ray_walk() {
+        let field = terrain();
+        // low horizontal ray, hits the side of the peak
+        let ray = Ray3::new(Point3::new(-3., 1., 1.9), Vector3::new(1., 0., 0.));
+        let p = field.intersection(&ray).unwrap();
+        assert_ulps_eq!(1.6, p.x, epsilon = 1e-5);
+        assert_ulps_eq!(1., p.y);
+        // high horizontal ray passes over the terrain
+        let ray = Ray3::new(Point3::new(-3., 2.5, 1.9), Vector3::new(1., 0., 0.));
+        assert!(!field.intersects(&ray));
+        // diagonal ray, coming down on the far side
+        let ray = Ray3::new(
+            Point3::new(-1., 3., -1.),
+            Vector3::new(1., -0.5, 1.).normalize(),
+        );
+        let p = field.intersection(&ray).unwrap();
+        assert!(p.x > 0. && p.x < 2.);
+        assert_ulps_eq!(p.x, p.z, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_heightfield_ray_from_below() {
+        let field = terrain();
+        let ray = Ray3::new(Point3::new(3.5, -1., 0.5), Vector3::new(0., 1., 0.));
+        assert_ulps_eq!(Point3::new(3.5, 0., 0.5), field.intersection(&ray).unwrap());
+    }
+
+    #[test]
+    fn test_heightfield_ray_vertical_miss() {
+        let field = terrain();
+        // pointing up from above the terrain, inside the grid
+        let ray = Ray3::new(Point3::new(0.5, 10., 3.5), Vector3::new(0., 1., 0.));
+        assert!(!field.intersects(&ray));
+        assert_eq!(None, field.intersection(&ray));
+    }
+
+    #[test]
+    fn test_heightfield_ray_transformed() {
+        let field = terrain();
+        let ray = Ray3::new(Point3::new(0.5, 10., 3.5), Vector3::new(0., -1., 0.));
+        let t = transform(0., -2., 0.);
+        assert_ulps_eq!(
+            Point3::new(0.5, -2., 3.5),
+            field.intersection_transformed(&ray, &t).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_heightfield_primitive() {
+        let field = terrain();
+        let gjk = GJK3::new();
+        let sphere = Sphere::new(0.5);
+        assert!(field.intersects_primitive(&gjk, &sphere, &transform(0.5, 0.4, 0.5)));
+        assert!(!field.intersects_primitive(&gjk, &sphere, &transform(0.5, 0.6, 0.5)));
+        assert!(field.intersects_primitive(&gjk, &sphere, &transform(2., 2.4, 2.)));
+        assert!(!field.intersects_primitive(&gjk, &sphere, &transform(10., 0., 10.)));
+    }
+
+    #[test]
+    fn test_heightfield_contacts() {
+        let field = terrain();
+        let gjk = GJK3::new();
+        let cuboid = Cuboid::new(0.5, 0.5, 0.5);
+        let contacts = field.intersection_primitive(
+            &gjk,
+            &CollisionStrategy::FullResolution,
+            &cuboid,
+            &transform(3.5, 0.2, 0.5),
+        );
+        let mut triangles: Vec<usize> = contacts.iter().map(|(i, _)| *i).collect();
+        triangles.sort();
+        assert_eq!(vec![6, 7], triangles);
+        for (_, contact) in &contacts {
+            assert_ulps_eq!(0.05, contact.penetration_depth, epsilon = 1e-3);
+        }
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, dz: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(0.)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
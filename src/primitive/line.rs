@@ -1,16 +1,20 @@
-use cgmath::{BaseFloat, InnerSpace, Point2, Transform, Vector2};
+use cgmath::{BaseFloat, EuclideanSpace, InnerSpace, Transform};
 
-use crate::Aabb2;
-use crate::line::Line2;
-use crate::traits::{ComputeBound, Primitive};
+use crate::{Aabb2, Aabb3};
+use crate::line::{Line, Line2, Line3};
+use crate::primitive::util::closest_point_on_segment;
+use crate::traits::{ClosestPoint, ComputeBound, Primitive};
+use crate::volume::{Circle, Sphere};
 
-impl<S> Primitive for Line2<S>
+impl<S, V, P> Primitive for Line<S, V, P>
 where
     S: BaseFloat,
+    V: InnerSpace<Scalar = S>,
+    P: EuclideanSpace<Scalar = S, Diff = V>,
 {
-    type Point = Point2<S>;
+    type Point = P;
 
-    fn support_point<T>(&self, direction: &Vector2<S>, transform: &T) -> Self::Point
+    fn support_point<T>(&self, direction: &V, transform: &T) -> Self::Point
     where
         T: Transform<Self::Point>,
     {
@@ -24,6 +28,21 @@ where
     }
 }
 
+impl<S, V, P> ClosestPoint<P> for Line<S, V, P>
+where
+    S: BaseFloat,
+    V: InnerSpace<Scalar = S>,
+    P: EuclideanSpace<Scalar = S, Diff = V>,
+{
+    /// The line has no interior, so the point is never inside
+    fn closest_point(&self, point: &P) -> (P, bool) {
+        (
+            closest_point_on_segment(self.origin, self.dest, *point),
+            false,
+        )
+    }
+}
+
 impl<S> ComputeBound<Aabb2<S>> for Line2<S>
 where
     S: BaseFloat,
@@ -33,13 +52,49 @@ where
     }
 }
 
+impl<S> ComputeBound<Circle<S>> for Line2<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: self.origin.midpoint(self.dest),
+            radius: (self.dest - self.origin).magnitude() / (S::one() + S::one()),
+        }
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for Line3<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        Aabb3::new(self.origin, self.dest)
+    }
+}
+
+impl<S> ComputeBound<Sphere<S>> for Line3<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere {
+            center: self.origin.midpoint(self.dest),
+            radius: (self.dest - self.origin).magnitude() / (S::one() + S::one()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use crate::algorithm::minkowski::GJK2;
-    use cgmath::{Basis2, Decomposed, Rad, Rotation2};
-    use crate::primitive::Rectangle;
+    use crate::Ray2;
+    use crate::algorithm::minkowski::{GJK2, GJK3};
+    use crate::prelude::*;
+    use cgmath::{Basis2, Decomposed, Point2, Point3, Quaternion, Rad, Rotation2, Rotation3};
+    use cgmath::{Vector2, Vector3};
+    use crate::primitive::{Cuboid, Rectangle};
 
     fn transform(x: f32, y: f32, angle: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
         Decomposed {
@@ -49,6 +104,19 @@ mod tests {
         }
     }
 
+    fn transform_3d(
+        x: f32,
+        y: f32,
+        z: f32,
+        angle_z: f32,
+    ) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            disp: Vector3::new(x, y, z),
+            rot: Quaternion::from_angle_z(Rad(angle_z)),
+            scale: 1.,
+        }
+    }
+
     #[test]
     fn test_line_rectangle_intersect() {
         let line = Line2::new(Point2::new(0., -1.), Point2::new(0., 1.));
@@ -61,4 +129,65 @@ mod tests {
                 .is_some()
         );
     }
+
+    #[test]
+    fn test_line_support() {
+        let line = Line2::new(Point2::new(-1., 0.), Point2::new(1., 0.));
+        let t = transform(0., 5., 0.);
+        assert_eq!(
+            Point2::new(1., 5.),
+            line.support_point(&Vector2::new(1., 1.), &t)
+        );
+        assert_eq!(
+            Point2::new(-1., 5.),
+            line.support_point(&Vector2::new(-1., 1.), &t)
+        );
+    }
+
+    #[test]
+    fn test_line_bound() {
+        let line = Line2::new(Point2::new(1., -1.), Point2::new(-1., 2.));
+        assert_eq!(
+            Aabb2::new(Point2::new(-1., -1.), Point2::new(1., 2.)),
+            line.compute_bound()
+        );
+    }
+
+    #[test]
+    fn test_line_ray_transformed() {
+        let line = Line2::new(Point2::new(0., -1.), Point2::new(0., 1.));
+        let ray = Ray2::new(Point2::new(-5., 0.), Vector2::new(1., 0.));
+        let t = transform(2., 0., 0.);
+        assert!(line.intersects_transformed(&ray, &t));
+        assert_eq!(
+            Some(Point2::new(2., 0.)),
+            line.intersection_transformed(&ray, &t)
+        );
+        let t = transform(0., 2., std::f32::consts::PI / 2.);
+        assert!(!line.intersects_transformed(&ray, &t));
+    }
+
+    #[test]
+    fn test_line3_bound() {
+        let line = Line3::new(Point3::new(1., -1., 0.), Point3::new(-1., 1., 2.));
+        assert_eq!(
+            Aabb3::new(Point3::new(-1., -1., 0.), Point3::new(1., 1., 2.)),
+            line.compute_bound()
+        );
+        let sphere: Sphere<f32> = line.compute_bound();
+        assert_eq!(Point3::new(0., 0., 1.), sphere.center);
+        assert_eq!(3f32.sqrt(), sphere.radius);
+    }
+
+    #[test]
+    fn test_line3_cuboid_intersect() {
+        let line = Line3::new(Point3::new(0., -1., 0.), Point3::new(0., 1., 0.));
+        let cuboid = Cuboid::new(1., 0.2, 1.);
+        let gjk = GJK3::new();
+        let line_transform = transform_3d(1., 0., 0., 0.);
+        let near = transform_3d(1.1, 0., 0., 0.);
+        let far = transform_3d(2., 0., 0., 0.);
+        assert!(gjk.intersect(&line, &line_transform, &cuboid, &near).is_some());
+        assert!(gjk.intersect(&line, &line_transform, &cuboid, &far).is_none());
+    }
 }
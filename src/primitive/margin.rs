@@ -0,0 +1,180 @@
+//! Collision margin wrapper
+
+use cgmath::BaseFloat;
+use cgmath::prelude::*;
+
+use crate::Aabb;
+use crate::prelude::*;
+use crate::volume::{Circle, Sphere};
+
+/// Wrapper that adds a collision margin around a primitive.
+///
+/// The wrapped shape is grown by `margin` in all directions, which is the same as the Minkowski
+/// sum of the primitive and a sphere (or circle) with radius `margin`, and the bounding volumes
+/// are grown accordingly. Margins make GJK and EPA more robust for thin shapes, because the
+/// shapes get rounded corners and never degenerate to zero thickness.
+///
+/// To use the same margin for all primitives in a query, see
+/// [`GJK::with_margin`](../algorithm/minkowski/struct.GJK.html#method.with_margin), which also
+/// avoids EPA for contacts where only the margins overlap.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WithMargin<P, S> {
+    /// Wrapped primitive
+    pub primitive: P,
+    /// Margin to add around the primitive
+    pub margin: S,
+}
+
+impl<P, S> WithMargin<P, S> {
+    /// Create a new primitive with the given collision margin
+    pub fn new(primitive: P, margin: S) -> Self {
+        Self { primitive, margin }
+    }
+}
+
+impl<P, S> Primitive for WithMargin<P, S>
+where
+    S: BaseFloat,
+    P: Primitive,
+    P::Point: EuclideanSpace<Scalar = S>,
+    <P::Point as EuclideanSpace>::Diff: InnerSpace,
+{
+    type Point = P::Point;
+
+    fn support_point<T>(
+        &self,
+        direction: &<P::Point as EuclideanSpace>::Diff,
+        transform: &T,
+    ) -> P::Point
+    where
+        T: Transform<P::Point>,
+    {
+        if self.margin.is_zero() {
+            return self.primitive.support_point(direction, transform);
+        }
+        let local_direction = transform.inverse_transform_vector(*direction).unwrap();
+        let p = self.primitive.support_point(&local_direction, &T::one());
+        if local_direction.magnitude2().is_zero() {
+            transform.transform_point(p)
+        } else {
+            transform.transform_point(p + local_direction.normalize_to(self.margin))
+        }
+    }
+}
+
+impl<P, A> ComputeBound<A> for WithMargin<P, A::Scalar>
+where
+    P: ComputeBound<A>,
+    A: Aabb + Bound,
+{
+    fn compute_bound(&self) -> A {
+        self.primitive
+            .compute_bound()
+            .add_margin(A::Diff::from_value(self.margin))
+    }
+}
+
+impl<P, S> ComputeBound<Sphere<S>> for WithMargin<P, S>
+where
+    S: BaseFloat,
+    P: ComputeBound<Sphere<S>>,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        let sphere: Sphere<S> = self.primitive.compute_bound();
+        Sphere {
+            center: sphere.center,
+            radius: sphere.radius + self.margin,
+        }
+    }
+}
+
+impl<P, S> ComputeBound<Circle<S>> for WithMargin<P, S>
+where
+    S: BaseFloat,
+    P: ComputeBound<Circle<S>>,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        let circle: Circle<S> = self.primitive.compute_bound();
+        Circle {
+            center: circle.center,
+            radius: circle.radius + self.margin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Basis2, Decomposed, Point2, Point3, Rad, Rotation2, Vector2};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::{Aabb2, CollisionStrategy};
+    use crate::algorithm::minkowski::GJK2;
+    use crate::primitive::{Circle, Rectangle};
+    use crate::primitive::Sphere as SpherePrimitive;
+
+    #[test]
+    fn test_margin_support() {
+        let rectangle = WithMargin::new(Rectangle::new(4., 2.), 0.5);
+        let t = transform(0., 0., 0.);
+        let d = 0.5 * std::f32::consts::FRAC_1_SQRT_2;
+        assert_ulps_eq!(
+            Point2::new(2. + d, 1. + d),
+            rectangle.support_point(&Vector2::new(1., 1.), &t)
+        );
+        let t = transform(10., 0., std::f32::consts::PI);
+        assert_ulps_eq!(
+            Point2::new(12. + d, 1. + d),
+            rectangle.support_point(&Vector2::new(1., 1.), &t),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_margin_bound() {
+        let rectangle = WithMargin::new(Rectangle::new(4., 2.), 0.5);
+        let bound: Aabb2<f32> = rectangle.compute_bound();
+        assert_eq!(
+            Aabb2::new(Point2::new(-2.5, -1.5), Point2::new(2.5, 1.5)),
+            bound
+        );
+        let sphere = WithMargin::new(SpherePrimitive::new(1.), 0.5);
+        let bound: Sphere<f32> = sphere.compute_bound();
+        assert_eq!(Point3::new(0., 0., 0.), bound.center);
+        assert_ulps_eq!(1.5, bound.radius);
+    }
+
+    #[test]
+    fn test_margin_thin_shape() {
+        // a rectangle with zero thickness
+        let wall = WithMargin::new(Rectangle::new(10., 0.), 0.1);
+        let circle = Circle::new(1.);
+        let gjk = GJK2::new();
+        let t = transform(0., 0., 0.);
+        assert!(gjk
+            .intersect(&wall, &t, &circle, &transform(0., 1.2, 0.))
+            .is_none());
+        let contact = gjk
+            .intersection(
+                &CollisionStrategy::FullResolution,
+                &wall,
+                &t,
+                &circle,
+                &transform(0., 1.05, 0.),
+            )
+            .unwrap();
+        assert_ulps_eq!(0.05, contact.penetration_depth, epsilon = 1e-3);
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Minkowski sum of two primitives
+
+use cgmath::{BaseFloat, Point2, Point3};
+use cgmath::prelude::*;
+
+use crate::Aabb;
+use crate::prelude::*;
+use crate::volume::{Circle, Sphere};
+
+/// Minkowski sum of two primitives, in the local space of the sum.
+///
+/// The support point of the sum is the sum of the support points of the two primitives, so
+/// dilated shapes can be used without generating vertices. For example, the sum of a `Rectangle`
+/// and a `Circle` is a rectangle with rounded corners.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MinkowskiSum<A, B> {
+    /// First primitive
+    pub a: A,
+    /// Second primitive
+    pub b: B,
+}
+
+impl<A, B> MinkowskiSum<A, B> {
+    /// Create a new Minkowski sum of two primitives
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+}
+
+impl<A, B> Primitive for MinkowskiSum<A, B>
+where
+    A: Primitive,
+    B: Primitive<Point = A::Point>,
+{
+    type Point = A::Point;
+
+    fn support_point<T>(
+        &self,
+        direction: &<A::Point as EuclideanSpace>::Diff,
+        transform: &T,
+    ) -> A::Point
+    where
+        T: Transform<A::Point>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let a = self.a.support_point(&direction, &T::one());
+        let b = self.b.support_point(&direction, &T::one());
+        transform.transform_point(a + b.to_vec())
+    }
+}
+
+impl<A, B, V> ComputeBound<V> for MinkowskiSum<A, B>
+where
+    A: ComputeBound<V>,
+    B: ComputeBound<V>,
+    V: Aabb + Bound,
+{
+    fn compute_bound(&self) -> V {
+        let a: V = self.a.compute_bound();
+        let b: V = self.b.compute_bound();
+        V::new(a.min() + b.min().to_vec(), a.max() + b.max().to_vec())
+    }
+}
+
+impl<A, B, S> ComputeBound<Sphere<S>> for MinkowskiSum<A, B>
+where
+    S: BaseFloat,
+    A: ComputeBound<Sphere<S>>,
+    B: ComputeBound<Sphere<S>>,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        let a: Sphere<S> = self.a.compute_bound();
+        let b: Sphere<S> = self.b.compute_bound();
+        Sphere {
+            center: Point3::from_vec(a.center.to_vec() + b.center.to_vec()),
+            radius: a.radius + b.radius,
+        }
+    }
+}
+
+impl<A, B, S> ComputeBound<Circle<S>> for MinkowskiSum<A, B>
+where
+    S: BaseFloat,
+    A: ComputeBound<Circle<S>>,
+    B: ComputeBound<Circle<S>>,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        let a: Circle<S> = self.a.compute_bound();
+        let b: Circle<S> = self.b.compute_bound();
+        Circle {
+            center: Point2::from_vec(a.center.to_vec() + b.center.to_vec()),
+            radius: a.radius + b.radius,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{
+        Basis2, Decomposed, Point2, Point3, Quaternion, Rad, Rotation2, Vector2, Vector3,
+    };
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::{Aabb2, Aabb3};
+    use crate::algorithm::minkowski::GJK2;
+    use crate::primitive::{Circle, Cuboid, Rectangle};
+    use crate::primitive::Sphere as SpherePrimitive;
+
+    #[test]
+    fn test_sum_support() {
+        let sum = MinkowskiSum::new(Rectangle::new(4., 2.), Circle::new(1.));
+        let t = transform(0., 0., 0.);
+        let d = std::f32::consts::FRAC_1_SQRT_2;
+        assert_ulps_eq!(
+            Point2::new(2. + d, 1. + d),
+            sum.support_point(&Vector2::new(1., 1.), &t)
+        );
+        assert_ulps_eq!(
+            Point2::new(-2. - d, -1. - d),
+            sum.support_point(&Vector2::new(-1., -1.), &t)
+        );
+    }
+
+    #[test]
+    fn test_sum_support_transformed() {
+        let sum = MinkowskiSum::new(Rectangle::new(4., 2.), Circle::new(1.));
+        let t = transform(10., 0., std::f32::consts::PI / 2.);
+        let p = sum.support_point(&Vector2::new(1., 1.), &t);
+        let d = std::f32::consts::FRAC_1_SQRT_2;
+        assert_ulps_eq!(Point2::new(11. + d, 2. + d), p, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_sum_bound() {
+        let sum = MinkowskiSum::new(Rectangle::new(4., 2.), Circle::new(1.));
+        let bound: Aabb2<f32> = sum.compute_bound();
+        assert_eq!(
+            Aabb2::new(Point2::new(-3., -2.), Point2::new(3., 2.)),
+            bound
+        );
+        let sum = MinkowskiSum::new(Cuboid::new(2., 2., 2.), SpherePrimitive::new(1.));
+        let bound: Aabb3<f32> = sum.compute_bound();
+        assert_eq!(
+            Aabb3::new(Point3::new(-2., -2., -2.), Point3::new(2., 2., 2.)),
+            bound
+        );
+        let sum = MinkowskiSum::new(SpherePrimitive::new(2.), SpherePrimitive::new(1.));
+        let bound: Sphere<f32> = sum.compute_bound();
+        assert_eq!(Point3::new(0., 0., 0.), bound.center);
+        assert_ulps_eq!(3., bound.radius);
+    }
+
+    #[test]
+    fn test_sum_support_3d() {
+        let sum = MinkowskiSum::new(Cuboid::new(2., 2., 2.), SpherePrimitive::new(1.));
+        let t = Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(0.)),
+            disp: Vector3::new(0., 0., 0.),
+        };
+        assert_ulps_eq!(
+            Point3::new(1., 1., 2.),
+            sum.support_point(&Vector3::new(0., 0., 1.), &t)
+        );
+    }
+
+    #[test]
+    fn test_sum_gjk() {
+        let sum = MinkowskiSum::new(Rectangle::new(4., 2.), Circle::new(1.));
+        let circle = Circle::new(1.);
+        let gjk = GJK2::new();
+        let t = transform(0., 0., 0.);
+        assert!(gjk
+            .intersect(&sum, &t, &circle, &transform(3.9, 0., 0.))
+            .is_some());
+        // the corner is rounded
+        assert!(gjk
+            .intersect(&sum, &t, &circle, &transform(3.9, 2.9, 0.))
+            .is_none());
+        assert!(gjk
+            .intersect(&sum, &t, &circle, &transform(3.9, 1., 0.))
+            .is_some());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+}
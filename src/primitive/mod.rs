@@ -1,31 +1,75 @@
 //! Collision primitives
 
+pub use self::annulus::Annulus;
 pub use self::capsule::Capsule;
+pub use self::capsule2::Capsule2;
 pub use self::circle::Circle;
+pub use self::compound::{Compound, Compound2, Compound3};
+pub use self::concave_polygon::Polygon;
+pub use self::cone::Cone;
 pub use self::cuboid::{Cube, Cuboid};
 pub use self::cylinder::Cylinder;
+pub use self::ellipse::Ellipse;
+pub use self::ellipsoid::Ellipsoid;
+pub use self::extrusion::Extrusion;
+pub use self::halfspace::{HalfPlane, HalfSpace};
+pub use self::heightfield::HeightField;
+pub use self::margin::WithMargin;
+pub use self::minkowski_sum::MinkowskiSum;
 pub use self::particle::*;
-pub use self::polygon::ConvexPolygon;
+pub use self::polygon::{ConvexPolygon, PolygonError};
 pub use self::polyhedron::ConvexPolyhedron;
+pub use self::polyline::Polyline;
 pub use self::primitive2::Primitive2;
 pub use self::primitive3::Primitive3;
 pub use self::quad::Quad;
 pub use self::rectangle::{Rectangle, Square};
+pub use self::revolution::Revolution;
+pub use self::rounded_cuboid::RoundedCuboid;
+pub use self::scaled::Scaled;
+pub use self::sdf::SdfPrimitive;
+pub use self::sector::CircleSector;
 pub use self::sphere::Sphere;
+pub use self::torus::Torus;
+pub use self::triangle::Triangle;
+pub use self::trimesh::TriMesh;
+pub use self::wedge::Wedge;
 
+mod annulus;
 mod circle;
 mod cylinder;
 mod capsule;
+mod capsule2;
+mod compound;
+mod concave_polygon;
+mod cone;
 mod cuboid;
+mod ellipse;
+mod ellipsoid;
+mod extrusion;
+mod halfspace;
+mod heightfield;
 mod line;
+mod margin;
+mod minkowski_sum;
 mod particle;
 mod polygon;
+mod polyline;
 mod polyhedron;
 mod primitive2;
 mod primitive3;
 mod rectangle;
 mod quad;
+mod revolution;
+mod rounded_cuboid;
+mod scaled;
+mod sdf;
+mod sector;
 mod sphere;
+mod torus;
+mod triangle;
+mod trimesh;
+mod wedge;
 
 pub(crate) mod util;
 
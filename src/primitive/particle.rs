@@ -6,8 +6,9 @@ use std::ops::Range;
 use cgmath::{BaseFloat, Point2, Point3};
 use cgmath::prelude::*;
 
-use crate::Ray;
+use crate::{Aabb2, Aabb3, Ray};
 use crate::prelude::*;
+use crate::volume::{Circle, Sphere};
 
 /// Represents a particle in space.
 ///
@@ -37,6 +38,75 @@ pub type Particle2<S> = Particle<Point2<S>>;
 /// 3D particle
 pub type Particle3<S> = Particle<Point3<S>>;
 
+impl<P> Primitive for Particle<P>
+where
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+{
+    type Point = P;
+
+    /// The particle has no extent, so the support point is always the particle position.
+    fn support_point<T>(&self, _: &P::Diff, transform: &T) -> P
+    where
+        T: Transform<P>,
+    {
+        transform.transform_point(P::origin())
+    }
+}
+
+impl<P> ClosestPoint<P> for Particle<P>
+where
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+{
+    /// The particle has no interior, so the point is never inside
+    fn closest_point(&self, _: &P) -> (P, bool) {
+        (P::origin(), false)
+    }
+}
+
+impl<S> ComputeBound<Aabb2<S>> for Particle2<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb2<S> {
+        Aabb2::zero()
+    }
+}
+
+impl<S> ComputeBound<Circle<S>> for Particle2<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: S::zero(),
+        }
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for Particle3<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        Aabb3::zero()
+    }
+}
+
+impl<S> ComputeBound<Sphere<S>> for Particle3<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere {
+            center: Point3::origin(),
+            radius: S::zero(),
+        }
+    }
+}
+
 impl<P, C> Discrete<(Particle<P>, Range<P>)> for C
 where
     C: Continuous<Ray<P::Scalar, P, P::Diff>, Result = P>,
@@ -166,7 +236,65 @@ mod tests {
     use approx::assert_ulps_eq;
 
     use super::*;
-    use crate::primitive::Circle;
+    use crate::algorithm::minkowski::GJK2;
+    use crate::primitive::{Circle, Rectangle};
+
+    #[test]
+    fn test_support_point() {
+        let particle = Particle2::<f32>::new();
+        let t = transform(5., 3., 1.);
+        assert_ulps_eq!(
+            Point2::new(5., 3.),
+            particle.support_point(&Vector2::new(1., 1.), &t)
+        );
+        assert_ulps_eq!(
+            Point2::new(5., 3.),
+            particle.support_point(&Vector2::new(-1., 0.), &t)
+        );
+    }
+
+    #[test]
+    fn test_bound() {
+        let particle = Particle2::<f32>::new();
+        assert_eq!(Aabb2::zero(), particle.compute_bound());
+        let particle = Particle3::<f32>::new();
+        assert_eq!(Aabb3::zero(), particle.compute_bound());
+        let sphere: Sphere<f32> = particle.compute_bound();
+        assert_eq!(0., sphere.radius);
+    }
+
+    #[test]
+    fn test_gjk_point_containment() {
+        let gjk = GJK2::new();
+        let particle = Particle2::new();
+        let rectangle = Rectangle::new(2., 2.);
+        let rectangle_transform = transform(0., 0., 0.3);
+        assert!(
+            gjk.intersect(
+                &particle,
+                &transform(0.5, 0.5, 0.),
+                &rectangle,
+                &rectangle_transform
+            ).is_some()
+        );
+        assert!(
+            gjk.intersect(
+                &particle,
+                &transform(1.5, 0., 0.),
+                &rectangle,
+                &rectangle_transform
+            ).is_none()
+        );
+        assert_ulps_eq!(
+            1.,
+            gjk.distance(
+                &particle,
+                &transform(2., 0., 0.),
+                &rectangle,
+                &transform(0., 0., 0.)
+            ).unwrap()
+        );
+    }
 
     #[test]
     fn test_discrete() {
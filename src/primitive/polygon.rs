@@ -1,27 +1,210 @@
 //! Convex polygon primitive
 
-use cgmath::{BaseFloat, Point2, Vector2};
+use std::error::Error;
+use std::fmt;
+
+use cgmath::{BaseFloat, Point2, Rad, Vector2};
 use cgmath::prelude::*;
+use cgmath::num_traits::NumCast;
 
 use crate::{Aabb2, Line2, Ray2};
 use crate::prelude::*;
-use crate::primitive::util::{get_bound, get_max_point};
+use crate::primitive::util::{
+    closest_point_on_polygon, cross_2d, get_bound, get_max_distance, get_max_point,
+    polygon_area_centroid, polygon_perimeter,
+};
+use crate::volume::Circle;
 
 /// Convex polygon primitive.
 ///
 /// Can contain any number of vertices, but a high number of vertices will
 /// affect performance of course. Vertices need to be in CCW order.
+///
+/// The AABB of the vertices is computed when the polygon is created, so the vertices can not be
+/// changed afterwards, create a new polygon instead.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct ConvexPolygon<S> {
-    /// Vertices of the convex polygon.
-    pub vertices: Vec<Point2<S>>,
+pub struct ConvexPolygon<S>
+where
+    S: BaseFloat,
+{
+    vertices: Vec<Point2<S>>,
+    bound: Aabb2<S>,
+}
+
+/// Problems found when validating a convex polygon, see
+/// [`ConvexPolygon::validate`](struct.ConvexPolygon.html#method.validate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonError {
+    /// The polygon has less than 3 vertices
+    TooFewVertices,
+    /// The vertex at the given index is equal to the next vertex
+    DuplicateVertex(usize),
+    /// The vertex at the given index is collinear with its neighbours
+    CollinearVertex(usize),
+    /// The vertices are in clockwise order
+    ClockwiseWinding,
+    /// The polygon is not convex at the vertex at the given index
+    NotConvex(usize),
 }
 
-impl<S> ConvexPolygon<S> {
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PolygonError::TooFewVertices => write!(f, "polygon has less than 3 vertices"),
+            PolygonError::DuplicateVertex(i) => write!(f, "vertex {} is a duplicate", i),
+            PolygonError::CollinearVertex(i) => {
+                write!(f, "vertex {} is collinear with its neighbours", i)
+            }
+            PolygonError::ClockwiseWinding => write!(f, "vertices are in clockwise order"),
+            PolygonError::NotConvex(i) => write!(f, "polygon is not convex at vertex {}", i),
+        }
+    }
+}
+
+impl Error for PolygonError {}
+
+impl<S> ConvexPolygon<S>
+where
+    S: BaseFloat,
+{
     /// Create a new convex polygon from the given vertices. Vertices need to be in CCW order.
+    ///
+    /// The vertices are not checked, use [`validate`](#method.validate) for that.
     pub fn new(vertices: Vec<Point2<S>>) -> Self {
-        Self { vertices }
+        Self {
+            bound: get_bound(vertices.iter()),
+            vertices,
+        }
+    }
+
+    /// Create a new regular convex polygon with `n_sides` vertices, all at distance `radius` from
+    /// the origin. The first vertex is on the positive X axis, and vertices are in CCW order.
+    ///
+    /// ## Panics
+    ///
+    /// If `n_sides` is less than 3.
+    pub fn regular(n_sides: usize, radius: S) -> Self {
+        assert!(n_sides >= 3, "a polygon needs at least 3 sides");
+        let step = Rad::full_turn() / <S as NumCast>::from(n_sides).unwrap();
+        let vertices = (0..n_sides)
+            .map(|i| {
+                let angle = step * <S as NumCast>::from(i).unwrap();
+                Point2::new(radius * Rad::cos(angle), radius * Rad::sin(angle))
+            })
+            .collect();
+        Self::new(vertices)
+    }
+
+    /// Vertices of the convex polygon.
+    pub fn vertices(&self) -> &[Point2<S>] {
+        &self.vertices
+    }
+
+    /// Check that the polygon is valid: it needs at least 3 vertices, in CCW order, without
+    /// duplicate or collinear vertices, and it must be convex.
+    ///
+    /// Invalid polygons will give incorrect support points and ray intersections.
+    pub fn validate(&self) -> Result<(), PolygonError> {
+        let n = self.vertices.len();
+        if n < 3 {
+            return Err(PolygonError::TooFewVertices);
+        }
+        if let Some(i) = (0..n).find(|&i| self.vertices[i] == self.vertices[(i + 1) % n]) {
+            return Err(PolygonError::DuplicateVertex(i));
+        }
+        if self.signed_area() < S::zero() {
+            return Err(PolygonError::ClockwiseWinding);
+        }
+        for i in 0..n {
+            let turn = cross_2d(
+                self.vertices[(i + n - 1) % n],
+                self.vertices[i],
+                self.vertices[(i + 1) % n],
+            );
+            if turn.is_zero() {
+                return Err(PolygonError::CollinearVertex(i));
+            }
+            if turn < S::zero() {
+                return Err(PolygonError::NotConvex(i));
+            }
+        }
+        // a self intersecting polygon can turn the same way at every vertex, so also check that
+        // all vertices are on the inside of every edge
+        for i in 0..n {
+            let (a, b) = (self.vertices[i], self.vertices[(i + 1) % n]);
+            if self.vertices.iter().any(|&p| cross_2d(a, b, p) < S::zero()) {
+                return Err(PolygonError::NotConvex(i));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reverse the order of the vertices if they are in clockwise order
+    pub fn fix_winding(&mut self) {
+        if self.signed_area() < S::zero() {
+            self.vertices.reverse();
+        }
+    }
+
+    /// Get the polygon with all vertices transformed by the given transform, which must not mirror
+    /// the polygon, to keep the vertices in CCW order
+    pub fn transformed<T>(&self, transform: &T) -> Self
+    where
+        T: Transform<Point2<S>>,
+    {
+        Self::new(
+            self.vertices
+                .iter()
+                .map(|v| transform.transform_point(*v))
+                .collect(),
+        )
+    }
+
+    /// Compute the polygon where this polygon and the other polygon overlap, with
+    /// Sutherland-Hodgman clipping of this polygon against each edge of the other polygon. Both
+    /// polygons must be in the same space, see [`transformed`](#method.transformed).
+    ///
+    /// ## Returns
+    ///
+    /// The overlap polygon, with vertices in CCW order, or `None` if the polygons do not overlap,
+    /// or only touch.
+    pub fn intersection_polygon(&self, other: &ConvexPolygon<S>) -> Option<ConvexPolygon<S>> {
+        let n = other.vertices.len();
+        let mut clipped = self.vertices.clone();
+        for i in 0..n {
+            clipped = clip_polygon(&clipped, other.vertices[i], other.vertices[(i + 1) % n]);
+            if clipped.is_empty() {
+                return None;
+            }
+        }
+        clipped.dedup();
+        if clipped.len() > 1 && clipped.first() == clipped.last() {
+            clipped.pop();
+        }
+        let polygon = ConvexPolygon::new(clipped);
+        if polygon.vertices.len() < 3 || polygon.signed_area() <= S::zero() {
+            None
+        } else {
+            Some(polygon)
+        }
+    }
+
+    /// Compute the area where this polygon and the other polygon overlap, see
+    /// [`intersection_polygon`](#method.intersection_polygon)
+    pub fn intersection_area(&self, other: &ConvexPolygon<S>) -> S {
+        self.intersection_polygon(other)
+            .map(|polygon| polygon.signed_area() / (S::one() + S::one()))
+            .unwrap_or_else(S::zero)
+    }
+
+    /// Twice the signed area of the polygon, positive if the vertices are in CCW order
+    fn signed_area(&self) -> S {
+        let n = self.vertices.len();
+        (0..n).fold(S::zero(), |area, i| {
+            let (a, b) = (self.vertices[i], self.vertices[(i + 1) % n]);
+            area + a.x * b.y - b.x * a.y
+        })
     }
 }
 
@@ -48,7 +231,46 @@ where
     S: BaseFloat,
 {
     fn compute_bound(&self) -> Aabb2<S> {
-        get_bound(self.vertices.iter())
+        self.bound
+    }
+}
+
+impl<S> ComputeBound<Circle<S>> for ConvexPolygon<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: get_max_distance(self.vertices.iter()),
+        }
+    }
+}
+
+impl<S> Contains<Point2<S>> for ConvexPolygon<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        let n = self.vertices.len();
+        (0..n).all(|i| {
+            let edge = self.vertices[(i + 1) % n] - self.vertices[i];
+            edge.perp_dot(*point - self.vertices[i]) >= S::zero()
+        })
+    }
+}
+
+impl<S> ClosestPoint<Point2<S>> for ConvexPolygon<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point2<S>) -> (Point2<S>, bool) {
+        (
+            closest_point_on_polygon(&self.vertices, *point),
+            self.contains(point),
+        )
     }
 }
 
@@ -58,7 +280,7 @@ where
 {
     /// Ray must be in object space
     fn intersects(&self, ray: &Ray2<S>) -> bool {
-        for j in 0..self.vertices.len() - 1 {
+        for j in 0..self.vertices.len() {
             let i = if j == 0 {
                 self.vertices.len() - 1
             } else {
@@ -90,7 +312,7 @@ where
 
     /// Ray must be in object space
     fn intersection(&self, ray: &Ray2<S>) -> Option<Point2<S>> {
-        for j in 0..self.vertices.len() - 1 {
+        for j in 0..self.vertices.len() {
             let i = if j == 0 {
                 self.vertices.len() - 1
             } else {
@@ -193,6 +415,46 @@ where
     }.dot(*direction)
 }
 
+/// Clip a polygon against the line through `a` and `b`, keeping the part of the polygon to the
+/// left of the line
+fn clip_polygon<S>(polygon: &[Point2<S>], a: Point2<S>, b: Point2<S>) -> Vec<Point2<S>>
+where
+    S: BaseFloat,
+{
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for i in 0..polygon.len() {
+        let previous = polygon[(i + polygon.len() - 1) % polygon.len()];
+        let current = polygon[i];
+        let (dp, dc) = (cross_2d(a, b, previous), cross_2d(a, b, current));
+        if (dp >= S::zero()) != (dc >= S::zero()) {
+            clipped.push(previous + (current - previous) * (dp / (dp - dc)));
+        }
+        if dc >= S::zero() {
+            clipped.push(current);
+        }
+    }
+    clipped
+}
+
+impl<S> Geometry2 for ConvexPolygon<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        polygon_area_centroid(&self.vertices).0
+    }
+
+    fn perimeter(&self) -> S {
+        polygon_perimeter(&self.vertices)
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        polygon_area_centroid(&self.vertices).1
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use cgmath::{Basis2, Decomposed, Point2, Rad, Vector2};
@@ -296,6 +558,20 @@ mod tests {
         assert!(polygon.intersects(&ray));
     }
 
+    #[test]
+    fn test_ray_last_edge() {
+        // only the edge between the last two vertices faces the ray
+        let polygon = ConvexPolygon::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(1., 0.),
+            Point2::new(1., 1.),
+            Point2::new(0., 1.),
+        ]);
+        let ray = Ray2::new(Point2::new(0.5, 5.), Vector2::new(0., -1.));
+        assert!(polygon.intersects(&ray));
+        assert_eq!(Some(Point2::new(0.5, 1.)), polygon.intersection(&ray));
+    }
+
     #[test]
     fn test_ray_discrete_transformed() {
         let vertices = vec![
@@ -411,6 +687,109 @@ mod tests {
         assert_ulps_eq!(6.8, p.y);
     }
 
+    #[test]
+    fn test_regular() {
+        let polygon = ConvexPolygon::regular(4, 2.);
+        assert_eq!(4, polygon.vertices.len());
+        assert_ulps_eq!(Point2::new(2., 0.), polygon.vertices[0]);
+        assert_ulps_eq!(Point2::new(0., 2.), polygon.vertices[1], epsilon = 1e-6);
+        assert_ulps_eq!(Point2::new(-2., 0.), polygon.vertices[2], epsilon = 1e-6);
+        assert_ulps_eq!(Point2::new(0., -2.), polygon.vertices[3], epsilon = 1e-6);
+        assert_ulps_eq!(Point2::new(-2., -2.), polygon.bound.min, epsilon = 1e-6);
+        assert_ulps_eq!(Point2::new(2., 2.), polygon.bound.max, epsilon = 1e-6);
+
+        // the bound is computed again for the new vertices
+        let moved = polygon.transformed(&transform(3., 1., 0.));
+        let bound: Aabb2<_> = moved.compute_bound();
+        assert_ulps_eq!(Point2::new(1., -1.), bound.min, epsilon = 1e-6);
+        assert_ulps_eq!(Point2::new(5., 3.), bound.max, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_regular_hexagon() {
+        let polygon = ConvexPolygon::<f32>::regular(6, 1.);
+        assert_eq!(6, polygon.vertices.len());
+        for (i, v) in polygon.vertices.iter().enumerate() {
+            let next = polygon.vertices[(i + 1) % 6];
+            assert_ulps_eq!(1., v.to_vec().magnitude());
+            assert_ulps_eq!(1., (next - v).magnitude(), epsilon = 1e-6);
+            // CCW winding
+            assert!(v.to_vec().perp_dot(next.to_vec()) > 0.);
+        }
+        let bound: Aabb2<_> = polygon.compute_bound();
+        assert_ulps_eq!(-1., bound.min.x);
+        assert_ulps_eq!(1., bound.max.x);
+        assert_ulps_eq!(-0.8660254, bound.min.y);
+        assert_ulps_eq!(0.8660254, bound.max.y);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_regular_too_few_sides() {
+        ConvexPolygon::regular(2, 1.);
+    }
+
+    #[test]
+    fn test_validate() {
+        let polygon = |vertices: &[(f32, f32)]| {
+            ConvexPolygon::new(vertices.iter().map(|&(x, y)| Point2::new(x, y)).collect())
+        };
+        let square = polygon(&[(0., 0.), (1., 0.), (1., 1.), (0., 1.)]);
+        assert_eq!(Ok(()), square.validate());
+        assert_eq!(Ok(()), ConvexPolygon::<f32>::regular(7, 2.).validate());
+
+        assert_eq!(
+            Err(PolygonError::TooFewVertices),
+            polygon(&[(0., 0.), (1., 0.)]).validate()
+        );
+        assert_eq!(
+            Err(PolygonError::DuplicateVertex(1)),
+            polygon(&[(0., 0.), (1., 0.), (1., 0.), (0., 1.)]).validate()
+        );
+        assert_eq!(
+            Err(PolygonError::ClockwiseWinding),
+            polygon(&[(0., 0.), (0., 1.), (1., 1.), (1., 0.)]).validate()
+        );
+        assert_eq!(
+            Err(PolygonError::CollinearVertex(1)),
+            polygon(&[(0., 0.), (1., 0.), (2., 0.), (2., 2.)]).validate()
+        );
+        assert_eq!(
+            Err(PolygonError::NotConvex(2)),
+            polygon(&[(0., 0.), (2., 0.), (1., 0.5), (2., 2.), (0., 2.)]).validate()
+        );
+        // a pentagram turns left at every vertex, but winds around twice
+        let pentagram = ConvexPolygon::new(
+            [0, 2, 4, 1, 3]
+                .iter()
+                .map(|&i| ConvexPolygon::<f32>::regular(5, 1.).vertices[i])
+                .collect(),
+        );
+        assert_eq!(Err(PolygonError::NotConvex(0)), pentagram.validate());
+    }
+
+    #[test]
+    fn test_fix_winding() {
+        let clockwise = vec![
+            Point2::new(0., 0.),
+            Point2::new(0., 1.),
+            Point2::new(1., 1.),
+            Point2::new(1., 0.),
+        ];
+        let mut polygon = ConvexPolygon::new(clockwise.clone());
+        polygon.fix_winding();
+        assert_eq!(Ok(()), polygon.validate());
+        let mut reversed = clockwise;
+        reversed.reverse();
+        assert_eq!(reversed, polygon.vertices);
+
+        // already CCW, unchanged
+        let mut hexagon = ConvexPolygon::<f32>::regular(6, 1.);
+        let vertices = hexagon.vertices.clone();
+        hexagon.fix_winding();
+        assert_eq!(vertices, hexagon.vertices);
+    }
+
     fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
         Decomposed {
             scale: 1.,
@@ -418,4 +797,52 @@ mod tests {
             disp: Vector2::new(dx, dy),
         }
     }
+
+    #[test]
+    fn test_geometry() {
+        let polygon = ConvexPolygon::new(vec![
+            Point2::new(1., 1.),
+            Point2::new(3., 1.),
+            Point2::new(3., 2.),
+            Point2::new(1., 2.),
+        ]);
+        assert_ulps_eq!(2., polygon.area());
+        assert_ulps_eq!(6., polygon.perimeter());
+        assert_ulps_eq!(Point2::new(2., 1.5), polygon.centroid());
+        let hexagon = ConvexPolygon::<f32>::regular(6, 1.);
+        assert_ulps_eq!(3. * 3f32.sqrt() / 2., hexagon.area());
+        assert_ulps_eq!(6., hexagon.perimeter(), epsilon = 1e-6);
+        assert_ulps_eq!(Point2::new(0., 0.), hexagon.centroid(), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_intersection_polygon() {
+        let square = ConvexPolygon::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(2., 0.),
+            Point2::new(2., 2.),
+            Point2::new(0., 2.),
+        ]);
+        let other = square.transformed(&transform(1., 1., 0.));
+        let overlap = square.intersection_polygon(&other).unwrap();
+        assert_eq!(4, overlap.vertices.len());
+        assert_ulps_eq!(1., overlap.area());
+        assert_ulps_eq!(Point2::new(1.5, 1.5), overlap.centroid());
+        assert_ulps_eq!(1., square.intersection_area(&other));
+
+        // a square rotated by 45 degrees cuts the corners off the other square
+        let diamond = ConvexPolygon::<f32>::regular(4, 1.2);
+        let centered = square.transformed(&transform(-1., -1., 0.));
+        let overlap = centered.intersection_polygon(&diamond).unwrap();
+        assert_eq!(8, overlap.vertices.len());
+        assert!(overlap.validate().is_ok());
+        assert_ulps_eq!(4. - 4. * 0.8 * 0.8 / 2., overlap.area(), epsilon = 1e-5);
+
+        // touching along an edge, and separated
+        let touching = square.transformed(&transform(2., 0., 0.));
+        assert!(square.intersection_polygon(&touching).is_none());
+        assert_eq!(0., square.intersection_area(&touching));
+        let separated = square.transformed(&transform(5., 0., 0.));
+        assert!(square.intersection_polygon(&separated).is_none());
+    }
 }
@@ -2,12 +2,14 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use bit_set::BitSet;
-use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::{BaseFloat, Decomposed, Point3, Quaternion, Vector3};
 use cgmath::prelude::*;
 
-use crate::{Aabb3, Plane, Ray3};
+use crate::{Aabb3, CollisionStrategy, Plane, Ray3};
+use crate::algorithm::minkowski::GJK3;
 use crate::prelude::*;
-use crate::primitive::util::barycentric_point;
+use crate::primitive::Particle3;
+use crate::primitive::util::{barycentric_point, closest_point_on_triangle, nearest};
 use crate::volume::Sphere;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -139,6 +141,89 @@ where
         }
     }
 
+    /// Return an iterator over the vertex positions
+    pub(crate) fn vertex_positions(&self) -> impl Iterator<Item = &Point3<S>> {
+        self.vertices.iter().map(|v| &v.position)
+    }
+
+    /// Return an iterator that will yield the outward normal and a vertex of each face, in face
+    /// index order
+    pub(crate) fn face_normals(&self) -> impl Iterator<Item = (Vector3<S>, Point3<S>)> + '_ {
+        assert_eq!(self.mode, PolyhedronMode::HalfEdge);
+        self.faces
+            .iter()
+            .map(move |face| (face.plane.n, self.vertices[face.vertices.0].position))
+    }
+
+    /// Return an iterator that will yield each edge once, as the index of the half edge, its
+    /// start and end positions, and the normals of the faces to the left and right of it
+    pub(crate) fn edge_features(
+        &self,
+    ) -> impl Iterator<Item = (usize, Point3<S>, Point3<S>, Vector3<S>, Vector3<S>)> + '_ {
+        assert_eq!(self.mode, PolyhedronMode::HalfEdge);
+        self.edges
+            .iter()
+            .enumerate()
+            .filter(|&(index, edge)| index < edge.twin_edge)
+            .map(move |(index, edge)| {
+                let twin = &self.edges[edge.twin_edge];
+                (
+                    index,
+                    self.vertices[twin.target_vertex].position,
+                    self.vertices[edge.target_vertex].position,
+                    self.faces[edge.left_face].plane.n,
+                    self.faces[twin.left_face].plane.n,
+                )
+            })
+    }
+
+    /// Return the position of the vertex with the given index
+    pub(crate) fn vertex_position(&self, vertex: usize) -> Point3<S> {
+        self.vertices[vertex].position
+    }
+
+    /// Return the indices of the start and end vertices of the given half edge
+    pub(crate) fn edge_vertices(&self, edge: usize) -> (usize, usize) {
+        let e = &self.edges[edge];
+        (self.edges[e.twin_edge].target_vertex, e.target_vertex)
+    }
+
+    /// Return the start and end positions of the given half edge
+    pub(crate) fn edge_segment(&self, edge: usize) -> (Point3<S>, Point3<S>) {
+        let (start, end) = self.edge_vertices(edge);
+        (self.vertices[start].position, self.vertices[end].position)
+    }
+
+    /// Return the indices of the vertices of the flat side of the polyhedron that contains the
+    /// given face, in CCW order around the face normal. The faces are triangles, so a side with
+    /// more than three vertices is made of several coplanar faces.
+    pub(crate) fn facet(&self, face: usize) -> Vec<usize> {
+        assert_eq!(self.mode, PolyhedronMode::HalfEdge);
+        let normal = self.faces[face].plane.n;
+        let tolerance = S::default_epsilon().sqrt();
+        let mut indices: Vec<usize> = Vec::new();
+        for f in self.faces
+            .iter()
+            .filter(|f| f.plane.n.dot(normal) >= S::one() - tolerance)
+        {
+            for &i in &[f.vertices.0, f.vertices.1, f.vertices.2] {
+                if !indices.contains(&i) {
+                    indices.push(i);
+                }
+            }
+        }
+        let points: Vec<_> = indices.iter().map(|&i| self.vertices[i].position).collect();
+        let center = Point3::centroid(&points);
+        let u = (points[0] - center).normalize();
+        let w = normal.cross(u);
+        let angle = |i: &usize| {
+            let d = self.vertices[*i].position - center;
+            d.dot(w).atan2(d.dot(u))
+        };
+        indices.sort_by(|a, b| angle(a).partial_cmp(&angle(b)).unwrap_or(Ordering::Equal));
+        indices
+    }
+
     #[inline]
     fn brute_force_support_point(&self, direction: Vector3<S>) -> Point3<S> {
         let (p, _) = self.vertices
@@ -403,6 +488,78 @@ where
     }
 }
 
+impl<S> Contains<Point3<S>> for ConvexPolyhedron<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space.
+    ///
+    /// Uses the face planes if the faces are given, otherwise GJK against the point.
+    fn contains(&self, point: &Point3<S>) -> bool {
+        match self.mode {
+            PolyhedronMode::HalfEdge => self
+                .face_normals()
+                .all(|(normal, vertex)| normal.dot(*point - vertex) <= S::zero()),
+            PolyhedronMode::VertexOnly => {
+                let identity = Decomposed::<Vector3<S>, Quaternion<S>>::one();
+                let point_transform = Decomposed {
+                    disp: point.to_vec(),
+                    ..identity
+                };
+                GJK3::new()
+                    .intersect(self, &identity, &Particle3::new(), &point_transform)
+                    .is_some()
+            }
+        }
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for ConvexPolyhedron<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space.
+    ///
+    /// Uses the faces if they are given. Otherwise GJK is used for points outside, and EPA for
+    /// points inside, which is only approximate.
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        match self.mode {
+            PolyhedronMode::HalfEdge => {
+                let closest = nearest(
+                    self.faces_iter()
+                        .map(|(a, b, c)| closest_point_on_triangle(*a, *b, *c, *point).0),
+                    *point,
+                );
+                (closest, self.contains(point))
+            }
+            PolyhedronMode::VertexOnly => {
+                let identity = Decomposed::<Vector3<S>, Quaternion<S>>::one();
+                let point_transform = Decomposed {
+                    disp: point.to_vec(),
+                    ..identity
+                };
+                let gjk = GJK3::new();
+                let particle = Particle3::new();
+                if let Some((closest, _)) =
+                    gjk.closest_points(self, &identity, &particle, &point_transform)
+                {
+                    return (closest, false);
+                }
+                match gjk.intersection(
+                    &CollisionStrategy::FullResolution,
+                    self,
+                    &identity,
+                    &particle,
+                    &point_transform,
+                ) {
+                    Some(contact) => (*point + contact.normal * contact.penetration_depth, true),
+                    None => (*point, true),
+                }
+            }
+        }
+    }
+}
+
 /// TODO: better algorithm for finding faces to intersect with?
 impl<S> Discrete<Ray3<S>> for ConvexPolyhedron<S>
 where
@@ -728,6 +885,51 @@ mod tests {
         assert_ulps_eq!(0.25, p.z);
     }
 
+    #[test]
+    fn test_contains() {
+        let vertices = vec![
+            Point3::<f32>::new(1., 0., 0.),
+            Point3::<f32>::new(0., 1., 0.),
+            Point3::<f32>::new(0., 0., 1.),
+            Point3::<f32>::new(0., 0., 0.),
+        ];
+        let faces = vec![(1, 3, 2), (3, 1, 0), (2, 0, 1), (0, 2, 3)];
+
+        let polytope_with_faces = ConvexPolyhedron::new_with_faces(vertices.clone(), faces);
+        let polytope = ConvexPolyhedron::new(vertices);
+        for polytope in &[polytope, polytope_with_faces] {
+            assert!(polytope.contains(&Point3::new(0.2, 0.2, 0.2)));
+            assert!(!polytope.contains(&Point3::new(0.4, 0.4, 0.4)));
+            assert!(!polytope.contains(&Point3::new(-0.1, 0.2, 0.2)));
+            let t = transform(10., 0., 0., 0.);
+            assert!(polytope.contains_transformed(&Point3::new(10.2, 0.2, 0.2), &t));
+            assert!(!polytope.contains_transformed(&Point3::new(0.2, 0.2, 0.2), &t));
+        }
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let vertices = vec![
+            Point3::<f32>::new(1., 0., 0.),
+            Point3::<f32>::new(0., 1., 0.),
+            Point3::<f32>::new(0., 0., 1.),
+            Point3::<f32>::new(0., 0., 0.),
+        ];
+        let faces = vec![(1, 3, 2), (3, 1, 0), (2, 0, 1), (0, 2, 3)];
+
+        let polytope_with_faces = ConvexPolyhedron::new_with_faces(vertices.clone(), faces);
+        let polytope = ConvexPolyhedron::new(vertices);
+        for polytope in &[polytope, polytope_with_faces] {
+            let (closest, inside) = polytope.closest_point(&Point3::new(-1., 0.2, 0.2));
+            assert!(!inside);
+            assert_ulps_eq!(Point3::new(0., 0.2, 0.2), closest, epsilon = 1e-5);
+
+            let (closest, inside) = polytope.closest_point(&Point3::new(0.1, 0.3, 0.3));
+            assert!(inside);
+            assert_ulps_eq!(Point3::new(0., 0.3, 0.3), closest, epsilon = 1e-2);
+        }
+    }
+
     #[test]
     fn test_intersect_face() {
         let vertices = vec![
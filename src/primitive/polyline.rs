@@ -0,0 +1,271 @@
+//! Polyline primitive
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use cgmath::{BaseFloat, Point2};
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Contact, Line2, Ray2};
+use crate::algorithm::minkowski::GJK2;
+use crate::prelude::*;
+use crate::primitive::util::{get_bound, get_max_distance};
+use crate::volume::Circle;
+
+/// Polyline primitive, an open chain of line segments.
+///
+/// Mostly useful for static 2D terrain. The polyline is not convex, so it does not implement
+/// `Primitive`. Instead, ray tests are done against each segment, and moving convex primitives
+/// can be swept against the chain with [`sweep`](#method.sweep). A bounding box is kept for each
+/// segment, and used to prune segments before doing the exact tests.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Polyline<S: BaseFloat> {
+    vertices: Vec<Point2<S>>,
+    segment_bounds: Vec<Aabb2<S>>,
+}
+
+impl<S> Polyline<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new polyline from the given vertices, in order along the chain
+    pub fn new(vertices: Vec<Point2<S>>) -> Self {
+        let segment_bounds = vertices
+            .windows(2)
+            .map(|w| Aabb2::new(w[0], w[1]))
+            .collect();
+        Self {
+            vertices,
+            segment_bounds,
+        }
+    }
+
+    /// Get the vertices of the polyline
+    pub fn vertices(&self) -> &[Point2<S>] {
+        &self.vertices
+    }
+
+    /// Get the bounding boxes of the segments of the polyline
+    pub fn segment_bounds(&self) -> &[Aabb2<S>] {
+        &self.segment_bounds
+    }
+
+    /// Get the segments of the polyline
+    pub fn segments(&self) -> impl Iterator<Item = Line2<S>> + '_ {
+        self.vertices.windows(2).map(|w| Line2::new(w[0], w[1]))
+    }
+
+    /// Sweep a convex primitive against the polyline.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `gjk`: GJK algorithm to use for the time of impact tests on each segment
+    /// - `primitive`: the moving primitive
+    /// - `transform`: start and end transforms of the primitive, relative to the polyline
+    ///
+    /// ## Returns:
+    ///
+    /// The contact with the earliest time of impact over all segments, or None if the primitive
+    /// does not hit the polyline during the move.
+    pub fn sweep<P, T>(
+        &self,
+        gjk: &GJK2<S>,
+        primitive: &P,
+        transform: Range<&T>,
+    ) -> Option<Contact<Point2<S>>>
+    where
+        P: Primitive<Point = Point2<S>> + ComputeBound<Aabb2<S>>,
+        T: Transform<Point2<S>> + TranslationInterpolate<S>,
+    {
+        let bound: Aabb2<S> = primitive.compute_bound();
+        let swept = bound
+            .transform_volume(transform.start)
+            .union(&bound.transform_volume(transform.end));
+        let identity = T::one();
+        self.segments()
+            .zip(self.segment_bounds.iter())
+            .filter(|(_, segment_bound)| swept.intersects(*segment_bound))
+            .filter_map(|(segment, _)| {
+                gjk.intersection_time_of_impact(
+                    &segment,
+                    &identity..&identity,
+                    primitive,
+                    transform.clone(),
+                )
+            })
+            .min_by(|l, r| {
+                l.time_of_impact
+                    .partial_cmp(&r.time_of_impact)
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
+
+impl<S> ComputeBound<Aabb2<S>> for Polyline<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb2<S> {
+        get_bound(self.vertices.iter())
+    }
+}
+
+impl<S> ComputeBound<Circle<S>> for Polyline<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: get_max_distance(self.vertices.iter()),
+        }
+    }
+}
+
+impl<S> Discrete<Ray2<S>> for Polyline<S>
+where
+    S: BaseFloat,
+{
+    /// Ray must be in object space
+    fn intersects(&self, ray: &Ray2<S>) -> bool {
+        self.segments()
+            .zip(self.segment_bounds.iter())
+            .any(|(segment, bound)| bound.intersects(ray) && segment.intersects(ray))
+    }
+}
+
+impl<S> Continuous<Ray2<S>> for Polyline<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point2<S>;
+
+    /// Ray must be in object space. Will return the closest intersection point over all segments.
+    fn intersection(&self, ray: &Ray2<S>) -> Option<Point2<S>> {
+        self.segments()
+            .zip(self.segment_bounds.iter())
+            .filter(|(_, bound)| bound.intersects(ray))
+            .filter_map(|(segment, _)| segment.intersection(ray))
+            .fold(None, |closest: Option<Point2<S>>, p| match closest {
+                Some(c) if (c - ray.origin).magnitude2() <= (p - ray.origin).magnitude2() => {
+                    Some(c)
+                }
+                _ => Some(p),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Basis2, Decomposed, Point2, Rad, Rotation2, Vector2};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::primitive::{Circle, Rectangle};
+
+    fn terrain() -> Polyline<f32> {
+        Polyline::new(vec![
+            Point2::new(0., 0.),
+            Point2::new(4., 0.),
+            Point2::new(6., 2.),
+            Point2::new(10., 2.),
+        ])
+    }
+
+    #[test]
+    fn test_polyline_segments() {
+        let polyline = terrain();
+        assert_eq!(3, polyline.segments().count());
+        assert_eq!(3, polyline.segment_bounds().len());
+        assert_eq!(
+            Aabb2::new(Point2::new(4., 0.), Point2::new(6., 2.)),
+            polyline.segment_bounds()[1]
+        );
+        assert_eq!(
+            Aabb2::new(Point2::new(0., 0.), Point2::new(10., 2.)),
+            polyline.compute_bound()
+        );
+    }
+
+    #[test]
+    fn test_polyline_ray() {
+        let polyline = terrain();
+        let ray = Ray2::new(Point2::new(2., 5.), Vector2::new(0., -1.));
+        assert!(polyline.intersects(&ray));
+        assert_eq!(Some(Point2::new(2., 0.)), polyline.intersection(&ray));
+        let ray = Ray2::new(Point2::new(8., 5.), Vector2::new(0., -1.));
+        assert_eq!(Some(Point2::new(8., 2.)), polyline.intersection(&ray));
+        let ray = Ray2::new(Point2::new(12., 5.), Vector2::new(0., -1.));
+        assert!(!polyline.intersects(&ray));
+        assert_eq!(None, polyline.intersection(&ray));
+    }
+
+    #[test]
+    fn test_polyline_ray_closest() {
+        let polyline = terrain();
+        // hits the slope before the flat part behind it
+        let ray = Ray2::new(Point2::new(0., 1.), Vector2::new(1., 0.));
+        assert_ulps_eq!(
+            Point2::new(5., 1.),
+            polyline.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_polyline_ray_transformed() {
+        let polyline = terrain();
+        let ray = Ray2::new(Point2::new(2., 5.), Vector2::new(0., -1.));
+        let t = transform(0., -1., 0.);
+        assert_ulps_eq!(
+            Point2::new(2., -1.),
+            polyline.intersection_transformed(&ray, &t).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_polyline_sweep_circle() {
+        let polyline = terrain();
+        let gjk = GJK2::new();
+        let circle = Circle::new(1.);
+        let start = transform(2., 5., 0.);
+        let end = transform(2., -5., 0.);
+        let contact = polyline.sweep(&gjk, &circle, &start..&end).unwrap();
+        // falls 4 of 10 units before touching the ground
+        assert_ulps_eq!(0.4, contact.time_of_impact, epsilon = 1e-3);
+        let end = transform(2., 3., 0.);
+        assert!(polyline.sweep(&gjk, &circle, &start..&end).is_none());
+    }
+
+    #[test]
+    fn test_polyline_sweep_earliest() {
+        let polyline = terrain();
+        let gjk = GJK2::new();
+        let rectangle = Rectangle::new(1., 1.);
+        // moving right along the ground, hits the slope
+        let start = transform(1., 1., 0.);
+        let end = transform(9., 1., 0.);
+        let contact = polyline.sweep(&gjk, &rectangle, &start..&end).unwrap();
+        assert_ulps_eq!(0.375, contact.time_of_impact, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_polyline_sweep_pruned() {
+        let polyline = terrain();
+        let gjk = GJK2::new();
+        let circle = Circle::new(1.);
+        let start = transform(8., 10., 0.);
+        let end = transform(8., 6., 0.);
+        assert!(polyline.sweep(&gjk, &circle, &start..&end).is_none());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+}
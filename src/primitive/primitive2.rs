@@ -1,17 +1,22 @@
 //! Wrapper enum for 2D primitives
 
-use cgmath::{BaseFloat, Point2, Vector2};
+use cgmath::{BaseFloat, Basis2, Decomposed, Point2, Vector2};
 use cgmath::prelude::*;
 
-use crate::{Aabb2, Line2, Ray2};
+use crate::{Aabb2, Dop2, Line2, Obb2, Ray2};
+use crate::algorithm::minkowski::GJK2;
 use crate::prelude::*;
-use crate::primitive::{Circle, ConvexPolygon, Particle2, Rectangle, Square};
+use crate::primitive::{Capsule2, Circle, ConvexPolygon, Ellipse, Particle2, Rectangle, Square};
+use crate::primitive::Triangle;
 
 /// Wrapper enum for 2D primitives, that also implements the `Primitive` trait, making it easier
 /// to use many different primitives in algorithms.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub enum Primitive2<S> {
+pub enum Primitive2<S>
+where
+    S: BaseFloat,
+{
     /// Particle
     Particle(Particle2<S>),
     /// Line
@@ -24,56 +29,200 @@ pub enum Primitive2<S> {
     Square(Square<S>),
     /// Convex polygon with any number of vertices.
     ConvexPolygon(ConvexPolygon<S>),
+    /// Ellipse
+    Ellipse(Ellipse<S>),
+    /// Capsule
+    Capsule(Capsule2<S>),
+    /// Triangle
+    Triangle(Triangle<S>),
 }
 
-impl<S> From<Particle2<S>> for Primitive2<S> {
+impl<S> From<Particle2<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
     fn from(particle: Particle2<S>) -> Primitive2<S> {
         Primitive2::Particle(particle)
     }
 }
 
-impl<S> From<Line2<S>> for Primitive2<S> {
+impl<S> From<Line2<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
     fn from(line: Line2<S>) -> Primitive2<S> {
         Primitive2::Line(line)
     }
 }
 
-impl<S> From<Circle<S>> for Primitive2<S> {
+impl<S> From<Circle<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
     fn from(circle: Circle<S>) -> Primitive2<S> {
         Primitive2::Circle(circle)
     }
 }
 
-impl<S> From<Rectangle<S>> for Primitive2<S> {
+impl<S> From<Rectangle<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
     fn from(rectangle: Rectangle<S>) -> Primitive2<S> {
         Primitive2::Rectangle(rectangle)
     }
 }
 
-impl<S> From<Square<S>> for Primitive2<S> {
+impl<S> From<Square<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
     fn from(rectangle: Square<S>) -> Primitive2<S> {
         Primitive2::Square(rectangle)
     }
 }
 
-impl<S> From<ConvexPolygon<S>> for Primitive2<S> {
+impl<S> From<ConvexPolygon<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
     fn from(polygon: ConvexPolygon<S>) -> Primitive2<S> {
         Primitive2::ConvexPolygon(polygon)
     }
 }
 
+impl<S> From<Ellipse<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    fn from(ellipse: Ellipse<S>) -> Primitive2<S> {
+        Primitive2::Ellipse(ellipse)
+    }
+}
+
+impl<S> From<Capsule2<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    fn from(capsule: Capsule2<S>) -> Primitive2<S> {
+        Primitive2::Capsule(capsule)
+    }
+}
+
+impl<S> From<Triangle<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    fn from(triangle: Triangle<S>) -> Primitive2<S> {
+        Primitive2::Triangle(triangle)
+    }
+}
+
 impl<S> ComputeBound<Aabb2<S>> for Primitive2<S>
 where
     S: BaseFloat,
 {
     fn compute_bound(&self) -> Aabb2<S> {
         match *self {
-            Primitive2::Particle(_) => Aabb2::zero(),
+            Primitive2::Particle(ref particle) => particle.compute_bound(),
             Primitive2::Line(ref line) => line.compute_bound(),
             Primitive2::Circle(ref circle) => circle.compute_bound(),
             Primitive2::Rectangle(ref rectangle) => rectangle.compute_bound(),
             Primitive2::Square(ref square) => square.compute_bound(),
             Primitive2::ConvexPolygon(ref polygon) => polygon.compute_bound(),
+            Primitive2::Ellipse(ref ellipse) => ellipse.compute_bound(),
+            Primitive2::Capsule(ref capsule) => capsule.compute_bound(),
+            Primitive2::Triangle(ref triangle) => triangle.compute_bound(),
+        }
+    }
+}
+
+impl<S> ComputeBound<crate::volume::Circle<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> crate::volume::Circle<S> {
+        match *self {
+            Primitive2::Particle(ref particle) => particle.compute_bound(),
+            Primitive2::Line(ref line) => line.compute_bound(),
+            Primitive2::Circle(ref circle) => circle.compute_bound(),
+            Primitive2::Rectangle(ref rectangle) => rectangle.compute_bound(),
+            Primitive2::Square(ref square) => square.compute_bound(),
+            Primitive2::ConvexPolygon(ref polygon) => polygon.compute_bound(),
+            Primitive2::Ellipse(ref ellipse) => ellipse.compute_bound(),
+            Primitive2::Capsule(ref capsule) => capsule.compute_bound(),
+            Primitive2::Triangle(ref triangle) => triangle.compute_bound(),
+        }
+    }
+}
+
+/// The local box is the AABB of the primitive. Unlike the AABB, the box rotates with the
+/// primitive in [`compute_bound_transformed`](../trait.ComputeBound.html#method.compute_bound_transformed),
+/// so it does not grow when the primitive is rotated.
+impl<S> ComputeBound<Obb2<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Obb2<S> {
+        let aabb: Aabb2<S> = self.compute_bound();
+        Obb2::from_aabb(&aabb, &Decomposed::<Vector2<S>, Basis2<S>>::one())
+    }
+}
+
+impl<S> ComputeBound<Dop2<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Dop2<S> {
+        Dop2::from_primitive(self, &Decomposed::<Vector2<S>, Basis2<S>>::one())
+    }
+}
+
+impl<S> Geometry2 for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        match *self {
+            Primitive2::Particle(_) | Primitive2::Line(_) => S::zero(),
+            Primitive2::Circle(ref circle) => circle.area(),
+            Primitive2::Rectangle(ref rectangle) => rectangle.area(),
+            Primitive2::Square(ref square) => square.area(),
+            Primitive2::ConvexPolygon(ref polygon) => polygon.area(),
+            Primitive2::Ellipse(ref ellipse) => ellipse.area(),
+            Primitive2::Capsule(ref capsule) => capsule.area(),
+            Primitive2::Triangle(ref triangle) => triangle.area(),
+        }
+    }
+
+    /// The perimeter of a line is its length
+    fn perimeter(&self) -> S {
+        match *self {
+            Primitive2::Particle(_) => S::zero(),
+            Primitive2::Line(ref line) => (line.dest - line.origin).magnitude(),
+            Primitive2::Circle(ref circle) => circle.perimeter(),
+            Primitive2::Rectangle(ref rectangle) => rectangle.perimeter(),
+            Primitive2::Square(ref square) => square.perimeter(),
+            Primitive2::ConvexPolygon(ref polygon) => polygon.perimeter(),
+            Primitive2::Ellipse(ref ellipse) => ellipse.perimeter(),
+            Primitive2::Capsule(ref capsule) => capsule.perimeter(),
+            Primitive2::Triangle(ref triangle) => triangle.perimeter(),
+        }
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        match *self {
+            Primitive2::Particle(_) => Point2::origin(),
+            Primitive2::Line(ref line) => line.origin.midpoint(line.dest),
+            Primitive2::Circle(ref circle) => circle.centroid(),
+            Primitive2::Rectangle(ref rectangle) => rectangle.centroid(),
+            Primitive2::Square(ref square) => square.centroid(),
+            Primitive2::ConvexPolygon(ref polygon) => polygon.centroid(),
+            Primitive2::Ellipse(ref ellipse) => ellipse.centroid(),
+            Primitive2::Capsule(ref capsule) => capsule.centroid(),
+            Primitive2::Triangle(ref triangle) => triangle.centroid(),
         }
     }
 }
@@ -89,12 +238,107 @@ where
         T: Transform<Point2<S>>,
     {
         match *self {
-            Primitive2::Particle(_) => transform.transform_point(Point2::origin()),
+            Primitive2::Particle(ref particle) => particle.support_point(direction, transform),
             Primitive2::Line(ref line) => line.support_point(direction, transform),
             Primitive2::Circle(ref circle) => circle.support_point(direction, transform),
             Primitive2::Rectangle(ref rectangle) => rectangle.support_point(direction, transform),
             Primitive2::Square(ref square) => square.support_point(direction, transform),
             Primitive2::ConvexPolygon(ref polygon) => polygon.support_point(direction, transform),
+            Primitive2::Ellipse(ref ellipse) => ellipse.support_point(direction, transform),
+            Primitive2::Capsule(ref capsule) => capsule.support_point(direction, transform),
+            Primitive2::Triangle(ref triangle) => triangle.support_point(direction, transform),
+        }
+    }
+}
+
+impl<S> Primitive2<S>
+where
+    S: BaseFloat,
+{
+    /// Center and radius in world space, if the primitive is a circle or a particle
+    fn as_circle<T>(&self, transform: &T) -> Option<(Point2<S>, S)>
+    where
+        T: Transform<Point2<S>>,
+    {
+        let center = transform.transform_point(Point2::origin());
+        match *self {
+            Primitive2::Particle(_) => Some((center, S::zero())),
+            Primitive2::Circle(ref circle) => Some((
+                center,
+                transform
+                    .transform_vector(Vector2::new(circle.radius, S::zero()))
+                    .magnitude(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl<S> Distance for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point2<S>;
+
+    fn distance<TL, TR>(
+        &self,
+        transform: &TL,
+        other: &Primitive2<S>,
+        other_transform: &TR,
+    ) -> Option<S>
+    where
+        TL: Transform<Point2<S>>,
+        TR: Transform<Point2<S>>,
+    {
+        match (self.as_circle(transform), other.as_circle(other_transform)) {
+            (Some((center, radius)), Some((other_center, other_radius))) => {
+                let distance = (other_center - center).magnitude() - radius - other_radius;
+                if distance > S::zero() {
+                    Some(distance)
+                } else {
+                    None
+                }
+            }
+            _ => GJK2::new().distance(self, transform, other, other_transform),
+        }
+    }
+}
+
+impl<S> Contains<Point2<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space. Particles and lines contain no points.
+    fn contains(&self, point: &Point2<S>) -> bool {
+        match *self {
+            Primitive2::Particle(_) | Primitive2::Line(_) => false,
+            Primitive2::Circle(ref circle) => circle.contains(point),
+            Primitive2::Rectangle(ref rectangle) => rectangle.contains(point),
+            Primitive2::Square(ref square) => square.contains(point),
+            Primitive2::ConvexPolygon(ref polygon) => polygon.contains(point),
+            Primitive2::Ellipse(ref ellipse) => ellipse.contains(point),
+            Primitive2::Capsule(ref capsule) => capsule.contains(point),
+            Primitive2::Triangle(ref triangle) => triangle.contains(point),
+        }
+    }
+}
+
+impl<S> ClosestPoint<Point2<S>> for Primitive2<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point2<S>) -> (Point2<S>, bool) {
+        match *self {
+            Primitive2::Particle(ref particle) => particle.closest_point(point),
+            Primitive2::Line(ref line) => line.closest_point(point),
+            Primitive2::Circle(ref circle) => circle.closest_point(point),
+            Primitive2::Rectangle(ref rectangle) => rectangle.closest_point(point),
+            Primitive2::Square(ref square) => square.closest_point(point),
+            Primitive2::ConvexPolygon(ref polygon) => polygon.closest_point(point),
+            Primitive2::Ellipse(ref ellipse) => ellipse.closest_point(point),
+            Primitive2::Capsule(ref capsule) => capsule.closest_point(point),
+            Primitive2::Triangle(ref triangle) => triangle.closest_point(point),
         }
     }
 }
@@ -120,6 +364,9 @@ where
             Primitive2::ConvexPolygon(ref polygon) => {
                 polygon.intersects_transformed(ray, transform)
             }
+            Primitive2::Ellipse(ref ellipse) => ellipse.intersects_transformed(ray, transform),
+            Primitive2::Capsule(ref capsule) => capsule.intersects_transformed(ray, transform),
+            Primitive2::Triangle(ref triangle) => triangle.intersects_transformed(ray, transform),
         }
     }
 }
@@ -146,6 +393,221 @@ where
             Primitive2::ConvexPolygon(ref polygon) => {
                 polygon.intersection_transformed(ray, transform)
             }
+            Primitive2::Ellipse(ref ellipse) => ellipse.intersection_transformed(ray, transform),
+            Primitive2::Capsule(ref capsule) => capsule.intersection_transformed(ray, transform),
+            Primitive2::Triangle(ref triangle) => triangle.intersection_transformed(ray, transform),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Basis2, Decomposed, Point2, Rad, Rotation2, Vector2};
+
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK2;
+
+    #[test]
+    fn test_heterogeneous_storage() {
+        let shapes: Vec<Primitive2<f32>> = vec![
+            Circle::new(1.).into(),
+            Rectangle::new(2., 2.).into(),
+            Ellipse::new(2., 1.).into(),
+            Capsule2::new(1., 1.).into(),
+            Triangle::new(
+                Point2::new(-1., -1.),
+                Point2::new(1., -1.),
+                Point2::new(0., 1.),
+            )
+            .into(),
+        ];
+        let gjk = GJK2::new();
+        let t = transform(0., 0., 0.);
+        for shape in &shapes {
+            let bound: Aabb2<f32> = shape.compute_bound();
+            assert!(bound.contains(&Point2::origin()));
+            assert!(gjk
+                .intersect(shape, &t, &Primitive2::from(Circle::new(0.5)), &t)
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn test_forward_ray() {
+        let ray = Ray2::new(Point2::new(-10., 0.), Vector2::new(1., 0.));
+        let t = transform(0., 0., 0.);
+        let ellipse = Primitive2::from(Ellipse::new(2., 1.));
+        assert_eq!(
+            Some(Point2::new(-2., 0.)),
+            ellipse.intersection_transformed(&ray, &t)
+        );
+        let capsule = Primitive2::from(Capsule2::new(1., 1.));
+        assert!(capsule.intersects_transformed(&ray, &t));
+        let triangle = Primitive2::from(Triangle::new(
+            Point2::new(0., -1.),
+            Point2::new(1., 0.),
+            Point2::new(0., 1.),
+        ));
+        assert_eq!(
+            Some(Point2::new(0., 0.)),
+            triangle.intersection_transformed(&ray, &t)
+        );
+    }
+
+    #[test]
+    fn test_geometry() {
+        let line: Primitive2<f32> = Line2::new(Point2::new(1., 1.), Point2::new(4., 5.)).into();
+        assert_eq!(0., line.area());
+        assert_eq!(5., line.perimeter());
+        assert_eq!(Point2::new(2.5, 3.), line.centroid());
+        let rectangle: Primitive2<f32> = Rectangle::new(2., 3.).into();
+        assert_eq!(6., rectangle.area());
+        assert_eq!(10., rectangle.perimeter());
+    }
+
+    #[test]
+    fn test_bounds() {
+        let circle = Primitive2::from(Circle::new(1.));
+        let dop: Dop2<f32> = circle.compute_bound();
+        assert_ulps_eq!(2f32.sqrt(), dop.max[2]);
+        assert_ulps_eq!(-(2f32.sqrt()), dop.min[3]);
+        let rectangle = Primitive2::from(Rectangle::new(2., 3.));
+        let obb: Obb2<f32> = rectangle.compute_bound();
+        assert_eq!(Vector2::new(1., 1.5), obb.half_extents);
+        // the diagonal slabs fit a rotated square tighter than its AABB
+        let square = Primitive2::from(Square::new(2.));
+        let dop = Dop2::from_primitive(&square, &transform(0., 0., std::f32::consts::PI / 4.));
+        assert_ulps_eq!(2f32.sqrt(), dop.max[0], epsilon = 1e-6);
+        assert_ulps_eq!(2f32.sqrt(), dop.max[2], epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_circle_bounds() {
+        let rectangle = Primitive2::from(Rectangle::new(6., 8.));
+        let bound: crate::volume::Circle<f32> = rectangle.compute_bound();
+        assert_eq!(Point2::new(0., 0.), bound.center);
+        assert_eq!(5., bound.radius);
+        let triangle = Primitive2::from(Triangle::new(
+            Point2::new(-1., 0.),
+            Point2::new(3., 4.),
+            Point2::new(0., 1.),
+        ));
+        let bound: crate::volume::Circle<f32> = triangle.compute_bound();
+        assert_eq!(5., bound.radius);
+        // the bound does not depend on the rotation of the primitive
+        let t = transform(1., 2., 0.7);
+        let rotated: crate::volume::Circle<f32> = triangle.compute_bound_transformed(&t);
+        assert_eq!(5., rotated.radius);
+        assert_ulps_eq!(Point2::new(1., 2.), rotated.center);
+    }
+
+    #[test]
+    fn test_distance() {
+        let circle = Primitive2::from(Circle::new(1.));
+        let particle = Primitive2::from(Particle2::new());
+        let rectangle = Primitive2::from(Rectangle::new(2., 2.));
+        let t = transform(0., 0., 0.);
+        assert_eq!(
+            Some(3.),
+            circle.distance(&t, &circle, &transform(5., 0., 0.))
+        );
+        assert_eq!(
+            Some(4.),
+            circle.distance(&t, &particle, &transform(0., 5., 0.))
+        );
+        assert_eq!(None, circle.distance(&t, &circle, &transform(1., 1., 0.)));
+        assert_ulps_eq!(
+            3.,
+            rectangle
+                .distance(&t, &circle, &transform(5., 0., 0.3))
+                .unwrap(),
+            epsilon = 1e-5
+        );
+        assert_eq!(
+            None,
+            rectangle.distance(&t, &rectangle, &transform(1., 1., 0.3))
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let inside = Point2::new(0.4, 0.4);
+        let outside = Point2::new(0.9, 0.9);
+        let shapes: Vec<Primitive2<f32>> = vec![
+            Circle::new(1.).into(),
+            Rectangle::new(1.6, 1.).into(),
+            Square::new(1.).into(),
+            ConvexPolygon::regular(6, 1.).into(),
+            Ellipse::new(1., 0.8).into(),
+            Capsule2::new(0.5, 0.5).into(),
+        ];
+        for shape in &shapes {
+            assert!(shape.contains(&inside), "{:?}", shape);
+            assert!(!shape.contains(&outside), "{:?}", shape);
+        }
+        assert!(!Primitive2::from(Particle2::new()).contains(&Point2::new(0f32, 0.)));
+
+        let rectangle = Primitive2::from(Rectangle::new(4., 2.));
+        let t = transform(5., 0., Rad::<f32>::turn_div_4().0);
+        assert!(rectangle.contains_transformed(&Point2::new(5., 1.5), &t));
+        assert!(!rectangle.contains_transformed(&Point2::new(6.5, 0.), &t));
+    }
+
+    #[test]
+    fn test_closest_point() {
+        assert_closest(Circle::new(1.), [0., 3.], [0., 1.], false);
+        assert_closest(Rectangle::new(2., 1.), [0.9, 0.1], [1., 0.1], true);
+        assert_closest(Square::new(2.), [3., 3.], [1., 1.], false);
+        assert_closest(
+            ConvexPolygon::regular(4, 1.),
+            [0.1, 0.05],
+            [0.525, 0.475],
+            true,
+        );
+        assert_closest(
+            Triangle::new(
+                Point2::new(0., 0.),
+                Point2::new(1., 0.),
+                Point2::new(0., 1.),
+            ),
+            [1., 1.],
+            [0.5, 0.5],
+            false,
+        );
+        assert_closest(Ellipse::new(2., 1.), [0., 0.5], [0., 1.], true);
+        assert_closest(Capsule2::new(1., 0.5), [0., 3.], [0., 1.5], false);
+        assert_closest(
+            Line2::new(Point2::new(-1., 0.), Point2::new(1., 0.)),
+            [3., 1.],
+            [1., 0.],
+            false,
+        );
+        assert_closest(Particle2::new(), [1., 1.], [0., 0.], false);
+
+        let rectangle = Primitive2::from(Rectangle::new(4., 2.));
+        let t = transform(5., 0., Rad::<f32>::turn_div_4().0);
+        let (closest, inside) = rectangle.closest_point_transformed(&Point2::new(7., 0.), &t);
+        assert!(!inside);
+        assert_ulps_eq!(Point2::new(6., 0.), closest, epsilon = 1e-5);
+    }
+
+    fn assert_closest<C>(shape: C, point: [f32; 2], expected: [f32; 2], expected_inside: bool)
+    where
+        C: Into<Primitive2<f32>>,
+    {
+        let shape = shape.into();
+        let (closest, inside) = shape.closest_point(&Point2::from(point));
+        assert_ulps_eq!(Point2::from(expected), closest, epsilon = 1e-5);
+        assert_eq!(expected_inside, inside, "{:?}", shape);
+    }
+
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
         }
     }
 }
@@ -1,11 +1,15 @@
 //! Wrapper enum for 3D primitives
 
-use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::{BaseFloat, Decomposed, Point3, Quaternion, Vector3};
 use cgmath::prelude::*;
 
-use crate::{Aabb3, Ray3};
+use crate::{Aabb3, Dop3, Line3, Obb3, Ray3};
+use crate::algorithm::minkowski::GJK3;
 use crate::prelude::*;
-use crate::primitive::{Capsule, ConvexPolyhedron, Cube, Cuboid, Cylinder, Particle3, Quad, Sphere};
+use crate::primitive::{
+    Capsule, Cone, ConvexPolyhedron, Cube, Cuboid, Cylinder, Ellipsoid, Particle3, Quad,
+    RoundedCuboid, Sphere, Wedge,
+};
 
 /// Wrapper enum for 3D primitives, that also implements the `Primitive` trait, making it easier
 /// to use many different primitives in algorithms.
@@ -29,6 +33,14 @@ where
     Cylinder(Cylinder<S>),
     /// Capsule
     Capsule(Capsule<S>),
+    /// Cone
+    Cone(Cone<S>),
+    /// Ellipsoid
+    Ellipsoid(Ellipsoid<S>),
+    /// Cuboid with rounded edges and corners
+    RoundedCuboid(RoundedCuboid<S>),
+    /// Wedge
+    Wedge(Wedge<S>),
     /// Convex polyhedron with any number of vertices/faces
     ConvexPolyhedron(ConvexPolyhedron<S>),
 }
@@ -96,6 +108,42 @@ where
     }
 }
 
+impl<S> From<Cone<S>> for Primitive3<S>
+where
+    S: BaseFloat,
+{
+    fn from(cone: Cone<S>) -> Primitive3<S> {
+        Primitive3::Cone(cone)
+    }
+}
+
+impl<S> From<Ellipsoid<S>> for Primitive3<S>
+where
+    S: BaseFloat,
+{
+    fn from(ellipsoid: Ellipsoid<S>) -> Primitive3<S> {
+        Primitive3::Ellipsoid(ellipsoid)
+    }
+}
+
+impl<S> From<RoundedCuboid<S>> for Primitive3<S>
+where
+    S: BaseFloat,
+{
+    fn from(cuboid: RoundedCuboid<S>) -> Primitive3<S> {
+        Primitive3::RoundedCuboid(cuboid)
+    }
+}
+
+impl<S> From<Wedge<S>> for Primitive3<S>
+where
+    S: BaseFloat,
+{
+    fn from(wedge: Wedge<S>) -> Primitive3<S> {
+        Primitive3::Wedge(wedge)
+    }
+}
+
 impl<S> From<ConvexPolyhedron<S>> for Primitive3<S>
 where
     S: BaseFloat,
@@ -111,13 +159,17 @@ where
 {
     fn compute_bound(&self) -> Aabb3<S> {
         match *self {
-            Primitive3::Particle(_) => Aabb3::zero(),
+            Primitive3::Particle(ref particle) => particle.compute_bound(),
             Primitive3::Quad(ref quad) => quad.compute_bound(),
             Primitive3::Cuboid(ref cuboid) => cuboid.compute_bound(),
             Primitive3::Cube(ref cuboid) => cuboid.compute_bound(),
             Primitive3::Sphere(ref sphere) => sphere.compute_bound(),
             Primitive3::Cylinder(ref cylinder) => cylinder.compute_bound(),
             Primitive3::Capsule(ref capsule) => capsule.compute_bound(),
+            Primitive3::Cone(ref cone) => cone.compute_bound(),
+            Primitive3::Ellipsoid(ref ellipsoid) => ellipsoid.compute_bound(),
+            Primitive3::RoundedCuboid(ref cuboid) => cuboid.compute_bound(),
+            Primitive3::Wedge(ref wedge) => wedge.compute_bound(),
             Primitive3::ConvexPolyhedron(ref polyhedron) => polyhedron.compute_bound(),
         }
     }
@@ -129,21 +181,44 @@ where
 {
     fn compute_bound(&self) -> crate::volume::Sphere<S> {
         match *self {
-            Primitive3::Particle(_) => crate::volume::Sphere {
-                center: Point3::origin(),
-                radius: S::zero(),
-            },
+            Primitive3::Particle(ref particle) => particle.compute_bound(),
             Primitive3::Quad(ref quad) => quad.compute_bound(),
             Primitive3::Cuboid(ref cuboid) => cuboid.compute_bound(),
             Primitive3::Cube(ref cuboid) => cuboid.compute_bound(),
             Primitive3::Sphere(ref sphere) => sphere.compute_bound(),
             Primitive3::Cylinder(ref cylinder) => cylinder.compute_bound(),
             Primitive3::Capsule(ref capsule) => capsule.compute_bound(),
+            Primitive3::Cone(ref cone) => cone.compute_bound(),
+            Primitive3::Ellipsoid(ref ellipsoid) => ellipsoid.compute_bound(),
+            Primitive3::RoundedCuboid(ref cuboid) => cuboid.compute_bound(),
+            Primitive3::Wedge(ref wedge) => wedge.compute_bound(),
             Primitive3::ConvexPolyhedron(ref polyhedron) => polyhedron.compute_bound(),
         }
     }
 }
 
+/// The local box is the AABB of the primitive. Unlike the AABB, the box rotates with the
+/// primitive in [`compute_bound_transformed`](../trait.ComputeBound.html#method.compute_bound_transformed),
+/// so it does not grow when the primitive is rotated.
+impl<S> ComputeBound<Obb3<S>> for Primitive3<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Obb3<S> {
+        let aabb: Aabb3<S> = self.compute_bound();
+        Obb3::from_aabb(&aabb, &Decomposed::<Vector3<S>, Quaternion<S>>::one())
+    }
+}
+
+impl<S> ComputeBound<Dop3<S>> for Primitive3<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Dop3<S> {
+        Dop3::from_primitive(self, &Decomposed::<Vector3<S>, Quaternion<S>>::one())
+    }
+}
+
 impl<S> Primitive for Primitive3<S>
 where
     S: BaseFloat,
@@ -155,13 +230,17 @@ where
         T: Transform<Point3<S>>,
     {
         match *self {
-            Primitive3::Particle(_) => transform.transform_point(Point3::origin()),
+            Primitive3::Particle(ref particle) => particle.support_point(direction, transform),
             Primitive3::Quad(ref quad) => quad.support_point(direction, transform),
             Primitive3::Sphere(ref sphere) => sphere.support_point(direction, transform),
             Primitive3::Cuboid(ref cuboid) => cuboid.support_point(direction, transform),
             Primitive3::Cube(ref cuboid) => cuboid.support_point(direction, transform),
             Primitive3::Cylinder(ref cylinder) => cylinder.support_point(direction, transform),
             Primitive3::Capsule(ref capsule) => capsule.support_point(direction, transform),
+            Primitive3::Cone(ref cone) => cone.support_point(direction, transform),
+            Primitive3::Ellipsoid(ref ellipsoid) => ellipsoid.support_point(direction, transform),
+            Primitive3::RoundedCuboid(ref cuboid) => cuboid.support_point(direction, transform),
+            Primitive3::Wedge(ref wedge) => wedge.support_point(direction, transform),
             Primitive3::ConvexPolyhedron(ref polyhedron) => {
                 polyhedron.support_point(direction, transform)
             }
@@ -169,6 +248,155 @@ where
     }
 }
 
+impl<S> Primitive3<S>
+where
+    S: BaseFloat,
+{
+    /// Center and radius in world space, if the primitive is a sphere or a particle
+    pub(crate) fn as_sphere<T>(&self, transform: &T) -> Option<(Point3<S>, S)>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let center = transform.transform_point(Point3::origin());
+        match *self {
+            Primitive3::Particle(_) => Some((center, S::zero())),
+            Primitive3::Sphere(ref sphere) => Some((
+                center,
+                transform
+                    .transform_vector(Vector3::new(sphere.radius, S::zero(), S::zero()))
+                    .magnitude(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Box in world space, if the primitive is a cuboid or a cube, and the transform does not
+    /// rotate it
+    pub(crate) fn as_aabb<T>(&self, transform: &T) -> Option<Aabb3<S>>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let half_dim = match *self {
+            Primitive3::Cuboid(ref cuboid) => *cuboid.half_dim(),
+            Primitive3::Cube(ref cube) => Vector3::from_value(cube.half_dim()),
+            _ => return None,
+        };
+        let x = transform.transform_vector(Vector3::new(half_dim.x, S::zero(), S::zero()));
+        let y = transform.transform_vector(Vector3::new(S::zero(), half_dim.y, S::zero()));
+        let z = transform.transform_vector(Vector3::new(S::zero(), S::zero(), half_dim.z));
+        let aligned = x.y.is_zero()
+            && x.z.is_zero()
+            && y.x.is_zero()
+            && y.z.is_zero()
+            && z.x.is_zero()
+            && z.y.is_zero();
+        if !aligned {
+            return None;
+        }
+        let center = transform.transform_point(Point3::origin());
+        let half_extents = Vector3::new(x.x.abs(), y.y.abs(), z.z.abs());
+        Some(Aabb3::new(center - half_extents, center + half_extents))
+    }
+
+    /// Core segment and radius in world space, if the primitive is a capsule
+    pub(crate) fn as_capsule<T>(&self, transform: &T) -> Option<(Line3<S>, S)>
+    where
+        T: Transform<Point3<S>>,
+    {
+        match *self {
+            Primitive3::Capsule(ref capsule) => {
+                let segment = capsule.segment();
+                let radius = transform
+                    .transform_vector(Vector3::new(capsule.radius(), S::zero(), S::zero()))
+                    .magnitude();
+                Some((
+                    Line3::new(
+                        transform.transform_point(segment.origin),
+                        transform.transform_point(segment.dest),
+                    ),
+                    radius,
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<S> Distance for Primitive3<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn distance<TL, TR>(
+        &self,
+        transform: &TL,
+        other: &Primitive3<S>,
+        other_transform: &TR,
+    ) -> Option<S>
+    where
+        TL: Transform<Point3<S>>,
+        TR: Transform<Point3<S>>,
+    {
+        match (self.as_sphere(transform), other.as_sphere(other_transform)) {
+            (Some((center, radius)), Some((other_center, other_radius))) => {
+                let distance = (other_center - center).magnitude() - radius - other_radius;
+                if distance > S::zero() {
+                    Some(distance)
+                } else {
+                    None
+                }
+            }
+            _ => GJK3::new().distance(self, transform, other, other_transform),
+        }
+    }
+}
+
+impl<S> Contains<Point3<S>> for Primitive3<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space. Particles and quads contain no points.
+    fn contains(&self, point: &Point3<S>) -> bool {
+        match *self {
+            Primitive3::Particle(_) | Primitive3::Quad(_) => false,
+            Primitive3::Sphere(ref sphere) => sphere.contains(point),
+            Primitive3::Cuboid(ref cuboid) => cuboid.contains(point),
+            Primitive3::Cube(ref cube) => cube.contains(point),
+            Primitive3::Cylinder(ref cylinder) => cylinder.contains(point),
+            Primitive3::Capsule(ref capsule) => capsule.contains(point),
+            Primitive3::Cone(ref cone) => cone.contains(point),
+            Primitive3::Ellipsoid(ref ellipsoid) => ellipsoid.contains(point),
+            Primitive3::RoundedCuboid(ref cuboid) => cuboid.contains(point),
+            Primitive3::Wedge(ref wedge) => wedge.contains(point),
+            Primitive3::ConvexPolyhedron(ref polyhedron) => polyhedron.contains(point),
+        }
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for Primitive3<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        match *self {
+            Primitive3::Particle(ref particle) => particle.closest_point(point),
+            Primitive3::Quad(ref quad) => quad.closest_point(point),
+            Primitive3::Sphere(ref sphere) => sphere.closest_point(point),
+            Primitive3::Cuboid(ref cuboid) => cuboid.closest_point(point),
+            Primitive3::Cube(ref cube) => cube.closest_point(point),
+            Primitive3::Cylinder(ref cylinder) => cylinder.closest_point(point),
+            Primitive3::Capsule(ref capsule) => capsule.closest_point(point),
+            Primitive3::Cone(ref cone) => cone.closest_point(point),
+            Primitive3::Ellipsoid(ref ellipsoid) => ellipsoid.closest_point(point),
+            Primitive3::RoundedCuboid(ref cuboid) => cuboid.closest_point(point),
+            Primitive3::Wedge(ref wedge) => wedge.closest_point(point),
+            Primitive3::ConvexPolyhedron(ref polyhedron) => polyhedron.closest_point(point),
+        }
+    }
+}
+
 impl<S> DiscreteTransformed<Ray3<S>> for Primitive3<S>
 where
     S: BaseFloat,
@@ -187,6 +415,12 @@ where
             Primitive3::Cube(ref cuboid) => cuboid.intersects_transformed(ray, transform),
             Primitive3::Cylinder(ref cylinder) => cylinder.intersects_transformed(ray, transform),
             Primitive3::Capsule(ref capsule) => capsule.intersects_transformed(ray, transform),
+            Primitive3::Cone(ref cone) => cone.intersects_transformed(ray, transform),
+            Primitive3::Ellipsoid(ref ellipsoid) => {
+                ellipsoid.intersects_transformed(ray, transform)
+            }
+            Primitive3::RoundedCuboid(ref cuboid) => cuboid.intersects_transformed(ray, transform),
+            Primitive3::Wedge(ref wedge) => wedge.intersects_transformed(ray, transform),
             Primitive3::ConvexPolyhedron(ref polyhedron) => {
                 polyhedron.intersects_transformed(ray, transform)
             }
@@ -213,9 +447,228 @@ where
             Primitive3::Cube(ref cuboid) => cuboid.intersection_transformed(ray, transform),
             Primitive3::Cylinder(ref cylinder) => cylinder.intersection_transformed(ray, transform),
             Primitive3::Capsule(ref capsule) => capsule.intersection_transformed(ray, transform),
+            Primitive3::Cone(ref cone) => cone.intersection_transformed(ray, transform),
+            Primitive3::Ellipsoid(ref ellipsoid) => {
+                ellipsoid.intersection_transformed(ray, transform)
+            }
+            Primitive3::RoundedCuboid(ref cuboid) => {
+                cuboid.intersection_transformed(ray, transform)
+            }
+            Primitive3::Wedge(ref wedge) => wedge.intersection_transformed(ray, transform),
             Primitive3::ConvexPolyhedron(ref polyhedron) => {
                 polyhedron.intersection_transformed(ray, transform)
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Decomposed, Quaternion, Rad, Rotation3};
+
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK3;
+
+    #[test]
+    fn test_heterogeneous_storage() {
+        let shapes: Vec<Primitive3<f32>> = vec![
+            Sphere::new(1.).into(),
+            Cuboid::new(2., 2., 2.).into(),
+            Capsule::new(1., 0.5).into(),
+            Cone::new(1., 1.).into(),
+            Ellipsoid::new(2., 1., 1.).into(),
+            RoundedCuboid::new(2., 2., 2., 0.5).into(),
+            Wedge::new(2., 2., 2.).into(),
+        ];
+        let gjk = GJK3::new();
+        let t = transform(0., 0., 0.);
+        for shape in &shapes {
+            let bound: Aabb3<f32> = shape.compute_bound();
+            assert!(bound.contains(&Point3::origin()));
+            assert!(gjk
+                .intersect(shape, &t, &Primitive3::from(Sphere::new(0.5)), &t)
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn test_forward_ray() {
+        let ray = Ray3::new(Point3::new(-10., 0., 0.), Vector3::new(1., 0., 0.));
+        let t = transform(0., 0., 0.);
+        let ellipsoid = Primitive3::from(Ellipsoid::new(2., 1., 1.));
+        assert_eq!(
+            Some(Point3::new(-2., 0., 0.)),
+            ellipsoid.intersection_transformed(&ray, &t)
+        );
+        let cone = Primitive3::from(Cone::new(1., 1.));
+        assert!(cone.intersects_transformed(&ray, &t));
+        let cuboid = Primitive3::from(RoundedCuboid::new(2., 2., 2., 0.5));
+        assert_eq!(
+            Some(Point3::new(-1., 0., 0.)),
+            cuboid.intersection_transformed(&ray, &t)
+        );
+        assert!(!cuboid.intersects_transformed(&ray, &transform(0., 5., 0.)));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let sphere = Primitive3::from(Sphere::new(1.));
+        let dop: Dop3<f32> = sphere.compute_bound();
+        assert_ulps_eq!(3f32.sqrt(), dop.max[9]);
+        let cuboid = Primitive3::from(Cuboid::new(2., 2., 2.));
+        let dop: Dop3<f32> = cuboid.compute_bound();
+        assert_eq!(3., dop.max[9]);
+        assert_eq!(-2., dop.min[3]);
+        let obb: Obb3<f32> = cuboid.compute_bound();
+        assert_eq!(Vector3::new(1., 1., 1.), obb.half_extents);
+    }
+
+    #[test]
+    fn test_distance() {
+        let sphere = Primitive3::from(Sphere::new(1.));
+        let particle = Primitive3::from(Particle3::new());
+        let cuboid = Primitive3::from(Cuboid::new(2., 2., 2.));
+        let t = transform(0., 0., 0.);
+        assert_eq!(
+            Some(3.),
+            sphere.distance(&t, &sphere, &transform(5., 0., 0.))
+        );
+        assert_eq!(
+            Some(4.),
+            sphere.distance(&t, &particle, &transform(0., 0., 5.))
+        );
+        assert_eq!(None, sphere.distance(&t, &sphere, &transform(1., 1., 0.)));
+        assert_ulps_eq!(
+            3.,
+            cuboid
+                .distance(&t, &sphere, &transform(0., 5., 0.))
+                .unwrap(),
+            epsilon = 1e-5
+        );
+        assert_eq!(None, cuboid.distance(&t, &cuboid, &transform(1., 1., 1.)));
+    }
+
+    #[test]
+    fn test_contains() {
+        let inside = Point3::new(-0.3, -0.3, 0.3);
+        let outside = Point3::new(0.9, 0.9, 0.9);
+        let shapes: Vec<Primitive3<f32>> = vec![
+            Sphere::new(1.).into(),
+            Cuboid::new(1.6, 1., 1.).into(),
+            Cube::new(1.).into(),
+            Cylinder::new(0.5, 0.6).into(),
+            Capsule::new(0.5, 0.6).into(),
+            Cone::new(1., 1.5).into(),
+            Ellipsoid::new(1., 0.8, 1.).into(),
+            RoundedCuboid::new(1.6, 1.6, 1.6, 0.5).into(),
+            Wedge::new(2., 2., 2.).into(),
+        ];
+        for shape in &shapes {
+            assert!(shape.contains(&inside), "{:?}", shape);
+            assert!(!shape.contains(&outside), "{:?}", shape);
+        }
+        assert!(!Primitive3::from(Particle3::new()).contains(&Point3::new(0f32, 0., 0.)));
+
+        let sphere = Primitive3::from(Sphere::new(1.));
+        let t = transform(0., 0., 5.);
+        assert!(sphere.contains_transformed(&Point3::new(0., 0., 4.5), &t));
+        assert!(!sphere.contains_transformed(&Point3::origin(), &t));
+    }
+
+    #[test]
+    fn test_closest_point() {
+        assert_closest(Sphere::new(1.), [0., 0., 3.], [0., 0., 1.], false);
+        assert_closest(Sphere::new(1.), [0., 0., 0.5], [0., 0., 1.], true);
+        assert_closest(Cuboid::new(2., 2., 2.), [3., 0.5, 0.], [1., 0.5, 0.], false);
+        assert_closest(Cube::new(2.), [0.8, 0.1, 0.], [1., 0.1, 0.], true);
+        assert_closest(Cylinder::new(1., 0.5), [0., 3., 0.2], [0., 1., 0.2], false);
+        assert_closest(Cylinder::new(1., 0.5), [0., 0., 0.4], [0., 0., 0.5], true);
+        assert_closest(Capsule::new(1., 0.5), [3., 0.5, 0.], [0.5, 0.5, 0.], false);
+        assert_closest(Cone::new(1., 1.), [1., 1., 0.], [0.2, 0.6, 0.], false);
+        assert_closest(
+            Ellipsoid::new(2., 1., 1.),
+            [0., 3., 0.],
+            [0., 1., 0.],
+            false,
+        );
+        assert_closest(Ellipsoid::new(2., 1., 1.), [0., 0., 0.], [0., 1., 0.], true);
+        assert_closest(
+            RoundedCuboid::new(2., 2., 2., 0.5),
+            [0.1, 0.9, 0.],
+            [0.1, 1., 0.],
+            true,
+        );
+        assert_closest(
+            RoundedCuboid::new(2., 2., 2., 0.5),
+            [0., 0.2, 0.1],
+            [0., 1., 0.1],
+            true,
+        );
+        assert_closest(Wedge::new(2., 2., 2.), [-2., 0., 0.], [-1., 0., 0.], false);
+        assert_closest(Wedge::new(2., 2., 2.), [0.5, 0.5, 0.], [0., 0., 0.], false);
+        assert_closest(Quad::new(2., 2.), [0.5, 3., 2.], [0.5, 1., 0.], false);
+        assert_closest(Particle3::new(), [1., 1., 1.], [0., 0., 0.], false);
+
+        // the closest point on an ellipsoid is where the surface normal points at the point
+        let point = Point3::new(3., 2., 0.);
+        let (closest, inside) = Ellipsoid::new(2., 1., 1.).closest_point(&point);
+        assert!(!inside);
+        assert_ulps_eq!(
+            1.,
+            closest.x * closest.x / 4. + closest.y * closest.y,
+            epsilon = 1e-5
+        );
+        let normal = Vector3::new(closest.x / 4., closest.y, 0.);
+        assert_ulps_eq!(
+            0.,
+            normal.cross(point - closest).magnitude(),
+            epsilon = 1e-5
+        );
+
+        let sphere = Primitive3::from(Sphere::new(1.));
+        let t = transform(0., 0., 5.);
+        let (closest, inside) = sphere.closest_point_transformed(&Point3::origin(), &t);
+        assert!(!inside);
+        assert_ulps_eq!(Point3::new(0., 0., 4.), closest, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_signed_distance() {
+        let cube = Primitive3::from(Cube::new(2.));
+        assert_ulps_eq!(2., cube.signed_distance(&Point3::new(3., 0., 0.)));
+        assert_ulps_eq!(-0.5, cube.signed_distance(&Point3::new(0., 0.5, 0.)));
+        let quad = Primitive3::from(Quad::new(2., 2.));
+        assert_ulps_eq!(0., quad.signed_distance(&Point3::new(0.5, 0.5, 0.)));
+
+        let sphere = Primitive3::from(Sphere::new(1.));
+        let t = transform(0., 0., 5.);
+        assert_ulps_eq!(
+            -0.5,
+            sphere.signed_distance_transformed(&Point3::new(0., 0., 4.5), &t)
+        );
+        assert_ulps_eq!(
+            4.,
+            sphere.signed_distance_transformed(&Point3::origin(), &t)
+        );
+    }
+
+    fn assert_closest<C>(shape: C, point: [f32; 3], expected: [f32; 3], expected_inside: bool)
+    where
+        C: Into<Primitive3<f32>>,
+    {
+        let shape = shape.into();
+        let (closest, inside) = shape.closest_point(&Point3::from(point));
+        assert_ulps_eq!(Point3::from(expected), closest, epsilon = 1e-5);
+        assert_eq!(expected_inside, inside, "{:?}", shape);
+    }
+
+    fn transform(dx: f32, dy: f32, dz: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(0.)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
@@ -9,7 +9,8 @@ use crate::primitive::util::get_max_point;
 
 /// Rectangular plane primitive. Will lie on the xy plane when not transformed.
 ///
-/// Have a cached set of corner points to speed up computation.
+/// Have a cached set of corner points to speed up computation. Ray tests hit the quad from both
+/// sides, which makes it usable for thin panels, billboards and portals.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Quad<S> {
@@ -48,6 +49,23 @@ where
         &self.half_dim
     }
 
+    /// Find the ray parameter where the ray passes through the quad, from either side
+    fn ray_parameter(&self, ray: &Ray3<S>) -> Option<S> {
+        if ray.direction.z.is_zero() {
+            return None;
+        }
+        let t = -ray.origin.z / ray.direction.z;
+        if t < S::zero() {
+            return None;
+        }
+        let p = ray.origin + ray.direction * t;
+        if p.x.abs() <= self.half_dim.x && p.y.abs() <= self.half_dim.y {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
     fn generate_corners(half_dim: &Vector2<S>) -> [Point3<S>; 4] {
         [
             Point3::new(half_dim.x, half_dim.y, S::zero()),
@@ -91,19 +109,33 @@ where
     fn compute_bound(&self) -> Sphere<S> {
         Sphere {
             center: Point3::origin(),
-            radius: self.half_dim.x.max(self.half_dim.y),
+            radius: self.half_dim.magnitude(),
         }
     }
 }
 
+impl<S> ClosestPoint<Point3<S>> for Quad<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space. The quad has no interior, so the point is never inside.
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        let closest = Point3::new(
+            point.x.max(-self.half_dim.x).min(self.half_dim.x),
+            point.y.max(-self.half_dim.y).min(self.half_dim.y),
+            S::zero(),
+        );
+        (closest, false)
+    }
+}
+
 impl<S> Discrete<Ray3<S>> for Quad<S>
 where
     S: BaseFloat,
 {
     /// Ray must be in object space of the rectangle
     fn intersects(&self, ray: &Ray3<S>) -> bool {
-        let aabb: Aabb3<S> = self.compute_bound();
-        aabb.intersects(ray)
+        self.ray_parameter(ray).is_some()
     }
 }
 
@@ -113,10 +145,10 @@ where
 {
     type Result = Point3<S>;
 
-    /// Ray must be in object space of the rectangle
+    /// Ray must be in object space of the rectangle. Rays lying in the plane of the quad never
+    /// intersect.
     fn intersection(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
-        let aabb: Aabb3<S> = self.compute_bound();
-        aabb.intersection(ray)
+        self.ray_parameter(ray).map(|t| ray.origin + ray.direction * t)
     }
 }
 
@@ -148,4 +180,55 @@ mod tests {
                 .is_some()
         );
     }
+
+    #[test]
+    fn test_quad_bound() {
+        let quad = Quad::new(6., 8.);
+        assert_eq!(
+            Aabb3::new(Point3::new(-3., -4., 0.), Point3::new(3., 4., 0.)),
+            quad.compute_bound()
+        );
+        let sphere: Sphere<f32> = quad.compute_bound();
+        assert_eq!(5., sphere.radius);
+    }
+
+    #[test]
+    fn test_quad_ray_both_sides() {
+        let quad = Quad::new(2., 2.);
+        let ray = Ray3::new(Point3::new(0.5, 0.5, 5.), Vector3::new(0., 0., -1.));
+        assert!(quad.intersects(&ray));
+        assert_eq!(Some(Point3::new(0.5, 0.5, 0.)), quad.intersection(&ray));
+        let ray = Ray3::new(Point3::new(0.5, 0.5, -5.), Vector3::new(0., 0., 1.));
+        assert!(quad.intersects(&ray));
+        assert_eq!(Some(Point3::new(0.5, 0.5, 0.)), quad.intersection(&ray));
+        let ray = Ray3::new(Point3::new(-2., 0., -1.), Vector3::new(1., 0., 1.));
+        assert_eq!(Some(Point3::new(-1., 0., 0.)), quad.intersection(&ray));
+    }
+
+    #[test]
+    fn test_quad_ray_miss() {
+        let quad = Quad::new(2., 2.);
+        // outside the quad
+        let ray = Ray3::new(Point3::new(1.5, 0., 5.), Vector3::new(0., 0., -1.));
+        assert!(!quad.intersects(&ray));
+        // pointing away
+        let ray = Ray3::new(Point3::new(0., 0., 5.), Vector3::new(0., 0., 1.));
+        assert!(!quad.intersects(&ray));
+        assert_eq!(None, quad.intersection(&ray));
+        // parallel to the quad
+        let ray = Ray3::new(Point3::new(-5., 0., 0.), Vector3::new(1., 0., 0.));
+        assert!(!quad.intersects(&ray));
+        assert_eq!(None, quad.intersection(&ray));
+    }
+
+    #[test]
+    fn test_quad_ray_transformed() {
+        let quad = Quad::new(2., 2.);
+        let ray = Ray3::new(Point3::new(0.5, 0.5, 5.), Vector3::new(0., 0., -1.));
+        assert_eq!(
+            Some(Point3::new(0.5, 0.5, 1.)),
+            quad.intersection_transformed(&ray, &transform(0., 0., 1.))
+        );
+        assert!(!quad.intersects_transformed(&ray, &transform(2., 0., 1.)));
+    }
 }
@@ -5,7 +5,8 @@ use cgmath::prelude::*;
 
 use crate::{Aabb2, Ray2};
 use crate::prelude::*;
-use crate::primitive::util::get_max_point;
+use crate::primitive::util::{closest_point_on_box, get_max_point};
+use crate::volume::Circle;
 
 /// Rectangle primitive.
 ///
@@ -84,13 +85,47 @@ where
     }
 }
 
+impl<S> ComputeBound<Circle<S>> for Rectangle<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: self.half_dim.magnitude(),
+        }
+    }
+}
+
+impl<S> Contains<Point2<S>> for Rectangle<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        point.x.abs() <= self.half_dim.x && point.y.abs() <= self.half_dim.y
+    }
+}
+
+impl<S> ClosestPoint<Point2<S>> for Rectangle<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point2<S>) -> (Point2<S>, bool) {
+        let (closest, inside) = closest_point_on_box(point.to_vec(), self.half_dim);
+        (Point2::from_vec(closest), inside)
+    }
+}
+
 impl<S> Discrete<Ray2<S>> for Rectangle<S>
 where
     S: BaseFloat,
 {
     /// Ray must be in object space of the rectangle
     fn intersects(&self, ray: &Ray2<S>) -> bool {
-        self.compute_bound().intersects(ray)
+        let bound: Aabb2<S> = self.compute_bound();
+        bound.intersects(ray)
     }
 }
 
@@ -102,7 +137,8 @@ where
 
     /// Ray must be in object space of the rectangle
     fn intersection(&self, ray: &Ray2<S>) -> Option<Point2<S>> {
-        self.compute_bound().intersection(ray)
+        let bound: Aabb2<S> = self.compute_bound();
+        bound.intersection(ray)
     }
 }
 
@@ -160,6 +196,35 @@ where
     }
 }
 
+impl<S> ComputeBound<Circle<S>> for Square<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        self.rectangle.compute_bound()
+    }
+}
+
+impl<S> Contains<Point2<S>> for Square<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        self.rectangle.contains(point)
+    }
+}
+
+impl<S> ClosestPoint<Point2<S>> for Square<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point2<S>) -> (Point2<S>, bool) {
+        self.rectangle.closest_point(point)
+    }
+}
+
 impl<S> Discrete<Ray2<S>> for Square<S>
 where
     S: BaseFloat,
@@ -182,6 +247,44 @@ where
     }
 }
 
+impl<S> Geometry2 for Rectangle<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        self.dim.x * self.dim.y
+    }
+
+    fn perimeter(&self) -> S {
+        (self.dim.x + self.dim.y) * (S::one() + S::one())
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        Point2::origin()
+    }
+}
+
+impl<S> Geometry2 for Square<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        self.rectangle.area()
+    }
+
+    fn perimeter(&self) -> S {
+        self.rectangle.perimeter()
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        self.rectangle.centroid()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use cgmath::{Basis2, Decomposed, Point2, Rad, Vector2};
@@ -242,6 +345,17 @@ mod tests {
         assert_ulps_eq!(0., p.y);
     }
 
+    #[test]
+    fn test_rectangle_geometry() {
+        let r = Rectangle::new(10., 4.);
+        assert_ulps_eq!(40., r.area());
+        assert_ulps_eq!(28., r.perimeter());
+        assert_eq!(Point2::new(0., 0.), r.centroid());
+        let s = Square::new(3.);
+        assert_ulps_eq!(9., s.area());
+        assert_ulps_eq!(12., s.perimeter());
+    }
+
     // util
     fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
         Decomposed {
@@ -0,0 +1,201 @@
+//! Surface of revolution primitive
+
+use cgmath::{BaseFloat, Basis2, Decomposed, Point2, Point3, Vector2, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Aabb3};
+use crate::prelude::*;
+use crate::volume::Sphere;
+
+/// Wrapper that revolves a convex 2D profile around the Y axis.
+///
+/// The profile is a 2D primitive, where the X coordinate is the distance from the Y axis, and the
+/// Y coordinate is the height along the Y axis. Revolving the profile gives a lathed shape, for
+/// example a `Rectangle` gives a cylinder, a `Circle` gives a sphere, and a `Triangle` with one
+/// side on the Y axis gives a cone, with the support function computed exactly from the profile.
+///
+/// The profile should be convex and lie on the positive side of the Y axis, or be symmetric
+/// around the Y axis. Otherwise, the support function is the support function of the convex hull
+/// of the revolved shape. There are no ray tests for surfaces of revolution.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Revolution<P> {
+    /// Profile to revolve around the Y axis
+    pub profile: P,
+}
+
+impl<P> Revolution<P> {
+    /// Create a new surface of revolution from the given profile
+    pub fn new(profile: P) -> Self {
+        Self { profile }
+    }
+}
+
+impl<P, S> Primitive for Revolution<P>
+where
+    S: BaseFloat,
+    P: Primitive<Point = Point2<S>>,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        // the support point lies in the plane through the Y axis and the direction
+        let radial = Vector2::new(direction.x, direction.z);
+        let length = radial.magnitude();
+        let p = self.profile.support_point(
+            &Vector2::new(length, direction.y),
+            &Decomposed::<Vector2<S>, Basis2<S>>::one(),
+        );
+        let radial = if length.is_zero() {
+            Vector2::unit_x()
+        } else {
+            radial / length
+        };
+        transform.transform_point(Point3::new(radial.x * p.x, p.y, radial.y * p.x))
+    }
+}
+
+impl<P, S> ComputeBound<Aabb3<S>> for Revolution<P>
+where
+    S: BaseFloat,
+    P: ComputeBound<Aabb2<S>>,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        let bound: Aabb2<S> = self.profile.compute_bound();
+        let radius = bound.min.x.abs().max(bound.max.x.abs());
+        Aabb3::new(
+            Point3::new(-radius, bound.min.y, -radius),
+            Point3::new(radius, bound.max.y, radius),
+        )
+    }
+}
+
+impl<P, S> ComputeBound<Sphere<S>> for Revolution<P>
+where
+    S: BaseFloat,
+    P: ComputeBound<Aabb2<S>>,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        let bound: Aabb2<S> = self.profile.compute_bound();
+        let radius = bound.min.x.abs().max(bound.max.x.abs());
+        let half_height = (bound.max.y - bound.min.y) / (S::one() + S::one());
+        Sphere {
+            center: Point3::new(S::zero(), bound.min.y + half_height, S::zero()),
+            radius: (radius * radius + half_height * half_height).sqrt(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK3;
+    use crate::primitive::{Circle, Cone, Cylinder, Rectangle, Triangle};
+
+    #[test]
+    fn test_revolution_cylinder() {
+        let revolution = Revolution::new(Rectangle::new(4., 2.));
+        let cylinder = Cylinder::new(1., 2.);
+        let t = transform(0., 0., 0., 0.);
+        for direction in &[
+            Vector3::new(1., 0.5, 0.2),
+            Vector3::new(-0.3, -1., 0.7),
+            Vector3::new(0.1, 2., -1.),
+        ] {
+            assert_ulps_eq!(
+                cylinder.support_point(direction, &t),
+                revolution.support_point(direction, &t),
+                epsilon = 1e-5
+            );
+        }
+    }
+
+    #[test]
+    fn test_revolution_cone() {
+        let profile = Triangle::new(
+            Point2::new(0., 1.),
+            Point2::new(0., -1.),
+            Point2::new(2., -1.),
+        );
+        let revolution = Revolution::new(profile);
+        let cone = Cone::new(1., 2.);
+        let t = transform(0., 0., 0., 0.);
+        for direction in &[
+            Vector3::new(1., 0.5, 0.2),
+            Vector3::new(-0.3, -1., 0.7),
+            Vector3::new(0.1, 2., -1.),
+        ] {
+            assert_ulps_eq!(
+                cone.support_point(direction, &t),
+                revolution.support_point(direction, &t),
+                epsilon = 1e-5
+            );
+        }
+        // along the axis
+        assert_ulps_eq!(
+            Point3::new(0., 1., 0.),
+            revolution.support_point(&Vector3::new(0., 1., 0.), &t)
+        );
+    }
+
+    #[test]
+    fn test_revolution_transformed() {
+        let revolution = Revolution::new(Circle::new(2.));
+        let t = transform(0., 10., 0., std::f32::consts::PI / 2.);
+        assert_ulps_eq!(
+            Point3::new(2., 10., 0.),
+            revolution.support_point(&Vector3::new(1., 0., 0.), &t),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_revolution_bound() {
+        let profile = Triangle::new(
+            Point2::new(0., 3.),
+            Point2::new(0., -1.),
+            Point2::new(2., -1.),
+        );
+        let revolution = Revolution::new(profile);
+        assert_eq!(
+            Aabb3::new(Point3::new(-2., -1., -2.), Point3::new(2., 3., 2.)),
+            revolution.compute_bound()
+        );
+        let sphere: Sphere<f32> = revolution.compute_bound();
+        assert_eq!(Point3::new(0., 1., 0.), sphere.center);
+        assert_ulps_eq!(8f32.sqrt(), sphere.radius);
+    }
+
+    #[test]
+    fn test_revolution_gjk() {
+        let revolution = Revolution::new(Rectangle::new(2., 2.));
+        let other = Revolution::new(Circle::new(1.));
+        let gjk = GJK3::new();
+        let t = transform(0., 0., 0., 0.);
+        // round in the XZ plane, so the corner of the bounding box is empty
+        assert!(gjk
+            .intersect(&revolution, &t, &other, &transform(1.6, 0.1, 1.6, 0.))
+            .is_none());
+        assert!(gjk
+            .intersect(&revolution, &t, &other, &transform(1.3, 0.1, 1.3, 0.))
+            .is_some());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(rot)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
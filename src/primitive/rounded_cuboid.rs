@@ -0,0 +1,416 @@
+//! Rounded cuboid primitive
+
+use std::cmp::Ordering;
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, Ray3};
+use crate::prelude::*;
+use crate::primitive::util::closest_point_on_box;
+use crate::volume::Sphere;
+
+/// Cuboid primitive with rounded edges and corners.
+///
+/// The shape is a cuboid core, grown by `radius` in all directions. The rounded shape has smooth
+/// normals everywhere, which gives much more stable contacts than a sharp cuboid when resting on
+/// other shapes, and the support function is as cheap as the support function of a cuboid.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RoundedCuboid<S> {
+    /// Dimensions of the box, including the rounding
+    dim: Vector3<S>,
+    half_dim: Vector3<S>,
+    core: Vector3<S>,
+    radius: S,
+}
+
+impl<S> RoundedCuboid<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new rounded cuboid primitive from component dimensions, and the radius of the
+    /// edges and corners. The dimensions include the rounding, so the rounded cuboid fits exactly
+    /// in a `Cuboid` with the same dimensions.
+    ///
+    /// ## Panics
+    ///
+    /// If the radius is negative, or larger than half the smallest dimension.
+    pub fn new(dim_x: S, dim_y: S, dim_z: S, radius: S) -> Self {
+        Self::new_impl(Vector3::new(dim_x, dim_y, dim_z), radius)
+    }
+
+    /// Create a new rounded cuboid primitive from a vector of component dimensions, and the
+    /// radius of the edges and corners.
+    ///
+    /// ## Panics
+    ///
+    /// If the radius is negative, or larger than half the smallest dimension.
+    pub fn new_impl(dim: Vector3<S>, radius: S) -> Self {
+        let half_dim = dim / (S::one() + S::one());
+        assert!(radius >= S::zero());
+        assert!(radius <= half_dim.x.min(half_dim.y).min(half_dim.z));
+        Self {
+            dim,
+            half_dim,
+            core: half_dim - Vector3::from_value(radius),
+            radius,
+        }
+    }
+
+    /// Get the dimensions of the `RoundedCuboid`
+    pub fn dim(&self) -> &Vector3<S> {
+        &self.dim
+    }
+
+    /// Get the half dimensions of the `RoundedCuboid`
+    pub fn half_dim(&self) -> &Vector3<S> {
+        &self.half_dim
+    }
+
+    /// Get the radius of the edges and corners
+    pub fn radius(&self) -> S {
+        self.radius
+    }
+
+    /// Find the first ray parameter inside the shape, if any.
+    ///
+    /// The shape is the union of three boxes, each the core grown along one axis, twelve
+    /// cylinders along the edges, and eight spheres in the corners, so the ray enters the shape
+    /// where it enters the first of those parts.
+    fn ray_parameter(&self, ray: &Ray3<S>) -> Option<S> {
+        let o = ray.origin;
+        let d = ray.direction;
+        let c = self.core;
+        let r = self.radius;
+        let mut parts = Vec::with_capacity(23);
+        for axis in 0..3 {
+            let mut extent = c;
+            extent[axis] += r;
+            parts.push(
+                slab(o.x, d.x, extent.x)
+                    .and_then(|i| intersect(i, slab(o.y, d.y, extent.y)?))
+                    .and_then(|i| intersect(i, slab(o.z, d.z, extent.z)?)),
+            );
+        }
+        for &sx in &[-S::one(), S::one()] {
+            for &sy in &[-S::one(), S::one()] {
+                for &sz in &[-S::one(), S::one()] {
+                    let center = Vector3::new(sx * c.x, sy * c.y, sz * c.z);
+                    let l = o.to_vec() - center;
+                    parts.push(quadratic(d.magnitude2(), l.dot(d), l.magnitude2() - r * r));
+                }
+            }
+        }
+        for axis in 0..3 {
+            let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+            for &su in &[-S::one(), S::one()] {
+                for &sv in &[-S::one(), S::one()] {
+                    let lu = o[u] - su * c[u];
+                    let lv = o[v] - sv * c[v];
+                    let cylinder = quadratic(
+                        d[u] * d[u] + d[v] * d[v],
+                        lu * d[u] + lv * d[v],
+                        lu * lu + lv * lv - r * r,
+                    );
+                    parts.push(
+                        cylinder.and_then(|i| intersect(i, slab(o[axis], d[axis], c[axis])?)),
+                    );
+                }
+            }
+        }
+        parts
+            .into_iter()
+            .filter_map(|part| part.and_then(|i| intersect(i, (S::zero(), S::infinity()))))
+            .map(|(enter, _)| enter)
+            .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+    }
+}
+
+/// Ray parameter interval inside the slab `[-extent, extent]` along one axis
+fn slab<S>(origin: S, direction: S, extent: S) -> Option<(S, S)>
+where
+    S: BaseFloat,
+{
+    if direction.is_zero() {
+        if origin.abs() <= extent {
+            Some((S::neg_infinity(), S::infinity()))
+        } else {
+            None
+        }
+    } else {
+        let t1 = (-extent - origin) / direction;
+        let t2 = (extent - origin) / direction;
+        Some((t1.min(t2), t1.max(t2)))
+    }
+}
+
+/// Ray parameter interval where `a * t^2 + 2 * b * t + c <= 0`, for `a >= 0`
+fn quadratic<S>(a: S, b: S, c: S) -> Option<(S, S)>
+where
+    S: BaseFloat,
+{
+    if a.is_zero() {
+        return if c <= S::zero() {
+            Some((S::neg_infinity(), S::infinity()))
+        } else {
+            None
+        };
+    }
+    let discr = b * b - a * c;
+    if discr < S::zero() {
+        return None;
+    }
+    let discr = discr.sqrt();
+    Some(((-b - discr) / a, (-b + discr) / a))
+}
+
+fn intersect<S>(a: (S, S), b: (S, S)) -> Option<(S, S)>
+where
+    S: BaseFloat,
+{
+    let enter = a.0.max(b.0);
+    let exit = a.1.min(b.1);
+    if enter <= exit {
+        Some((enter, exit))
+    } else {
+        None
+    }
+}
+
+impl<S> Primitive for RoundedCuboid<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let corner = Point3::new(
+            extent(self.core.x, direction.x),
+            extent(self.core.y, direction.y),
+            extent(self.core.z, direction.z),
+        );
+        let point = if direction.magnitude2().is_zero() {
+            corner
+        } else {
+            corner + direction.normalize_to(self.radius)
+        };
+        transform.transform_point(point)
+    }
+}
+
+/// Extent of the core along one axis, towards the direction
+fn extent<S>(core: S, direction: S) -> S
+where
+    S: BaseFloat,
+{
+    if direction < S::zero() {
+        -core
+    } else {
+        core
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for RoundedCuboid<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        Aabb3::new(
+            Point3::from_vec(-self.half_dim),
+            Point3::from_vec(self.half_dim),
+        )
+    }
+}
+
+impl<S> ComputeBound<Sphere<S>> for RoundedCuboid<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere {
+            center: Point3::origin(),
+            radius: self.core.magnitude() + self.radius,
+        }
+    }
+}
+
+impl<S> Contains<Point3<S>> for RoundedCuboid<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        let closest = Point3::new(
+            point.x.max(-self.core.x).min(self.core.x),
+            point.y.max(-self.core.y).min(self.core.y),
+            point.z.max(-self.core.z).min(self.core.z),
+        );
+        point.distance2(closest) <= self.radius * self.radius
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for RoundedCuboid<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        let core_point = Point3::new(
+            point.x.max(-self.core.x).min(self.core.x),
+            point.y.max(-self.core.y).min(self.core.y),
+            point.z.max(-self.core.z).min(self.core.z),
+        );
+        let v = point - core_point;
+        let magnitude2 = v.magnitude2();
+        if magnitude2.is_zero() {
+            // inside the core, the closest point is on one of the flat faces
+            let (closest, _) = closest_point_on_box(point.to_vec(), self.half_dim);
+            return (Point3::from_vec(closest), true);
+        }
+        (
+            core_point + v * (self.radius / magnitude2.sqrt()),
+            magnitude2 <= self.radius * self.radius,
+        )
+    }
+}
+
+impl<S> Discrete<Ray3<S>> for RoundedCuboid<S>
+where
+    S: BaseFloat,
+{
+    fn intersects(&self, ray: &Ray3<S>) -> bool {
+        self.ray_parameter(ray).is_some()
+    }
+}
+
+impl<S> Continuous<Ray3<S>> for RoundedCuboid<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point3<S>;
+
+    fn intersection(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
+        self.ray_parameter(ray)
+            .map(|t| ray.origin + ray.direction * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK3;
+    use crate::primitive::Cuboid;
+
+    #[test]
+    fn test_rounded_cuboid_support() {
+        let cuboid = RoundedCuboid::new(4., 4., 4., 1.);
+        let t = transform(0., 0., 0., 0.);
+        assert_ulps_eq!(
+            Point3::new(2., 1., 1.),
+            cuboid.support_point(&Vector3::new(1., 0., 0.), &t)
+        );
+        let d = 1. / 3f32.sqrt();
+        assert_ulps_eq!(
+            Point3::new(1. + d, 1. + d, -1. - d),
+            cuboid.support_point(&Vector3::new(1., 1., -1.), &t),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_rounded_cuboid_support_transformed() {
+        let cuboid = RoundedCuboid::new(4., 2., 2., 0.5);
+        let t = transform(0., 10., 0., std::f32::consts::PI / 2.);
+        let point = cuboid.support_point(&Vector3::new(0., 1., 0.), &t);
+        assert_ulps_eq!(12., point.y, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_rounded_cuboid_bound() {
+        let cuboid = RoundedCuboid::new(4., 2., 6., 1.);
+        assert_eq!(
+            Aabb3::new(Point3::new(-2., -1., -3.), Point3::new(2., 1., 3.)),
+            cuboid.compute_bound()
+        );
+        let sphere: Sphere<f32> = cuboid.compute_bound();
+        assert_ulps_eq!(5f32.sqrt() + 1., sphere.radius);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rounded_cuboid_radius_too_large() {
+        RoundedCuboid::new(4., 2., 6., 1.5);
+    }
+
+    #[test]
+    fn test_rounded_cuboid_ray_face() {
+        let cuboid = RoundedCuboid::new(4., 4., 4., 1.);
+        let ray = Ray3::new(Point3::new(10., 0.5, 0.), Vector3::new(-1., 0., 0.));
+        assert!(cuboid.intersects(&ray));
+        assert_eq!(Some(Point3::new(2., 0.5, 0.)), cuboid.intersection(&ray));
+        let ray = Ray3::new(Point3::new(10., 0.5, 0.), Vector3::new(1., 0., 0.));
+        assert!(!cuboid.intersects(&ray));
+        // from inside
+        let ray = Ray3::new(Point3::new(0., 0.5, 0.), Vector3::new(1., 0., 0.));
+        assert_eq!(Some(Point3::new(0., 0.5, 0.)), cuboid.intersection(&ray));
+    }
+
+    #[test]
+    fn test_rounded_cuboid_ray_edge_and_corner() {
+        let cuboid = RoundedCuboid::new(4., 4., 4., 1.);
+        // misses the rounded edge, but hits the sharp box
+        let ray = Ray3::new(Point3::new(1.9, 10., 1.9), Vector3::new(0., -1., 0.));
+        assert!(!cuboid.intersects(&ray));
+        assert!(Cuboid::new(4., 4., 4.).intersects(&ray));
+        // hits the rounded edge along the Y axis
+        let ray = Ray3::new(Point3::new(10., 0., 10.), Vector3::new(-1., 0., -1.));
+        let d = 1. + std::f32::consts::FRAC_1_SQRT_2;
+        assert_ulps_eq!(
+            Point3::new(d, 0., d),
+            cuboid.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+        // hits the rounded corner
+        let ray = Ray3::new(Point3::new(10., 10., 10.), Vector3::new(-1., -1., -1.));
+        let d = 1. + 1. / 3f32.sqrt();
+        assert_ulps_eq!(
+            Point3::new(d, d, d),
+            cuboid.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_rounded_cuboid_gjk() {
+        let left = RoundedCuboid::new(2., 2., 2., 0.5);
+        let right = RoundedCuboid::new(2., 2., 2., 0.5);
+        let gjk = GJK3::new();
+        let t = transform(0., 0., 0., 0.);
+        assert!(gjk
+            .intersect(&left, &t, &right, &transform(1.9, 0.2, 0.1, 0.))
+            .is_some());
+        // the sharp corners would touch, but the rounded corners do not
+        assert!(gjk
+            .intersect(&left, &t, &right, &transform(1.9, 1.9, 1.9, 0.))
+            .is_none());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(rot)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Non-uniformly scaled primitive
+
+use cgmath::{BaseFloat, ElementWise};
+use cgmath::prelude::*;
+
+use crate::{Aabb, Ray};
+use crate::prelude::*;
+
+/// Wrapper that scales a primitive along each axis of its local space.
+///
+/// The support functions of most primitives assume that the transform they are given preserves
+/// their shape, so a non-uniform scale in the transform will give incorrect support points. By
+/// moving the scale into the primitive, the support point is computed correctly, for example a
+/// scaled `Circle` will give the same support points as an `Ellipse`.
+///
+/// The scale must be non-zero along all axes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Scaled<P, V> {
+    /// Wrapped primitive
+    pub primitive: P,
+    /// Scale along each local axis
+    pub scale: V,
+}
+
+impl<P, V> Scaled<P, V> {
+    /// Create a new scaled primitive
+    pub fn new(primitive: P, scale: V) -> Self {
+        Self { primitive, scale }
+    }
+}
+
+impl<P> Primitive for Scaled<P, <P::Point as EuclideanSpace>::Diff>
+where
+    P: Primitive,
+    <P::Point as EuclideanSpace>::Diff: ElementWise + Copy,
+{
+    type Point = P::Point;
+
+    fn support_point<T>(
+        &self,
+        direction: &<P::Point as EuclideanSpace>::Diff,
+        transform: &T,
+    ) -> P::Point
+    where
+        T: Transform<P::Point>,
+    {
+        // the support function of the scaled shape is the scaled support point of the shape in
+        // the scaled direction
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let p = self
+            .primitive
+            .support_point(&direction.mul_element_wise(self.scale), &T::one());
+        transform.transform_point(P::Point::from_vec(p.to_vec().mul_element_wise(self.scale)))
+    }
+}
+
+impl<P, A> ComputeBound<A> for Scaled<P, A::Diff>
+where
+    P: ComputeBound<A>,
+    A: Aabb + Bound,
+    A::Diff: Copy,
+{
+    fn compute_bound(&self) -> A {
+        self.primitive.compute_bound().mul_v(self.scale)
+    }
+}
+
+/// Map a ray from the local space of the scaled primitive to the space of the wrapped primitive
+fn inner_ray<S, P, V>(ray: &Ray<S, P, V>, scale: V) -> Ray<S, P, V>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S, Diff = V>,
+    V: InnerSpace<Scalar = S> + ElementWise,
+{
+    Ray::new(
+        P::from_vec(ray.origin.to_vec().div_element_wise(scale)),
+        ray.direction.div_element_wise(scale).normalize(),
+    )
+}
+
+impl<S, P, V, C> Discrete<Ray<S, P, V>> for Scaled<C, V>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S, Diff = V>,
+    V: InnerSpace<Scalar = S> + ElementWise,
+    C: Discrete<Ray<S, P, V>>,
+{
+    /// Ray must be in object space
+    fn intersects(&self, ray: &Ray<S, P, V>) -> bool {
+        self.primitive.intersects(&inner_ray(ray, self.scale))
+    }
+}
+
+impl<S, P, V, C> Continuous<Ray<S, P, V>> for Scaled<C, V>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S, Diff = V>,
+    V: InnerSpace<Scalar = S> + ElementWise,
+    C: Continuous<Ray<S, P, V>, Result = P>,
+{
+    type Result = P;
+
+    /// Ray must be in object space
+    fn intersection(&self, ray: &Ray<S, P, V>) -> Option<P> {
+        self.primitive
+            .intersection(&inner_ray(ray, self.scale))
+            .map(|p| P::from_vec(p.to_vec().mul_element_wise(self.scale)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{
+        Basis2, Decomposed, Point2, Point3, Quaternion, Rad, Rotation2, Vector2, Vector3,
+    };
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::{Aabb2, Aabb3, Ray2, Ray3};
+    use crate::algorithm::minkowski::GJK2;
+    use crate::primitive::{Circle, Cuboid, Ellipse, Sphere};
+
+    #[test]
+    fn test_scaled_circle_support() {
+        let scaled = Scaled::new(Circle::new(1.), Vector2::new(10., 5.));
+        let ellipse = Ellipse::new(10., 5.);
+        let t = transform(0., 0., 0.);
+        for direction in &[
+            Vector2::new(1., 0.),
+            Vector2::new(1., 1.),
+            Vector2::new(-0.3, 0.7),
+            Vector2::new(0., -1.),
+        ] {
+            assert_ulps_eq!(
+                ellipse.support_point(direction, &t),
+                scaled.support_point(direction, &t),
+                epsilon = 1e-5
+            );
+        }
+    }
+
+    #[test]
+    fn test_scaled_circle_support_transformed() {
+        let scaled = Scaled::new(Circle::new(1.), Vector2::new(10., 5.));
+        let ellipse = Ellipse::new(10., 5.);
+        let t = transform(3., -2., 0.4);
+        let direction = Vector2::new(1., 2.);
+        assert_ulps_eq!(
+            ellipse.support_point(&direction, &t),
+            scaled.support_point(&direction, &t),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_scaled_sphere_support() {
+        let scaled = Scaled::new(Sphere::new(1.), Vector3::new(1., 2., 3.));
+        let t = Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(0.)),
+            disp: Vector3::new(0., 0., 0.),
+        };
+        assert_ulps_eq!(
+            Point3::new(0., 0., 3.),
+            scaled.support_point(&Vector3::new(0., 0., 1.), &t)
+        );
+        assert_ulps_eq!(
+            Point3::new(0., -2., 0.),
+            scaled.support_point(&Vector3::new(0., -1., 0.), &t)
+        );
+    }
+
+    #[test]
+    fn test_scaled_bound() {
+        let scaled = Scaled::new(Circle::new(1.), Vector2::new(10., 5.));
+        let bound: Aabb2<f32> = scaled.compute_bound();
+        assert_eq!(
+            Aabb2::new(Point2::new(-10., -5.), Point2::new(10., 5.)),
+            bound
+        );
+        let scaled = Scaled::new(Cuboid::new(2., 2., 2.), Vector3::new(1., -2., 3.));
+        let bound: Aabb3<f32> = scaled.compute_bound();
+        assert_eq!(
+            Aabb3::new(Point3::new(-1., -2., -3.), Point3::new(1., 2., 3.)),
+            bound
+        );
+    }
+
+    #[test]
+    fn test_scaled_ray() {
+        let scaled = Scaled::new(Circle::new(1.), Vector2::new(10., 5.));
+        let ray = Ray2::new(Point2::new(20., 4.), Vector2::new(-1., 0.));
+        assert!(scaled.intersects(&ray));
+        let ray = Ray2::new(Point2::new(20., 6.), Vector2::new(-1., 0.));
+        assert!(!scaled.intersects(&ray));
+        let ray = Ray2::new(Point2::new(20., 0.), Vector2::new(-1., 0.));
+        assert_ulps_eq!(
+            Point2::new(10., 0.),
+            scaled.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+        let ray = Ray2::new(Point2::new(0., 20.), Vector2::new(0., -1.));
+        assert_ulps_eq!(
+            Point2::new(0., 5.),
+            scaled
+                .intersection_transformed(&ray, &transform(0., 0., 0.))
+                .unwrap(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_scaled_ray_3d() {
+        let scaled = Scaled::new(Sphere::new(1.), Vector3::new(1., 2., 3.));
+        let ray = Ray3::new(Point3::new(0., 0., 10.), Vector3::new(0., 0., -1.));
+        assert_ulps_eq!(
+            Point3::new(0., 0., 3.),
+            scaled.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_scaled_gjk() {
+        let scaled = Scaled::new(Circle::new(1.), Vector2::new(10., 1.));
+        let circle = Circle::new(1.);
+        let gjk = GJK2::new();
+        let t = transform(0., 0., 0.);
+        assert!(gjk
+            .intersect(&scaled, &t, &circle, &transform(10.5, 0., 0.))
+            .is_some());
+        assert!(gjk
+            .intersect(&scaled, &t, &circle, &transform(0., 2.5, 0.))
+            .is_none());
+        let t = transform(0., 0., std::f32::consts::PI / 2.);
+        assert!(gjk
+            .intersect(&scaled, &t, &circle, &transform(0., 10.5, 0.))
+            .is_some());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Implicit primitive defined by a signed distance function
+
+use std::fmt;
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, Ray3};
+use crate::prelude::*;
+use crate::volume::Sphere;
+
+/// Largest number of steps taken when sphere tracing a ray
+const MAX_TRACE_STEPS: usize = 256;
+
+/// Largest number of steps taken when searching for a support point
+const MAX_SUPPORT_STEPS: usize = 128;
+
+/// Largest number of steps taken when projecting a point onto the surface
+const MAX_PROJECT_STEPS: usize = 8;
+
+/// Implicit primitive, defined by a signed distance function and a bounding box.
+///
+/// The distance function gives the signed distance from a point in local space to the surface of
+/// the shape, negative inside the shape, and must not overestimate the distance. The bounding box
+/// must contain the whole shape. This makes it possible to use procedural shapes in queries.
+///
+/// Ray tests use sphere tracing, and the support function is found by gradient ascent along the
+/// surface, with the gradient estimated using central differences. Both are approximate, with a
+/// tolerance relative to the size of the bounding box. The support function is only correct for
+/// convex shapes.
+#[derive(Clone)]
+pub struct SdfPrimitive<F, S> {
+    distance: F,
+    bound: Aabb3<S>,
+}
+
+impl<F, S> SdfPrimitive<F, S>
+where
+    S: BaseFloat,
+    F: Fn(Point3<S>) -> S,
+{
+    /// Create a new implicit primitive from a signed distance function, and a bounding box that
+    /// contains the whole shape
+    pub fn new(distance: F, bound: Aabb3<S>) -> Self {
+        Self { distance, bound }
+    }
+
+    /// Get the bounding box of the shape
+    pub fn bound(&self) -> &Aabb3<S> {
+        &self.bound
+    }
+
+    /// Get the signed distance from the given point in local space to the surface
+    pub fn distance(&self, point: Point3<S>) -> S {
+        (self.distance)(point)
+    }
+
+    /// Estimate the outward surface normal at the given point in local space
+    pub fn normal(&self, point: Point3<S>) -> Vector3<S> {
+        let h = self.tolerance();
+        let gradient = Vector3::new(
+            self.distance(point + Vector3::unit_x() * h)
+                - self.distance(point - Vector3::unit_x() * h),
+            self.distance(point + Vector3::unit_y() * h)
+                - self.distance(point - Vector3::unit_y() * h),
+            self.distance(point + Vector3::unit_z() * h)
+                - self.distance(point - Vector3::unit_z() * h),
+        );
+        if gradient.magnitude2().is_zero() {
+            Vector3::zero()
+        } else {
+            gradient.normalize()
+        }
+    }
+
+    /// Distance below which a point is on the surface
+    fn tolerance(&self) -> S {
+        self.bound.dim().magnitude() * S::default_epsilon().sqrt()
+    }
+
+    /// Move a point onto the surface, if it is outside the shape
+    fn project(&self, mut point: Point3<S>) -> Point3<S> {
+        let tolerance = self.tolerance();
+        for _ in 0..MAX_PROJECT_STEPS {
+            let distance = self.distance(point);
+            if distance <= tolerance {
+                break;
+            }
+            point -= self.normal(point) * distance;
+        }
+        point
+    }
+
+    /// Find the part of the ray inside the bounding box, with a normalized direction
+    fn clip(&self, ray: &Ray3<S>) -> Option<(S, S)> {
+        let mut enter = S::zero();
+        let mut exit = S::infinity();
+        for i in 0..3 {
+            if ray.direction[i].is_zero() {
+                if ray.origin[i] < self.bound.min[i] || ray.origin[i] > self.bound.max[i] {
+                    return None;
+                }
+            } else {
+                let t1 = (self.bound.min[i] - ray.origin[i]) / ray.direction[i];
+                let t2 = (self.bound.max[i] - ray.origin[i]) / ray.direction[i];
+                enter = enter.max(t1.min(t2));
+                exit = exit.min(t1.max(t2));
+            }
+        }
+        if enter <= exit {
+            Some((enter, exit))
+        } else {
+            None
+        }
+    }
+
+    /// Sphere trace the ray, and return the first point on the surface, if any
+    fn trace(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
+        let ray = Ray3::new(ray.origin, ray.direction.normalize());
+        let (mut t, exit) = self.clip(&ray)?;
+        let tolerance = self.tolerance();
+        for _ in 0..MAX_TRACE_STEPS {
+            let point = ray.origin + ray.direction * t;
+            let distance = self.distance(point);
+            if distance <= tolerance {
+                return Some(point);
+            }
+            t += distance;
+            if t > exit {
+                return None;
+            }
+        }
+        None
+    }
+}
+
+impl<F, S> fmt::Debug for SdfPrimitive<F, S>
+where
+    S: BaseFloat,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SdfPrimitive")
+            .field("bound", &self.bound)
+            .finish()
+    }
+}
+
+impl<F, S> Primitive for SdfPrimitive<F, S>
+where
+    S: BaseFloat,
+    F: Fn(Point3<S>) -> S,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let direction = if direction.magnitude2().is_zero() {
+            Vector3::unit_x()
+        } else {
+            direction.normalize()
+        };
+        // step along the direction and back onto the surface, halving the step when there is
+        // no progress, so the point slides along the surface towards the support point
+        let tolerance = self.tolerance();
+        let mut step = self.bound.dim().magnitude() / (S::one() + S::one());
+        let mut point = self.project(self.bound.center());
+        for _ in 0..MAX_SUPPORT_STEPS {
+            if step < tolerance {
+                break;
+            }
+            let next = self.project(point + direction * step);
+            if next.dot(direction) > point.dot(direction) {
+                point = next;
+            } else {
+                step /= S::one() + S::one();
+            }
+        }
+        transform.transform_point(point)
+    }
+}
+
+impl<F, S> ComputeBound<Aabb3<S>> for SdfPrimitive<F, S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        self.bound
+    }
+}
+
+impl<F, S> ComputeBound<Sphere<S>> for SdfPrimitive<F, S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere {
+            center: self.bound.center(),
+            radius: self.bound.dim().magnitude() / (S::one() + S::one()),
+        }
+    }
+}
+
+impl<F, S> Sdf<Point3<S>> for SdfPrimitive<F, S>
+where
+    S: BaseFloat,
+    F: Fn(Point3<S>) -> S,
+{
+    /// Point must be in object space
+    fn signed_distance(&self, point: &Point3<S>) -> S {
+        self.distance(*point)
+    }
+}
+
+impl<F, S> Discrete<Ray3<S>> for SdfPrimitive<F, S>
+where
+    S: BaseFloat,
+    F: Fn(Point3<S>) -> S,
+{
+    fn intersects(&self, ray: &Ray3<S>) -> bool {
+        self.trace(ray).is_some()
+    }
+}
+
+impl<F, S> Continuous<Ray3<S>> for SdfPrimitive<F, S>
+where
+    S: BaseFloat,
+    F: Fn(Point3<S>) -> S,
+{
+    type Result = Point3<S>;
+
+    fn intersection(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
+        self.trace(ray)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK3;
+    use crate::primitive::Sphere as SpherePrimitive;
+
+    #[test]
+    fn test_sdf_support_sphere() {
+        let sdf = sphere(2.);
+        let reference = SpherePrimitive::new(2.);
+        let t = transform(0., 0., 0., 0.);
+        for direction in &[
+            Vector3::new(1., 0., 0.),
+            Vector3::new(-0.3, 1., 0.7),
+            Vector3::new(0.1, -2., -1.),
+        ] {
+            assert_ulps_eq!(
+                reference.support_point(direction, &t),
+                sdf.support_point(direction, &t),
+                epsilon = 1e-2
+            );
+        }
+    }
+
+    #[test]
+    fn test_sdf_support_transformed() {
+        let sdf = sphere(1.);
+        let t = transform(0., 10., 0., 0.5);
+        assert_ulps_eq!(
+            Point3::new(0., 11., 0.),
+            sdf.support_point(&Vector3::new(0., 1., 0.), &t),
+            epsilon = 1e-2
+        );
+    }
+
+    #[test]
+    fn test_sdf_bound() {
+        let sdf = sphere(1.);
+        assert_eq!(
+            Aabb3::new(Point3::new(-1., -1., -1.), Point3::new(1., 1., 1.)),
+            sdf.compute_bound()
+        );
+        let bound: Sphere<f32> = sdf.compute_bound();
+        assert_eq!(Point3::new(0., 0., 0.), bound.center);
+        assert_ulps_eq!(3f32.sqrt(), bound.radius);
+    }
+
+    #[test]
+    fn test_sdf_ray() {
+        let sdf = sphere(1.);
+        let ray = Ray3::new(Point3::new(-10., 0., 0.), Vector3::new(1., 0., 0.));
+        assert!(sdf.intersects(&ray));
+        assert_ulps_eq!(
+            Point3::new(-1., 0., 0.),
+            sdf.intersection(&ray).unwrap(),
+            epsilon = 1e-3
+        );
+        // misses the sphere, but passes through the corner of the bounding box
+        let ray = Ray3::new(Point3::new(-10., 0.9, 0.9), Vector3::new(1., 0., 0.));
+        assert!(!sdf.intersects(&ray));
+        let ray = Ray3::new(Point3::new(-10., 0., 0.), Vector3::new(-1., 0., 0.));
+        assert_eq!(None, sdf.intersection(&ray));
+        // from the inside
+        let ray = Ray3::new(Point3::new(0.2, 0., 0.), Vector3::new(1., 0., 0.));
+        assert_eq!(Some(Point3::new(0.2, 0., 0.)), sdf.intersection(&ray));
+    }
+
+    #[test]
+    fn test_sdf_gjk() {
+        let sdf = sphere(1.);
+        let other = SpherePrimitive::new(1.);
+        let gjk = GJK3::new();
+        let t = transform(0., 0., 0., 0.);
+        assert!(gjk
+            .intersect(&sdf, &t, &other, &transform(1.9, 0.1, 0.2, 0.))
+            .is_some());
+        assert!(gjk
+            .intersect(&sdf, &t, &other, &transform(2.1, 0.1, 0.2, 0.))
+            .is_none());
+    }
+
+    // util
+    #[test]
+    fn test_sdf_signed_distance() {
+        let sdf = sphere(2.);
+        let reference = SpherePrimitive::new(2.);
+        let t = transform(5., 0., 0., 0.);
+        for point in &[
+            Point3::new(0., 0., 0.),
+            Point3::new(5., 1., 0.),
+            Point3::new(8., 1., -2.),
+        ] {
+            assert_ulps_eq!(
+                reference.signed_distance_transformed(point, &t),
+                sdf.signed_distance_transformed(point, &t),
+                epsilon = 1e-5
+            );
+        }
+    }
+
+    fn sphere(radius: f32) -> SdfPrimitive<impl Fn(Point3<f32>) -> f32, f32> {
+        SdfPrimitive::new(
+            move |p: Point3<f32>| p.to_vec().magnitude() - radius,
+            Aabb3::new(
+                Point3::new(-radius, -radius, -radius),
+                Point3::new(radius, radius, radius),
+            ),
+        )
+    }
+
+    fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(rot)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
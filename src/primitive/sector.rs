@@ -0,0 +1,250 @@
+//! Circular sector primitive
+
+use cgmath::{BaseFloat, Point2, Rad, Vector2};
+use cgmath::prelude::*;
+
+use crate::Aabb2;
+use crate::prelude::*;
+use crate::volume::Circle;
+
+/// Circular sector primitive, the region of a circle between two radii.
+///
+/// The apex of the sector is at the local origin, and the sector is centered around the positive
+/// X axis, spanning `half_angle` on either side of it. Useful for vision cones and melee swing arcs.
+///
+/// Sectors with a half angle above a quarter turn are not convex. For those, the support function
+/// is the support function of the convex hull of the sector.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CircleSector<S> {
+    /// Radius of the sector
+    pub radius: S,
+    /// Angle between the X axis and each of the bounding radii
+    pub half_angle: Rad<S>,
+}
+
+impl<S> CircleSector<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new circular sector primitive
+    pub fn new(radius: S, half_angle: Rad<S>) -> Self {
+        Self { radius, half_angle }
+    }
+
+    /// Check if the given direction, in object space, lies within the angular span of the sector
+    fn in_span(&self, direction: &Vector2<S>) -> bool {
+        Rad::atan2(direction.y, direction.x).0.abs() <= self.half_angle.0
+    }
+
+    /// End points of the arc
+    fn arc_ends(&self) -> [Point2<S>; 2] {
+        let (sin, cos) = self.half_angle.sin_cos();
+        [
+            Point2::new(cos * self.radius, sin * self.radius),
+            Point2::new(cos * self.radius, -sin * self.radius),
+        ]
+    }
+}
+
+impl<S> Primitive for CircleSector<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point2<S>;
+
+    fn support_point<T>(&self, direction: &Vector2<S>, transform: &T) -> Point2<S>
+    where
+        T: Transform<Point2<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let point = if !direction.is_zero() && self.in_span(&direction) {
+            Point2::from_vec(direction.normalize_to(self.radius))
+        } else {
+            let ends = self.arc_ends();
+            let candidates = [Point2::origin(), ends[0], ends[1]];
+            let mut best = candidates[0];
+            let mut best_dot = best.dot(direction);
+            for p in &candidates[1..] {
+                let dot = p.dot(direction);
+                if dot > best_dot {
+                    best = *p;
+                    best_dot = dot;
+                }
+            }
+            best
+        };
+        transform.transform_point(point)
+    }
+}
+
+impl<S> ComputeBound<Aabb2<S>> for CircleSector<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb2<S> {
+        let ends = self.arc_ends();
+        let mut bound = Aabb2::new(ends[0], ends[1]).grow(Point2::origin());
+        for axis in &[
+            Vector2::unit_x(),
+            Vector2::unit_y(),
+            -Vector2::unit_x(),
+            -Vector2::unit_y(),
+        ] {
+            if self.in_span(axis) {
+                bound = bound.grow(Point2::from_vec(axis * self.radius));
+            }
+        }
+        bound
+    }
+}
+
+impl<S> ComputeBound<Circle<S>> for CircleSector<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: self.radius,
+        }
+    }
+}
+
+impl<S> Geometry2 for CircleSector<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        self.half_angle.0 * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> S {
+        (self.radius + self.half_angle.0 * self.radius) * (S::one() + S::one())
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        if self.half_angle.0.is_zero() {
+            return Point2::new(self.radius / (S::one() + S::one()), S::zero());
+        }
+        let three = S::one() + S::one() + S::one();
+        let distance =
+            (self.radius + self.radius) * self.half_angle.sin() / (three * self.half_angle.0);
+        Point2::new(distance, S::zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Basis2, Decomposed, Point2, Rad, Rotation2, Vector2};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK2;
+    use crate::primitive::Circle;
+
+    #[test]
+    fn test_sector_support_arc() {
+        let sector = CircleSector::new(10., Rad(std::f32::consts::PI / 4.));
+        let t = transform(0., 0., 0.);
+        assert_ulps_eq!(
+            Point2::new(10., 0.),
+            sector.support_point(&Vector2::new(1., 0.), &t)
+        );
+        let point = sector.support_point(&Vector2::new(1., 0.5), &t);
+        assert_ulps_eq!(10., point.to_vec().magnitude(), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_sector_support_edges() {
+        let sector = CircleSector::new(10., Rad(std::f32::consts::PI / 4.));
+        let t = transform(0., 0., 0.);
+        let d = 10. * std::f32::consts::FRAC_1_SQRT_2;
+        assert_ulps_eq!(
+            Point2::new(d, d),
+            sector.support_point(&Vector2::new(0., 1.), &t),
+            epsilon = 1e-5
+        );
+        assert_ulps_eq!(
+            Point2::new(d, -d),
+            sector.support_point(&Vector2::new(0., -1.), &t),
+            epsilon = 1e-5
+        );
+        assert_ulps_eq!(
+            Point2::new(0., 0.),
+            sector.support_point(&Vector2::new(-1., 0.), &t)
+        );
+    }
+
+    #[test]
+    fn test_sector_support_transformed() {
+        let sector = CircleSector::new(10., Rad(std::f32::consts::PI / 4.));
+        let t = transform(5., 5., std::f32::consts::PI / 2.);
+        let point = sector.support_point(&Vector2::new(0., 1.), &t);
+        assert_ulps_eq!(Point2::new(5., 15.), point, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_sector_bound() {
+        let sector = CircleSector::new(10., Rad(std::f32::consts::PI / 4.));
+        let bound: Aabb2<_> = sector.compute_bound();
+        let d = 10. * std::f32::consts::FRAC_1_SQRT_2;
+        assert_ulps_eq!(Point2::new(0., -d), bound.min, epsilon = 1e-5);
+        assert_ulps_eq!(Point2::new(10., d), bound.max, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_sector_bound_wide() {
+        let sector = CircleSector::new(10., Rad(std::f32::consts::PI * 3. / 4.));
+        let bound: Aabb2<_> = sector.compute_bound();
+        let d = 10. * std::f32::consts::FRAC_1_SQRT_2;
+        assert_ulps_eq!(Point2::new(-d, -10.), bound.min, epsilon = 1e-5);
+        assert_ulps_eq!(Point2::new(10., 10.), bound.max, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_sector_gjk() {
+        let sector = CircleSector::new(10., Rad(std::f32::consts::PI / 6.));
+        let circle = Circle::new(1.);
+        let gjk = GJK2::new();
+        let sector_transform = transform(0., 0., 0.);
+        // in front of the apex
+        assert!(gjk
+            .intersect(&sector, &sector_transform, &circle, &transform(5., 0., 0.))
+            .is_some());
+        // within range, but outside the angular span
+        assert!(gjk
+            .intersect(&sector, &sector_transform, &circle, &transform(0., 5., 0.))
+            .is_none());
+        // out of range
+        assert!(gjk
+            .intersect(&sector, &sector_transform, &circle, &transform(12., 0., 0.))
+            .is_none());
+    }
+
+    #[test]
+    fn test_sector_geometry() {
+        let half_circle = CircleSector::new(2., Rad(std::f32::consts::PI / 2.));
+        assert_ulps_eq!(2. * std::f32::consts::PI, half_circle.area());
+        assert_ulps_eq!(4. + 2. * std::f32::consts::PI, half_circle.perimeter());
+        assert_ulps_eq!(
+            Point2::new(8. / (3. * std::f32::consts::PI), 0.),
+            half_circle.centroid()
+        );
+        let circle = CircleSector::new(2., Rad(std::f32::consts::PI));
+        assert_ulps_eq!(Point2::new(0., 0.), circle.centroid(), epsilon = 1e-6);
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+}
@@ -3,8 +3,12 @@ use cgmath::prelude::*;
 
 use crate::{Aabb3, Ray3};
 use crate::prelude::*;
+use crate::primitive::util::closest_point_on_ball;
 
 /// Sphere primitive
+///
+/// The support function assumes that the transform does not scale the sphere non-uniformly. Use
+/// [`Scaled`](struct.Scaled.html) for non-uniformly scaled spheres.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Sphere<S> {
@@ -58,6 +62,26 @@ where
     }
 }
 
+impl<S> Contains<Point3<S>> for Sphere<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        point.to_vec().magnitude2() <= self.radius * self.radius
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for Sphere<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        closest_point_on_ball(Point3::origin(), self.radius, *point)
+    }
+}
+
 impl<S> Discrete<Ray3<S>> for Sphere<S>
 where
     S: BaseFloat,
@@ -0,0 +1,393 @@
+//! Torus primitive
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, Ray3};
+use crate::prelude::*;
+use crate::volume::Sphere;
+
+/// Maximum number of bisection steps when refining a root of the ray quartic
+const MAX_BISECTION_STEPS: u32 = 100;
+
+/// Torus primitive
+///
+/// The torus lies in the XZ plane, around the Y axis, with local origin in the center of the
+/// hole. The torus is not convex, so the support function is the support function of the
+/// convex hull of the torus, and GJK will treat the hole as solid. Ray tests are done against the
+/// actual surface of the torus.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Torus<S> {
+    /// Distance from the center of the torus to the center of the tube
+    pub major_radius: S,
+    /// Radius of the tube
+    pub minor_radius: S,
+}
+
+impl<S> Torus<S> {
+    /// Create a new torus primitive
+    pub fn new(major_radius: S, minor_radius: S) -> Self {
+        Self {
+            major_radius,
+            minor_radius,
+        }
+    }
+}
+
+impl<S> Primitive for Torus<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let direction = transform.inverse_transform_vector(*direction).unwrap();
+        let mut ring = Vector3::new(direction.x, S::zero(), direction.z);
+        if !ring.magnitude2().is_zero() {
+            ring = ring.normalize_to(self.major_radius);
+        }
+        let tube = if direction.magnitude2().is_zero() {
+            Vector3::zero()
+        } else {
+            direction.normalize_to(self.minor_radius)
+        };
+        transform.transform_point(Point3::from_vec(ring + tube))
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for Torus<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        let outer = self.major_radius + self.minor_radius;
+        Aabb3::new(
+            Point3::new(-outer, -self.minor_radius, -outer),
+            Point3::new(outer, self.minor_radius, outer),
+        )
+    }
+}
+
+impl<S> ComputeBound<Sphere<S>> for Torus<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere {
+            center: Point3::origin(),
+            radius: self.major_radius + self.minor_radius,
+        }
+    }
+}
+
+impl<S> Sdf<Point3<S>> for Torus<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn signed_distance(&self, point: &Point3<S>) -> S {
+        let ring = (point.x * point.x + point.z * point.z).sqrt() - self.major_radius;
+        (ring * ring + point.y * point.y).sqrt() - self.minor_radius
+    }
+}
+
+impl<S> Discrete<Ray3<S>> for Torus<S>
+where
+    S: BaseFloat,
+{
+    fn intersects(&self, r: &Ray3<S>) -> bool {
+        self.intersection(r).is_some()
+    }
+}
+
+impl<S> Continuous<Ray3<S>> for Torus<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point3<S>;
+
+    fn intersection(&self, r: &Ray3<S>) -> Option<Point3<S>> {
+        let two = S::one() + S::one();
+        let four = two + two;
+
+        // clip the ray against the bounding sphere first, and move the ray origin to the entry
+        // point, to keep the coefficients of the quartic small
+        let outer = self.major_radius + self.minor_radius;
+        let g = r.direction.magnitude2();
+        if g.is_zero() {
+            return None;
+        }
+        let l = r.origin.to_vec();
+        let tca = -l.dot(r.direction) / g;
+        let d2 = (l + r.direction * tca).magnitude2();
+        if d2 > outer * outer {
+            return None;
+        }
+        let thc = ((outer * outer - d2) / g).sqrt();
+        if tca + thc < S::zero() {
+            return None;
+        }
+        let start = (tca - thc).max(S::zero());
+        let o = r.origin + r.direction * start;
+        let d = r.direction;
+
+        // (|p|^2 + R^2 - r^2)^2 = 4 R^2 (x^2 + z^2)
+        let r2 = self.major_radius * self.major_radius;
+        let h = two * o.to_vec().dot(d);
+        let i = o.to_vec().magnitude2() + r2 - self.minor_radius * self.minor_radius;
+        let j = d.x * d.x + d.z * d.z;
+        let k = two * (o.x * d.x + o.z * d.z);
+        let m = o.x * o.x + o.z * o.z;
+        let coefficients = [
+            i * i - four * r2 * m,
+            two * h * i - four * r2 * k,
+            h * h + two * g * i - four * r2 * j,
+            two * g * h,
+            g * g,
+        ];
+
+        solve_polynomial(&coefficients, S::zero(), tca + thc - start)
+            .first()
+            .map(|t| o + d * *t)
+    }
+}
+
+/// Evaluate the polynomial with the given coefficients, in order of increasing degree
+fn evaluate<S>(coefficients: &[S], t: S) -> S
+where
+    S: BaseFloat,
+{
+    coefficients
+        .iter()
+        .rev()
+        .fold(S::zero(), |acc, c| acc * t + *c)
+}
+
+/// Find the real roots of the polynomial with the given coefficients, in order of increasing
+/// degree, in the interval `[lo, hi]`. The roots are returned in ascending order.
+///
+/// The roots of the derivative split the interval into pieces where the polynomial is monotonic,
+/// and each piece is then searched for a sign change using bisection.
+fn solve_polynomial<S>(coefficients: &[S], lo: S, hi: S) -> Vec<S>
+where
+    S: BaseFloat,
+{
+    let mut degree = coefficients.len() - 1;
+    while degree > 0 && coefficients[degree].is_zero() {
+        degree -= 1;
+    }
+    let coefficients = &coefficients[..=degree];
+    if degree == 0 {
+        return Vec::default();
+    }
+    if degree == 1 {
+        let t = -coefficients[0] / coefficients[1];
+        return if t >= lo && t <= hi {
+            vec![t]
+        } else {
+            Vec::default()
+        };
+    }
+
+    let derivative: Vec<S> = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(n, c)| *c * S::from(n).unwrap())
+        .collect();
+    let mut bounds = vec![lo];
+    bounds.extend(solve_polynomial(&derivative, lo, hi));
+    bounds.push(hi);
+
+    let mut roots = Vec::default();
+    for w in bounds.windows(2) {
+        let (mut a, mut b) = (w[0], w[1]);
+        let mut fa = evaluate(coefficients, a);
+        let fb = evaluate(coefficients, b);
+        // the pieces include both ends, roots shared by two pieces are removed below
+        if fa.is_zero() || fb.is_zero() {
+            if fa.is_zero() {
+                roots.push(a);
+            }
+            if fb.is_zero() {
+                roots.push(b);
+            }
+            continue;
+        }
+        if fa.signum() == fb.signum() {
+            continue;
+        }
+        for _ in 0..MAX_BISECTION_STEPS {
+            let mid = (a + b) / (S::one() + S::one());
+            if mid <= a || mid >= b {
+                break;
+            }
+            let fm = evaluate(coefficients, mid);
+            if fm.signum() == fa.signum() {
+                a = mid;
+                fa = fm;
+            } else {
+                b = mid;
+            }
+        }
+        roots.push(a);
+    }
+    roots.dedup();
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_torus_signed_distance() {
+        let torus = Torus::new(2., 0.5);
+        assert_ulps_eq!(-0.5, torus.signed_distance(&Point3::new(2., 0., 0.)));
+        assert_ulps_eq!(-0.25, torus.signed_distance(&Point3::new(0., 0.25, -2.)));
+        assert_ulps_eq!(1.5, torus.signed_distance(&Point3::new(0., 0., 0.)));
+        assert_ulps_eq!(1., torus.signed_distance(&Point3::new(2., 1.5, 0.)));
+    }
+
+    #[test]
+    fn test_torus_support() {
+        let torus = Torus::new(2., 0.5);
+        let t = transform(0., 0., 0., 0.);
+        assert_ulps_eq!(
+            Point3::new(2.5, 0., 0.),
+            torus.support_point(&Vector3::new(1., 0., 0.), &t)
+        );
+        assert_ulps_eq!(
+            Point3::new(0., 0.5, 0.),
+            torus.support_point(&Vector3::new(0., 1., 0.), &t)
+        );
+        let d = 0.5 * std::f32::consts::FRAC_1_SQRT_2;
+        assert_ulps_eq!(
+            Point3::new(2. + d, d, 0.),
+            torus.support_point(&Vector3::new(1., 1., 0.), &t)
+        );
+    }
+
+    #[test]
+    fn test_torus_support_transformed() {
+        let torus = Torus::new(2., 0.5);
+        let t = transform(10., 0., 0., std::f32::consts::PI / 2.);
+        assert_ulps_eq!(
+            Point3::new(10., 2.5, 0.),
+            torus.support_point(&Vector3::new(0., 1., 0.), &t),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_torus_bound() {
+        let torus = Torus::new(2., 0.5);
+        assert_eq!(
+            Aabb3::new(Point3::new(-2.5, -0.5, -2.5), Point3::new(2.5, 0.5, 2.5)),
+            torus.compute_bound()
+        );
+        let sphere: Sphere<f32> = torus.compute_bound();
+        assert_ulps_eq!(2.5, sphere.radius);
+    }
+
+    #[test]
+    fn test_torus_ray_outside() {
+        let torus = Torus::new(2., 0.5);
+        let ray = Ray3::new(Point3::new(-5., 0., 0.), Vector3::new(1., 0., 0.));
+        assert!(torus.intersects(&ray));
+        assert_ulps_eq!(
+            Point3::new(-2.5, 0., 0.),
+            torus.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+        let ray = Ray3::new(Point3::new(-5., 0.6, 0.), Vector3::new(1., 0., 0.));
+        assert!(!torus.intersects(&ray));
+        let ray = Ray3::new(Point3::new(-5., 0., 0.), Vector3::new(-1., 0., 0.));
+        assert!(!torus.intersects(&ray));
+    }
+
+    #[test]
+    fn test_torus_ray_through_hole() {
+        let torus = Torus::new(2., 0.5);
+        let ray = Ray3::new(Point3::new(0., 5., 0.), Vector3::new(0., -1., 0.));
+        assert!(!torus.intersects(&ray));
+        assert_eq!(None, torus.intersection(&ray));
+        let ray = Ray3::new(Point3::new(2., 5., 0.), Vector3::new(0., -2., 0.));
+        assert_ulps_eq!(
+            Point3::new(2., 0.5, 0.),
+            torus.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_torus_ray_from_hole() {
+        let torus = Torus::new(2., 0.5);
+        let ray = Ray3::new(Point3::new(0., 0., 0.), Vector3::new(0., 0., 1.));
+        assert_ulps_eq!(
+            Point3::new(0., 0., 1.5),
+            torus.intersection(&ray).unwrap(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_torus_ray_transformed() {
+        let torus = Torus::new(2., 0.5);
+        let ray = Ray3::new(Point3::new(0., 5., 0.), Vector3::new(0., -1., 0.));
+        let t = transform(0., 0., 0., std::f32::consts::PI / 2.);
+        assert_ulps_eq!(
+            Point3::new(0., 2.5, 0.),
+            torus.intersection_transformed(&ray, &t).unwrap(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn test_solve_polynomial() {
+        // (t - 1)(t - 2)(t - 3)(t - 4)
+        let roots = solve_polynomial(&[24., -50., 35., -10., 1.], 0., 10.);
+        assert_eq!(4, roots.len());
+        for (expected, root) in [1., 2., 3., 4.].iter().zip(roots.iter()) {
+            assert_ulps_eq!(*expected, *root, epsilon = 1e-5);
+        }
+        let roots = solve_polynomial(&[24., -50., 35., -10., 1.], 2.5, 10.);
+        assert_eq!(2, roots.len());
+        // t^2 + 1
+        assert!(solve_polynomial(&[1., 0., 1.], -10., 10.).is_empty());
+    }
+
+    #[test]
+    fn test_solve_polynomial_endpoints() {
+        // (t - 1)(t - 2)(t - 3)(t - 4), with roots at both ends of the interval
+        let roots = solve_polynomial(&[24., -50., 35., -10., 1.], 1., 4.);
+        assert_eq!(4, roots.len());
+        assert_eq!(1., roots[0]);
+        assert_eq!(4., roots[3]);
+        let roots = solve_polynomial(&[24., -50., 35., -10., 1.], 2.5, 4.);
+        assert_eq!(2, roots.len());
+        assert_eq!(4., roots[1]);
+        // (t - 2)^2, where the derivative has its root at the end too
+        assert_eq!(vec![2.], solve_polynomial(&[4., -4., 1.], 0., 2.));
+        // t^3
+        assert_eq!(vec![0.], solve_polynomial(&[0., 0., 0., 1.], -1., 0.));
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(rot)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Triangle primitive
+
+use cgmath::{BaseFloat, Point2, Vector2};
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Line2, Ray2};
+use crate::prelude::*;
+use crate::primitive::util::{
+    barycentric_point, closest_point_on_polygon, get_bound, get_max_distance, get_max_point,
+    polygon_area_centroid, polygon_perimeter,
+};
+use crate::volume::Circle;
+
+/// Triangle primitive
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Triangle<S> {
+    /// Vertices of the triangle
+    pub vertices: [Point2<S>; 3],
+}
+
+impl<S> Triangle<S> {
+    /// Create a new triangle primitive
+    pub fn new(a: Point2<S>, b: Point2<S>, c: Point2<S>) -> Self {
+        Self {
+            vertices: [a, b, c],
+        }
+    }
+}
+
+impl<S> Triangle<S>
+where
+    S: BaseFloat,
+{
+    fn edges(&self) -> [Line2<S>; 3] {
+        [
+            Line2::new(self.vertices[0], self.vertices[1]),
+            Line2::new(self.vertices[1], self.vertices[2]),
+            Line2::new(self.vertices[2], self.vertices[0]),
+        ]
+    }
+}
+
+impl<S> Primitive for Triangle<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point2<S>;
+
+    fn support_point<T>(&self, direction: &Vector2<S>, transform: &T) -> Point2<S>
+    where
+        T: Transform<Point2<S>>,
+    {
+        get_max_point(self.vertices.iter(), direction, transform)
+    }
+}
+
+impl<S> ComputeBound<Aabb2<S>> for Triangle<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb2<S> {
+        get_bound(self.vertices.iter())
+    }
+}
+
+impl<S> ComputeBound<Circle<S>> for Triangle<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Circle<S> {
+        Circle {
+            center: Point2::origin(),
+            radius: get_max_distance(self.vertices.iter()),
+        }
+    }
+}
+
+impl<S> Contains<Point2<S>> for Triangle<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point2<S>) -> bool {
+        let (u, v, w) =
+            barycentric_point(*point, self.vertices[0], self.vertices[1], self.vertices[2]);
+        u >= S::zero() && v >= S::zero() && w >= S::zero()
+    }
+}
+
+impl<S> ClosestPoint<Point2<S>> for Triangle<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point2<S>) -> (Point2<S>, bool) {
+        (
+            closest_point_on_polygon(&self.vertices, *point),
+            self.contains(point),
+        )
+    }
+}
+
+impl<S> Discrete<Ray2<S>> for Triangle<S>
+where
+    S: BaseFloat,
+{
+    /// Ray must be in object space
+    fn intersects(&self, ray: &Ray2<S>) -> bool {
+        self.edges()
+            .iter()
+            .any(|edge| ray.intersection(edge).is_some())
+    }
+}
+
+impl<S> Continuous<Ray2<S>> for Triangle<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point2<S>;
+
+    /// Ray must be in object space
+    fn intersection(&self, ray: &Ray2<S>) -> Option<Point2<S>> {
+        self.edges()
+            .iter()
+            .filter_map(|edge| ray.intersection(edge))
+            .fold(None, |closest: Option<Point2<S>>, p| match closest {
+                Some(c) if (c - ray.origin).magnitude2() <= (p - ray.origin).magnitude2() => {
+                    Some(c)
+                }
+                _ => Some(p),
+            })
+    }
+}
+
+impl<S> Geometry2 for Triangle<S>
+where
+    S: BaseFloat,
+{
+    type Scalar = S;
+
+    fn area(&self) -> S {
+        polygon_area_centroid(&self.vertices).0
+    }
+
+    fn perimeter(&self) -> S {
+        polygon_perimeter(&self.vertices)
+    }
+
+    fn centroid(&self) -> Point2<S> {
+        let three = S::one() + S::one() + S::one();
+        Point2::from_vec(
+            (self.vertices[0].to_vec() + self.vertices[1].to_vec() + self.vertices[2].to_vec())
+                / three,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Basis2, Decomposed, Point2, Rad, Rotation2, Vector2};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_triangle_support() {
+        let triangle = triangle();
+        let t = transform(0., 0., 0.);
+        assert_eq!(
+            Point2::new(2., 0.),
+            triangle.support_point(&Vector2::new(1., -1.), &t)
+        );
+        assert_eq!(
+            Point2::new(0., 2.),
+            triangle.support_point(&Vector2::new(-1., 1.), &t)
+        );
+    }
+
+    #[test]
+    fn test_triangle_support_transformed() {
+        let triangle = triangle();
+        let t = transform(10., 0., std::f32::consts::PI);
+        let point = triangle.support_point(&Vector2::new(-1., 0.), &t);
+        assert_ulps_eq!(Point2::new(8., 0.), point, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_triangle_bound() {
+        assert_eq!(bound(0., 0., 2., 2.), triangle().compute_bound());
+    }
+
+    #[test]
+    fn test_triangle_contains() {
+        let triangle = triangle();
+        assert!(triangle.contains(&Point2::new(0.5, 0.5)));
+        assert!(triangle.contains(&Point2::new(1., 1.)));
+        assert!(triangle.contains(&Point2::new(0., 0.)));
+        assert!(!triangle.contains(&Point2::new(1.5, 1.5)));
+        assert!(!triangle.contains(&Point2::new(-0.1, 0.5)));
+    }
+
+    #[test]
+    fn test_triangle_ray_discrete() {
+        let triangle = triangle();
+        let ray = Ray2::new(Point2::new(-5., 1.), Vector2::new(1., 0.));
+        assert!(triangle.intersects(&ray));
+        let ray = Ray2::new(Point2::new(-5., 3.), Vector2::new(1., 0.));
+        assert!(!triangle.intersects(&ray));
+        let ray = Ray2::new(Point2::new(-5., 1.), Vector2::new(-1., 0.));
+        assert!(!triangle.intersects(&ray));
+    }
+
+    #[test]
+    fn test_triangle_ray_continuous() {
+        let triangle = triangle();
+        let ray = Ray2::new(Point2::new(-5., 1.), Vector2::new(1., 0.));
+        assert_eq!(Some(Point2::new(0., 1.)), triangle.intersection(&ray));
+        let ray = Ray2::new(Point2::new(5., 1.), Vector2::new(-1., 0.));
+        assert_eq!(Some(Point2::new(1., 1.)), triangle.intersection(&ray));
+        let ray = Ray2::new(Point2::new(-5., 3.), Vector2::new(1., 0.));
+        assert_eq!(None, triangle.intersection(&ray));
+    }
+
+    #[test]
+    fn test_triangle_ray_transformed() {
+        let triangle = triangle();
+        let ray = Ray2::new(Point2::new(-5., 1.), Vector2::new(1., 0.));
+        let t = transform(1., 0., 0.);
+        assert!(triangle.intersects_transformed(&ray, &t));
+        assert_eq!(
+            Some(Point2::new(1., 1.)),
+            triangle.intersection_transformed(&ray, &t)
+        );
+        let t = transform(0., 5., 0.);
+        assert!(!triangle.intersects_transformed(&ray, &t));
+        assert_eq!(None, triangle.intersection_transformed(&ray, &t));
+    }
+
+    #[test]
+    fn test_triangle_geometry() {
+        let triangle = triangle();
+        assert_ulps_eq!(2., triangle.area());
+        assert_ulps_eq!(4. + 8f32.sqrt(), triangle.perimeter());
+        assert_ulps_eq!(Point2::new(2. / 3., 2. / 3.), triangle.centroid());
+    }
+
+    // util
+    fn triangle() -> Triangle<f32> {
+        Triangle::new(
+            Point2::new(0., 0.),
+            Point2::new(2., 0.),
+            Point2::new(0., 2.),
+        )
+    }
+
+    fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Rotation2::from_angle(Rad(rot)),
+            disp: Vector2::new(dx, dy),
+        }
+    }
+
+    fn bound(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Aabb2<f32> {
+        Aabb2::new(Point2::new(min_x, min_y), Point2::new(max_x, max_y))
+    }
+}
@@ -0,0 +1,338 @@
+//! Triangle mesh primitive
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, CollisionStrategy, Contact, Ray3, Sphere};
+use crate::algorithm::minkowski::GJK3;
+use crate::dbvt::{DiscreteVisitor, DynamicBoundingVolumeTree, TreeValueWrapped};
+use crate::prelude::*;
+use crate::primitive::util::{get_bound, ray_triangle, MeshTriangle};
+
+type TriangleValue<S> = TreeValueWrapped<usize, Aabb3<S>>;
+
+/// Triangle mesh primitive, for static level geometry.
+///
+/// The mesh is given as a list of vertices, and a list of triangles indexing into the vertex
+/// list. The mesh does not need to be closed or convex. On creation, the bounding box of each
+/// triangle is inserted into a
+/// [`DynamicBoundingVolumeTree`](../dbvt/struct.DynamicBoundingVolumeTree.html), which is used to
+/// prune triangles for all queries.
+///
+/// Ray tests are done against the triangles, from both sides. Convex primitives can be tested
+/// against the mesh with [`intersects_primitive`](#method.intersects_primitive) and
+/// [`intersection_primitive`](#method.intersection_primitive), which run GJK on each triangle
+/// that overlaps the bounding box of the primitive.
+#[derive(Debug)]
+pub struct TriMesh<S>
+where
+    S: BaseFloat,
+{
+    vertices: Vec<Point3<S>>,
+    indices: Vec<[usize; 3]>,
+    tree: DynamicBoundingVolumeTree<TriangleValue<S>>,
+}
+
+impl<S> TriMesh<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new triangle mesh.
+    ///
+    /// ## Parameters
+    ///
+    /// - `vertices`: vertex positions
+    /// - `indices`: triangles, as indices into the vertex list
+    pub fn new(vertices: Vec<Point3<S>>, indices: Vec<[usize; 3]>) -> Self {
+        let mut tree = DynamicBoundingVolumeTree::new();
+        for (i, triangle) in indices.iter().enumerate() {
+            let bound = get_bound(triangle.iter().map(|v| &vertices[*v]));
+            tree.insert(TreeValueWrapped::new(i, bound, Vector3::zero()));
+        }
+        tree.do_refit();
+        Self {
+            vertices,
+            indices,
+            tree,
+        }
+    }
+
+    /// Get the vertices of the mesh
+    pub fn vertices(&self) -> &[Point3<S>] {
+        &self.vertices
+    }
+
+    /// Get the triangles of the mesh, as indices into the vertex list
+    pub fn indices(&self) -> &[[usize; 3]] {
+        &self.indices
+    }
+
+    /// Get the vertex positions of the triangle with the given index
+    pub fn triangle(&self, index: usize) -> [Point3<S>; 3] {
+        let [a, b, c] = self.indices[index];
+        [self.vertices[a], self.vertices[b], self.vertices[c]]
+    }
+
+    /// Get the indices of all triangles whose bounding box intersects the given bound, which must
+    /// be in object space
+    pub fn query_triangles(&self, bound: &Aabb3<S>) -> Vec<usize> {
+        self.tree
+            .query(&mut DiscreteVisitor::<Aabb3<S>, TriangleValue<S>>::new(
+                bound,
+            ))
+            .into_iter()
+            .map(|(v, _)| v.value)
+            .collect()
+    }
+
+    /// Check if a convex primitive intersects the mesh.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `gjk`: GJK algorithm to use for the tests on each triangle
+    /// - `primitive`: the convex primitive
+    /// - `transform`: transform of the primitive, relative to the mesh
+    pub fn intersects_primitive<P, T>(&self, gjk: &GJK3<S>, primitive: &P, transform: &T) -> bool
+    where
+        P: Primitive<Point = Point3<S>> + ComputeBound<Aabb3<S>>,
+        T: Transform<Point3<S>>,
+    {
+        let identity = T::one();
+        self.candidates(primitive, transform).into_iter().any(|i| {
+            let triangle = MeshTriangle {
+                vertices: self.triangle(i),
+            };
+            gjk.intersect(&triangle, &identity, primitive, transform)
+                .is_some()
+        })
+    }
+
+    /// Compute the contacts between a convex primitive and the mesh.
+    ///
+    /// ## Parameters:
+    ///
+    /// - `gjk`: GJK algorithm to use for the tests on each triangle
+    /// - `strategy`: strategy to use for each triangle
+    /// - `primitive`: the convex primitive
+    /// - `transform`: transform of the primitive, relative to the mesh
+    ///
+    /// ## Returns:
+    ///
+    /// A list of the intersecting triangle indices, with the contact for each triangle. The
+    /// triangle is the first shape in each contact.
+    pub fn intersection_primitive<P, T>(
+        &self,
+        gjk: &GJK3<S>,
+        strategy: &CollisionStrategy,
+        primitive: &P,
+        transform: &T,
+    ) -> Vec<(usize, Contact<Point3<S>>)>
+    where
+        P: Primitive<Point = Point3<S>> + ComputeBound<Aabb3<S>>,
+        T: Transform<Point3<S>>,
+    {
+        let identity = T::one();
+        self.candidates(primitive, transform)
+            .into_iter()
+            .filter_map(|i| {
+                let triangle = MeshTriangle {
+                    vertices: self.triangle(i),
+                };
+                gjk.intersection(strategy, &triangle, &identity, primitive, transform)
+                    .map(|contact| (i, contact))
+            })
+            .collect()
+    }
+
+    fn candidates<P, T>(&self, primitive: &P, transform: &T) -> Vec<usize>
+    where
+        P: ComputeBound<Aabb3<S>>,
+        T: Transform<Point3<S>>,
+    {
+        let bound: Aabb3<S> = primitive.compute_bound();
+        self.query_triangles(&bound.transform_volume(transform))
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for TriMesh<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        get_bound(self.vertices.iter())
+    }
+}
+
+/// Sphere around the bounding box, since the shape has no natural center
+impl<S> ComputeBound<Sphere<S>> for TriMesh<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere::from(ComputeBound::<Aabb3<S>>::compute_bound(self))
+    }
+}
+
+impl<S> Discrete<Ray3<S>> for TriMesh<S>
+where
+    S: BaseFloat,
+{
+    /// Ray must be in object space
+    fn intersects(&self, ray: &Ray3<S>) -> bool {
+        self.tree
+            .query(&mut DiscreteVisitor::<Ray3<S>, TriangleValue<S>>::new(ray))
+            .into_iter()
+            .any(|(v, _)| ray_triangle(ray, &self.triangle(v.value)).is_some())
+    }
+}
+
+impl<S> Continuous<Ray3<S>> for TriMesh<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point3<S>;
+
+    /// Ray must be in object space. Will return the closest intersection point over all
+    /// triangles.
+    fn intersection(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
+        self.tree
+            .query(&mut DiscreteVisitor::<Ray3<S>, TriangleValue<S>>::new(ray))
+            .into_iter()
+            .filter_map(|(v, _)| ray_triangle(ray, &self.triangle(v.value)))
+            .fold(None, |closest: Option<S>, t| match closest {
+                Some(c) if c <= t => Some(c),
+                _ => Some(t),
+            })
+            .map(|t| ray.origin + ray.direction * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::primitive::{Cuboid, Sphere};
+
+    /// Flat 4x4 grid of quads in the XZ plane, with a bump in the middle
+    fn terrain() -> TriMesh<f32> {
+        let mut vertices = Vec::default();
+        for z in 0..5 {
+            for x in 0..5 {
+                let y = if x == 2 && z == 2 { 1. } else { 0. };
+                vertices.push(Point3::new(x as f32, y, z as f32));
+            }
+        }
+        let mut indices = Vec::default();
+        for z in 0..4 {
+            for x in 0..4 {
+                let i = z * 5 + x;
+                indices.push([i, i + 5, i + 1]);
+                indices.push([i + 1, i + 5, i + 6]);
+            }
+        }
+        TriMesh::new(vertices, indices)
+    }
+
+    #[test]
+    fn test_trimesh_bound() {
+        let mesh = terrain();
+        assert_eq!(32, mesh.indices().len());
+        assert_eq!(
+            Aabb3::new(Point3::new(0., 0., 0.), Point3::new(4., 1., 4.)),
+            mesh.compute_bound()
+        );
+        let bound = Aabb3::new(Point3::new(0.1, -1., 0.1), Point3::new(0.9, 1., 0.9));
+        let mut triangles = mesh.query_triangles(&bound);
+        triangles.sort();
+        assert_eq!(vec![0, 1], triangles);
+    }
+
+    #[test]
+    fn test_trimesh_ray() {
+        let mesh = terrain();
+        let ray = Ray3::new(Point3::new(0.5, 5., 3.5), Vector3::new(0., -1., 0.));
+        assert!(mesh.intersects(&ray));
+        assert_ulps_eq!(Point3::new(0.5, 0., 3.5), mesh.intersection(&ray).unwrap());
+        let ray = Ray3::new(Point3::new(1.9, 5., 1.9), Vector3::new(0., -1., 0.));
+        assert_ulps_eq!(
+            Point3::new(1.9, 0.8, 1.9),
+            mesh.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        let ray = Ray3::new(Point3::new(5., 5., 2.), Vector3::new(0., -1., 0.));
+        assert!(!mesh.intersects(&ray));
+        assert_eq!(None, mesh.intersection(&ray));
+    }
+
+    #[test]
+    fn test_trimesh_ray_closest_both_sides() {
+        let mesh = terrain();
+        // from below, hits the back side of the bump
+        let ray = Ray3::new(Point3::new(1.9, -5., 1.9), Vector3::new(0., 1., 0.));
+        assert_ulps_eq!(
+            Point3::new(1.9, 0.8, 1.9),
+            mesh.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        // horizontal ray hits the front of the bump first
+        let ray = Ray3::new(Point3::new(-1., 0.5, 1.9), Vector3::new(1., 0., 0.));
+        assert_ulps_eq!(
+            Point3::new(1.6, 0.5, 1.9),
+            mesh.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_trimesh_ray_transformed() {
+        let mesh = terrain();
+        let ray = Ray3::new(Point3::new(0.5, 5., 3.5), Vector3::new(0., -1., 0.));
+        let t = transform(0., -2., 0.);
+        assert_ulps_eq!(
+            Point3::new(0.5, -2., 3.5),
+            mesh.intersection_transformed(&ray, &t).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_trimesh_primitive() {
+        let mesh = terrain();
+        let gjk = GJK3::new();
+        let sphere = Sphere::new(0.5);
+        assert!(mesh.intersects_primitive(&gjk, &sphere, &transform(0.5, 0.4, 0.5)));
+        assert!(!mesh.intersects_primitive(&gjk, &sphere, &transform(0.5, 0.6, 0.5)));
+        // resting on the bump
+        assert!(mesh.intersects_primitive(&gjk, &sphere, &transform(2., 1.4, 2.)));
+        assert!(!mesh.intersects_primitive(&gjk, &sphere, &transform(10., 0., 10.)));
+    }
+
+    #[test]
+    fn test_trimesh_contacts() {
+        let mesh = terrain();
+        let gjk = GJK3::new();
+        let cuboid = Cuboid::new(0.5, 0.5, 0.5);
+        let contacts = mesh.intersection_primitive(
+            &gjk,
+            &CollisionStrategy::FullResolution,
+            &cuboid,
+            &transform(0.5, 0.2, 0.5),
+        );
+        let mut triangles: Vec<usize> = contacts.iter().map(|(i, _)| *i).collect();
+        triangles.sort();
+        assert_eq!(vec![0, 1], triangles);
+        for (_, contact) in &contacts {
+            assert_ulps_eq!(0.05, contact.penetration_depth, epsilon = 1e-3);
+        }
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, dz: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(0.)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
@@ -1,10 +1,14 @@
 //! Utilities
 //!
 
-use std::ops::Neg;
+use std::cmp::Ordering;
+use std::ops::{Add, Sub};
+
+use approx::AbsDiffEq;
 
 use crate::{Aabb, Ray3};
-use cgmath::{BaseFloat, BaseNum, Vector2};
+use crate::prelude::*;
+use cgmath::{BaseFloat, BaseNum, Point2, Point3, Vector2, Vector3};
 use cgmath::prelude::*;
 use cgmath::num_traits::Float;
 
@@ -29,12 +33,29 @@ where
     transform.transform_point(p)
 }
 
-pub(crate) fn get_bound<'a, I, A: 'a>(vertices: I) -> A
+pub(crate) fn get_bound<'a, I, A: 'a>(mut vertices: I) -> A
 where
     A: Aabb,
     I: Iterator<Item = &'a A::Point>,
 {
-    vertices.fold(A::zero(), |bound, p| bound.grow(*p))
+    match vertices.next() {
+        Some(first) => vertices.fold(A::new(*first, *first), |bound, p| bound.grow(*p)),
+        None => A::zero(),
+    }
+}
+
+/// Largest distance from the local origin to any of the given points, which is the radius of the
+/// bounding sphere or circle centered on the origin
+pub(crate) fn get_max_distance<'a, P, I>(vertices: I) -> P::Scalar
+where
+    P: EuclideanSpace + 'a,
+    P::Scalar: BaseFloat,
+    I: Iterator<Item = &'a P>,
+{
+    vertices
+        .map(|v| v.dot(v.to_vec()))
+        .fold(P::Scalar::zero(), |max, d| max.max(d))
+        .sqrt()
 }
 
 #[allow(dead_code)]
@@ -71,6 +92,51 @@ where
     (u, v, w)
 }
 
+/// Z component of the cross product `(a - o) x (b - o)`, positive if `o -> a -> b` is a CCW turn
+#[inline]
+pub(crate) fn cross_2d<S>(o: Point2<S>, a: Point2<S>, b: Point2<S>) -> S
+where
+    S: BaseFloat,
+{
+    (a - o).perp_dot(b - o)
+}
+
+/// Area and centroid of a simple polygon
+pub(crate) fn polygon_area_centroid<S>(vertices: &[Point2<S>]) -> (S, Point2<S>)
+where
+    S: BaseFloat,
+{
+    let n = vertices.len();
+    if n == 0 {
+        return (S::zero(), Point2::origin());
+    }
+    // relative to the first vertex, to reduce round off errors
+    let origin = vertices[0];
+    let (area, centroid) = (1..n).fold((S::zero(), Vector2::zero()), |(area, centroid), i| {
+        let a = vertices[i] - origin;
+        let b = vertices[(i + 1) % n] - origin;
+        let cross = a.perp_dot(b);
+        (area + cross, centroid + (a + b) * cross)
+    });
+    if area.is_zero() {
+        return (S::zero(), origin);
+    }
+    let three = S::one() + S::one() + S::one();
+    let two = S::one() + S::one();
+    ((area / two).abs(), origin + centroid / (three * area))
+}
+
+/// Length of the boundary of a closed polygon
+pub(crate) fn polygon_perimeter<S>(vertices: &[Point2<S>]) -> S
+where
+    S: BaseFloat,
+{
+    let n = vertices.len();
+    (0..n).fold(S::zero(), |sum, i| {
+        sum + (vertices[(i + 1) % n] - vertices[i]).magnitude()
+    })
+}
+
 /// Compute barycentric coordinates of p in relation to the triangle defined by (a, b, c).
 pub(crate) fn barycentric_point<P>(p: P, a: P, b: P, c: P) -> (P::Scalar, P::Scalar, P::Scalar)
 where
@@ -94,23 +160,335 @@ where
     (u, v, w)
 }
 
-#[inline]
-pub(crate) fn get_closest_point_on_edge<V>(start: &V, end: &V, point: &V) -> V
+/// Parameter `t` in `[0, 1]` of the point `start + (end - start) * t` on the segment that is
+/// closest to the given point, also for zero length segments, which give zero.
+///
+/// Works for both points and vectors, the latter are used for the simplex in GJK.
+pub(crate) fn segment_parameter<P, V>(start: P, end: P, point: P) -> V::Scalar
 where
-    V: VectorSpace + InnerSpace + Neg<Output = V>,
+    P: Copy + Sub<P, Output = V>,
+    V: InnerSpace,
     V::Scalar: BaseFloat,
 {
-    let line = *end - *start;
-    let line_dir = line.normalize();
-    let v = *point - *start;
-    let d = v.dot(line_dir);
-    if d < V::Scalar::zero() {
-        *start
-    } else if (d * d) > line.magnitude2() {
-        *end
+    let d = end - start;
+    let length2 = d.magnitude2();
+    if length2.is_zero() {
+        return V::Scalar::zero();
+    }
+    ((point - start).dot(d) / length2)
+        .max(V::Scalar::zero())
+        .min(V::Scalar::one())
+}
+
+/// Closest point on the segment to the given point, also for zero length segments
+pub(crate) fn closest_point_on_segment<P, V>(start: P, end: P, point: P) -> P
+where
+    P: Copy + Sub<P, Output = V> + Add<V, Output = P>,
+    V: InnerSpace,
+    V::Scalar: BaseFloat,
+{
+    start + (end - start) * segment_parameter(start, end, point)
+}
+
+/// Closest points between the segment from `p1` to `q1` and the segment from `p2` to `q2`, also
+/// for zero length segments. For parallel segments, the closest point on the first segment is its
+/// start point, if possible.
+pub(crate) fn closest_points_on_segments<P>(p1: P, q1: P, p2: P, q2: P) -> (P, P)
+where
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+    P::Diff: InnerSpace,
+{
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.magnitude2();
+    let e = d2.magnitude2();
+    let f = d2.dot(r);
+    let epsilon = P::Scalar::default_epsilon();
+    let clamp = |t: P::Scalar| t.max(P::Scalar::zero()).min(P::Scalar::one());
+    let (s, t) = if a <= epsilon && e <= epsilon {
+        (P::Scalar::zero(), P::Scalar::zero())
+    } else if a <= epsilon {
+        (P::Scalar::zero(), clamp(f / e))
     } else {
-        *start + line_dir * d
+        let c = d1.dot(r);
+        if e <= epsilon {
+            (clamp(-c / a), P::Scalar::zero())
+        } else {
+            let b = d1.dot(d2);
+            let denominator = a * e - b * b;
+            let s = if denominator > epsilon {
+                clamp((b * f - c * e) / denominator)
+            } else {
+                P::Scalar::zero()
+            };
+            let t = (b * s + f) / e;
+            if t < P::Scalar::zero() {
+                (clamp(-c / a), P::Scalar::zero())
+            } else if t > P::Scalar::one() {
+                (clamp((b - c) / a), P::Scalar::one())
+            } else {
+                (s, t)
+            }
+        }
+    };
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+/// The candidate nearest to the given point, or the point itself if there are no candidates
+pub(crate) fn nearest<P, I>(candidates: I, point: P) -> P
+where
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+    P::Diff: InnerSpace,
+    I: IntoIterator<Item = P>,
+{
+    candidates
+        .into_iter()
+        .fold((point, P::Scalar::infinity()), |closest, p| {
+            let distance2 = (p - point).magnitude2();
+            if distance2 < closest.1 {
+                (p, distance2)
+            } else {
+                closest
+            }
+        })
+        .0
+}
+
+/// Closest point on the surface of the ball with the given center and radius, and whether the
+/// point is inside the ball. Any surface point is closest to the center, the one along the first
+/// axis is used.
+pub(crate) fn closest_point_on_ball<P>(center: P, radius: P::Scalar, point: P) -> (P, bool)
+where
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+    P::Diff: InnerSpace + Array<Element = P::Scalar>,
+{
+    let v = point - center;
+    let magnitude2 = v.magnitude2();
+    let direction = if magnitude2.is_zero() {
+        let mut axis = P::Diff::zero();
+        axis[0] = P::Scalar::one();
+        axis
+    } else {
+        v / magnitude2.sqrt()
+    };
+    (center + direction * radius, magnitude2 <= radius * radius)
+}
+
+/// Closest point on the boundary of a closed polygon, given by its vertices in order
+pub(crate) fn closest_point_on_polygon<S>(vertices: &[Point2<S>], point: Point2<S>) -> Point2<S>
+where
+    S: BaseFloat,
+{
+    let n = vertices.len();
+    nearest(
+        (0..n).map(|i| closest_point_on_segment(vertices[i], vertices[(i + 1) % n], point)),
+        point,
+    )
+}
+
+/// Closest point on the surface of a solid of revolution around the Y axis. The profile is the
+/// open polyline of the surface in the (radius, y) half plane, without the axis itself.
+pub(crate) fn closest_point_on_revolution<S>(profile: &[Point2<S>], point: Point3<S>) -> Point3<S>
+where
+    S: BaseFloat,
+{
+    let radial = Vector2::new(point.x, point.z);
+    let r = radial.magnitude();
+    let direction = if r.is_zero() {
+        Vector2::unit_x()
+    } else {
+        radial / r
+    };
+    let local = Point2::new(r, point.y);
+    let closest = nearest(
+        profile
+            .windows(2)
+            .map(|edge| closest_point_on_segment(edge[0], edge[1], local)),
+        local,
+    );
+    Point3::new(direction.x * closest.x, closest.y, direction.y * closest.x)
+}
+
+/// Closest point on the triangle ABC to the given point, see Ericson, Real-Time Collision
+/// Detection, 5.1.5, and which of the vertices A, B and C span the feature the closest point is
+/// on.
+///
+/// Works for both points and vectors, the latter are used for the simplex in GJK.
+pub(crate) fn closest_point_on_triangle<P, V>(a: P, b: P, c: P, p: P) -> (P, [bool; 3])
+where
+    P: Copy + Sub<P, Output = V> + Add<V, Output = P>,
+    V: InnerSpace,
+    V::Scalar: BaseFloat,
+{
+    let zero = V::Scalar::zero();
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= zero && d2 <= zero {
+        return (a, [true, false, false]);
+    }
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= zero && d4 <= d3 {
+        return (b, [false, true, false]);
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= zero && d1 >= zero && d3 <= zero {
+        return (a + ab * (d1 / (d1 - d3)), [true, true, false]);
+    }
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= zero && d5 <= d6 {
+        return (c, [false, false, true]);
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= zero && d2 >= zero && d6 <= zero {
+        return (a + ac * (d2 / (d2 - d6)), [true, false, true]);
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= zero && d4 - d3 >= zero && d5 - d6 >= zero {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return (b + (c - b) * w, [false, true, true]);
     }
+    let sum = va + vb + vc;
+    if sum <= V::Scalar::default_epsilon() * ab.magnitude2() * ac.magnitude2() {
+        // degenerate triangle, use the closest edge
+        let edges = [
+            (a, b, [true, true, false]),
+            (b, c, [false, true, true]),
+            (c, a, [true, false, true]),
+        ];
+        return edges
+            .iter()
+            .map(|&(s, e, keep)| (closest_point_on_segment(s, e, p), keep))
+            .fold((a, [true, false, false]), |min, (q, keep)| {
+                if (q - p).magnitude2() < (min.0 - p).magnitude2() {
+                    (q, keep)
+                } else {
+                    min
+                }
+            });
+    }
+    (a + ab * (vb / sum) + ac * (vc / sum), [true, true, true])
+}
+
+/// Closest point on the surface of the axis aligned box with the given half extents, centered
+/// on the origin, and whether the point is inside the box. Points inside the box are moved to
+/// the nearest face.
+pub(crate) fn closest_point_on_box<V>(point: V, half_extents: V) -> (V, bool)
+where
+    V: Array + Copy,
+    V::Element: BaseFloat,
+{
+    let mut closest = point;
+    let mut inside = true;
+    for i in 0..V::len() {
+        if point[i] > half_extents[i] {
+            closest[i] = half_extents[i];
+            inside = false;
+        } else if point[i] < -half_extents[i] {
+            closest[i] = -half_extents[i];
+            inside = false;
+        }
+    }
+    if inside {
+        let axis = (0..V::len())
+            .min_by(|&i, &j| {
+                let di = half_extents[i] - point[i].abs();
+                let dj = half_extents[j] - point[j].abs();
+                di.partial_cmp(&dj).unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+        closest[axis] = if point[axis] < V::Element::zero() {
+            -half_extents[axis]
+        } else {
+            half_extents[axis]
+        };
+    }
+    (closest, inside)
+}
+
+/// Closest point on the surface of the axis aligned ellipsoid with the given radii, centered on
+/// the origin, and whether the point is inside the ellipsoid. Works for any number of dimensions.
+///
+/// The closest point is `radius^2 * point / (t + radius^2)` on each axis, where `t` is the root
+/// of a monotonic function, found with bisection, see David Eberly, "Distance from a Point to an
+/// Ellipse, an Ellipsoid, or a Hyperellipsoid".
+pub(crate) fn closest_point_on_ellipsoid<V>(point: V, radii: V) -> (V, bool)
+where
+    V: Array + Copy,
+    V::Element: BaseFloat,
+{
+    let zero = V::Element::zero();
+    let one = V::Element::one();
+    let n = V::len();
+    let inside = (0..n).fold(zero, |sum, i| sum + (point[i] / radii[i]).powi(2)) <= one;
+    let smallest = (0..n)
+        .min_by(|&i, &j| radii[i].partial_cmp(&radii[j]).unwrap_or(Ordering::Equal))
+        .unwrap();
+    let r2_min = radii[smallest] * radii[smallest];
+    let mut closest = point;
+
+    // the point is on a symmetry plane of the smallest axes, and close enough to the center
+    // that the closest point is off that plane
+    let degenerate = (0..n).all(|i| radii[i] > radii[smallest] || point[i].is_zero());
+    if degenerate {
+        let mut sum = zero;
+        for i in 0..n {
+            if radii[i] > radii[smallest] {
+                let r2 = radii[i] * radii[i];
+                closest[i] = r2 * point[i] / (r2 - r2_min);
+                sum += (closest[i] / radii[i]).powi(2);
+            } else {
+                closest[i] = zero;
+            }
+        }
+        if sum < one {
+            closest[smallest] = radii[smallest] * (one - sum).sqrt();
+            return (closest, inside);
+        }
+    }
+
+    let f = |t: V::Element| {
+        (0..n).fold(-one, |sum, i| {
+            let r2 = radii[i] * radii[i];
+            sum + (radii[i] * point[i] / (t + r2)).powi(2)
+        })
+    };
+    let norm = (0..n)
+        .fold(zero, |sum, i| sum + (radii[i] * point[i]).powi(2))
+        .sqrt();
+    let mut low = -r2_min;
+    let mut high = -r2_min + norm;
+    loop {
+        let t = (low + high) / (one + one);
+        if t <= low || t >= high {
+            break;
+        }
+        if f(t) > zero {
+            low = t;
+        } else {
+            high = t;
+        }
+    }
+    let t = (low + high) / (one + one);
+    for i in 0..n {
+        let r2 = radii[i] * radii[i];
+        closest[i] = if (t + r2).is_zero() {
+            zero
+        } else {
+            r2 * point[i] / (t + r2)
+        };
+    }
+    (closest, inside)
 }
 
 pub(crate) fn cylinder_ray_quadratic_solve<S>(r: &Ray3<S>, radius: S) -> Option<(S, S)>
@@ -134,6 +512,60 @@ where
     Some((t1, t2))
 }
 
+/// Single triangle in 3D, used as the convex primitive for GJK on triangle soups
+pub(crate) struct MeshTriangle<S> {
+    pub(crate) vertices: [Point3<S>; 3],
+}
+
+impl<S> Primitive for MeshTriangle<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        get_max_point(self.vertices.iter(), direction, transform)
+    }
+}
+
+/// Möller-Trumbore ray triangle intersection, returns the ray parameter of the hit, if any.
+/// Hits are reported for both sides of the triangle.
+pub(crate) fn ray_triangle<S>(ray: &Ray3<S>, triangle: &[Point3<S>; 3]) -> Option<S>
+where
+    S: BaseFloat,
+{
+    let edge1 = triangle[1] - triangle[0];
+    let edge2 = triangle[2] - triangle[0];
+    let p = ray.direction.cross(edge2);
+    let det = edge1.dot(p);
+    // the determinant scales with the ray direction and both edges, so the parallel test is
+    // relative to their lengths, to treat small and large triangles the same
+    let scale = ray.direction.magnitude() * edge1.magnitude() * edge2.magnitude();
+    if det.abs() <= scale * S::default_epsilon() {
+        return None;
+    }
+    let inv_det = S::one() / det;
+    let s = ray.origin - triangle[0];
+    let u = s.dot(p) * inv_det;
+    if u < S::zero() || u > S::one() {
+        return None;
+    }
+    let q = s.cross(edge1);
+    let v = ray.direction.dot(q) * inv_det;
+    if v < S::zero() || u + v > S::one() {
+        return None;
+    }
+    let t = edge2.dot(q) * inv_det;
+    if t >= S::zero() {
+        Some(t)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std;
@@ -157,6 +589,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_bound_offset() {
+        // the bound must not include the origin, when none of the vertices are there
+        let triangle = vec![
+            Point2::new(2., 3.),
+            Point2::new(4., 2.),
+            Point2::new(3., 5.),
+        ];
+        assert_eq!(
+            Aabb2::new(Point2::new(2., 2.), Point2::new(4., 5.)),
+            get_bound(triangle.iter())
+        );
+    }
+
     fn test_max_point(dx: f32, dy: f32, px: f32, py: f32, rot_angle: f32) {
         let direction = Vector2::new(dx, dy);
         let point = Point2::new(px, py);
@@ -230,25 +676,58 @@ mod tests {
         let start = Vector2::new(3., -1.);
         let end = Vector2::new(-1., 3.);
         let point = Vector2::zero();
-        let p = get_closest_point_on_edge(&start, &end, &point);
+        let p = closest_point_on_segment(start, end, point);
         assert_ulps_eq!(Vector2::new(1f32, 1f32), p);
 
         let start = Vector2::new(2., -2.);
         let end = Vector2::new(2., 2.);
-        let p = get_closest_point_on_edge(&start, &end, &point);
+        let p = closest_point_on_segment(start, end, point);
         assert_ulps_eq!(Vector2::new(2f32, 0f32), p);
 
         let start = Vector2::new(2., 4.);
         let end = Vector2::new(2., 2.);
-        let p = get_closest_point_on_edge(&start, &end, &point);
+        let p = closest_point_on_segment(start, end, point);
         assert_ulps_eq!(Vector2::new(2f32, 2f32), p);
 
         let start = Vector2::new(2., -2.);
         let end = Vector2::new(2., -4.);
-        let p = get_closest_point_on_edge(&start, &end, &point);
+        let p = closest_point_on_segment(start, end, point);
         assert_ulps_eq!(Vector2::new(2f32, -2f32), p);
     }
 
+    #[test]
+    fn test_closest_point_on_triangle() {
+        let (a, b, c) = (
+            Point3::new(0., 0., 0.),
+            Point3::new(2., 0., 0.),
+            Point3::new(0., 2., 0.),
+        );
+        let closest = |p| closest_point_on_triangle(a, b, c, p);
+        assert_eq!((a, [true, false, false]), closest(Point3::new(-1., -1., 1.)));
+        assert_eq!((c, [false, false, true]), closest(Point3::new(-1., 3., 0.)));
+        assert_eq!(
+            (Point3::new(1., 0., 0.), [true, true, false]),
+            closest(Point3::new(1., -1., 1.))
+        );
+        assert_eq!(
+            (Point3::new(1., 1., 0.), [false, true, true]),
+            closest(Point3::new(2., 2., -1.))
+        );
+        let (p, keep) = closest(Point3::new(0.5, 0.5, 3.));
+        assert_ulps_eq!(Point3::new(0.5, 0.5, 0.), p);
+        assert_eq!([true, true, true], keep);
+
+        // degenerate triangle, with the closest point beyond B, on an edge ending at C
+        let (p, keep) = closest_point_on_triangle(
+            Vector3::new(0., 0., 0.),
+            Vector3::new(1., 0., 0.),
+            Vector3::new(4., 0., 0.),
+            Vector3::new(3., 1., 0.),
+        );
+        assert_ulps_eq!(Vector3::new(3., 0., 0.), p);
+        assert!(keep[2]);
+    }
+
     fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
         Decomposed {
             scale: 1.,
@@ -256,4 +735,72 @@ mod tests {
             disp: Vector2::new(dx, dy),
         }
     }
+
+    #[test]
+    fn test_closest_points_on_segments() {
+        let (a, b) = closest_points_on_segments(
+            Point3::new(-1., 0., 0.),
+            Point3::new(1., 0., 0.),
+            Point3::new(0.5, -1., 2.),
+            Point3::new(0.5, 1., 2.),
+        );
+        assert_ulps_eq!(Point3::new(0.5, 0., 0.), a);
+        assert_ulps_eq!(Point3::new(0.5, 0., 2.), b);
+
+        // clamped to the end points
+        let (a, b) = closest_points_on_segments(
+            Point2::new(0., 0.),
+            Point2::new(1., 0.),
+            Point2::new(2., 1.),
+            Point2::new(3., 2.),
+        );
+        assert_ulps_eq!(Point2::new(1., 0.), a);
+        assert_ulps_eq!(Point2::new(2., 1.), b);
+
+        // zero length segment
+        let (a, b) = closest_points_on_segments(
+            Point2::new(0.5, 3.),
+            Point2::new(0.5, 3.),
+            Point2::new(0., 0.),
+            Point2::new(1., 0.),
+        );
+        assert_ulps_eq!(Point2::new(0.5, 3.), a);
+        assert_ulps_eq!(Point2::new(0.5, 0.), b);
+    }
+
+    #[test]
+    fn test_ray_triangle_scale() {
+        // the same hit with the triangle and the ray origin scaled, down to a triangle smaller
+        // than the epsilon
+        for &scale in &[1e-4f32, 1e-2, 1., 1e2, 1e4] {
+            let triangle = [
+                Point3::new(0., 0., 0.),
+                Point3::new(scale, 0., 0.),
+                Point3::new(0., scale, 0.),
+            ];
+            let ray = Ray3::new(
+                Point3::new(0.25 * scale, 0.25 * scale, scale),
+                Vector3::new(0., 0., -1.),
+            );
+            let t = ray_triangle(&ray, &triangle).unwrap();
+            assert_ulps_eq!(scale, t, epsilon = scale * 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_ray_triangle_parallel() {
+        let triangle = [
+            Point3::new(0f32, 0., 0.),
+            Point3::new(1e4, 0., 0.),
+            Point3::new(0., 1e4, 0.),
+        ];
+        // in the plane of the triangle, and so close to it that the hit is only rounding
+        let ray = Ray3::new(Point3::new(-1., 10., 0.), Vector3::new(1., 0., 0.));
+        assert_eq!(None, ray_triangle(&ray, &triangle));
+        let ray = Ray3::new(
+            Point3::new(-1., 10., 1e-8),
+            Vector3::new(1., 0., -1e-10).normalize(),
+        );
+        assert_eq!(None, ray_triangle(&ray, &triangle));
+    }
 }
@@ -0,0 +1,316 @@
+//! Wedge primitive
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, Ray3};
+use crate::prelude::*;
+use crate::primitive::util::{closest_point_on_triangle, get_max_point, nearest};
+use crate::volume::Sphere;
+
+/// Wedge primitive, a triangular prism shaped like a ramp.
+///
+/// Fits in a cuboid with the given dimensions, with local origin in the center of the cuboid. The
+/// bottom face is at `-y`, the vertical back face is at `-x`, and the slope goes from the top of
+/// the back face down to the bottom face at `+x`. The triangle is extruded along the Z axis.
+///
+/// Have a cached set of corner points to speed up computation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Wedge<S> {
+    /// Dimensions of the wedge
+    dim: Vector3<S>,
+    half_dim: Vector3<S>,
+    corners: [Point3<S>; 6],
+}
+
+impl<S> Wedge<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new wedge primitive from component dimensions
+    pub fn new(dim_x: S, dim_y: S, dim_z: S) -> Self {
+        Self::new_impl(Vector3::new(dim_x, dim_y, dim_z))
+    }
+
+    /// Create a new wedge primitive from a vector of component dimensions
+    pub fn new_impl(dim: Vector3<S>) -> Self {
+        let half_dim = dim / (S::one() + S::one());
+        Self {
+            dim,
+            half_dim,
+            corners: Self::generate_corners(&half_dim),
+        }
+    }
+
+    /// Get the dimensions of the `Wedge`
+    pub fn dim(&self) -> &Vector3<S> {
+        &self.dim
+    }
+
+    /// Get the half dimensions of the `Wedge`
+    pub fn half_dim(&self) -> &Vector3<S> {
+        &self.half_dim
+    }
+
+    /// Get the outward normal of the slope
+    pub fn slope_normal(&self) -> Vector3<S> {
+        Vector3::new(self.half_dim.y, self.half_dim.x, S::zero()).normalize()
+    }
+
+    fn generate_corners(half_dim: &Vector3<S>) -> [Point3<S>; 6] {
+        [
+            Point3::new(-half_dim.x, -half_dim.y, half_dim.z),
+            Point3::new(half_dim.x, -half_dim.y, half_dim.z),
+            Point3::new(-half_dim.x, half_dim.y, half_dim.z),
+            Point3::new(-half_dim.x, -half_dim.y, -half_dim.z),
+            Point3::new(half_dim.x, -half_dim.y, -half_dim.z),
+            Point3::new(-half_dim.x, half_dim.y, -half_dim.z),
+        ]
+    }
+
+    /// All faces as `normal.dot(p) <= offset`, the slope passes through the origin
+    fn faces(&self) -> [(Vector3<S>, S); 5] {
+        let h = self.half_dim;
+        [
+            (Vector3::new(S::zero(), -S::one(), S::zero()), h.y),
+            (Vector3::new(-S::one(), S::zero(), S::zero()), h.x),
+            (Vector3::new(S::zero(), S::zero(), S::one()), h.z),
+            (Vector3::new(S::zero(), S::zero(), -S::one()), h.z),
+            (self.slope_normal(), S::zero()),
+        ]
+    }
+
+    /// Clip the ray against the planes of all faces, and return the entry parameter, if any
+    fn ray_parameter(&self, ray: &Ray3<S>) -> Option<S> {
+        let mut enter = S::zero();
+        let mut exit = S::infinity();
+        for &(normal, offset) in &self.faces() {
+            let distance = normal.dot(ray.origin.to_vec()) - offset;
+            let speed = normal.dot(ray.direction);
+            if speed.is_zero() {
+                if distance > S::zero() {
+                    return None;
+                }
+            } else {
+                let t = -distance / speed;
+                if speed < S::zero() {
+                    enter = enter.max(t);
+                } else {
+                    exit = exit.min(t);
+                }
+            }
+            if enter > exit {
+                return None;
+            }
+        }
+        Some(enter)
+    }
+}
+
+impl<S> Primitive for Wedge<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn support_point<T>(&self, direction: &Vector3<S>, transform: &T) -> Point3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        get_max_point(self.corners.iter(), direction, transform)
+    }
+}
+
+impl<S> ComputeBound<Aabb3<S>> for Wedge<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Aabb3<S> {
+        Aabb3::new(
+            Point3::from_vec(-self.half_dim),
+            Point3::from_vec(self.half_dim),
+        )
+    }
+}
+
+impl<S> ComputeBound<Sphere<S>> for Wedge<S>
+where
+    S: BaseFloat,
+{
+    fn compute_bound(&self) -> Sphere<S> {
+        Sphere {
+            center: Point3::origin(),
+            radius: self.half_dim.magnitude(),
+        }
+    }
+}
+
+impl<S> Contains<Point3<S>> for Wedge<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn contains(&self, point: &Point3<S>) -> bool {
+        self.faces()
+            .iter()
+            .all(|&(normal, offset)| normal.dot(point.to_vec()) <= offset)
+    }
+}
+
+impl<S> ClosestPoint<Point3<S>> for Wedge<S>
+where
+    S: BaseFloat,
+{
+    /// Point must be in object space
+    fn closest_point(&self, point: &Point3<S>) -> (Point3<S>, bool) {
+        const TRIANGLES: [(usize, usize, usize); 8] = [
+            (0, 1, 4),
+            (0, 4, 3),
+            (0, 3, 5),
+            (0, 5, 2),
+            (0, 1, 2),
+            (3, 4, 5),
+            (1, 4, 5),
+            (1, 5, 2),
+        ];
+        let c = &self.corners;
+        let closest = nearest(
+            TRIANGLES
+                .iter()
+                .map(|&(a, b, d)| closest_point_on_triangle(c[a], c[b], c[d], *point).0),
+            *point,
+        );
+        (closest, self.contains(point))
+    }
+}
+
+impl<S> Discrete<Ray3<S>> for Wedge<S>
+where
+    S: BaseFloat,
+{
+    fn intersects(&self, ray: &Ray3<S>) -> bool {
+        self.ray_parameter(ray).is_some()
+    }
+}
+
+impl<S> Continuous<Ray3<S>> for Wedge<S>
+where
+    S: BaseFloat,
+{
+    type Result = Point3<S>;
+
+    fn intersection(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
+        self.ray_parameter(ray)
+            .map(|t| ray.origin + ray.direction * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std;
+
+    use cgmath::{Decomposed, Quaternion, Rad, Vector3};
+    use approx::assert_ulps_eq;
+
+    use super::*;
+    use crate::algorithm::minkowski::GJK3;
+    use crate::primitive::Sphere as SpherePrimitive;
+
+    #[test]
+    fn test_wedge_support() {
+        let wedge = Wedge::new(4., 2., 2.);
+        let t = transform(0., 0., 0., 0.);
+        assert_eq!(
+            Point3::new(2., -1., 1.),
+            wedge.support_point(&Vector3::new(1., 0.1, 0.1), &t)
+        );
+        assert_eq!(
+            Point3::new(-2., 1., -1.),
+            wedge.support_point(&Vector3::new(0.1, 1., -0.1), &t)
+        );
+        // the top front corner of the cuboid is cut away
+        let p = wedge.support_point(&Vector3::new(1., 1., 0.1), &t);
+        assert!(p != Point3::new(2., 1., 1.));
+    }
+
+    #[test]
+    fn test_wedge_support_transformed() {
+        let wedge = Wedge::new(4., 2., 2.);
+        let t = transform(0., 5., 0., std::f32::consts::PI);
+        assert_ulps_eq!(
+            Point3::new(2., 4., 1.),
+            wedge.support_point(&Vector3::new(1., -0.1, 0.1), &t),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_wedge_bound() {
+        let wedge = Wedge::new(4., 2., 2.);
+        assert_eq!(
+            Aabb3::new(Point3::new(-2., -1., -1.), Point3::new(2., 1., 1.)),
+            wedge.compute_bound()
+        );
+        let sphere: Sphere<f32> = wedge.compute_bound();
+        assert_ulps_eq!(6f32.sqrt(), sphere.radius);
+        assert_ulps_eq!(Vector3::new(1., 2., 0.).normalize(), wedge.slope_normal());
+    }
+
+    #[test]
+    fn test_wedge_ray() {
+        let wedge = Wedge::new(4., 2., 2.);
+        // down onto the slope, which is at height 0 in the middle
+        let ray = Ray3::new(Point3::new(0.2, 10., 0.3), Vector3::new(0., -1., 0.));
+        assert!(wedge.intersects(&ray));
+        assert_ulps_eq!(
+            Point3::new(0.2, -0.1, 0.3),
+            wedge.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        // above the slope, inside the bounding box
+        let ray = Ray3::new(Point3::new(10., 0.5, 0.3), Vector3::new(-1., 0., 0.));
+        assert_ulps_eq!(
+            Point3::new(-1., 0.5, 0.3),
+            wedge.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        let ray = Ray3::new(Point3::new(1.5, 10., 0.3), Vector3::new(0., -1., 0.));
+        assert_ulps_eq!(
+            Point3::new(1.5, -0.75, 0.3),
+            wedge.intersection(&ray).unwrap(),
+            epsilon = 1e-6
+        );
+        let ray = Ray3::new(Point3::new(2.5, 10., 0.3), Vector3::new(0., -1., 0.));
+        assert!(!wedge.intersects(&ray));
+        assert_eq!(None, wedge.intersection(&ray));
+        // from the back
+        let ray = Ray3::new(Point3::new(-10., 0.5, 0.3), Vector3::new(1., 0., 0.));
+        assert_eq!(Some(Point3::new(-2., 0.5, 0.3)), wedge.intersection(&ray));
+    }
+
+    #[test]
+    fn test_wedge_gjk() {
+        let wedge = Wedge::new(4., 2., 2.);
+        let sphere = SpherePrimitive::new(0.5);
+        let gjk = GJK3::new();
+        let t = transform(0., 0., 0., 0.);
+        // resting on the slope
+        assert!(gjk
+            .intersect(&wedge, &t, &sphere, &transform(0.5, 0.1, 0.2, 0.))
+            .is_some());
+        // inside the bounding box, but above the slope
+        assert!(gjk
+            .intersect(&wedge, &t, &sphere, &transform(1.4, 0.5, 0.2, 0.))
+            .is_none());
+    }
+
+    // util
+    fn transform(dx: f32, dy: f32, dz: f32, rot: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+        Decomposed {
+            scale: 1.,
+            rot: Quaternion::from_angle_z(Rad(rot)),
+            disp: Vector3::new(dx, dy, dz),
+        }
+    }
+}
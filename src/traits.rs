@@ -1,4 +1,4 @@
-use cgmath::BaseNum;
+use cgmath::{BaseFloat, BaseNum, Point2};
 use cgmath::prelude::*;
 
 /// An intersection test with a result.
@@ -37,6 +37,23 @@ pub trait SurfaceArea {
     fn surface_area(&self) -> Self::Scalar;
 }
 
+/// Geometric properties of 2D shapes, for use in physics integration and mass property
+/// computation.
+///
+pub trait Geometry2 {
+    /// Scalar type
+    type Scalar: BaseFloat;
+
+    /// Compute the area of the shape
+    fn area(&self) -> Self::Scalar;
+
+    /// Compute the length of the boundary of the shape
+    fn perimeter(&self) -> Self::Scalar;
+
+    /// Compute the centroid of the shape, in object space
+    fn centroid(&self) -> Point2<Self::Scalar>;
+}
+
 /// Build the union of two shapes.
 ///
 pub trait Union<RHS = Self> {
@@ -76,12 +93,33 @@ pub trait HasBound {
 }
 
 /// Utilities for computing bounding volumes of primitives
+///
+/// Generic over the bounding volume type, so a primitive can compute several kinds of bounds,
+/// for example `Aabb3`, `Sphere`, `Obb3` or `Dop3`. The DBVT and most broad phase algorithms work
+/// with any bound that has the operations they require, for example spheres work with
+/// [`TreeBroadPhase`](algorithm/broad_phase/struct.TreeBroadPhase.html), while
+/// [`OrthtreeBroadPhase`](algorithm/broad_phase/struct.OrthtreeBroadPhase.html) needs boxes.
+///
+/// The primitives compute an AABB, and a `Sphere` in 3D or a `Circle` in 2D. Sphere and circle
+/// bounds are rotation invariant, so the bound of a rotating primitive only needs its center
+/// transformed, instead of being recomputed every frame.
 pub trait ComputeBound<B>
 where
     B: Bound,
 {
     /// Compute the bounding volume
     fn compute_bound(&self) -> B;
+
+    /// Compute the bounding volume in world space, with the given local to world transform.
+    ///
+    /// The default implementation transforms the local bounding volume, so for rotated
+    /// primitives the result is conservative, but not necessarily the tightest bound.
+    fn compute_bound_transformed<T>(&self, transform: &T) -> B
+    where
+        T: Transform<B::Point>,
+    {
+        self.compute_bound().transform_volume(transform)
+    }
 }
 
 /// Minkowski support function for primitive
@@ -114,6 +152,24 @@ pub trait Primitive {
         T: Transform<Self::Point>;
 }
 
+impl<P> Primitive for &P
+where
+    P: Primitive + ?Sized,
+{
+    type Point = P::Point;
+
+    fn support_point<T>(
+        &self,
+        direction: &<Self::Point as EuclideanSpace>::Diff,
+        transform: &T,
+    ) -> Self::Point
+    where
+        T: Transform<Self::Point>,
+    {
+        (*self).support_point(direction, transform)
+    }
+}
+
 /// Discrete intersection test on transformed primitive
 pub trait DiscreteTransformed<RHS> {
     /// Point type for transformation of self
@@ -139,6 +195,174 @@ pub trait ContinuousTransformed<RHS> {
         T: Transform<Self::Point>;
 }
 
+/// Containment test on transformed primitive, for points in world space
+pub trait ContainsTransformed<RHS> {
+    /// Point type for transformation of self
+    type Point: EuclideanSpace;
+
+    /// Containment test for transformed self
+    fn contains_transformed<T>(&self, _: &RHS, _: &T) -> bool
+    where
+        T: Transform<Self::Point>;
+}
+
+impl<P, C> ContainsTransformed<P> for C
+where
+    C: Contains<P>,
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+{
+    type Point = P;
+
+    fn contains_transformed<T>(&self, point: &P, transform: &T) -> bool
+    where
+        T: Transform<P>,
+    {
+        self.contains(
+            &transform
+                .inverse_transform()
+                .unwrap()
+                .transform_point(*point),
+        )
+    }
+}
+
+/// Closest point on the surface of a shape
+pub trait ClosestPoint<P> {
+    /// Compute the point on the surface of the shape closest to the given point.
+    ///
+    /// For primitives, both points are in object space. For flat or degenerate shapes, like lines
+    /// and quads, the whole shape counts as the surface.
+    ///
+    /// ## Returns
+    ///
+    /// The closest point on the surface, and true if the given point is inside the shape.
+    fn closest_point(&self, point: &P) -> (P, bool);
+}
+
+/// Closest point on the surface of a transformed primitive, for points in world space
+pub trait ClosestPointTransformed<RHS> {
+    /// Point type for transformation of self
+    type Point: EuclideanSpace;
+
+    /// Closest point query for transformed self, see [`ClosestPoint`](trait.ClosestPoint.html).
+    /// The returned point is in world space.
+    fn closest_point_transformed<T>(&self, _: &RHS, _: &T) -> (RHS, bool)
+    where
+        T: Transform<Self::Point>;
+}
+
+impl<P, C> ClosestPointTransformed<P> for C
+where
+    C: ClosestPoint<P>,
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+{
+    type Point = P;
+
+    fn closest_point_transformed<T>(&self, point: &P, transform: &T) -> (P, bool)
+    where
+        T: Transform<P>,
+    {
+        let local = transform
+            .inverse_transform()
+            .unwrap()
+            .transform_point(*point);
+        let (closest, inside) = self.closest_point(&local);
+        (transform.transform_point(closest), inside)
+    }
+}
+
+/// Signed distance from a point to the surface of a shape
+pub trait Sdf<P: EuclideanSpace> {
+    /// Compute the signed distance from the given point to the surface of the shape, negative
+    /// inside the shape. For primitives, the point is in object space.
+    fn signed_distance(&self, point: &P) -> P::Scalar;
+}
+
+/// Shapes with a closest point query get the signed distance from the distance to that point
+impl<P, C> Sdf<P> for C
+where
+    C: ClosestPoint<P>,
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+    P::Diff: InnerSpace,
+{
+    fn signed_distance(&self, point: &P) -> P::Scalar {
+        let (closest, inside) = self.closest_point(point);
+        let distance = (*point - closest).magnitude();
+        if inside {
+            -distance
+        } else {
+            distance
+        }
+    }
+}
+
+/// Signed distance from a point in world space to the surface of a transformed primitive
+pub trait SdfTransformed<RHS: EuclideanSpace> {
+    /// Point type for transformation of self
+    type Point: EuclideanSpace;
+
+    /// Signed distance for transformed self, see [`Sdf`](trait.Sdf.html). The transform must not
+    /// scale the primitive.
+    fn signed_distance_transformed<T>(&self, _: &RHS, _: &T) -> RHS::Scalar
+    where
+        T: Transform<Self::Point>;
+}
+
+impl<P, C> SdfTransformed<P> for C
+where
+    C: Sdf<P>,
+    P: EuclideanSpace,
+    P::Scalar: BaseFloat,
+{
+    type Point = P;
+
+    fn signed_distance_transformed<T>(&self, point: &P, transform: &T) -> P::Scalar
+    where
+        T: Transform<P>,
+    {
+        self.signed_distance(
+            &transform
+                .inverse_transform()
+                .unwrap()
+                .transform_point(*point),
+        )
+    }
+}
+
+/// Distance query between transformed shapes
+///
+/// Implemented for the `Primitive2` and `Primitive3` wrappers, so the distance between any pair
+/// of primitives can be computed the same way. Cheap pairs are computed directly, all others use
+/// GJK.
+pub trait Distance<RHS = Self> {
+    /// Point type for transformation of the shapes
+    type Point: EuclideanSpace;
+
+    /// Compute the smallest distance between self and the other shape.
+    ///
+    /// ## Parameters
+    ///
+    /// - `transform`: model-to-world-transform for self
+    /// - `other`: the other shape
+    /// - `other_transform`: model-to-world-transform for the other shape
+    ///
+    /// ## Returns
+    ///
+    /// The distance between the shapes, or None if they intersect.
+    fn distance<TL, TR>(
+        &self,
+        transform: &TL,
+        other: &RHS,
+        other_transform: &TR,
+    ) -> Option<<Self::Point as EuclideanSpace>::Scalar>
+    where
+        TL: Transform<Self::Point>,
+        TR: Transform<Self::Point>;
+}
+
 /// Trait used for interpolation of values
 ///
 /// ## Type parameters:
@@ -7,8 +7,9 @@ use cgmath::{BaseFloat, BaseNum, Point2, Vector2};
 use cgmath::prelude::*;
 
 use super::{max, min};
-use crate::{Line2, Ray2};
+use crate::{Circle, Dop2, Line2, Obb2, Ray2, Relation};
 use crate::prelude::*;
+use crate::primitive::util::closest_point_on_box;
 
 /// A two-dimensional AABB, aka a rectangle.
 #[derive(Copy, Clone, PartialEq)]
@@ -42,6 +43,44 @@ impl<S: BaseNum> Aabb2<S> {
     }
 }
 
+impl<S: BaseFloat> Aabb2<S> {
+    /// Classify the given AABB against this AABB: `In` if it is completely contained, `Cross`
+    /// if they overlap, and `Out` otherwise.
+    ///
+    /// Useful for hierarchical culling, where the children of a contained node need no further
+    /// tests.
+    pub fn relate_aabb(&self, other: &Aabb2<S>) -> Relation {
+        if self.contains(other) {
+            Relation::In
+        } else if self.intersects(other) {
+            Relation::Cross
+        } else {
+            Relation::Out
+        }
+    }
+
+    /// Classify the given circle against this AABB: `In` if it is completely contained, `Cross`
+    /// if they overlap, and `Out` otherwise.
+    pub fn relate_circle(&self, circle: &Circle<S>) -> Relation {
+        if self.contains(circle) {
+            Relation::In
+        } else if self.intersects(circle) {
+            Relation::Cross
+        } else {
+            Relation::Out
+        }
+    }
+
+    /// Squared distance from the given point to the closest point in the AABB, zero inside
+    fn distance2(&self, p: Point2<S>) -> S {
+        let closest = Point2::new(
+            p.x.max(self.min.x).min(self.max.x),
+            p.y.max(self.min.y).min(self.max.y),
+        );
+        closest.distance2(p)
+    }
+}
+
 impl<S: BaseNum> Aabb for Aabb2<S> {
     type Scalar = S;
     type Diff = Vector2<S>;
@@ -97,6 +136,14 @@ impl<S: BaseNum> Contains<Point2<S>> for Aabb2<S> {
     }
 }
 
+impl<S: BaseFloat> ClosestPoint<Point2<S>> for Aabb2<S> {
+    fn closest_point(&self, p: &Point2<S>) -> (Point2<S>, bool) {
+        let center = self.center();
+        let (closest, inside) = closest_point_on_box(p - center, self.half_extents());
+        (center + closest, inside)
+    }
+}
+
 impl<S: BaseNum> Contains<Aabb2<S>> for Aabb2<S> {
     #[inline]
     fn contains(&self, other: &Aabb2<S>) -> bool {
@@ -108,6 +155,17 @@ impl<S: BaseNum> Contains<Aabb2<S>> for Aabb2<S> {
     }
 }
 
+impl<S: BaseFloat> Contains<Circle<S>> for Aabb2<S> {
+    // will return true for border hits on both min and max extents
+    #[inline]
+    fn contains(&self, circle: &Circle<S>) -> bool {
+        (circle.center.x - circle.radius) >= self.min.x
+            && (circle.center.y - circle.radius) >= self.min.y
+            && (circle.center.x + circle.radius) <= self.max.x
+            && (circle.center.y + circle.radius) <= self.max.y
+    }
+}
+
 impl<S: BaseNum> Contains<Line2<S>> for Aabb2<S> {
     #[inline]
     fn contains(&self, line: &Line2<S>) -> bool {
@@ -217,3 +275,47 @@ impl<S: BaseFloat> Discrete<Aabb2<S>> for Aabb2<S> {
         a1.x > b0.x && a0.x < b1.x && a1.y > b0.y && a0.y < b1.y
     }
 }
+
+impl<S: BaseFloat> Discrete<Circle<S>> for Aabb2<S> {
+    fn intersects(&self, circle: &Circle<S>) -> bool {
+        self.distance2(circle.center) <= circle.radius * circle.radius
+    }
+}
+
+impl<S: BaseFloat> Continuous<Aabb2<S>> for Aabb2<S> {
+    type Result = Aabb2<S>;
+
+    /// Compute the overlapping part of the two boxes. Boxes that only touch do not overlap, like
+    /// in the `Discrete` test.
+    fn intersection(&self, aabb: &Aabb2<S>) -> Option<Aabb2<S>> {
+        if self.intersects(aabb) {
+            Some(Aabb2::new(
+                MinMax::max(self.min(), aabb.min()),
+                MinMax::min(self.max(), aabb.max()),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl<S: BaseFloat> From<Circle<S>> for Aabb2<S> {
+    /// Smallest AABB enclosing the circle
+    fn from(circle: Circle<S>) -> Self {
+        Aabb2::new(circle.min_extent(), circle.max_extent())
+    }
+}
+
+impl<S: BaseFloat> From<Obb2<S>> for Aabb2<S> {
+    /// Smallest AABB enclosing the OBB
+    fn from(obb: Obb2<S>) -> Self {
+        Aabb2::new(obb.min_extent(), obb.max_extent())
+    }
+}
+
+impl<S: BaseFloat> From<Dop2<S>> for Aabb2<S> {
+    /// Smallest AABB enclosing the DOP
+    fn from(dop: Dop2<S>) -> Self {
+        dop.to_aabb()
+    }
+}
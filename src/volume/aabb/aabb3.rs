@@ -7,8 +7,9 @@ use cgmath::{BaseFloat, BaseNum, Point3, Vector3};
 use cgmath::prelude::*;
 
 use super::{max, min};
-use crate::{Line3, Plane, Ray3, Sphere};
+use crate::{Capsule, Dop3, Line3, Obb3, Plane, Ray3, Relation, Sphere};
 use crate::prelude::*;
+use crate::primitive::util::closest_point_on_box;
 
 /// A three-dimensional AABB, aka a rectangular prism.
 #[derive(Copy, Clone, PartialEq)]
@@ -46,6 +47,45 @@ impl<S: BaseNum> Aabb3<S> {
     }
 }
 
+impl<S: BaseFloat> Aabb3<S> {
+    /// Classify the given AABB against this AABB: `In` if it is completely contained, `Cross`
+    /// if they overlap, and `Out` otherwise.
+    ///
+    /// Useful for hierarchical culling, where the children of a contained node need no further
+    /// tests.
+    pub fn relate_aabb(&self, other: &Aabb3<S>) -> Relation {
+        if self.contains(other) {
+            Relation::In
+        } else if self.intersects(other) {
+            Relation::Cross
+        } else {
+            Relation::Out
+        }
+    }
+
+    /// Classify the given sphere against this AABB: `In` if it is completely contained, `Cross`
+    /// if they overlap, and `Out` otherwise.
+    pub fn relate_sphere(&self, sphere: &Sphere<S>) -> Relation {
+        if self.contains(sphere) {
+            Relation::In
+        } else if self.intersects(sphere) {
+            Relation::Cross
+        } else {
+            Relation::Out
+        }
+    }
+
+    /// Squared distance from the given point to the closest point in the AABB, zero inside
+    fn distance2(&self, p: Point3<S>) -> S {
+        let closest = Point3::new(
+            p.x.max(self.min.x).min(self.max.x),
+            p.y.max(self.min.y).min(self.max.y),
+            p.z.max(self.min.z).min(self.max.z),
+        );
+        closest.distance2(p)
+    }
+}
+
 impl<S: BaseNum> Aabb for Aabb3<S> {
     type Scalar = S;
     type Diff = Vector3<S>;
@@ -110,6 +150,14 @@ impl<S: BaseNum> Contains<Point3<S>> for Aabb3<S> {
     }
 }
 
+impl<S: BaseFloat> ClosestPoint<Point3<S>> for Aabb3<S> {
+    fn closest_point(&self, p: &Point3<S>) -> (Point3<S>, bool) {
+        let center = self.center();
+        let (closest, inside) = closest_point_on_box(p - center, self.half_extents());
+        (center + closest, inside)
+    }
+}
+
 impl<S: BaseNum> Contains<Aabb3<S>> for Aabb3<S> {
     #[inline]
     fn contains(&self, other: &Aabb3<S>) -> bool {
@@ -240,6 +288,29 @@ impl<S: BaseFloat> Discrete<Aabb3<S>> for Aabb3<S> {
     }
 }
 
+impl<S: BaseFloat> Discrete<Sphere<S>> for Aabb3<S> {
+    fn intersects(&self, sphere: &Sphere<S>) -> bool {
+        self.distance2(sphere.center) <= sphere.radius * sphere.radius
+    }
+}
+
+impl<S: BaseFloat> Continuous<Aabb3<S>> for Aabb3<S> {
+    type Result = Aabb3<S>;
+
+    /// Compute the overlapping part of the two boxes. Boxes that only touch do not overlap, like
+    /// in the `Discrete` test.
+    fn intersection(&self, aabb: &Aabb3<S>) -> Option<Aabb3<S>> {
+        if self.intersects(aabb) {
+            Some(Aabb3::new(
+                MinMax::max(self.min(), aabb.min()),
+                MinMax::min(self.max(), aabb.max()),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
 impl<S: BaseFloat> PlaneBound<S> for Aabb3<S> {
     fn relate_plane(&self, plane: Plane<S>) -> Relation {
         let corners = self.to_corners();
@@ -262,3 +333,31 @@ impl<S: BaseNum> SurfaceArea for Aabb3<S> {
         two * ((dim.x * dim.y) + (dim.x * dim.z) + (dim.y * dim.z))
     }
 }
+
+impl<S: BaseFloat> From<Sphere<S>> for Aabb3<S> {
+    /// Smallest AABB enclosing the sphere
+    fn from(sphere: Sphere<S>) -> Self {
+        Aabb3::new(sphere.min_extent(), sphere.max_extent())
+    }
+}
+
+impl<S: BaseFloat> From<Obb3<S>> for Aabb3<S> {
+    /// Smallest AABB enclosing the OBB
+    fn from(obb: Obb3<S>) -> Self {
+        Aabb3::new(obb.min_extent(), obb.max_extent())
+    }
+}
+
+impl<S: BaseFloat> From<Capsule<S>> for Aabb3<S> {
+    /// Smallest AABB enclosing the capsule
+    fn from(capsule: Capsule<S>) -> Self {
+        Aabb3::new(capsule.min_extent(), capsule.max_extent())
+    }
+}
+
+impl<S: BaseFloat> From<Dop3<S>> for Aabb3<S> {
+    /// Smallest AABB enclosing the DOP
+    fn from(dop: Dop3<S>) -> Self {
+        dop.to_aabb()
+    }
+}
@@ -99,12 +99,44 @@ pub trait Aabb: Sized {
         self.max() - self.min()
     }
 
+    /// Return half of the dimensions of this AABB, the distance from the center to the faces.
+    #[inline]
+    fn half_extents(&self) -> Self::Diff {
+        let two = Self::Scalar::one() + Self::Scalar::one();
+        self.dim() / two
+    }
+
+    /// Return the index of the axis along which this AABB is largest, for example the axis to
+    /// split along when building a bounding volume hierarchy. Ties go to the lowest index.
+    fn longest_axis(&self) -> usize {
+        let dim = self.dim();
+        (1..Self::Diff::len()).fold(0, |longest, i| {
+            if dim[i] > dim[longest] {
+                i
+            } else {
+                longest
+            }
+        })
+    }
+
     /// Return the volume this AABB encloses.
     #[inline]
     fn volume(&self) -> Self::Scalar {
         self.dim().product()
     }
 
+    /// Return the volume of the overlapping part of this AABB and the given AABB, or the area in
+    /// 2D. Returns zero if the boxes do not overlap.
+    fn overlap_volume(&self, other: &Self) -> Self::Scalar {
+        let dim = MinMax::min(self.max(), other.max()) - MinMax::max(self.min(), other.min());
+        for i in 0..Self::Diff::len() {
+            if dim[i] <= Self::Scalar::zero() {
+                return Self::Scalar::zero();
+            }
+        }
+        dim.product()
+    }
+
     /// Return the center point of this AABB.
     #[inline]
     fn center(&self) -> Self::Point {
@@ -117,6 +149,18 @@ pub trait Aabb: Sized {
         Aabb::new(MinMax::min(self.min(), p), MinMax::max(self.max(), p))
     }
 
+    /// Returns a new AABB that contains both this AABB and this AABB displaced by the given
+    /// motion vector. This is the swept bound of a box moving along the vector, as used in the
+    /// broad phase of continuous collision detection.
+    fn expanded_by(&self, motion: &Self::Diff) -> Self {
+        let min = self.min();
+        let max = self.max();
+        Aabb::new(
+            MinMax::min(min, min + *motion),
+            MinMax::max(max, max + *motion),
+        )
+    }
+
     /// Add a vector to every point in the AABB, returning a new AABB.
     #[inline]
     fn add_v(&self, v: Self::Diff) -> Self {
@@ -126,6 +170,12 @@ pub trait Aabb: Sized {
     /// Add a margin of the given width around the AABB, returning a new AABB.
     fn add_margin(&self, margin: Self::Diff) -> Self;
 
+    /// Add a margin of the same width along all axes around the AABB, returning a new AABB.
+    #[inline]
+    fn add_margin_s(&self, margin: Self::Scalar) -> Self {
+        self.add_margin(Self::Diff::from_value(margin))
+    }
+
     /// Multiply every point in the AABB by a scalar, returning a new AABB.
     #[inline]
     fn mul_s(&self, s: Self::Scalar) -> Self {
@@ -0,0 +1,252 @@
+//! Bounding capsule
+
+use cgmath::{BaseFloat, Matrix3, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, Sphere};
+use crate::prelude::*;
+use crate::primitive::util::closest_point_on_ball;
+
+/// Number of power iterations used to find the principal axis of a point set
+const POWER_ITERATIONS: usize = 32;
+
+/// Number of golden section steps used to find the closest point on the segment to a box
+const SEARCH_STEPS: usize = 64;
+
+/// Bounding capsule, the set of all points within `radius` of the segment from `start` to `end`.
+///
+/// A tighter fit than a sphere or an AABB for elongated shapes in arbitrary orientations, while
+/// still being cheap to transform and test.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Capsule<S: BaseFloat> {
+    /// Start point of the core segment in world space
+    pub start: Point3<S>,
+    /// End point of the core segment in world space
+    pub end: Point3<S>,
+    /// Capsule radius
+    pub radius: S,
+}
+
+impl<S: BaseFloat> Capsule<S> {
+    /// Construct a new capsule from the end points of the core segment, and the radius
+    #[inline]
+    pub fn new(start: Point3<S>, end: Point3<S>, radius: S) -> Self {
+        Capsule { start, end, radius }
+    }
+
+    /// Fit a bounding capsule to the given points.
+    ///
+    /// The axis of the capsule is the principal axis of the points, found from their covariance.
+    /// The radius is the largest distance from a point to the axis, and the end points are then
+    /// moved as far towards each other as the caps allow. This is not the minimal capsule, but is
+    /// a good fit for elongated point sets. Returns an empty capsule if there are no points.
+    pub fn from_points(points: &[Point3<S>]) -> Self {
+        if points.is_empty() {
+            return Self::empty();
+        }
+        let count = S::from(points.len()).unwrap();
+        let mean = points
+            .iter()
+            .fold(Vector3::zero(), |sum, p| sum + p.to_vec())
+            / count;
+        let center = Point3::from_vec(mean);
+        let axis = principal_axis(points, center);
+
+        // distance along the axis and squared distance from the axis, for each point
+        let coordinates: Vec<_> = points
+            .iter()
+            .map(|p| {
+                let v = *p - center;
+                let t = v.dot(axis);
+                (t, (v - axis * t).magnitude2())
+            })
+            .collect();
+        let radius2 = coordinates
+            .iter()
+            .fold(S::zero(), |max, &(_, d2)| max.max(d2));
+
+        // each point must be inside the cap spheres, or between them
+        let mut lower = S::infinity();
+        let mut upper = S::neg_infinity();
+        for &(t, d2) in &coordinates {
+            let cap = (radius2 - d2).max(S::zero()).sqrt();
+            lower = lower.min(t + cap);
+            upper = upper.max(t - cap);
+        }
+        if lower > upper {
+            // all points fit in a single sphere on the axis
+            let mid = (lower + upper) / (S::one() + S::one());
+            lower = mid;
+            upper = mid;
+        }
+        Capsule::new(center + axis * lower, center + axis * upper, radius2.sqrt())
+    }
+
+    /// Closest point on the core segment to the given point
+    pub fn closest_axis_point(&self, point: &Point3<S>) -> Point3<S> {
+        self.point_at(self.parameter(point))
+    }
+
+    /// Point on the core segment at the given parameter, from `start` at 0 to `end` at 1
+    #[inline]
+    fn point_at(&self, t: S) -> Point3<S> {
+        self.start + (self.end - self.start) * t
+    }
+
+    /// Parameter of the closest point on the core segment to the given point
+    fn parameter(&self, point: &Point3<S>) -> S {
+        let d = self.end - self.start;
+        let length2 = d.magnitude2();
+        if length2.is_zero() {
+            S::zero()
+        } else {
+            ((point - self.start).dot(d) / length2)
+                .max(S::zero())
+                .min(S::one())
+        }
+    }
+}
+
+/// Direction of largest variance of the points around the center, found with power iteration on
+/// the covariance matrix
+fn principal_axis<S: BaseFloat>(points: &[Point3<S>], center: Point3<S>) -> Vector3<S> {
+    let covariance = points.iter().fold(Matrix3::zero(), |sum, p| {
+        let v = *p - center;
+        sum + Matrix3::from_cols(v * v.x, v * v.y, v * v.z)
+    });
+    // start from the largest column, which is never orthogonal to the principal axis
+    let mut axis = (0..3)
+        .map(|i| covariance[i])
+        .fold(Vector3::zero(), |max: Vector3<S>, c| {
+            if c.magnitude2() > max.magnitude2() {
+                c
+            } else {
+                max
+            }
+        });
+    if axis.magnitude2().is_zero() {
+        return Vector3::unit_x();
+    }
+    for _ in 0..POWER_ITERATIONS {
+        let next = covariance * axis;
+        if next.magnitude2().is_zero() {
+            break;
+        }
+        axis = next.normalize();
+    }
+    axis.normalize()
+}
+
+/// Squared distance from a point to an AABB
+fn aabb_distance2<S: BaseFloat>(aabb: &Aabb3<S>, point: Point3<S>) -> S {
+    let mut distance2 = S::zero();
+    for i in 0..3 {
+        let d = (aabb.min[i] - point[i]).max(point[i] - aabb.max[i]);
+        if d > S::zero() {
+            distance2 += d * d;
+        }
+    }
+    distance2
+}
+
+impl<S> Bound for Capsule<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point3<S>;
+
+    fn min_extent(&self) -> Point3<S> {
+        Point3::new(
+            self.start.x.min(self.end.x),
+            self.start.y.min(self.end.y),
+            self.start.z.min(self.end.z),
+        ) + Vector3::from_value(-self.radius)
+    }
+
+    fn max_extent(&self) -> Point3<S> {
+        Point3::new(
+            self.start.x.max(self.end.x),
+            self.start.y.max(self.end.y),
+            self.start.z.max(self.end.z),
+        ) + Vector3::from_value(self.radius)
+    }
+
+    fn with_margin(&self, add: Vector3<S>) -> Self {
+        let max = add.x.max(add.y).max(add.z);
+        Capsule::new(self.start, self.end, self.radius + max)
+    }
+
+    fn transform_volume<T>(&self, transform: &T) -> Self
+    where
+        T: Transform<Self::Point>,
+    {
+        Capsule::new(
+            transform.transform_point(self.start),
+            transform.transform_point(self.end),
+            self.radius,
+        )
+    }
+
+    fn empty() -> Self {
+        Capsule::new(Point3::origin(), Point3::origin(), S::zero())
+    }
+}
+
+impl<S: BaseFloat> Contains<Point3<S>> for Capsule<S> {
+    #[inline]
+    fn contains(&self, p: &Point3<S>) -> bool {
+        self.closest_axis_point(p).distance2(*p) <= self.radius * self.radius
+    }
+}
+
+impl<S: BaseFloat> ClosestPoint<Point3<S>> for Capsule<S> {
+    #[inline]
+    fn closest_point(&self, p: &Point3<S>) -> (Point3<S>, bool) {
+        closest_point_on_ball(self.closest_axis_point(p), self.radius, *p)
+    }
+}
+
+impl<S: BaseFloat> Discrete<Sphere<S>> for Capsule<S> {
+    fn intersects(&self, sphere: &Sphere<S>) -> bool {
+        let radiuses = self.radius + sphere.radius;
+        let closest = self.closest_axis_point(&sphere.center);
+        closest.distance2(sphere.center) <= radiuses * radiuses
+    }
+}
+
+impl<S: BaseFloat> Discrete<Aabb3<S>> for Capsule<S> {
+    /// The distance from the box to a point moving along the segment is convex, so the closest
+    /// point on the segment is found with a golden section search
+    fn intersects(&self, aabb: &Aabb3<S>) -> bool {
+        let radius2 = self.radius * self.radius;
+        let distance2 = |t: S| aabb_distance2(aabb, self.point_at(t));
+        let ratio = (S::from(5).unwrap().sqrt() - S::one()) / (S::one() + S::one());
+        let (mut a, mut b) = (S::zero(), S::one());
+        let mut c = b - (b - a) * ratio;
+        let mut d = a + (b - a) * ratio;
+        let (mut fc, mut fd) = (distance2(c), distance2(d));
+        for _ in 0..SEARCH_STEPS {
+            if fc.min(fd) <= radius2 {
+                return true;
+            }
+            if fc < fd {
+                b = d;
+                d = c;
+                fd = fc;
+                c = b - (b - a) * ratio;
+                fc = distance2(c);
+            } else {
+                a = c;
+                c = d;
+                fc = fd;
+                d = a + (b - a) * ratio;
+                fd = distance2(d);
+            }
+        }
+        distance2(S::zero())
+            .min(distance2(S::one()))
+            .min(fc.min(fd))
+            <= radius2
+    }
+}
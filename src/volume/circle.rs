@@ -0,0 +1,228 @@
+//! Bounding circle
+
+use cgmath::{BaseFloat, Point2, Vector2};
+use cgmath::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::{Aabb2, Obb2};
+use crate::prelude::*;
+use crate::primitive::util::closest_point_on_ball;
+
+/// Bounding circle, the 2D counterpart of the bounding `Sphere`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Circle<S: BaseFloat> {
+    /// Center point of the circle in world space
+    pub center: Point2<S>,
+    /// Circle radius
+    pub radius: S,
+}
+
+impl<S: BaseFloat> Circle<S> {
+    /// Compute the minimal bounding circle of the given points, using Welzl's algorithm.
+    ///
+    /// Runs in expected `O(n)` time, the points are shuffled internally with a fixed seed, so the
+    /// result is the same for the same points. The radius is inflated by the tolerance used for
+    /// the points on the boundary, so all points are contained in the result despite rounding.
+    /// Returns an empty circle if there are no points.
+    pub fn from_points(points: &[Point2<S>]) -> Self {
+        let mut points = points.to_vec();
+        points.shuffle(&mut StdRng::seed_from_u64(0));
+
+        // move-to-front formulation, each loop finds the smallest circle with the given points
+        // on the boundary, that contains all points before it
+        let mut circle = Self::empty();
+        for i in 0..points.len() {
+            if i > 0 && circle.encloses(points[i]) {
+                continue;
+            }
+            circle = Self::from_boundary(&[points[i]]);
+            for j in 0..i {
+                if circle.encloses(points[j]) {
+                    continue;
+                }
+                circle = Self::from_boundary(&[points[i], points[j]]);
+                for k in 0..j {
+                    if circle.encloses(points[k]) {
+                        continue;
+                    }
+                    circle = Self::from_boundary(&[points[i], points[j], points[k]]);
+                }
+            }
+        }
+        if !points.is_empty() {
+            circle.radius += circle.tolerance();
+        }
+        circle
+    }
+
+    /// Tolerance for points on the boundary
+    fn tolerance(&self) -> S {
+        S::default_epsilon().sqrt() * (S::one() + self.radius)
+    }
+
+    /// Containment test with a tolerance for points on the boundary
+    fn encloses(&self, point: Point2<S>) -> bool {
+        self.center.distance(point) <= self.radius + self.tolerance()
+    }
+
+    /// Smallest circle with up to 3 points on the boundary
+    fn from_boundary(points: &[Point2<S>]) -> Self {
+        Self::circumcircle(points).unwrap_or_else(|| {
+            // collinear points, use the circle through the farthest pair
+            let first = Self::from_boundary(&[points[0], points[1]]);
+            [(0, 2), (1, 2)]
+                .iter()
+                .map(|&(i, j)| Self::from_boundary(&[points[i], points[j]]))
+                .fold(first, |farthest, circle| {
+                    if circle.radius > farthest.radius {
+                        circle
+                    } else {
+                        farthest
+                    }
+                })
+        })
+    }
+
+    /// Smallest circle with all given points on the boundary, if the points are not collinear
+    fn circumcircle(points: &[Point2<S>]) -> Option<Self> {
+        let two = S::one() + S::one();
+        let a = points[0];
+        match points.len() {
+            1 => Some(Circle {
+                center: a,
+                radius: S::zero(),
+            }),
+            2 => Some(Circle {
+                center: a.midpoint(points[1]),
+                radius: a.distance(points[1]) / two,
+            }),
+            _ => {
+                let ab = points[1] - a;
+                let ac = points[2] - a;
+                let denominator = two * ab.perp_dot(ac);
+                if denominator.is_zero() {
+                    return None;
+                }
+                let offset = Vector2::new(
+                    ac.y * ab.magnitude2() - ab.y * ac.magnitude2(),
+                    ab.x * ac.magnitude2() - ac.x * ab.magnitude2(),
+                ) / denominator;
+                Some(Circle {
+                    center: a + offset,
+                    radius: offset.magnitude(),
+                })
+            }
+        }
+    }
+}
+
+impl<S> Bound for Circle<S>
+where
+    S: BaseFloat,
+{
+    type Point = Point2<S>;
+
+    fn min_extent(&self) -> Point2<S> {
+        self.center + Vector2::from_value(-self.radius)
+    }
+
+    fn max_extent(&self) -> Point2<S> {
+        self.center + Vector2::from_value(self.radius)
+    }
+
+    fn with_margin(&self, add: Vector2<S>) -> Self {
+        Circle {
+            center: self.center,
+            radius: self.radius + add.x.max(add.y),
+        }
+    }
+
+    fn transform_volume<T>(&self, transform: &T) -> Self
+    where
+        T: Transform<Self::Point>,
+    {
+        Circle {
+            center: transform.transform_point(self.center),
+            radius: self.radius,
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            center: Point2::origin(),
+            radius: S::zero(),
+        }
+    }
+}
+
+impl<S: BaseFloat> Discrete<Circle<S>> for Circle<S> {
+    fn intersects(&self, other: &Circle<S>) -> bool {
+        let radiuses = self.radius + other.radius;
+        self.center.distance2(other.center) <= radiuses * radiuses
+    }
+}
+
+impl<S: BaseFloat> Contains<Point2<S>> for Circle<S> {
+    #[inline]
+    fn contains(&self, p: &Point2<S>) -> bool {
+        self.center.distance2(*p) <= self.radius * self.radius
+    }
+}
+
+impl<S: BaseFloat> ClosestPoint<Point2<S>> for Circle<S> {
+    #[inline]
+    fn closest_point(&self, p: &Point2<S>) -> (Point2<S>, bool) {
+        closest_point_on_ball(self.center, self.radius, *p)
+    }
+}
+
+impl<S: BaseFloat> Contains<Circle<S>> for Circle<S> {
+    #[inline]
+    fn contains(&self, other: &Circle<S>) -> bool {
+        self.center.distance(other.center) + other.radius <= self.radius
+    }
+}
+
+impl<S: BaseFloat> Union for Circle<S> {
+    type Output = Circle<S>;
+
+    fn union(&self, other: &Circle<S>) -> Circle<S> {
+        if self.contains(other) {
+            return *self;
+        }
+        if other.contains(self) {
+            return *other;
+        }
+        let two = S::one() + S::one();
+        let center_diff = other.center - self.center;
+        let center_diff_s = center_diff.magnitude();
+        let radius = (self.radius + other.radius + center_diff_s) / two;
+        Circle {
+            radius,
+            center: self.center + center_diff * (radius - self.radius) / center_diff_s,
+        }
+    }
+}
+
+impl<S: BaseFloat> From<Aabb2<S>> for Circle<S> {
+    /// Smallest circle enclosing the AABB
+    fn from(aabb: Aabb2<S>) -> Self {
+        Circle {
+            center: aabb.center(),
+            radius: aabb.max().distance(aabb.center()),
+        }
+    }
+}
+
+impl<S: BaseFloat> From<Obb2<S>> for Circle<S> {
+    /// Smallest circle enclosing the OBB
+    fn from(obb: Obb2<S>) -> Self {
+        Circle {
+            center: obb.center,
+            radius: obb.half_extents.magnitude(),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Bounding cone for sets of directions
+
+use cgmath::{BaseFloat, Point3, Rad, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Frustum, Relation};
+use crate::prelude::*;
+
+/// Bounding volume for a set of directions, given by an axis and the largest angle between the
+/// axis and any of the directions.
+///
+/// Used as a normal cone, bounding the face normals of a part of a mesh, to cull the whole part
+/// when all faces point away from the viewer. Unlike the other volumes this bounds directions,
+/// not points, so it is not a `Bound`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DirectionCone<S> {
+    /// Axis of the cone, must be normalized
+    pub axis: Vector3<S>,
+    /// Largest angle between the axis and a bounded direction, at most half a turn
+    pub half_angle: Rad<S>,
+}
+
+impl<S: BaseFloat> DirectionCone<S> {
+    /// Construct a new cone from a normalized axis and the half angle
+    #[inline]
+    pub fn new(axis: Vector3<S>, half_angle: Rad<S>) -> Self {
+        DirectionCone { axis, half_angle }
+    }
+
+    /// Construct the cone bounding a single direction, which does not need to be normalized
+    #[inline]
+    pub fn from_direction(direction: Vector3<S>) -> Self {
+        DirectionCone::new(direction.normalize(), Rad::zero())
+    }
+
+    /// Construct a cone bounding all the given directions, by merging them one at a time. The
+    /// result is not necessarily the smallest bounding cone. Returns `None` if there are no
+    /// directions.
+    pub fn from_directions(directions: &[Vector3<S>]) -> Option<Self> {
+        let (first, rest) = directions.split_first()?;
+        Some(rest.iter().fold(Self::from_direction(*first), |cone, d| {
+            cone.union(&Self::from_direction(*d))
+        }))
+    }
+
+    /// Returns true if the cone bounds all directions
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.half_angle >= Rad::turn_div_2()
+    }
+
+    /// Test if all directions in the cone point away from the given view direction, i.e. if all
+    /// faces with normals in the cone are back facing for an orthographic view.
+    ///
+    /// The view direction points from the viewer into the scene, and does not need to be
+    /// normalized.
+    pub fn is_back_facing(&self, view_direction: Vector3<S>) -> bool {
+        let angle = self.axis.angle(view_direction.normalize());
+        angle + self.half_angle < Rad::turn_div_4()
+    }
+
+    /// Test if all faces with normals in the cone, and positions inside the given sphere, are back
+    /// facing for a perspective view from the given eye position.
+    ///
+    /// The directions from the eye to the sphere form another cone, so the faces are back facing
+    /// if the angle between the two axes and the two half angles add up to less than a quarter
+    /// turn. Always returns false if the eye is inside the sphere.
+    pub fn is_back_facing_from(&self, center: Point3<S>, radius: S, eye: Point3<S>) -> bool {
+        let to_center = center - eye;
+        let distance = to_center.magnitude();
+        if distance <= radius {
+            return false;
+        }
+        let view_half_angle = Rad::asin(radius / distance);
+        let angle = self.axis.angle(to_center / distance);
+        angle + self.half_angle + view_half_angle < Rad::turn_div_4()
+    }
+
+    /// Classify the faces with normals in the cone, and positions inside the given frustum, by
+    /// the view directions through the frustum.
+    ///
+    /// The view directions are bounded by the four side edges of the frustum, from the near to
+    /// the far corners, so this works for both perspective and orthographic frusta.
+    ///
+    /// ## Returns
+    ///
+    /// `Relation::Out` if all faces are back facing, so they can be culled, `Relation::In` if all
+    /// faces are front facing, and `Relation::Cross` otherwise, or if the frustum corners can not
+    /// be computed.
+    pub fn relate_frustum(&self, frustum: &Frustum<S>) -> Relation {
+        let points = match frustum.to_points() {
+            Some(points) => points,
+            None => return Relation::Cross,
+        };
+        let edges = [
+            points.far_top_left - points.near_top_left,
+            points.far_top_right - points.near_top_right,
+            points.far_bottom_left - points.near_bottom_left,
+            points.far_bottom_right - points.near_bottom_right,
+        ];
+        // the back facing directions of a cone narrower than a quarter turn form a convex cone,
+        // so it contains all view directions if it contains the edges
+        let flipped = DirectionCone::new(-self.axis, self.half_angle);
+        if edges.iter().all(|edge| self.is_back_facing(*edge)) {
+            Relation::Out
+        } else if edges.iter().all(|edge| flipped.is_back_facing(*edge)) {
+            Relation::In
+        } else {
+            Relation::Cross
+        }
+    }
+}
+
+/// Any unit vector perpendicular to the given unit vector
+fn perpendicular<S: BaseFloat>(v: Vector3<S>) -> Vector3<S> {
+    let other = if v.x.abs() < v.y.abs() {
+        Vector3::unit_x()
+    } else {
+        Vector3::unit_y()
+    };
+    v.cross(other).normalize()
+}
+
+impl<S: BaseFloat> Contains<Vector3<S>> for DirectionCone<S> {
+    /// Direction does not need to be normalized
+    #[inline]
+    fn contains(&self, direction: &Vector3<S>) -> bool {
+        self.is_full() || self.axis.angle(direction.normalize()) <= self.half_angle
+    }
+}
+
+impl<S: BaseFloat> Contains<DirectionCone<S>> for DirectionCone<S> {
+    #[inline]
+    fn contains(&self, other: &DirectionCone<S>) -> bool {
+        self.is_full() || self.axis.angle(other.axis) + other.half_angle <= self.half_angle
+    }
+}
+
+impl<S: BaseFloat> Union for DirectionCone<S> {
+    type Output = DirectionCone<S>;
+
+    /// Smallest cone containing both cones
+    fn union(&self, other: &DirectionCone<S>) -> DirectionCone<S> {
+        if self.contains(other) {
+            return *self;
+        }
+        if other.contains(self) {
+            return *other;
+        }
+        let two = S::one() + S::one();
+        let angle = self.axis.angle(other.axis);
+        let half_angle = (self.half_angle + angle + other.half_angle) / two;
+        if half_angle >= Rad::turn_div_2() {
+            return DirectionCone::new(self.axis, Rad::turn_div_2());
+        }
+        // rotate the axis towards the other axis, in the plane of both axes
+        let towards = other.axis - self.axis * self.axis.dot(other.axis);
+        let towards = if towards.magnitude2() > S::default_epsilon() {
+            towards.normalize()
+        } else {
+            perpendicular(self.axis)
+        };
+        let rotation = half_angle - self.half_angle;
+        DirectionCone::new(
+            (self.axis * Rad::cos(rotation) + towards * Rad::sin(rotation)).normalize(),
+            half_angle,
+        )
+    }
+}
@@ -0,0 +1,181 @@
+//! Discrete oriented polytope for 2D.
+
+use cgmath::{BaseFloat, Point2, Vector2};
+use cgmath::prelude::*;
+
+use crate::Aabb2;
+use crate::prelude::*;
+
+/// Number of normal directions of a `Dop2`
+const AXES: usize = 4;
+
+/// A two-dimensional 8-DOP, aka an AABB with its corners cut off at 45 degrees.
+///
+/// The normals are the X and Y axes, and the two diagonals `(1, 1)` and `(1, -1)`. The
+/// diagonals are not normalized, so the interval along a diagonal is in units of the projection
+/// onto the unnormalized diagonal.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Dop2<S> {
+    /// Minimum projection onto each of the normals
+    pub min: [S; AXES],
+    /// Maximum projection onto each of the normals
+    pub max: [S; AXES],
+}
+
+impl<S: BaseFloat> Dop2<S> {
+    /// Get the normal directions of the DOP, in the order of the intervals
+    pub fn axes() -> [Vector2<S>; AXES] {
+        let (o, z) = (S::one(), S::zero());
+        [
+            Vector2::new(o, z),
+            Vector2::new(z, o),
+            Vector2::new(o, o),
+            Vector2::new(o, -o),
+        ]
+    }
+
+    /// Construct the DOP of a single point.
+    pub fn from_point(point: Point2<S>) -> Self {
+        let mut min = [S::zero(); AXES];
+        for (value, axis) in min.iter_mut().zip(Self::axes().iter()) {
+            *value = point.dot(*axis);
+        }
+        Dop2 { min, max: min }
+    }
+
+    /// Construct the tightest DOP containing all the given points. Returns an empty DOP if there
+    /// are no points.
+    pub fn from_points(points: &[Point2<S>]) -> Self {
+        match points.split_first() {
+            Some((first, rest)) => rest
+                .iter()
+                .fold(Self::from_point(*first), |dop, p| dop.grow(*p)),
+            None => Self::empty(),
+        }
+    }
+
+    /// Construct the tightest DOP of a primitive under the given transform, using the support
+    /// function of the primitive. For concave primitives, this is the DOP of the convex hull.
+    pub fn from_primitive<P, T>(primitive: &P, transform: &T) -> Self
+    where
+        P: Primitive<Point = Point2<S>>,
+        T: Transform<Point2<S>>,
+    {
+        let mut dop = Self::empty();
+        for (i, axis) in Self::axes().iter().enumerate() {
+            dop.min[i] = primitive.support_point(&-*axis, transform).dot(*axis);
+            dop.max[i] = primitive.support_point(axis, transform).dot(*axis);
+        }
+        dop
+    }
+
+    /// Construct the DOP of an AABB.
+    pub fn from_aabb(aabb: &Aabb2<S>) -> Self {
+        Self::from_points(&aabb.to_corners())
+    }
+
+    /// Get the AABB of the DOP.
+    pub fn to_aabb(&self) -> Aabb2<S> {
+        Aabb2::new(self.min_extent(), self.max_extent())
+    }
+
+    /// Returns a new DOP that is grown to include the given point.
+    pub fn grow(&self, point: Point2<S>) -> Self {
+        self.union(&Self::from_point(point))
+    }
+}
+
+impl<S: BaseFloat> Bound for Dop2<S> {
+    type Point = Point2<S>;
+
+    fn min_extent(&self) -> Point2<S> {
+        Point2::new(self.min[0], self.min[1])
+    }
+
+    fn max_extent(&self) -> Point2<S> {
+        Point2::new(self.max[0], self.max[1])
+    }
+
+    /// Grow the DOP by the given amount along the X and Y axes, like the AABB it bounds
+    fn with_margin(&self, add: Vector2<S>) -> Self {
+        let mut dop = *self;
+        for (i, axis) in Self::axes().iter().enumerate() {
+            let margin = axis.x.abs() * add.x + axis.y.abs() * add.y;
+            dop.min[i] -= margin;
+            dop.max[i] += margin;
+        }
+        dop
+    }
+
+    /// Conservative bound of the transformed DOP, computed from the corners of its AABB
+    fn transform_volume<T>(&self, transform: &T) -> Self
+    where
+        T: Transform<Self::Point>,
+    {
+        let corners = self.to_aabb().to_corners();
+        let mut transformed = [Point2::origin(); 4];
+        for (t, corner) in transformed.iter_mut().zip(corners.iter()) {
+            *t = transform.transform_point(*corner);
+        }
+        Self::from_points(&transformed)
+    }
+
+    fn empty() -> Self {
+        Self::from_point(Point2::origin())
+    }
+}
+
+impl<S: BaseFloat> Contains<Point2<S>> for Dop2<S> {
+    #[inline]
+    fn contains(&self, p: &Point2<S>) -> bool {
+        Self::axes().iter().enumerate().all(|(i, axis)| {
+            let d = p.dot(*axis);
+            self.min[i] <= d && d <= self.max[i]
+        })
+    }
+}
+
+impl<S: BaseFloat> Contains<Dop2<S>> for Dop2<S> {
+    #[inline]
+    fn contains(&self, other: &Dop2<S>) -> bool {
+        (0..AXES).all(|i| self.min[i] <= other.min[i] && other.max[i] <= self.max[i])
+    }
+}
+
+impl<S: BaseFloat> Union for Dop2<S> {
+    type Output = Dop2<S>;
+
+    fn union(&self, other: &Dop2<S>) -> Dop2<S> {
+        let mut dop = *self;
+        for i in 0..AXES {
+            dop.min[i] = dop.min[i].min(other.min[i]);
+            dop.max[i] = dop.max[i].max(other.max[i]);
+        }
+        dop
+    }
+}
+
+impl<S: BaseFloat> SurfaceArea for Dop2<S> {
+    type Scalar = S;
+
+    /// Area of the AABB of the DOP, which is cheap to compute, and good enough as a heuristic
+    fn surface_area(&self) -> S {
+        self.to_aabb().surface_area()
+    }
+}
+
+impl<S: BaseFloat> Discrete<Dop2<S>> for Dop2<S> {
+    /// Overlap test of the intervals along all normals. Exact in 2D, since the normals include
+    /// all edge normals of both DOPs.
+    fn intersects(&self, other: &Dop2<S>) -> bool {
+        (0..AXES).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+}
+
+impl<S: BaseFloat> From<Aabb2<S>> for Dop2<S> {
+    /// Smallest DOP enclosing the AABB
+    fn from(aabb: Aabb2<S>) -> Self {
+        Dop2::from_aabb(&aabb)
+    }
+}
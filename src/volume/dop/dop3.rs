@@ -0,0 +1,191 @@
+//! Discrete oriented polytope for 3D.
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::Aabb3;
+use crate::prelude::*;
+
+/// Number of normal directions of a `Dop3`
+const AXES: usize = 13;
+
+/// A three-dimensional 26-DOP, aka an AABB with its edges and corners cut off at 45 degrees.
+///
+/// The normals are the X, Y and Z axes, the 6 edge diagonals like `(1, 1, 0)`, and the 4 corner
+/// diagonals like `(1, 1, 1)`. The diagonals are not normalized, so the interval along a diagonal
+/// is in units of the projection onto the unnormalized diagonal.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Dop3<S> {
+    /// Minimum projection onto each of the normals
+    pub min: [S; AXES],
+    /// Maximum projection onto each of the normals
+    pub max: [S; AXES],
+}
+
+impl<S: BaseFloat> Dop3<S> {
+    /// Get the normal directions of the DOP, in the order of the intervals
+    pub fn axes() -> [Vector3<S>; AXES] {
+        let (o, z) = (S::one(), S::zero());
+        [
+            Vector3::new(o, z, z),
+            Vector3::new(z, o, z),
+            Vector3::new(z, z, o),
+            Vector3::new(o, o, z),
+            Vector3::new(o, -o, z),
+            Vector3::new(o, z, o),
+            Vector3::new(o, z, -o),
+            Vector3::new(z, o, o),
+            Vector3::new(z, o, -o),
+            Vector3::new(o, o, o),
+            Vector3::new(o, o, -o),
+            Vector3::new(o, -o, o),
+            Vector3::new(o, -o, -o),
+        ]
+    }
+
+    /// Construct the DOP of a single point.
+    pub fn from_point(point: Point3<S>) -> Self {
+        let mut min = [S::zero(); AXES];
+        for (value, axis) in min.iter_mut().zip(Self::axes().iter()) {
+            *value = point.dot(*axis);
+        }
+        Dop3 { min, max: min }
+    }
+
+    /// Construct the tightest DOP containing all the given points. Returns an empty DOP if there
+    /// are no points.
+    pub fn from_points(points: &[Point3<S>]) -> Self {
+        match points.split_first() {
+            Some((first, rest)) => rest
+                .iter()
+                .fold(Self::from_point(*first), |dop, p| dop.grow(*p)),
+            None => Self::empty(),
+        }
+    }
+
+    /// Construct the tightest DOP of a primitive under the given transform, using the support
+    /// function of the primitive. For concave primitives, this is the DOP of the convex hull.
+    pub fn from_primitive<P, T>(primitive: &P, transform: &T) -> Self
+    where
+        P: Primitive<Point = Point3<S>>,
+        T: Transform<Point3<S>>,
+    {
+        let mut dop = Self::empty();
+        for (i, axis) in Self::axes().iter().enumerate() {
+            dop.min[i] = primitive.support_point(&-*axis, transform).dot(*axis);
+            dop.max[i] = primitive.support_point(axis, transform).dot(*axis);
+        }
+        dop
+    }
+
+    /// Construct the DOP of an AABB.
+    pub fn from_aabb(aabb: &Aabb3<S>) -> Self {
+        Self::from_points(&aabb.to_corners())
+    }
+
+    /// Get the AABB of the DOP.
+    pub fn to_aabb(&self) -> Aabb3<S> {
+        Aabb3::new(self.min_extent(), self.max_extent())
+    }
+
+    /// Returns a new DOP that is grown to include the given point.
+    pub fn grow(&self, point: Point3<S>) -> Self {
+        self.union(&Self::from_point(point))
+    }
+}
+
+impl<S: BaseFloat> Bound for Dop3<S> {
+    type Point = Point3<S>;
+
+    fn min_extent(&self) -> Point3<S> {
+        Point3::new(self.min[0], self.min[1], self.min[2])
+    }
+
+    fn max_extent(&self) -> Point3<S> {
+        Point3::new(self.max[0], self.max[1], self.max[2])
+    }
+
+    /// Grow the DOP by the given amount along the X, Y and Z axes, like the AABB it bounds
+    fn with_margin(&self, add: Vector3<S>) -> Self {
+        let mut dop = *self;
+        for (i, axis) in Self::axes().iter().enumerate() {
+            let margin = axis.x.abs() * add.x + axis.y.abs() * add.y + axis.z.abs() * add.z;
+            dop.min[i] -= margin;
+            dop.max[i] += margin;
+        }
+        dop
+    }
+
+    /// Conservative bound of the transformed DOP, computed from the corners of its AABB
+    fn transform_volume<T>(&self, transform: &T) -> Self
+    where
+        T: Transform<Self::Point>,
+    {
+        let corners = self.to_aabb().to_corners();
+        let mut transformed = [Point3::origin(); 8];
+        for (t, corner) in transformed.iter_mut().zip(corners.iter()) {
+            *t = transform.transform_point(*corner);
+        }
+        Self::from_points(&transformed)
+    }
+
+    fn empty() -> Self {
+        Self::from_point(Point3::origin())
+    }
+}
+
+impl<S: BaseFloat> Contains<Point3<S>> for Dop3<S> {
+    #[inline]
+    fn contains(&self, p: &Point3<S>) -> bool {
+        Self::axes().iter().enumerate().all(|(i, axis)| {
+            let d = p.dot(*axis);
+            self.min[i] <= d && d <= self.max[i]
+        })
+    }
+}
+
+impl<S: BaseFloat> Contains<Dop3<S>> for Dop3<S> {
+    #[inline]
+    fn contains(&self, other: &Dop3<S>) -> bool {
+        (0..AXES).all(|i| self.min[i] <= other.min[i] && other.max[i] <= self.max[i])
+    }
+}
+
+impl<S: BaseFloat> Union for Dop3<S> {
+    type Output = Dop3<S>;
+
+    fn union(&self, other: &Dop3<S>) -> Dop3<S> {
+        let mut dop = *self;
+        for i in 0..AXES {
+            dop.min[i] = dop.min[i].min(other.min[i]);
+            dop.max[i] = dop.max[i].max(other.max[i]);
+        }
+        dop
+    }
+}
+
+impl<S: BaseFloat> SurfaceArea for Dop3<S> {
+    type Scalar = S;
+
+    /// Surface area of the AABB of the DOP, which is cheap to compute, and good enough as a
+    /// heuristic
+    fn surface_area(&self) -> S {
+        self.to_aabb().surface_area()
+    }
+}
+
+impl<S: BaseFloat> Discrete<Dop3<S>> for Dop3<S> {
+    /// Overlap test of the intervals along all normals. Conservative in 3D, DOPs that are only
+    /// separated along a cross product of their edges are reported as overlapping.
+    fn intersects(&self, other: &Dop3<S>) -> bool {
+        (0..AXES).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+}
+
+impl<S: BaseFloat> From<Aabb3<S>> for Dop3<S> {
+    /// Smallest DOP enclosing the AABB
+    fn from(aabb: Aabb3<S>) -> Self {
+        Dop3::from_aabb(&aabb)
+    }
+}
@@ -0,0 +1,12 @@
+//! Discrete oriented polytopes
+//!
+//! A k-DOP is a convex polytope bounded by `k / 2` pairs of parallel planes, with a fixed set of
+//! normals shared by all DOPs of the same type. Like an AABB, it is stored as an interval along
+//! each of the normals, so overlap tests and merges are cheap, but the extra diagonal normals
+//! give a much tighter fit for diagonal shapes. An AABB is a 4-DOP in 2D, or a 6-DOP in 3D.
+
+pub use self::dop2::Dop2;
+pub use self::dop3::Dop3;
+
+mod dop2;
+mod dop3;
@@ -1,9 +1,17 @@
 pub use self::aabb::*;
+pub use self::capsule::Capsule;
+pub use self::circle::Circle;
 pub use self::cylinder::Cylinder;
+pub use self::direction_cone::DirectionCone;
+pub use self::dop::*;
 pub use self::obb::*;
 pub use self::sphere::*;
 
 mod cylinder;
 mod aabb;
+mod capsule;
+mod circle;
+mod direction_cone;
+mod dop;
 mod obb;
 mod sphere;
@@ -0,0 +1,67 @@
+//! Oriented bounding boxes
+//!
+//! An OBB is a box that encompasses a set of points, like an AABB, but with its own set of
+//! axes, so it can be rotated to fit long or diagonal shapes much tighter than an AABB.
+
+use std::marker::PhantomData;
+
+use cgmath::{Point2, Point3, Vector2, Vector3};
+
+mod obb2;
+mod obb3;
+
+/// Vector type of an OBB, with one local axis per dimension
+pub trait ObbAxes {
+    /// Local axes of the OBB, one vector per dimension
+    type Axes;
+}
+
+impl<S> ObbAxes for Vector2<S> {
+    type Axes = [Vector2<S>; 2];
+}
+
+impl<S> ObbAxes for Vector3<S> {
+    type Axes = [Vector3<S>; 3];
+}
+
+/// Generic object bounding box, centered on `center`, aligned with the orthonormal `axes`, and
+/// with half of its size along each axis in `half_extents`.
+///
+/// Use the [`Obb2`](type.Obb2.html) and [`Obb3`](type.Obb3.html) aliases for the 2D and 3D
+/// boxes.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Obb<S, V, P>
+where
+    V: ObbAxes,
+{
+    /// Center point of the OBB in world space
+    pub center: P,
+    /// Local axes of the OBB in world space, must be orthonormal
+    pub axes: V::Axes,
+    /// Half of the size of the OBB along each of the local axes
+    pub half_extents: V,
+    marker: PhantomData<S>,
+}
+
+impl<S, V, P> Obb<S, V, P>
+where
+    V: ObbAxes,
+{
+    /// Construct a new oriented bounding box from center, orthonormal axes and half extents.
+    #[inline]
+    pub fn new(center: P, axes: V::Axes, half_extents: V) -> Self {
+        Obb {
+            center,
+            axes,
+            half_extents,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// A two-dimensional OBB, aka a rotated rectangle.
+pub type Obb2<S> = Obb<S, Vector2<S>, Point2<S>>;
+
+/// A three-dimensional OBB, aka a rotated rectangular prism.
+pub type Obb3<S> = Obb<S, Vector3<S>, Point3<S>>;
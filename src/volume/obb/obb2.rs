@@ -0,0 +1,294 @@
+//! Oriented bounding box for 2D.
+
+use cgmath::{BaseFloat, Point2, Vector2};
+use cgmath::prelude::*;
+
+use crate::{Aabb2, Circle, Obb2, Ray2};
+use crate::algorithm::convexhull::monotone_chain;
+use crate::prelude::*;
+use crate::primitive::util::closest_point_on_box;
+
+impl<S: BaseFloat> Obb2<S> {
+    /// Construct the oriented bounding box of an AABB under the given transform. Only rotation,
+    /// translation and uniform scale are preserved exactly.
+    pub fn from_aabb<T>(aabb: &Aabb2<S>, transform: &T) -> Obb2<S>
+    where
+        T: Transform<Point2<S>>,
+    {
+        let x = transform.transform_vector(Vector2::unit_x());
+        let y = transform.transform_vector(Vector2::unit_y());
+        let half_dim = aabb.half_extents();
+        Obb2::new(
+            transform.transform_point(aabb.center()),
+            [x.normalize(), y.normalize()],
+            Vector2::new(half_dim.x * x.magnitude(), half_dim.y * y.magnitude()),
+        )
+    }
+
+    /// Compute the minimum area oriented bounding rectangle of the given points, using rotating
+    /// calipers on their convex hull.
+    ///
+    /// The minimal rectangle has a side collinear with an edge of the hull, so the calipers visit
+    /// each hull edge once, in `O(n)` after the `O(n log n)` hull construction. Collinear points
+    /// give a rectangle with zero height, and no points give an empty OBB.
+    pub fn from_points(points: &[Point2<S>]) -> Obb2<S> {
+        let hull = match monotone_chain(points) {
+            Some(hull) => hull,
+            None => return Self::from_collinear(points),
+        };
+        let hull = hull.vertices();
+        let n = hull.len();
+        let two = S::one() + S::one();
+        let mut best = (S::infinity(), Self::empty());
+        let (mut right, mut top, mut left) = (1, 0, 0);
+        for i in 0..n {
+            // hull is CCW, so the hull is on the left of the edge, in the direction of v
+            let a = hull[i];
+            let u = (hull[(i + 1) % n] - a).normalize();
+            let v = Vector2::new(-u.y, u.x);
+            let along = |j: usize, axis: Vector2<S>| (hull[j % n] - a).dot(axis);
+
+            // the extreme vertices only move forward as the calipers rotate
+            while along(right + 1, u) > along(right, u) {
+                right = (right + 1) % n;
+            }
+            if i == 0 {
+                top = right;
+            }
+            while along(top + 1, v) > along(top, v) {
+                top = (top + 1) % n;
+            }
+            if i == 0 {
+                left = top;
+            }
+            while along(left + 1, u) < along(left, u) {
+                left = (left + 1) % n;
+            }
+
+            let (min_u, max_u, max_v) = (along(left, u), along(right, u), along(top, v));
+            let area = (max_u - min_u) * max_v;
+            if area < best.0 {
+                let obb = Obb2::new(
+                    a + u * ((min_u + max_u) / two) + v * (max_v / two),
+                    [u, v],
+                    Vector2::new((max_u - min_u) / two, max_v / two),
+                );
+                best = (area, obb);
+            }
+        }
+        best.1
+    }
+
+    /// Bounding rectangle of points that do not span an area, along the line through them
+    fn from_collinear(points: &[Point2<S>]) -> Obb2<S> {
+        let first = match points.first() {
+            Some(first) => *first,
+            None => return Self::empty(),
+        };
+        let farthest = points.iter().fold(first, |f, p| {
+            if first.distance2(*p) > first.distance2(f) {
+                *p
+            } else {
+                f
+            }
+        });
+        let u = if farthest == first {
+            Vector2::unit_x()
+        } else {
+            (farthest - first).normalize()
+        };
+        let (min_u, max_u) = points.iter().fold((S::zero(), S::zero()), |(min, max), p| {
+            let d = (p - first).dot(u);
+            (min.min(d), max.max(d))
+        });
+        let two = S::one() + S::one();
+        Obb2::new(
+            first + u * ((min_u + max_u) / two),
+            [u, Vector2::new(-u.y, u.x)],
+            Vector2::new((max_u - min_u) / two, S::zero()),
+        )
+    }
+
+    /// Compute corners.
+    #[inline]
+    pub fn to_corners(&self) -> [Point2<S>; 4] {
+        let x = self.axes[0] * self.half_extents.x;
+        let y = self.axes[1] * self.half_extents.y;
+        [
+            self.center - x - y,
+            self.center + x - y,
+            self.center - x + y,
+            self.center + x + y,
+        ]
+    }
+
+    /// Coordinates of the given point along the local axes, relative to the center
+    #[inline]
+    fn local_coordinates(&self, point: Point2<S>) -> Vector2<S> {
+        let v = point - self.center;
+        Vector2::new(v.dot(self.axes[0]), v.dot(self.axes[1]))
+    }
+
+    /// Half of the length of the projection of the OBB onto the given axis
+    #[inline]
+    fn project(&self, axis: Vector2<S>) -> S {
+        self.half_extents.x * self.axes[0].dot(axis).abs()
+            + self.half_extents.y * self.axes[1].dot(axis).abs()
+    }
+
+    /// Half of the size of the AABB of the OBB
+    #[inline]
+    fn aabb_half_extents(&self) -> Vector2<S> {
+        Vector2::new(
+            self.project(Vector2::unit_x()),
+            self.project(Vector2::unit_y()),
+        )
+    }
+
+    /// Find the entry parameter of the ray, using the slab test in local space
+    fn ray_parameter(&self, ray: &Ray2<S>) -> Option<S> {
+        let origin = self.local_coordinates(ray.origin);
+        let direction = Vector2::new(
+            ray.direction.dot(self.axes[0]),
+            ray.direction.dot(self.axes[1]),
+        );
+        let mut enter = S::zero();
+        let mut exit = S::infinity();
+        for i in 0..2 {
+            if direction[i].is_zero() {
+                if origin[i].abs() > self.half_extents[i] {
+                    return None;
+                }
+            } else {
+                let t1 = (-self.half_extents[i] - origin[i]) / direction[i];
+                let t2 = (self.half_extents[i] - origin[i]) / direction[i];
+                enter = enter.max(t1.min(t2));
+                exit = exit.min(t1.max(t2));
+            }
+        }
+        if enter <= exit {
+            Some(enter)
+        } else {
+            None
+        }
+    }
+}
+
+impl<S: BaseFloat> Bound for Obb2<S> {
+    type Point = Point2<S>;
+
+    fn min_extent(&self) -> Point2<S> {
+        self.center - self.aabb_half_extents()
+    }
+
+    fn max_extent(&self) -> Point2<S> {
+        self.center + self.aabb_half_extents()
+    }
+
+    fn with_margin(&self, add: Vector2<S>) -> Self {
+        Obb2::new(self.center, self.axes, self.half_extents + add)
+    }
+
+    fn transform_volume<T>(&self, transform: &T) -> Self
+    where
+        T: Transform<Self::Point>,
+    {
+        let x = transform.transform_vector(self.axes[0]);
+        let y = transform.transform_vector(self.axes[1]);
+        Obb2::new(
+            transform.transform_point(self.center),
+            [x.normalize(), y.normalize()],
+            Vector2::new(
+                self.half_extents.x * x.magnitude(),
+                self.half_extents.y * y.magnitude(),
+            ),
+        )
+    }
+
+    fn empty() -> Self {
+        Obb2::new(
+            Point2::origin(),
+            [Vector2::unit_x(), Vector2::unit_y()],
+            Vector2::zero(),
+        )
+    }
+}
+
+impl<S: BaseFloat> Contains<Point2<S>> for Obb2<S> {
+    #[inline]
+    fn contains(&self, p: &Point2<S>) -> bool {
+        let local = self.local_coordinates(*p);
+        local.x.abs() <= self.half_extents.x && local.y.abs() <= self.half_extents.y
+    }
+}
+
+impl<S: BaseFloat> ClosestPoint<Point2<S>> for Obb2<S> {
+    fn closest_point(&self, p: &Point2<S>) -> (Point2<S>, bool) {
+        let (local, inside) = closest_point_on_box(self.local_coordinates(*p), self.half_extents);
+        let closest = self
+            .axes
+            .iter()
+            .enumerate()
+            .fold(self.center, |closest, (i, axis)| closest + axis * local[i]);
+        (closest, inside)
+    }
+}
+
+impl<S: BaseFloat> Discrete<Obb2<S>> for Obb2<S> {
+    /// Separating axis test, using the axes of both boxes
+    fn intersects(&self, other: &Obb2<S>) -> bool {
+        let d = other.center - self.center;
+        self.axes
+            .iter()
+            .chain(other.axes.iter())
+            .all(|&axis| d.dot(axis).abs() <= self.project(axis) + other.project(axis))
+    }
+}
+
+impl<S: BaseFloat> Discrete<Aabb2<S>> for Obb2<S> {
+    fn intersects(&self, aabb: &Aabb2<S>) -> bool {
+        self.intersects(&Obb2::new(
+            aabb.center(),
+            [Vector2::unit_x(), Vector2::unit_y()],
+            aabb.half_extents(),
+        ))
+    }
+}
+
+impl<S: BaseFloat> Discrete<Circle<S>> for Obb2<S> {
+    fn intersects(&self, circle: &Circle<S>) -> bool {
+        // closest point in the box to the center of the circle, in local space
+        let local = self.local_coordinates(circle.center);
+        let closest = Vector2::new(
+            local.x.max(-self.half_extents.x).min(self.half_extents.x),
+            local.y.max(-self.half_extents.y).min(self.half_extents.y),
+        );
+        (local - closest).magnitude2() <= circle.radius * circle.radius
+    }
+}
+
+impl<S: BaseFloat> Discrete<Ray2<S>> for Obb2<S> {
+    fn intersects(&self, ray: &Ray2<S>) -> bool {
+        self.ray_parameter(ray).is_some()
+    }
+}
+
+impl<S: BaseFloat> Continuous<Ray2<S>> for Obb2<S> {
+    type Result = Point2<S>;
+
+    fn intersection(&self, ray: &Ray2<S>) -> Option<Point2<S>> {
+        self.ray_parameter(ray)
+            .map(|t| ray.origin + ray.direction * t)
+    }
+}
+
+impl<S: BaseFloat> From<Aabb2<S>> for Obb2<S> {
+    /// OBB with the same extents as the AABB, use `Obb2::from_aabb` for a transformed AABB
+    fn from(aabb: Aabb2<S>) -> Self {
+        Obb2::new(
+            aabb.center(),
+            [Vector2::unit_x(), Vector2::unit_y()],
+            aabb.half_extents(),
+        )
+    }
+}
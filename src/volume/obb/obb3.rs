@@ -0,0 +1,242 @@
+//! Oriented bounding box for 3D.
+
+use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::{Aabb3, Obb3, Ray3, Sphere};
+use crate::prelude::*;
+use crate::primitive::util::closest_point_on_box;
+
+impl<S: BaseFloat> Obb3<S> {
+    /// Construct the oriented bounding box of an AABB under the given transform. Only rotation,
+    /// translation and uniform scale are preserved exactly.
+    pub fn from_aabb<T>(aabb: &Aabb3<S>, transform: &T) -> Obb3<S>
+    where
+        T: Transform<Point3<S>>,
+    {
+        let half_dim = aabb.half_extents();
+        Obb3::new(
+            aabb.center(),
+            [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()],
+            half_dim,
+        ).transform_volume(transform)
+    }
+
+    /// Compute corners.
+    #[inline]
+    pub fn to_corners(&self) -> [Point3<S>; 8] {
+        let x = self.axes[0] * self.half_extents.x;
+        let y = self.axes[1] * self.half_extents.y;
+        let z = self.axes[2] * self.half_extents.z;
+        [
+            self.center - x - y - z,
+            self.center + x - y - z,
+            self.center - x + y - z,
+            self.center + x + y - z,
+            self.center - x - y + z,
+            self.center + x - y + z,
+            self.center - x + y + z,
+            self.center + x + y + z,
+        ]
+    }
+
+    /// Coordinates of the given point along the local axes, relative to the center
+    #[inline]
+    fn local_coordinates(&self, point: Point3<S>) -> Vector3<S> {
+        self.local_vector(point - self.center)
+    }
+
+    /// Components of the given vector along the local axes
+    #[inline]
+    fn local_vector(&self, v: Vector3<S>) -> Vector3<S> {
+        Vector3::new(
+            v.dot(self.axes[0]),
+            v.dot(self.axes[1]),
+            v.dot(self.axes[2]),
+        )
+    }
+
+    /// Half of the length of the projection of the OBB onto the given axis
+    #[inline]
+    fn project(&self, axis: Vector3<S>) -> S {
+        self.half_extents.x * self.axes[0].dot(axis).abs()
+            + self.half_extents.y * self.axes[1].dot(axis).abs()
+            + self.half_extents.z * self.axes[2].dot(axis).abs()
+    }
+
+    /// Half of the size of the AABB of the OBB
+    #[inline]
+    fn aabb_half_extents(&self) -> Vector3<S> {
+        Vector3::new(
+            self.project(Vector3::unit_x()),
+            self.project(Vector3::unit_y()),
+            self.project(Vector3::unit_z()),
+        )
+    }
+
+    /// Find the entry parameter of the ray, using the slab test in local space
+    fn ray_parameter(&self, ray: &Ray3<S>) -> Option<S> {
+        let origin = self.local_coordinates(ray.origin);
+        let direction = self.local_vector(ray.direction);
+        let mut enter = S::zero();
+        let mut exit = S::infinity();
+        for i in 0..3 {
+            if direction[i].is_zero() {
+                if origin[i].abs() > self.half_extents[i] {
+                    return None;
+                }
+            } else {
+                let t1 = (-self.half_extents[i] - origin[i]) / direction[i];
+                let t2 = (self.half_extents[i] - origin[i]) / direction[i];
+                enter = enter.max(t1.min(t2));
+                exit = exit.min(t1.max(t2));
+            }
+        }
+        if enter <= exit {
+            Some(enter)
+        } else {
+            None
+        }
+    }
+}
+
+impl<S: BaseFloat> Bound for Obb3<S> {
+    type Point = Point3<S>;
+
+    fn min_extent(&self) -> Point3<S> {
+        self.center - self.aabb_half_extents()
+    }
+
+    fn max_extent(&self) -> Point3<S> {
+        self.center + self.aabb_half_extents()
+    }
+
+    fn with_margin(&self, add: Vector3<S>) -> Self {
+        Obb3::new(self.center, self.axes, self.half_extents + add)
+    }
+
+    fn transform_volume<T>(&self, transform: &T) -> Self
+    where
+        T: Transform<Self::Point>,
+    {
+        let x = transform.transform_vector(self.axes[0]);
+        let y = transform.transform_vector(self.axes[1]);
+        let z = transform.transform_vector(self.axes[2]);
+        Obb3::new(
+            transform.transform_point(self.center),
+            [x.normalize(), y.normalize(), z.normalize()],
+            Vector3::new(
+                self.half_extents.x * x.magnitude(),
+                self.half_extents.y * y.magnitude(),
+                self.half_extents.z * z.magnitude(),
+            ),
+        )
+    }
+
+    fn empty() -> Self {
+        Obb3::new(
+            Point3::origin(),
+            [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()],
+            Vector3::zero(),
+        )
+    }
+}
+
+impl<S: BaseFloat> Contains<Point3<S>> for Obb3<S> {
+    #[inline]
+    fn contains(&self, p: &Point3<S>) -> bool {
+        let local = self.local_coordinates(*p);
+        local.x.abs() <= self.half_extents.x
+            && local.y.abs() <= self.half_extents.y
+            && local.z.abs() <= self.half_extents.z
+    }
+}
+
+impl<S: BaseFloat> ClosestPoint<Point3<S>> for Obb3<S> {
+    fn closest_point(&self, p: &Point3<S>) -> (Point3<S>, bool) {
+        let (local, inside) = closest_point_on_box(self.local_coordinates(*p), self.half_extents);
+        let closest = self
+            .axes
+            .iter()
+            .enumerate()
+            .fold(self.center, |closest, (i, axis)| closest + axis * local[i]);
+        (closest, inside)
+    }
+}
+
+impl<S: BaseFloat> Discrete<Obb3<S>> for Obb3<S> {
+    /// Separating axis test, using the 3 face axes of each box, and the 9 cross products of
+    /// their edge axes
+    fn intersects(&self, other: &Obb3<S>) -> bool {
+        let d = other.center - self.center;
+        let separates =
+            |axis: Vector3<S>| d.dot(axis).abs() > self.project(axis) + other.project(axis);
+        if self
+            .axes
+            .iter()
+            .chain(other.axes.iter())
+            .any(|&axis| separates(axis))
+        {
+            return false;
+        }
+        // cross products of parallel edges are zero, and never separate the boxes
+        for a in &self.axes {
+            for b in &other.axes {
+                let axis = a.cross(*b);
+                if axis.magnitude2() > S::default_epsilon() && separates(axis) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<S: BaseFloat> Discrete<Aabb3<S>> for Obb3<S> {
+    fn intersects(&self, aabb: &Aabb3<S>) -> bool {
+        self.intersects(&Obb3::new(
+            aabb.center(),
+            [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()],
+            aabb.half_extents(),
+        ))
+    }
+}
+
+impl<S: BaseFloat> Discrete<Sphere<S>> for Obb3<S> {
+    fn intersects(&self, sphere: &Sphere<S>) -> bool {
+        // closest point in the box to the center of the sphere, in local space
+        let local = self.local_coordinates(sphere.center);
+        let closest = Vector3::new(
+            local.x.max(-self.half_extents.x).min(self.half_extents.x),
+            local.y.max(-self.half_extents.y).min(self.half_extents.y),
+            local.z.max(-self.half_extents.z).min(self.half_extents.z),
+        );
+        (local - closest).magnitude2() <= sphere.radius * sphere.radius
+    }
+}
+
+impl<S: BaseFloat> Discrete<Ray3<S>> for Obb3<S> {
+    fn intersects(&self, ray: &Ray3<S>) -> bool {
+        self.ray_parameter(ray).is_some()
+    }
+}
+
+impl<S: BaseFloat> Continuous<Ray3<S>> for Obb3<S> {
+    type Result = Point3<S>;
+
+    fn intersection(&self, ray: &Ray3<S>) -> Option<Point3<S>> {
+        self.ray_parameter(ray)
+            .map(|t| ray.origin + ray.direction * t)
+    }
+}
+
+impl<S: BaseFloat> From<Aabb3<S>> for Obb3<S> {
+    /// OBB with the same extents as the AABB, use `Obb3::from_aabb` for a transformed AABB
+    fn from(aabb: Aabb3<S>) -> Self {
+        Obb3::new(
+            aabb.center(),
+            [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()],
+            aabb.half_extents(),
+        )
+    }
+}
@@ -1,10 +1,14 @@
 //! Bounding sphere
 
-use cgmath::{BaseFloat, Point3, Vector3};
+use cgmath::{BaseFloat, Matrix3, Point3, Vector3};
 use cgmath::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
 
-use crate::{Aabb3, Line3, Plane, Ray3};
+use crate::{Aabb3, Capsule, Line3, Obb3, Plane, Ray3};
 use crate::prelude::*;
+use crate::primitive::util::closest_point_on_ball;
 
 /// Bounding sphere.
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -16,6 +20,145 @@ pub struct Sphere<S: BaseFloat> {
     pub radius: S,
 }
 
+impl<S: BaseFloat> Sphere<S> {
+    /// Compute the minimal bounding sphere of the given points, using Welzl's algorithm.
+    ///
+    /// Runs in expected `O(n)` time, the points are shuffled internally with a fixed seed, so the
+    /// result is the same for the same points. The radius is inflated by the tolerance used for
+    /// the points on the boundary, so all points are contained in the result despite rounding.
+    /// Returns an empty sphere if there are no points.
+    pub fn from_points(points: &[Point3<S>]) -> Self {
+        let mut points = points.to_vec();
+        points.shuffle(&mut StdRng::seed_from_u64(0));
+
+        // move-to-front formulation, each loop finds the smallest sphere with the given points
+        // on the boundary, that contains all points before it
+        let mut sphere = Self::empty();
+        for i in 0..points.len() {
+            if i > 0 && sphere.encloses(points[i]) {
+                continue;
+            }
+            sphere = Self::from_boundary(&[points[i]]);
+            for j in 0..i {
+                if sphere.encloses(points[j]) {
+                    continue;
+                }
+                sphere = Self::from_boundary(&[points[i], points[j]]);
+                for k in 0..j {
+                    if sphere.encloses(points[k]) {
+                        continue;
+                    }
+                    sphere = Self::from_boundary(&[points[i], points[j], points[k]]);
+                    for l in 0..k {
+                        if sphere.encloses(points[l]) {
+                            continue;
+                        }
+                        sphere = Self::from_boundary(&[points[i], points[j], points[k], points[l]]);
+                    }
+                }
+            }
+        }
+        if !points.is_empty() {
+            sphere.radius += sphere.tolerance();
+        }
+        sphere
+    }
+
+    /// Tolerance for points on the boundary
+    fn tolerance(&self) -> S {
+        S::default_epsilon().sqrt() * (S::one() + self.radius)
+    }
+
+    /// Containment test with a tolerance for points on the boundary
+    fn encloses(&self, point: Point3<S>) -> bool {
+        self.center.distance(point) <= self.radius + self.tolerance()
+    }
+
+    /// Smallest sphere with up to 4 points on the boundary
+    fn from_boundary(points: &[Point3<S>]) -> Self {
+        Self::circumsphere(points).unwrap_or_else(|| {
+            // degenerate points, use the smallest sphere through some of them, grown to contain
+            // all of them in case rounding leaves a point just outside
+            let enclosing = |sphere: Self| Sphere {
+                center: sphere.center,
+                radius: points
+                    .iter()
+                    .fold(sphere.radius, |radius, p| radius.max(sphere.center.distance(*p))),
+            };
+            let mut best = enclosing(Sphere {
+                center: points[0],
+                radius: S::zero(),
+            });
+            let mut consider = |candidate: Option<Self>| {
+                if let Some(candidate) = candidate.map(enclosing) {
+                    if candidate.radius < best.radius {
+                        best = candidate;
+                    }
+                }
+            };
+            for i in 0..points.len() {
+                for j in i + 1..points.len() {
+                    consider(Self::circumsphere(&[points[i], points[j]]));
+                    for k in j + 1..points.len() {
+                        consider(Self::circumsphere(&[points[i], points[j], points[k]]));
+                    }
+                }
+            }
+            best
+        })
+    }
+
+    /// Smallest sphere with all given points on the boundary, if the points are not degenerate
+    fn circumsphere(points: &[Point3<S>]) -> Option<Self> {
+        let two = S::one() + S::one();
+        let a = points[0];
+        match points.len() {
+            1 => Some(Sphere {
+                center: a,
+                radius: S::zero(),
+            }),
+            2 => Some(Sphere {
+                center: a.midpoint(points[1]),
+                radius: a.distance(points[1]) / two,
+            }),
+            3 => {
+                let ab = points[1] - a;
+                let ac = points[2] - a;
+                let normal = ab.cross(ac);
+                let denominator = two * normal.magnitude2();
+                if denominator.is_zero() {
+                    return None;
+                }
+                let offset = (normal.cross(ab) * ac.magnitude2()
+                    + ac.cross(normal) * ab.magnitude2())
+                    / denominator;
+                Some(Sphere {
+                    center: a + offset,
+                    radius: offset.magnitude(),
+                })
+            }
+            _ => {
+                let ab = points[1] - a;
+                let ac = points[2] - a;
+                let ad = points[3] - a;
+                // the edges are the rows of the system, and cgmath matrices are column major
+                Matrix3::from_cols(ab, ac, ad)
+                    .transpose()
+                    .invert()
+                    .map(|inverse| {
+                        let offset = inverse
+                            * Vector3::new(ab.magnitude2(), ac.magnitude2(), ad.magnitude2())
+                            / two;
+                        Sphere {
+                            center: a + offset,
+                            radius: offset.magnitude(),
+                        }
+                    })
+            }
+        }
+    }
+}
+
 impl<S> Bound for Sphere<S>
 where
     S: BaseFloat,
@@ -133,6 +276,13 @@ impl<S: BaseFloat> Contains<Point3<S>> for Sphere<S> {
     }
 }
 
+impl<S: BaseFloat> ClosestPoint<Point3<S>> for Sphere<S> {
+    #[inline]
+    fn closest_point(&self, p: &Point3<S>) -> (Point3<S>, bool) {
+        closest_point_on_ball(self.center, self.radius, *p)
+    }
+}
+
 impl<S: BaseFloat> Contains<Line3<S>> for Sphere<S> {
     #[inline]
     fn contains(&self, line: &Line3<S>) -> bool {
@@ -206,3 +356,33 @@ impl<S: BaseFloat> SurfaceArea for Sphere<S> {
         four * pi * self.radius * self.radius
     }
 }
+
+impl<S: BaseFloat> From<Aabb3<S>> for Sphere<S> {
+    /// Smallest sphere enclosing the AABB
+    fn from(aabb: Aabb3<S>) -> Self {
+        Sphere {
+            center: aabb.center(),
+            radius: aabb.max().distance(aabb.center()),
+        }
+    }
+}
+
+impl<S: BaseFloat> From<Obb3<S>> for Sphere<S> {
+    /// Smallest sphere enclosing the OBB
+    fn from(obb: Obb3<S>) -> Self {
+        Sphere {
+            center: obb.center,
+            radius: obb.half_extents.magnitude(),
+        }
+    }
+}
+
+impl<S: BaseFloat> From<Capsule<S>> for Sphere<S> {
+    /// Smallest sphere enclosing the capsule
+    fn from(capsule: Capsule<S>) -> Self {
+        Sphere {
+            center: capsule.start.midpoint(capsule.end),
+            radius: capsule.start.distance(capsule.end) / (S::one() + S::one()) + capsule.radius,
+        }
+    }
+}
@@ -5,8 +5,8 @@ use cgmath::{Point2, Point3};
 use cgmath::{Vector2, Vector3};
 use cgmath::InnerSpace;
 use collision::{Aabb, Aabb2, Aabb3};
-use collision::{Contains, Continuous, Discrete, SurfaceArea, Union};
-use collision::{Line2, Line3, Ray2, Ray3, Sphere};
+use collision::{ClosestPoint, Contains, Continuous, Discrete, SurfaceArea, Union};
+use collision::{Circle, Line2, Line3, Ray2, Ray3, Sphere};
 use collision::{Plane, PlaneBound, Ray, Relation};
 
 #[test]
@@ -48,6 +48,10 @@ fn test_general() {
             Point2::new(12isize, 32isize),
         )
     );
+    assert_eq!(
+        aabb.add_margin_s(2isize),
+        aabb.add_margin(Vector2::new(2isize, 2isize))
+    );
 
     let aabb = Aabb3::new(
         Point3::new(-20isize, 30isize, 5isize),
@@ -98,6 +102,10 @@ fn test_general() {
             Point3::new(12isize, 32isize, 7isize),
         )
     );
+    assert_eq!(
+        aabb.add_margin_s(2isize),
+        aabb.add_margin(Vector3::new(2isize, 2isize, 2isize))
+    );
 }
 
 #[test]
@@ -533,3 +541,145 @@ fn test_aabb3_transform() {
         aabb.transform(&transform)
     );
 }
+
+#[test]
+fn test_aabb2_intersection_aabb2() {
+    let a = Aabb2::new(Point2::new(0f32, 0.), Point2::new(4., 3.));
+    let b = Aabb2::new(Point2::new(2., -1.), Point2::new(6., 2.));
+    assert_eq!(
+        Some(Aabb2::new(Point2::new(2., 0.), Point2::new(4., 2.))),
+        a.intersection(&b)
+    );
+    assert_eq!(4., a.overlap_volume(&b));
+    assert_eq!(a.overlap_volume(&b), b.overlap_volume(&a));
+    assert_eq!(12., a.overlap_volume(&a));
+
+    // touching boxes do not overlap
+    let c = Aabb2::new(Point2::new(4., 0.), Point2::new(5., 3.));
+    assert_eq!(None, a.intersection(&c));
+    assert_eq!(0., a.overlap_volume(&c));
+    let d = Aabb2::new(Point2::new(10., 10.), Point2::new(11., 11.));
+    assert_eq!(None, a.intersection(&d));
+    assert_eq!(0., a.overlap_volume(&d));
+}
+
+#[test]
+fn test_aabb3_intersection_aabb3() {
+    let a = Aabb3::new(Point3::new(0f32, 0., 0.), Point3::new(4., 3., 2.));
+    let b = Aabb3::new(Point3::new(2., -1., 1.), Point3::new(6., 2., 5.));
+    assert_eq!(
+        Some(Aabb3::new(Point3::new(2., 0., 1.), Point3::new(4., 2., 2.))),
+        a.intersection(&b)
+    );
+    assert_eq!(4., a.overlap_volume(&b));
+    assert_eq!(24., a.volume());
+    assert_eq!(
+        Aabb3::new(Point3::new(0., -1., 0.), Point3::new(6., 3., 5.)),
+        a.union(&b)
+    );
+    let c = Aabb3::new(Point3::new(0., 0., 3.), Point3::new(4., 3., 4.));
+    assert_eq!(None, a.intersection(&c));
+    assert_eq!(0., a.overlap_volume(&c));
+}
+
+#[test]
+fn test_aabb2_relate() {
+    let aabb = Aabb2::new(Point2::new(0., 0.), Point2::new(10., 10.));
+    let inner = Aabb2::new(Point2::new(2., 2.), Point2::new(4., 4.));
+    let crossing = Aabb2::new(Point2::new(8., 8.), Point2::new(12., 12.));
+    let outer = Aabb2::new(Point2::new(11., 11.), Point2::new(12., 12.));
+    assert_eq!(Relation::In, aabb.relate_aabb(&inner));
+    assert_eq!(Relation::Cross, aabb.relate_aabb(&crossing));
+    assert_eq!(Relation::Out, aabb.relate_aabb(&outer));
+
+    let circle = |x, y, radius| Circle {
+        center: Point2::new(x, y),
+        radius,
+    };
+    assert!(aabb.contains(&circle(5., 5., 5.)));
+    assert!(!aabb.contains(&circle(5., 5., 6.)));
+    assert_eq!(Relation::In, aabb.relate_circle(&circle(5., 5., 1.)));
+    assert_eq!(Relation::Cross, aabb.relate_circle(&circle(11., 5., 2.)));
+    // close to the corner, but outside the rounded corner region
+    assert_eq!(Relation::Out, aabb.relate_circle(&circle(11., 11., 1.2)));
+}
+
+#[test]
+fn test_aabb3_relate() {
+    let aabb = Aabb3::new(Point3::new(0., 0., 0.), Point3::new(10., 10., 10.));
+    let inner = Aabb3::new(Point3::new(2., 2., 2.), Point3::new(4., 4., 4.));
+    let crossing = Aabb3::new(Point3::new(8., 8., 8.), Point3::new(12., 12., 12.));
+    let outer = Aabb3::new(Point3::new(11., 0., 0.), Point3::new(12., 10., 10.));
+    assert_eq!(Relation::In, aabb.relate_aabb(&inner));
+    assert_eq!(Relation::Cross, aabb.relate_aabb(&crossing));
+    assert_eq!(Relation::Out, aabb.relate_aabb(&outer));
+
+    let sphere = |x, y, z, radius| Sphere {
+        center: Point3::new(x, y, z),
+        radius,
+    };
+    assert_eq!(Relation::In, aabb.relate_sphere(&sphere(5., 5., 5., 1.)));
+    assert_eq!(
+        Relation::Cross,
+        aabb.relate_sphere(&sphere(5., 5., 5., 10.))
+    );
+    assert_eq!(
+        Relation::Cross,
+        aabb.relate_sphere(&sphere(11., 5., 5., 2.))
+    );
+    assert_eq!(
+        Relation::Out,
+        aabb.relate_sphere(&sphere(11., 11., 11., 1.5))
+    );
+    assert!(aabb.intersects(&sphere(11., 11., 11., 1.8)));
+}
+
+#[test]
+fn test_aabb2_expanded_by() {
+    let aabb = Aabb2::new(Point2::new(0., 0.), Point2::new(1., 2.));
+    assert_eq!(
+        Aabb2::new(Point2::new(-3., 0.), Point2::new(1., 7.)),
+        aabb.expanded_by(&Vector2::new(-3., 5.))
+    );
+    assert_eq!(aabb, aabb.expanded_by(&Vector2::new(0., 0.)));
+}
+
+#[test]
+fn test_aabb3_expanded_by() {
+    let aabb = Aabb3::new(Point3::new(0., 0., 0.), Point3::new(1., 2., 3.));
+    let swept = aabb.expanded_by(&Vector3::new(2., -1., 0.));
+    assert_eq!(
+        Aabb3::new(Point3::new(0., -1., 0.), Point3::new(3., 2., 3.)),
+        swept
+    );
+    assert!(swept.contains(&aabb));
+    assert!(swept.contains(&aabb.add_v(Vector3::new(2., -1., 0.))));
+}
+
+#[test]
+fn test_aabb_extents() {
+    let aabb = Aabb2::new(Point2::new(-1., 2.), Point2::new(3., 3.));
+    assert_eq!(Vector2::new(2., 0.5), aabb.half_extents());
+    assert_eq!(0, aabb.longest_axis());
+
+    let aabb = Aabb3::new(Point3::new(0., 0., 0.), Point3::new(2., 6., 4.));
+    assert_eq!(Point3::new(1., 3., 2.), aabb.center());
+    assert_eq!(Vector3::new(1., 3., 2.), aabb.half_extents());
+    assert_eq!(1, aabb.longest_axis());
+    assert_eq!(2, Aabb3::new(Point3::new(0, 0, 0), Point3::new(1, 1, 5)).longest_axis());
+    // ties go to the first axis
+    assert_eq!(0, Aabb3::new(Point3::new(0, 0, 0), Point3::new(2, 2, 2)).longest_axis());
+}
+
+#[test]
+fn test_aabb3_closest_point() {
+    let aabb = Aabb3::new(Point3::new(0f32, 0., 0.), Point3::new(2., 4., 2.));
+    assert_eq!(
+        (Point3::new(2., 1., 0.), false),
+        aabb.closest_point(&Point3::new(3., 1., -1.))
+    );
+    assert_eq!(
+        (Point3::new(0., 2., 1.), true),
+        aabb.closest_point(&Point3::new(0.5, 2., 1.))
+    );
+}
@@ -0,0 +1,339 @@
+extern crate cgmath;
+extern crate collision;
+extern crate rand;
+
+use cgmath::{Decomposed, Point2, Point3, Quaternion, Rad, Rotation3, Vector2, Vector3};
+use collision::{Aabb2, Aabb3, Ray2, Ray3, Sphere};
+use collision::primitive::{self, Primitive3};
+use collision::algorithm::broad_phase::*;
+use collision::dbvt::{DynamicBoundingVolumeTree, TreeValue};
+use collision::prelude::*;
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+struct Shape<B> {
+    pub id: usize,
+    pub bound: B,
+}
+
+impl<B: Bound> HasBound for Shape<B> {
+    type Bound = B;
+
+    fn bound(&self) -> &B {
+        &self.bound
+    }
+}
+
+impl TreeValue for Shape<Aabb2<f32>> {
+    type Bound = Aabb2<f32>;
+
+    fn bound(&self) -> &Aabb2<f32> {
+        &self.bound
+    }
+
+    fn get_bound_with_margin(&self) -> Aabb2<f32> {
+        self.bound.add_margin(Vector2::new(0.5, 0.5))
+    }
+}
+
+fn shapes2(n: usize) -> Vec<Shape<Aabb2<f32>>> {
+    let mut rng = rand::thread_rng();
+    (0..n)
+        .map(|id| {
+            let min = Point2::new(rng.gen_range(-50., 50.), rng.gen_range(-50., 50.));
+            let max = min + Vector2::new(rng.gen_range(0.1, 8.), rng.gen_range(0.1, 8.));
+            Shape {
+                id,
+                bound: Aabb2::new(min, max),
+            }
+        })
+        .collect()
+}
+
+fn shapes3(n: usize) -> Vec<Shape<Aabb3<f32>>> {
+    let mut rng = rand::thread_rng();
+    (0..n)
+        .map(|id| {
+            let min = Point3::new(
+                rng.gen_range(-20., 20.),
+                rng.gen_range(-20., 20.),
+                rng.gen_range(-20., 20.),
+            );
+            let max = Point3::new(
+                min.x + rng.gen_range(0.1, 6.),
+                min.y + rng.gen_range(0.1, 6.),
+                min.z + rng.gen_range(0.1, 6.),
+            );
+            Shape {
+                id,
+                bound: Aabb3::new(min, max),
+            }
+        })
+        .collect()
+}
+
+/// Pairs of ids, with the lower id first, sorted
+fn ids<B>(shapes: &[Shape<B>], pairs: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut ids: Vec<(usize, usize)> = pairs
+        .iter()
+        .map(|&(l, r)| {
+            let (l, r) = (shapes[l].id, shapes[r].id);
+            (l.min(r), l.max(r))
+        })
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_sweep_and_prune_against_brute_force() {
+    let mut shapes = shapes2(300);
+    let expected = ids(&shapes, &BruteForce.find_collider_pairs(&shapes));
+    assert!(!expected.is_empty());
+    let mut sweep = SweepAndPrune2::new();
+    // the second call uses the order from the first
+    for _ in 0..2 {
+        let pairs = sweep.find_collider_pairs(&mut shapes);
+        assert_eq!(expected, ids(&shapes, &pairs));
+    }
+
+    let mut shapes = shapes3(300);
+    let expected = ids(&shapes, &BruteForce.find_collider_pairs(&shapes));
+    let pairs = SweepAndPrune3::new().find_collider_pairs(&mut shapes);
+    assert_eq!(expected, ids(&shapes, &pairs));
+}
+
+#[test]
+fn test_spatial_hash_against_brute_force() {
+    let shapes = shapes2(300);
+    let expected = BruteForce.find_collider_pairs(&shapes);
+    assert_eq!(expected, SpatialHash::new(4.).find_collider_pairs(&shapes));
+
+    let shapes = shapes3(300);
+    let expected = BruteForce.find_collider_pairs(&shapes);
+    assert_eq!(expected, SpatialHash::new(3.).find_collider_pairs(&shapes));
+}
+
+#[test]
+fn test_dbvt_against_brute_force() {
+    let shapes = shapes2(300);
+    let mut tree = DynamicBoundingVolumeTree::new();
+    for shape in &shapes {
+        tree.insert(shape.clone());
+    }
+    tree.do_refit();
+    let values: Vec<Shape<Aabb2<f32>>> = tree.values().iter().map(|v| v.1.clone()).collect();
+    let expected = ids(&values, &BruteForce.find_collider_pairs(&values));
+    let pairs = DbvtBroadPhase::new().find_collider_pairs(&tree, &vec![true; values.len()]);
+    assert_eq!(expected, ids(&values, &pairs));
+    assert_eq!(expected, ids(&values, &tree.overlapping_pairs()));
+}
+
+#[test]
+fn test_filtered_pairs_against_brute_force() {
+    // shapes with odd ids only interact with shapes with even ids
+    let filter =
+        |left: &Shape<Aabb2<f32>>, right: &Shape<Aabb2<f32>>| left.id % 2 == 0 || right.id % 2 == 0;
+    let mut shapes = shapes2(300);
+    let expected: Vec<(usize, usize)> = ids(&shapes, &BruteForce.find_collider_pairs(&shapes))
+        .into_iter()
+        .filter(|&(left, right)| left % 2 == 0 || right % 2 == 0)
+        .collect();
+    assert!(!expected.is_empty());
+    let pairs = BruteForce.find_collider_pairs_by(&shapes, filter);
+    assert_eq!(expected, ids(&shapes, &pairs));
+    let pairs = SpatialHash::new(4.).find_collider_pairs_by(&shapes, filter);
+    assert_eq!(expected, ids(&shapes, &pairs));
+    let pairs = SweepAndPrune2::new().find_collider_pairs_by(&mut shapes, filter);
+    assert_eq!(expected, ids(&shapes, &pairs));
+
+    let mut tree = DynamicBoundingVolumeTree::new();
+    for shape in &shapes {
+        tree.insert(shape.clone());
+    }
+    tree.do_refit();
+    let values: Vec<Shape<Aabb2<f32>>> = tree.values().iter().map(|v| v.1.clone()).collect();
+    assert_eq!(expected, ids(&values, &tree.overlapping_pairs_by(filter)));
+}
+
+#[test]
+fn test_broad_phase_implementations() {
+    check_broad_phase(ListBroadPhase::new(BruteForce));
+    check_broad_phase(ListBroadPhase::new(SweepAndPrune2::new()));
+    check_broad_phase(ListBroadPhase::new(SpatialHash::new(4.)));
+    check_broad_phase(TreeBroadPhase::new());
+    check_broad_phase(TreeBroadPhase::with_margin(Vector2::new(1., 1.)));
+    let region = Aabb2::new(Point2::new(-50., -50.), Point2::new(50., 50.));
+    check_broad_phase(OrthtreeBroadPhase::new(region));
+    check_broad_phase(
+        OrthtreeBroadPhase::new(region)
+            .with_leaf_capacity(2)
+            .with_looseness(1.5),
+    );
+}
+
+#[test]
+fn test_broad_phase_sphere_bounds() {
+    let mut rng = rand::thread_rng();
+    let shapes: Vec<Shape<Sphere<f32>>> = (0..200)
+        .map(|id| {
+            let primitive = if id % 2 == 0 {
+                Primitive3::from(primitive::Cuboid::new(
+                    rng.gen_range(0.2, 4.),
+                    rng.gen_range(0.2, 4.),
+                    rng.gen_range(0.2, 4.),
+                ))
+            } else {
+                Primitive3::from(primitive::Sphere::new(rng.gen_range(0.1, 2.)))
+            };
+            let transform = Decomposed {
+                scale: 1.,
+                rot: Quaternion::from_angle_y(Rad(rng.gen_range(0., 6.))),
+                disp: Vector3::new(
+                    rng.gen_range(-20., 20.),
+                    rng.gen_range(-20., 20.),
+                    rng.gen_range(-20., 20.),
+                ),
+            };
+            Shape {
+                id,
+                bound: primitive.compute_bound_transformed(&transform),
+            }
+        })
+        .collect();
+    let expected = ids(&shapes, &BruteForce.find_collider_pairs(&shapes));
+    assert!(!expected.is_empty());
+
+    let mut tree = TreeBroadPhase::new();
+    let mut list = ListBroadPhase::new(BruteForce);
+    for shape in &shapes {
+        tree.insert(shape.id, shape.bound);
+        list.insert(shape.id, shape.bound);
+    }
+    assert_eq!(expected, sorted_pairs(tree.compute_pairs()));
+    assert_eq!(expected, sorted_pairs(list.compute_pairs()));
+
+    let ray = Ray3::new(Point3::new(-30., 0., 0.), Vector3::new(1., 0., 0.));
+    let expected: Vec<usize> = shapes
+        .iter()
+        .filter(|shape| shape.bound.intersects(&ray))
+        .map(|shape| shape.id)
+        .collect();
+    let mut hits = tree.query_ray(&ray);
+    hits.sort();
+    assert_eq!(expected, hits);
+}
+
+/// Check the broad phase against testing all pairs, while adding, moving and removing objects
+fn check_broad_phase<P>(mut broad_phase: P)
+where
+    P: BroadPhase<usize, Bound = Aabb2<f32>>,
+{
+    let mut rng = rand::thread_rng();
+    let mut shapes = shapes2(200);
+    for shape in &shapes {
+        broad_phase.insert(shape.id, shape.bound);
+    }
+    assert_eq!(200, broad_phase.len());
+    assert_eq!(
+        expected_pairs(&shapes),
+        sorted_pairs(broad_phase.compute_pairs())
+    );
+
+    for shape in shapes.iter_mut().step_by(3) {
+        let offset = Vector2::new(rng.gen_range(-3., 3.), rng.gen_range(-3., 3.));
+        shape.bound = Aabb2::new(shape.bound.min + offset, shape.bound.max + offset);
+        assert!(broad_phase.update(shape.id, shape.bound));
+    }
+    for shape in shapes.iter().step_by(4) {
+        assert!(broad_phase.remove(shape.id));
+        assert!(!broad_phase.remove(shape.id));
+    }
+    shapes.retain(|shape| shape.id % 4 != 0);
+    assert_eq!(shapes.len(), broad_phase.len());
+    assert!(!broad_phase.update(0, Aabb2::new(Point2::new(0., 0.), Point2::new(1., 1.))));
+    assert_eq!(
+        expected_pairs(&shapes),
+        sorted_pairs(broad_phase.compute_pairs())
+    );
+
+    // objects with odd ids are debris, that does not interact with other debris
+    let groups = |id: usize| {
+        if id % 2 == 1 {
+            CollisionGroups::new(0b10, !0b10)
+        } else {
+            CollisionGroups::new(0b01, !0)
+        }
+    };
+    let expected: Vec<(usize, usize)> = expected_pairs(&shapes)
+        .into_iter()
+        .filter(|&(left, right)| left % 2 == 0 || right % 2 == 0)
+        .collect();
+    assert_eq!(
+        expected,
+        sorted_pairs(
+            broad_phase
+                .compute_filtered_pairs(|&left, &right| groups(left).can_interact(&groups(right)))
+        )
+    );
+
+    let region = Aabb2::new(Point2::new(-10., -10.), Point2::new(10., 10.));
+    let mut ids = broad_phase.query_region(&region);
+    ids.sort();
+    let expected: Vec<usize> = shapes
+        .iter()
+        .filter(|shape| shape.bound.intersects(&region))
+        .map(|shape| shape.id)
+        .collect();
+    assert_eq!(expected, ids);
+
+    let ray = Ray2::new(Point2::new(-60., 0.), Vector2::new(1., 0.));
+    let mut ids = broad_phase.query_ray(&ray);
+    ids.sort();
+    let expected: Vec<usize> = shapes
+        .iter()
+        .filter(|shape| shape.bound.intersects(&ray))
+        .map(|shape| shape.id)
+        .collect();
+    assert_eq!(expected, ids);
+}
+
+fn expected_pairs(shapes: &[Shape<Aabb2<f32>>]) -> Vec<(usize, usize)> {
+    ids(shapes, &BruteForce.find_collider_pairs(shapes))
+}
+
+fn sorted_pairs(pairs: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    let mut pairs: Vec<(usize, usize)> = pairs
+        .into_iter()
+        .map(|(l, r)| (l.min(r), l.max(r)))
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_against_brute_force() {
+    let mut shapes = shapes2(2000);
+    let expected = BruteForce.find_collider_pairs(&shapes);
+    assert_eq!(
+        expected,
+        SpatialHash::new(4.).par_find_collider_pairs(&shapes)
+    );
+    let mut sweep = SweepAndPrune2::new();
+    for _ in 0..2 {
+        let pairs = sweep.par_find_collider_pairs(&mut shapes);
+        assert_eq!(expected, ids(&shapes, &pairs));
+        // sorted, unlike the pairs from the serial sweep
+        assert!(pairs.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    let mut shapes = shapes3(2000);
+    let expected = BruteForce.find_collider_pairs(&shapes);
+    assert_eq!(
+        expected,
+        SpatialHash::new(3.).par_find_collider_pairs(&shapes)
+    );
+    let pairs = SweepAndPrune3::new().par_find_collider_pairs(&mut shapes);
+    assert_eq!(expected, ids(&shapes, &pairs));
+}
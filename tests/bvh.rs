@@ -0,0 +1,153 @@
+extern crate cgmath;
+extern crate collision;
+extern crate rand;
+
+use cgmath::{Deg, PerspectiveFov, Point3, Vector3};
+use cgmath::prelude::*;
+use collision::{Aabb3, Projection, Ray3, Relation};
+use collision::bvh::Bvh;
+use collision::prelude::*;
+use rand::Rng;
+
+struct Value {
+    aabb: Aabb3<f32>,
+}
+
+impl HasBound for Value {
+    type Bound = Aabb3<f32>;
+
+    fn bound(&self) -> &Aabb3<f32> {
+        &self.aabb
+    }
+}
+
+fn random_values(n: usize) -> Vec<Value> {
+    let mut rng = rand::thread_rng();
+    (0..n)
+        .map(|_| {
+            let min = Point3::new(
+                rng.gen_range(-50., 50.),
+                rng.gen_range(-50., 50.),
+                rng.gen_range(-5., 5.),
+            );
+            let size = Vector3::new(
+                rng.gen_range(0.1, 5.),
+                rng.gen_range(0.1, 5.),
+                rng.gen_range(0.1, 5.),
+            );
+            Value {
+                aabb: Aabb3::new(min, min + size),
+            }
+        })
+        .collect()
+}
+
+fn sorted(mut indices: Vec<usize>) -> Vec<usize> {
+    indices.sort();
+    indices
+}
+
+#[test]
+fn test_bvh_empty() {
+    let bvh = Bvh::<Aabb3<f32>>::new::<Value>(&[]);
+    assert!(bvh.is_empty());
+    assert_eq!(None, bvh.bound());
+    let ray = Ray3::new(Point3::origin(), Vector3::unit_x());
+    assert!(bvh.query_ray(&ray).is_empty());
+}
+
+#[test]
+fn test_bvh_leaf_size() {
+    let values = random_values(100);
+    let bvh = Bvh::with_max_leaf_size(&values, 1);
+    assert_eq!(100, bvh.len());
+    // a full binary tree with a value in each leaf
+    assert_eq!(199, bvh.node_count());
+    let bvh = Bvh::new(&values);
+    assert!(bvh.node_count() <= 199);
+
+    // splitting overlapping values does not lower the cost
+    let values: Vec<Value> = (0..4)
+        .map(|i| Value {
+            aabb: Aabb3::new(
+                Point3::new(0.1 * i as f32, 0., 0.),
+                Point3::new(0.1 * i as f32 + 1., 1., 1.),
+            ),
+        })
+        .collect();
+    assert_eq!(1, Bvh::new(&values).node_count());
+    assert_eq!(7, Bvh::with_max_leaf_size(&values, 1).node_count());
+
+    // values with the same center can not be split
+    let values: Vec<Value> = (0..10)
+        .map(|i| Value {
+            aabb: Aabb3::new(
+                Point3::new(-i as f32, -1., -1.),
+                Point3::new(i as f32, 1., 1.),
+            ),
+        })
+        .collect();
+    assert_eq!(1, Bvh::new(&values).node_count());
+}
+
+#[test]
+fn test_bvh_queries() {
+    let values = random_values(500);
+    let bvh = Bvh::new(&values);
+    let bound = values
+        .iter()
+        .fold(values[0].aabb, |bound, v| bound.union(&v.aabb));
+    assert_eq!(Some(&bound), bvh.bound());
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..20 {
+        let min = Point3::new(rng.gen_range(-50., 50.), rng.gen_range(-50., 50.), -1.);
+        let region = Aabb3::new(min, min + Vector3::new(10., 10., 2.));
+        let expected: Vec<usize> = (0..values.len())
+            .filter(|&i| values[i].aabb.intersects(&region))
+            .collect();
+        assert_eq!(expected, sorted(bvh.query_aabb(&region)));
+
+        let origin = Point3::new(rng.gen_range(-60., 60.), -60., 0.);
+        let ray = Ray3::new(
+            origin,
+            Vector3::new(rng.gen_range(-1., 1.), 1., 0.).normalize(),
+        );
+        let expected: Vec<usize> = (0..values.len())
+            .filter(|&i| values[i].aabb.intersects(&ray))
+            .collect();
+        assert_eq!(expected, sorted(bvh.query_ray(&ray)));
+    }
+}
+
+#[test]
+fn test_bvh_frustum() {
+    // in front of the camera, which looks along negative z
+    let offset = Vector3::new(0., 0., -20.);
+    let values: Vec<Value> = random_values(500)
+        .into_iter()
+        .map(|v| Value {
+            aabb: Aabb3::new(v.aabb.min + offset, v.aabb.max + offset),
+        })
+        .collect();
+    let frustum = PerspectiveFov {
+        fovy: Deg(60.).into(),
+        aspect: 16. / 9.,
+        near: 0.1,
+        far: 40.,
+    }
+    .to_frustum();
+    let bvh = Bvh::new(&values);
+
+    // same relations as testing all values
+    let expected: Vec<(usize, Relation)> = values
+        .iter()
+        .map(|v| frustum.contains(&v.aabb))
+        .enumerate()
+        .filter(|&(_, r)| r != Relation::Out)
+        .collect();
+    let mut result = bvh.query_frustum(&frustum);
+    result.sort_by_key(|&(index, _)| index);
+    assert_eq!(expected, result);
+    assert!(result.iter().any(|&(_, r)| r == Relation::In));
+}
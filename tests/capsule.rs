@@ -0,0 +1,93 @@
+#[macro_use]
+extern crate approx;
+extern crate cgmath;
+extern crate collision;
+
+use cgmath::{Point3, Vector3};
+use cgmath::prelude::*;
+use collision::{Aabb3, Bound, Capsule, Sphere};
+use collision::{ClosestPoint, Contains, Discrete};
+
+#[test]
+fn test_capsule_from_points() {
+    // limb along the diagonal, with some points around it
+    let axis = Vector3::new(1f32, 1., 0.).normalize();
+    let side = Vector3::new(0., 0., 1.);
+    let mut points = Vec::new();
+    for i in 0..=10 {
+        let p = Point3::new(1., 2., 3.) + axis * (i as f32 - 5.);
+        points.push(p + side * 0.5);
+        points.push(p - side * 0.5);
+    }
+    let capsule = Capsule::from_points(&points);
+    assert_ulps_eq!(0.5, capsule.radius, epsilon = 1e-4);
+    let core = capsule.end - capsule.start;
+    assert_ulps_eq!(1., core.normalize().dot(axis).abs(), epsilon = 1e-4);
+    assert_ulps_eq!(10., core.magnitude(), epsilon = 1e-3);
+    let grown = capsule.with_margin(Vector3::from_value(1e-4));
+    assert!(points.iter().all(|p| grown.contains(p)));
+}
+
+#[test]
+fn test_capsule_from_points_degenerate() {
+    assert_eq!(Capsule::empty(), Capsule::<f32>::from_points(&[]));
+    let capsule = Capsule::from_points(&[Point3::new(1f32, 2., 3.)]);
+    assert_eq!(Point3::new(1., 2., 3.), capsule.start);
+    assert_eq!(Point3::new(1., 2., 3.), capsule.end);
+    assert_eq!(0., capsule.radius);
+}
+
+#[test]
+fn test_capsule_sphere() {
+    let capsule = capsule();
+    assert!(capsule.intersects(&Sphere {
+        center: Point3::new(3., 1.5, 0.),
+        radius: 0.6,
+    }));
+    assert!(!capsule.intersects(&Sphere {
+        center: Point3::new(3., 1.5, 0.),
+        radius: 0.4,
+    }));
+    // beyond the end cap
+    assert!(!capsule.intersects(&Sphere {
+        center: Point3::new(6.5, 0., 0.),
+        radius: 0.4,
+    }));
+}
+
+#[test]
+fn test_capsule_aabb() {
+    let capsule = capsule();
+    assert_eq!(Point3::new(-1., -1., -1.), capsule.min_extent());
+    assert_eq!(Point3::new(6., 1., 1.), capsule.max_extent());
+    assert!(capsule.intersects(&Aabb3::new(
+        Point3::new(2., 0.5, -0.5),
+        Point3::new(3., 2., 0.5)
+    )));
+    assert!(!capsule.intersects(&Aabb3::new(
+        Point3::new(2., 1.5, -0.5),
+        Point3::new(3., 2., 0.5)
+    )));
+    // inside the AABB of the capsule, but outside the rounded end
+    assert!(!capsule.intersects(&Aabb3::new(
+        Point3::new(5.8, 0.8, -0.1),
+        Point3::new(6., 1., 0.1)
+    )));
+}
+
+// util
+/// Capsule along the X axis, with radius 1
+fn capsule() -> Capsule<f32> {
+    Capsule::new(Point3::new(0., 0., 0.), Point3::new(5., 0., 0.), 1.)
+}
+
+#[test]
+fn test_capsule_closest_point() {
+    let capsule = Capsule::new(Point3::origin(), Point3::new(0f32, 2., 0.), 1.);
+    let (closest, inside) = capsule.closest_point(&Point3::new(3., 1., 0.));
+    assert!(!inside);
+    assert_ulps_eq!(Point3::new(1., 1., 0.), closest);
+    let (closest, inside) = capsule.closest_point(&Point3::new(0., 2.5, 0.));
+    assert!(inside);
+    assert_ulps_eq!(Point3::new(0., 3., 0.), closest);
+}
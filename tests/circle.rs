@@ -0,0 +1,128 @@
+#[macro_use]
+extern crate approx;
+extern crate cgmath;
+extern crate collision;
+extern crate rand;
+
+use cgmath::Point2;
+use collision::{Aabb2, Bound, Circle, Dop2, Obb2};
+use collision::{Contains, Discrete};
+use rand::Rng;
+
+#[test]
+fn test_circle() {
+    let circle = Circle {
+        center: Point2::new(1f32, 1.),
+        radius: 2.,
+    };
+    assert!(circle.contains(&Point2::new(2., 2.)));
+    assert!(!circle.contains(&Point2::new(3., 3.)));
+    let other = Circle {
+        center: Point2::new(4., 1.),
+        radius: 1.,
+    };
+    assert!(circle.intersects(&other));
+    assert!(!circle.contains(&other));
+    assert_eq!(Point2::new(-1., -1.), circle.min_extent());
+}
+
+#[test]
+fn test_circle_from_points() {
+    let points = [
+        Point2::new(1f64, 1.),
+        Point2::new(5., 1.),
+        Point2::new(3., 3.),
+        Point2::new(3., -1.),
+        Point2::new(2., 0.5),
+    ];
+    let circle = Circle::from_points(&points);
+    assert_ulps_eq!(Point2::new(3., 1.), circle.center, epsilon = 1e-10);
+    assert_ulps_eq!(2., circle.radius, epsilon = 1e-7);
+
+    // obtuse triangle, the longest side is the diameter
+    let points = [
+        Point2::new(-2f64, 0.),
+        Point2::new(2., 0.),
+        Point2::new(0.5, 0.5),
+    ];
+    let circle = Circle::from_points(&points);
+    assert_ulps_eq!(Point2::new(0., 0.), circle.center, epsilon = 1e-10);
+    assert_ulps_eq!(2., circle.radius, epsilon = 1e-7);
+
+    // equilateral triangle
+    let points = [
+        Point2::new(0f64, 2.),
+        Point2::new(3f64.sqrt(), -1.),
+        Point2::new(-(3f64.sqrt()), -1.),
+    ];
+    let circle = Circle::from_points(&points);
+    assert_ulps_eq!(Point2::new(0., 0.), circle.center, epsilon = 1e-10);
+    assert_ulps_eq!(2., circle.radius, epsilon = 1e-7);
+}
+
+#[test]
+fn test_circle_from_points_collinear() {
+    let points = [
+        Point2::new(0f64, 0.),
+        Point2::new(1., 1.),
+        Point2::new(-3., -3.),
+        Point2::new(2., 2.),
+    ];
+    let circle = Circle::from_points(&points);
+    assert_ulps_eq!(Point2::new(-0.5, -0.5), circle.center, epsilon = 1e-10);
+    assert_ulps_eq!(2.5 * 2f64.sqrt(), circle.radius, epsilon = 1e-7);
+    assert_eq!(0., Circle::<f64>::from_points(&[]).radius);
+}
+
+#[test]
+fn test_circle_from_points_coincident() {
+    let points = [
+        Point2::new(1f64, 2.),
+        Point2::new(1., 2.),
+        Point2::new(1., 2.),
+        Point2::new(3., 2.),
+        Point2::new(3., 2.),
+        Point2::new(1., 2.),
+    ];
+    let circle = Circle::from_points(&points);
+    assert_ulps_eq!(Point2::new(2., 2.), circle.center, epsilon = 1e-10);
+    assert_ulps_eq!(1., circle.radius, epsilon = 1e-7);
+
+    let circle = Circle::from_points(&[Point2::new(1f64, 2.); 4]);
+    assert_eq!(Point2::new(1., 2.), circle.center);
+    assert_ulps_eq!(0., circle.radius, epsilon = 1e-7);
+}
+
+#[test]
+fn test_circle_from_points_encloses_random() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..200 {
+        let scale = rng.gen_range(0.1f32, 100.);
+        let points: Vec<_> = (0..rng.gen_range(1, 60))
+            .map(|_| Point2::new(rng.gen_range(-scale, scale), rng.gen_range(-scale, scale)))
+            .collect();
+        let circle = Circle::from_points(&points);
+        for point in &points {
+            assert!(circle.contains(point), "{:?} outside {:?}", point, circle);
+        }
+        // the order is fixed, so the result is too
+        assert_eq!(circle, Circle::from_points(&points));
+    }
+}
+
+#[test]
+fn test_circle_conversions() {
+    let aabb = Aabb2::new(Point2::new(-3f32, 0.), Point2::new(3., 8.));
+    let circle = Circle::from(aabb);
+    assert_eq!(Point2::new(0., 4.), circle.center);
+    assert_eq!(5., circle.radius);
+    assert_eq!(
+        Aabb2::new(Point2::new(-5., -1.), Point2::new(5., 9.)),
+        Aabb2::from(circle)
+    );
+
+    let obb = Obb2::from(aabb);
+    assert_eq!(aabb, Aabb2::from(obb));
+    assert_eq!(circle, Circle::from(obb));
+    assert_eq!(aabb, Aabb2::from(Dop2::from(aabb)));
+}
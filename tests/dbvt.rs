@@ -276,6 +276,214 @@ fn test_remove_last() {
     assert_eq!(0, tree.height());
 }
 
+#[test]
+fn test_tree_margin() {
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::with_margin(Vector2::new(1., 1.));
+    let node_index = tree.insert(Value2::new(10, aabb2(0., 0., 1., 1.)));
+    tree.insert(Value2::new(11, aabb2(5., 5., 1., 1.)));
+    tree.do_refit();
+    assert_eq!(vec![aabb2(-1., -1., 8., 8.)], branch_bounds(&tree));
+
+    // moves inside the fat bound, so the tree is not changed
+    tree.update_node(node_index, Value2::new(10, aabb2(-0.5, -0.5, 1., 1.)));
+    tree.tick();
+    assert_eq!(vec![aabb2(-1., -1., 8., 8.)], branch_bounds(&tree));
+
+    tree.update_node(node_index, Value2::new(10, aabb2(-2., -0.5, 1., 1.)));
+    tree.tick();
+    assert_eq!(vec![aabb2(-3., -1.5, 10., 8.5)], branch_bounds(&tree));
+}
+
+#[test]
+fn test_refit() {
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    let node_index = tree.insert(Value2::new(10, aabb2(0., 0., 1., 1.)));
+    tree.insert(Value2::new(11, aabb2(5., 5., 1., 1.)));
+    let other_index = tree.insert(Value2::new(12, aabb2(10., 10., 1., 1.)));
+    tree.do_refit();
+    let height = tree.height();
+    assert_eq!(aabb2(0., 0., 11., 11.), branch_bounds(&tree)[0]);
+
+    // shrinks inside the old bound, which update leaves alone
+    tree.update_node(node_index, Value2::new(10, aabb2(0.5, 0.5, 0.5, 0.5)));
+    tree.update_node(other_index, Value2::new(12, aabb2(9., 9., 1., 1.)));
+    tree.refit(&[node_index, other_index]);
+    assert_eq!(aabb2(0.5, 0.5, 9.5, 9.5), branch_bounds(&tree)[0]);
+    assert_eq!(height, tree.height());
+
+    // grows out of the old bound
+    tree.update_node(node_index, Value2::new(10, aabb2(-2., 0.5, 0.5, 0.5)));
+    tree.refit(&[node_index]);
+    assert_eq!(aabb2(-2., 0.5, 12., 9.5), branch_bounds(&tree)[0]);
+
+    // branches and unused node indices are ignored
+    tree.refit(&[0, 1000]);
+    assert_eq!(aabb2(-2., 0.5, 12., 9.5), branch_bounds(&tree)[0]);
+}
+
+#[test]
+fn test_refit_random() {
+    let mut rng = rand::thread_rng();
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    let mut node_indices = Vec::new();
+    for i in 0..300 {
+        let x = rng.gen_range(-100., 100.);
+        let y = rng.gen_range(-100., 100.);
+        node_indices.push(tree.insert(Value2::new(i, aabb2(x, y, 5., 5.))));
+    }
+    tree.do_refit();
+    let height = tree.height();
+
+    // move all values a little, and refit them in one go
+    for &node_index in &node_indices {
+        let (_, value) = tree.values()[tree.value_index(node_index).unwrap()].clone();
+        let offset = Vector2::new(rng.gen_range(-3., 3.), rng.gen_range(-3., 3.));
+        let bound = Aabb2::new(value.aabb.min + offset, value.aabb.max + offset);
+        tree.update_node(node_index, Value2::new(value.id, bound));
+    }
+    tree.refit(&node_indices);
+    assert_eq!(height, tree.height());
+
+    // the root is the tight bound of all values, and all pairs are still found
+    let values = tree.values();
+    let all = values
+        .iter()
+        .skip(1)
+        .fold(values[0].1.aabb, |all, (_, value)| all.union(&value.aabb));
+    assert_eq!(all, branch_bounds(&tree)[0]);
+    let mut expected = Vec::new();
+    for i in 0..values.len() {
+        for j in (i + 1)..values.len() {
+            if values[i].1.bound().intersects(values[j].1.bound()) {
+                expected.push((i, j));
+            }
+        }
+    }
+    assert_eq!(expected, tree.overlapping_pairs());
+}
+
+#[test]
+fn test_rebalance() {
+    let mut rng = rand::thread_rng();
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    let mut node_indices = Vec::new();
+    for i in 0..200 {
+        let x = rng.gen_range(-100., 100.);
+        let y = rng.gen_range(-100., 100.);
+        node_indices.push(tree.insert(Value2::new(i, aabb2(x, y, 5., 5.))));
+    }
+    tree.do_refit();
+    // move all values, so the tree no longer fits them well
+    for (i, &node_index) in node_indices.iter().enumerate() {
+        let x = rng.gen_range(-100., 100.);
+        let y = rng.gen_range(-100., 100.);
+        tree.update_node(node_index, Value2::new(i as u32, aabb2(x, y, 5., 5.)));
+    }
+    for &node_index in node_indices.iter().step_by(2) {
+        tree.remove(node_index);
+    }
+    tree.tick();
+    let pairs = tree.overlapping_pairs();
+    let area = total_area(&branch_bounds(&tree));
+
+    tree.rebalance();
+    assert_eq!(pairs, tree.overlapping_pairs());
+    assert!(total_area(&branch_bounds(&tree)) <= area);
+    let region = aabb2(-200., -200., 400., 400.);
+    let mut visitor = DiscreteVisitor::<Aabb2<f32>, Value2>::new(&region);
+    assert_eq!(tree.values().len(), tree.query(&mut visitor).len());
+}
+
+#[test]
+fn test_build() {
+    let mut rng = rand::thread_rng();
+    let values: Vec<Value2> = (0..500)
+        .map(|i| {
+            let x = rng.gen_range(-100., 100.);
+            let y = rng.gen_range(-100., 100.);
+            Value2::new(i, aabb2(x, y, 5., 5.))
+        })
+        .collect();
+
+    let mut inserted = DynamicBoundingVolumeTree::<Value2>::new();
+    for value in &values {
+        inserted.insert(value.clone());
+    }
+    inserted.do_refit();
+
+    let mut tree = values
+        .iter()
+        .cloned()
+        .collect::<DynamicBoundingVolumeTree<Value2>>();
+    assert_eq!(2 * values.len() - 1, tree.size());
+    // 500 values need a height of at least 10
+    assert_eq!(10, tree.height());
+    assert_eq!(inserted.overlapping_pairs(), tree.overlapping_pairs());
+    for (i, (node_index, value)) in tree.values().iter().enumerate() {
+        assert_eq!(values[i].id, value.id);
+        assert_eq!(Some(i), tree.value_index(*node_index));
+    }
+    let region = aabb2(-20., -20., 40., 40.);
+    let mut ids: Vec<u32> = tree.query_aabb(&region).iter().map(|v| v.id).collect();
+    ids.sort();
+    let mut expected: Vec<u32> = inserted.query_aabb(&region).iter().map(|v| v.id).collect();
+    expected.sort();
+    assert_eq!(expected, ids);
+
+    // the tree can be changed as usual after building it
+    let node_index = tree.values()[3].0;
+    tree.remove(node_index);
+    tree.insert(Value2::new(1000, aabb2(200., 200., 5., 5.)));
+    tree.do_refit();
+    assert_eq!(values.len(), tree.values().len());
+    assert_eq!(1, tree.query_aabb(&aabb2(190., 190., 20., 20.)).len());
+
+    tree.build(None);
+    assert_eq!(0, tree.size());
+    assert_eq!(0, tree.height());
+    tree.build(values.into_iter().take(1));
+    assert_eq!(1, tree.size());
+    assert_eq!(1, tree.height());
+}
+
+#[test]
+fn test_overlapping_pairs() {
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    assert!(tree.overlapping_pairs().is_empty());
+    tree.insert(Value2::new(10, aabb2(0., 0., 2., 2.)));
+    assert!(tree.overlapping_pairs().is_empty());
+    tree.insert(Value2::new(11, aabb2(1., 1., 2., 2.)));
+    tree.insert(Value2::new(12, aabb2(10., 10., 2., 2.)));
+    tree.insert(Value2::new(13, aabb2(11., 11., 2., 2.)));
+    tree.insert(Value2::new(14, aabb2(1.5, 0., 10., 1.5)));
+    tree.do_refit();
+    assert_eq!(vec![(0, 1), (0, 4), (1, 4), (2, 3)], tree.overlapping_pairs());
+}
+
+#[test]
+fn test_overlapping_pairs_random() {
+    let mut rng = rand::thread_rng();
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    for i in 0..100 {
+        let x = rng.gen_range(-100., 100.);
+        let y = rng.gen_range(-100., 100.);
+        tree.insert(Value2::new(i, aabb2(x, y, 10., 10.)));
+    }
+    tree.do_refit();
+
+    // same pairs as testing all values against each other
+    let values = tree.values();
+    let mut expected = Vec::new();
+    for i in 0..values.len() {
+        for j in (i + 1)..values.len() {
+            if values[i].1.bound().intersects(values[j].1.bound()) {
+                expected.push((i, j));
+            }
+        }
+    }
+    assert_eq!(expected, tree.overlapping_pairs());
+}
+
 #[test]
 fn test_ray_closest() {
     let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
@@ -298,6 +506,183 @@ fn test_ray_closest() {
     assert_eq!(Point2::new(18., 5.9999995), p);
 }
 
+#[test]
+fn test_ray_closest_by() {
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    tree.insert(Value2::new(1, aabb2(0., 0., 1., 1.)));
+    tree.insert(Value2::new(2, aabb2(3., 0., 1., 1.)));
+    tree.insert(Value2::new(3, aabb2(6., 0., 1., 1.)));
+    tree.insert(Value2::new(4, aabb2(9., 0., 1., 1.)));
+    tree.insert(Value2::new(5, aabb2(3., 5., 1., 1.)));
+    tree.do_refit();
+
+    // the first value is hollow, and the ray passes through it
+    let mut tested = Vec::new();
+    let result = query_ray_closest_by(
+        &tree,
+        Ray2::new(Point2::new(-5., 0.5), Vector2::new(1., 0.)),
+        |value, ray| {
+            tested.push(value.id);
+            if value.id == 1 {
+                None
+            } else {
+                value.aabb.intersection(ray)
+            }
+        },
+    );
+    let (v, p) = result.unwrap();
+    assert_eq!(2, v.id);
+    assert_eq!(Point2::new(3., 0.5), p);
+    assert_eq!(vec![1, 2], tested);
+
+    let result = query_ray_closest_by(
+        &tree,
+        Ray2::new(Point2::new(-5., 0.5), Vector2::new(1., 0.)),
+        |_, _| None,
+    );
+    assert!(result.is_none());
+
+    // the ray starts inside the first value, and exits its bound after entering the second
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    tree.insert(Value2::new(1, aabb2(0., 0., 10., 1.)));
+    tree.insert(Value2::new(2, aabb2(3., 0., 6., 1.)));
+    tree.do_refit();
+    let result = query_ray_closest_by(
+        &tree,
+        Ray2::new(Point2::new(1., 0.5), Vector2::new(1., 0.)),
+        |value, ray| {
+            let t = if value.id == 1 { 1. } else { 5. };
+            Some(ray.origin + ray.direction * t)
+        },
+    );
+    let (v, p) = result.unwrap();
+    assert_eq!(1, v.id);
+    assert_eq!(Point2::new(2., 0.5), p);
+}
+
+#[test]
+fn test_query_aabb() {
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    tree.insert(Value2::new(10, aabb2(0., 0., 2., 2.)));
+    tree.insert(Value2::new(11, aabb2(5., 5., 2., 2.)));
+    tree.insert(Value2::new(12, aabb2(1., 5., 2., 2.)));
+    tree.insert(Value2::new(13, aabb2(20., 20., 2., 2.)));
+    tree.do_refit();
+
+    let mut ids: Vec<u32> = tree
+        .query_aabb(&aabb2(1., 1., 5., 5.))
+        .iter()
+        .map(|v| v.id)
+        .collect();
+    ids.sort();
+    assert_eq!(vec![10, 11, 12], ids);
+    assert!(tree.query_aabb(&aabb2(10., 10., 5., 5.)).is_empty());
+}
+
+#[test]
+fn test_nearest() {
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    assert!(tree.nearest(&Point2::new(0., 0.)).is_none());
+    tree.insert(Value2::new(10, aabb2(0., 0., 2., 2.)));
+    tree.insert(Value2::new(11, aabb2(5., 0., 2., 2.)));
+    tree.insert(Value2::new(12, aabb2(0., 5., 2., 2.)));
+    tree.do_refit();
+
+    let (value, distance) = tree.nearest(&Point2::new(4., 1.)).unwrap();
+    assert_eq!(11, value.id);
+    assert_eq!(1., distance);
+    let (value, distance) = tree.nearest(&Point2::new(1., 1.)).unwrap();
+    assert_eq!(10, value.id);
+    assert_eq!(0., distance);
+}
+
+#[test]
+fn test_nearest_random() {
+    let mut rng = rand::thread_rng();
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    for i in 0..200 {
+        let x = rng.gen_range(-100., 100.);
+        let y = rng.gen_range(-100., 100.);
+        tree.insert(Value2::new(i, aabb2(x, y, rng.gen_range(0.5, 5.), 2.)));
+    }
+    tree.do_refit();
+
+    let center_distance = |value: &Value2, point: Point2<f32>| value.aabb.center().distance(point);
+    for _ in 0..20 {
+        let point = Point2::new(rng.gen_range(-120., 120.), rng.gen_range(-120., 120.));
+
+        // same distances as testing all values
+        let min_bound = tree
+            .values()
+            .iter()
+            .map(|(_, v)| bound_distance(&v.aabb, point))
+            .fold(f32::INFINITY, f32::min);
+        let (value, distance) = tree.nearest(&point).unwrap();
+        assert_eq!(min_bound, distance);
+        assert_eq!(min_bound, bound_distance(&value.aabb, point));
+
+        let min_center = tree
+            .values()
+            .iter()
+            .map(|(_, v)| center_distance(v, point))
+            .fold(f32::INFINITY, f32::min);
+        let (value, distance) = tree
+            .nearest_by(&point, |v| center_distance(v, point))
+            .unwrap();
+        assert_eq!(min_center, distance);
+        assert_eq!(min_center, center_distance(value, point));
+    }
+}
+
+#[test]
+fn test_k_nearest() {
+    let mut rng = rand::thread_rng();
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    assert!(tree.k_nearest(&Point2::new(0., 0.), 3).is_empty());
+    for i in 0..200 {
+        let x = rng.gen_range(-100., 100.);
+        let y = rng.gen_range(-100., 100.);
+        tree.insert(Value2::new(i, aabb2(x, y, rng.gen_range(0.5, 5.), 2.)));
+    }
+    tree.do_refit();
+
+    let center_distance = |value: &Value2, point: Point2<f32>| value.aabb.center().distance(point);
+    for _ in 0..20 {
+        let point = Point2::new(rng.gen_range(-120., 120.), rng.gen_range(-120., 120.));
+
+        // same distances as sorting all values
+        let mut expected: Vec<f32> = tree
+            .values()
+            .iter()
+            .map(|(_, v)| bound_distance(&v.aabb, point))
+            .collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let result = tree.k_nearest(&point, 5);
+        let distances: Vec<f32> = result.iter().map(|&(_, d)| d).collect();
+        assert_eq!(&expected[..5], &distances[..]);
+        for (value, distance) in result {
+            assert_eq!(distance, bound_distance(&value.aabb, point));
+        }
+
+        let mut expected: Vec<f32> = tree
+            .values()
+            .iter()
+            .map(|(_, v)| center_distance(v, point))
+            .collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let distances: Vec<f32> = tree
+            .k_nearest_by(&point, 8, |v| center_distance(v, point))
+            .iter()
+            .map(|&(_, d)| d)
+            .collect();
+        assert_eq!(&expected[..8], &distances[..]);
+    }
+
+    let point = Point2::new(0., 0.);
+    assert!(tree.k_nearest(&point, 0).is_empty());
+    assert_eq!(200, tree.k_nearest(&point, 300).len());
+}
+
 #[test]
 fn test_ray_discrete() {
     let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
@@ -351,6 +736,61 @@ fn test_frustum() {
     assert_eq!(11, v.id);
 }
 
+#[test]
+fn test_query_frustum() {
+    let mut tree = DynamicBoundingVolumeTree::<Value3>::new();
+    let mut id = 0;
+    for x in -4..5 {
+        for y in -4..5 {
+            for z in 1..6 {
+                let (x, y, z) = (x as f32 * 0.5, y as f32 * 0.5, z as f32 * -0.9);
+                tree.insert(Value3::new(id, aabb3(x, y, z, x + 0.2, y + 0.2, z + 0.2)));
+                id += 1;
+            }
+        }
+    }
+    tree.do_refit();
+
+    // same relations as testing all values
+    let frustum = frustum();
+    let mut expected: Vec<(u32, Relation)> = tree
+        .values()
+        .iter()
+        .map(|(_, v)| (v.id, frustum.contains(&v.aabb)))
+        .filter(|&(_, r)| r != Relation::Out)
+        .collect();
+    expected.sort_by_key(|&(id, _)| id);
+    let mut result: Vec<(u32, Relation)> = tree
+        .query_frustum(&frustum)
+        .into_iter()
+        .map(|(v, r)| (v.id, r))
+        .collect();
+    result.sort_by_key(|&(id, _)| id);
+    assert_eq!(expected, result);
+    assert!(result.iter().any(|&(_, r)| r == Relation::In));
+    assert!(result.len() < id as usize);
+}
+
+struct BranchVisitor(Vec<Aabb2<f32>>);
+
+impl Visitor for BranchVisitor {
+    type Bound = Aabb2<f32>;
+    type Result = ();
+
+    fn accept(&mut self, bound: &Aabb2<f32>, is_leaf: bool) -> Option<()> {
+        if !is_leaf {
+            self.0.push(*bound);
+        }
+        Some(())
+    }
+}
+
+fn branch_bounds(tree: &DynamicBoundingVolumeTree<Value2>) -> Vec<Aabb2<f32>> {
+    let mut visitor = BranchVisitor(Vec::new());
+    tree.query(&mut visitor);
+    visitor.0
+}
+
 fn aabb2(minx: f32, miny: f32, width: f32, height: f32) -> Aabb2<f32> {
     Aabb2::new(
         Point2::new(minx, miny),
@@ -372,3 +812,14 @@ fn frustum() -> Frustum<f32> {
     };
     projection.to_frustum()
 }
+
+fn total_area(bounds: &[Aabb2<f32>]) -> f32 {
+    bounds.iter().map(|bound| bound.surface_area()).sum()
+}
+
+fn bound_distance(aabb: &Aabb2<f32>, point: Point2<f32>) -> f32 {
+    match aabb.closest_point(&point) {
+        (_, true) => 0.,
+        (closest, false) => closest.distance(point),
+    }
+}
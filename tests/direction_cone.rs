@@ -0,0 +1,98 @@
+#[macro_use]
+extern crate approx;
+extern crate cgmath;
+extern crate collision;
+
+use cgmath::{Deg, Ortho, PerspectiveFov, Point3, Rad, Vector3};
+use cgmath::prelude::*;
+use collision::{Contains, DirectionCone, Frustum, Projection, Relation, Union};
+
+#[test]
+fn test_cone_from_directions() {
+    let directions = [
+        Vector3::new(1f32, 0., 1.),
+        Vector3::new(-1., 0., 1.),
+        Vector3::new(0., 1., 1.),
+        Vector3::new(0., 0., 2.),
+    ];
+    let cone = DirectionCone::from_directions(&directions).unwrap();
+    assert!(directions.iter().all(|d| cone.contains(&(*d * 0.999))));
+    assert!(!cone.contains(&Vector3::new(0., -1., 0.)));
+    assert!(DirectionCone::<f32>::from_directions(&[]).is_none());
+}
+
+#[test]
+fn test_cone_union() {
+    let a = DirectionCone::new(Vector3::unit_z(), Rad::from(Deg(10f32)));
+    let b = DirectionCone::new(Vector3::unit_x(), Rad::from(Deg(20f32)));
+    let cone = a.union(&b);
+    assert_ulps_eq!(Rad::from(Deg(60.)), cone.half_angle, epsilon = 1e-5);
+    assert!(cone.contains(&a));
+    assert!(cone.contains(&b));
+    assert_eq!(cone, cone.union(&a));
+
+    // opposite directions give a cone around a perpendicular axis
+    let cone = DirectionCone::from_direction(Vector3::unit_z())
+        .union(&DirectionCone::from_direction(-Vector3::unit_z()));
+    assert_ulps_eq!(Rad::turn_div_4(), cone.half_angle);
+    assert_ulps_eq!(0., cone.axis.z);
+    assert!(!cone.is_full());
+
+    let full = cone.union(&DirectionCone::new(Vector3::unit_x(), Rad::from(Deg(170.))));
+    assert!(full.is_full());
+    assert!(full.contains(&Vector3::new(-1., -1., -1.)));
+}
+
+#[test]
+fn test_cone_back_facing() {
+    let cone = DirectionCone::new(Vector3::unit_z(), Rad::from(Deg(30f32)));
+    assert!(cone.is_back_facing(Vector3::unit_z()));
+    assert!(cone.is_back_facing(Vector3::new(0., 1., 1.)));
+    assert!(!cone.is_back_facing(Vector3::new(0., 1., 0.5)));
+    assert!(!cone.is_back_facing(-Vector3::unit_z()));
+
+    let center = Point3::new(0., 0., 10.);
+    assert!(cone.is_back_facing_from(center, 1., Point3::new(0., 0., 0.)));
+    // the sphere covers too large a part of the view
+    assert!(!cone.is_back_facing_from(center, 9., Point3::new(0., 0., 0.)));
+    assert!(!cone.is_back_facing_from(center, 1., Point3::new(0., 10., 9.)));
+    assert!(!cone.is_back_facing_from(center, 1., Point3::new(0., 0., 10.5)));
+}
+
+#[test]
+fn test_cone_relate_frustum() {
+    // looks down -Z, with view directions up to 45 degrees from the axis
+    let frustum = PerspectiveFov {
+        fovy: Rad::from(Deg(90f32)),
+        aspect: 1.,
+        near: 1.,
+        far: 100.,
+    }.to_frustum();
+
+    // normals pointing away from the viewer, even at the corners of the view
+    let away = DirectionCone::new(-Vector3::unit_z(), Rad::from(Deg(10f32)));
+    assert_eq!(Relation::Out, away.relate_frustum(&frustum));
+    // normals pointing towards the viewer
+    let towards = DirectionCone::new(Vector3::unit_z(), Rad::from(Deg(10f32)));
+    assert_eq!(Relation::In, towards.relate_frustum(&frustum));
+    // back facing in the middle of the view, but not in the corners, which are 54.7 degrees
+    // from the axis
+    let wide = DirectionCone::new(-Vector3::unit_z(), Rad::from(Deg(40f32)));
+    assert_eq!(Relation::Cross, wide.relate_frustum(&frustum));
+    let sideways = DirectionCone::new(Vector3::unit_x(), Rad::from(Deg(10f32)));
+    assert_eq!(Relation::Cross, sideways.relate_frustum(&frustum));
+
+    // all view directions are the same for an orthographic view
+    let ortho = Ortho {
+        left: -1f32,
+        right: 1.,
+        bottom: -1.,
+        top: 1.,
+        near: 1.,
+        far: 100.,
+    };
+    let frustum = Frustum::from_matrix4(ortho.into()).unwrap();
+    assert_eq!(Relation::Out, wide.relate_frustum(&frustum));
+    let tilted = DirectionCone::new(Vector3::new(1., 0., 1.).normalize(), Rad::from(Deg(40f32)));
+    assert_eq!(Relation::In, tilted.relate_frustum(&frustum));
+}
@@ -0,0 +1,144 @@
+extern crate cgmath;
+extern crate collision;
+
+use cgmath::{Point2, Point3, Vector2, Vector3};
+use collision::{Aabb2, Aabb3, Bound, Dop2, Dop3};
+use collision::{Contains, Discrete, SurfaceArea, Union};
+use collision::algorithm::broad_phase::DbvtBroadPhase;
+use collision::dbvt::{DynamicBoundingVolumeTree, TreeValue};
+
+#[derive(Debug, Clone)]
+struct Value2 {
+    dop: Dop2<f32>,
+}
+
+impl TreeValue for Value2 {
+    type Bound = Dop2<f32>;
+
+    fn bound(&self) -> &Dop2<f32> {
+        &self.dop
+    }
+
+    fn get_bound_with_margin(&self) -> Dop2<f32> {
+        self.dop.with_margin(Vector2::new(0.1, 0.1))
+    }
+}
+
+#[test]
+fn test_dop2_from_points() {
+    let dop = diagonal2(0., 0.);
+    assert_eq!([-1., -1., -2., -2.], dop.min);
+    assert_eq!([2., 2., 4., 2.], dop.max);
+    assert_eq!(
+        Aabb2::new(Point2::new(-1., -1.), Point2::new(2., 2.)),
+        dop.to_aabb()
+    );
+    assert!(dop.contains(&Point2::new(0.5, 0.5)));
+    // inside the AABB, but in the cut off corner
+    assert!(!dop.contains(&Point2::new(1.9, -0.9)));
+}
+
+#[test]
+fn test_dop2_intersects() {
+    let dop = diagonal2(0., 0.);
+    // the AABBs overlap, but the diagonal slabs separate the DOPs
+    let other = diagonal2(2.5, -2.5);
+    assert!(dop.to_aabb().intersects(&other.to_aabb()));
+    assert!(!dop.intersects(&other));
+    let other = diagonal2(1., -1.);
+    assert!(dop.intersects(&other));
+}
+
+#[test]
+fn test_dop2_union() {
+    let dop = diagonal2(0., 0.);
+    let other = diagonal2(5., 0.);
+    let union = dop.union(&other);
+    assert!(union.contains(&dop));
+    assert!(union.contains(&other));
+    assert!(!dop.contains(&union));
+    assert_eq!(24., union.surface_area());
+    assert_eq!(
+        dop.union(&Dop2::from_point(Point2::new(3., 3.))),
+        dop.grow(Point2::new(3., 3.))
+    );
+}
+
+#[test]
+fn test_dop2_margin() {
+    let dop = Dop2::from_aabb(&Aabb2::new(Point2::new(0., 0.), Point2::new(1., 1.)));
+    let fat = dop.with_margin(Vector2::new(1., 0.5));
+    assert_eq!(Point2::new(-1., -0.5), fat.min_extent());
+    assert_eq!(Point2::new(2., 1.5), fat.max_extent());
+    assert_eq!(-1.5, fat.min[2]);
+    assert_eq!(3.5, fat.max[2]);
+}
+
+#[test]
+fn test_dop2_broad_phase() {
+    let mut tree = DynamicBoundingVolumeTree::<Value2>::new();
+    tree.insert(Value2 {
+        dop: diagonal2(0., 0.),
+    });
+    tree.insert(Value2 {
+        dop: diagonal2(2.5, -2.5),
+    });
+    tree.insert(Value2 {
+        dop: diagonal2(1., 1.),
+    });
+    tree.do_refit();
+    let pairs = DbvtBroadPhase::new().find_collider_pairs(&tree, &[true, true, true]);
+    assert_eq!(vec![(0, 2)], pairs);
+}
+
+#[test]
+fn test_dop3_from_points() {
+    let dop = diagonal3(0.);
+    assert_eq!(
+        Aabb3::new(Point3::new(0., 0., 0.), Point3::new(1., 1., 1.)),
+        dop.to_aabb()
+    );
+    assert_eq!(Point3::new(0., 0., 0.), dop.min_extent());
+    assert!(dop.contains(&Point3::new(0.2, 0.2, 0.2)));
+    // inside the AABB, but in the cut off corner
+    assert!(!dop.contains(&Point3::new(0.9, 0.9, 0.9)));
+}
+
+#[test]
+fn test_dop3_intersects() {
+    let dop = diagonal3(0.);
+    // the AABBs overlap, but the corner diagonal separates the DOPs
+    let other = Dop3::from_aabb(&Aabb3::new(
+        Point3::new(0.8, 0.8, 0.8),
+        Point3::new(2., 2., 2.),
+    ));
+    assert!(dop.to_aabb().intersects(&other.to_aabb()));
+    assert!(!dop.intersects(&other));
+    assert!(dop.intersects(&diagonal3(0.2)));
+    assert!(dop.union(&other).contains(&dop));
+    let fat = dop.with_margin(Vector3::new(1., 1., 1.));
+    assert!(fat.contains(&Point3::new(1.5, 1.5, 0.5)));
+    assert!(!fat.contains(&Point3::new(1.5, 1.5, 1.5)));
+}
+
+// util
+/// Quadrilateral with corners at (-1, -1), (2, 0), (2, 2) and (0, 2), moved by the given
+/// displacement
+fn diagonal2(dx: f32, dy: f32) -> Dop2<f32> {
+    Dop2::from_points(&[
+        Point2::new(-1. + dx, -1. + dy),
+        Point2::new(2. + dx, 0. + dy),
+        Point2::new(0. + dx, 2. + dy),
+        Point2::new(2. + dx, 2. + dy),
+    ])
+}
+
+/// Tetrahedron at the origin, moved along the diagonal by the given displacement
+fn diagonal3(d: f32) -> Dop3<f32> {
+    Dop3::from_points(&[
+        Point3::new(d, d, d),
+        Point3::new(1. + d, d, d),
+        Point3::new(d, 1. + d, d),
+        Point3::new(d, d, 1. + d),
+    ])
+}
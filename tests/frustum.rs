@@ -1,8 +1,13 @@
+#[macro_use]
+extern crate approx;
 extern crate cgmath;
 extern crate collision;
 
-use cgmath::{PerspectiveFov, Point3, Rad};
-use collision::{Projection, Relation, Sphere};
+use cgmath::{Decomposed, PerspectiveFov, Point3, Quaternion, Rad, Vector3};
+use cgmath::prelude::*;
+use collision::{Aabb3, ComputeBound, Frustum, Projection, Relation, Sphere};
+use collision::algorithm::minkowski::GJK3;
+use collision::primitive;
 
 #[test]
 fn test_contains() {
@@ -34,3 +39,79 @@ fn test_contains() {
         Relation::Out
     );
 }
+
+#[test]
+fn test_to_points() {
+    let points = frustum().to_points().unwrap();
+    let near = 0.5f32.tan();
+    let far = near * 10.;
+    assert_ulps_eq!(
+        points.near_top_left,
+        Point3::new(-near, near, -1.),
+        epsilon = 1e-5
+    );
+    assert_ulps_eq!(
+        points.near_bottom_right,
+        Point3::new(near, -near, -1.),
+        epsilon = 1e-5
+    );
+    assert_ulps_eq!(
+        points.far_top_right,
+        Point3::new(far, far, -10.),
+        epsilon = 1e-4
+    );
+    assert_ulps_eq!(
+        points.far_bottom_left,
+        Point3::new(-far, -far, -10.),
+        epsilon = 1e-4
+    );
+    let bound: Aabb3<f32> = points.compute_bound();
+    assert_ulps_eq!(bound.min, Point3::new(-far, -far, -10.), epsilon = 1e-4);
+    assert_ulps_eq!(bound.max, Point3::new(far, far, -1.), epsilon = 1e-4);
+}
+
+#[test]
+fn test_points_gjk() {
+    let frustum = frustum();
+    let points = frustum.to_points().unwrap();
+    let sphere = primitive::Sphere::new(1f32);
+    let gjk = GJK3::new();
+    let identity = transform(0., 0., 0.);
+
+    assert!(gjk
+        .intersect(&points, &identity, &sphere, &transform(0., 0., -5.))
+        .is_some());
+    assert!(gjk
+        .intersect(&points, &identity, &sphere, &transform(0., 4.5, -5.))
+        .is_none());
+
+    // close to the corner, the planes give a false positive, but GJK does not
+    let center = Point3::new(3.7f32, 3.7, -5.);
+    assert_eq!(
+        frustum.contains(&Sphere {
+            center,
+            radius: 1f32,
+        }),
+        Relation::Cross
+    );
+    assert!(gjk
+        .intersect(&points, &identity, &sphere, &transform(3.7, 3.7, -5.))
+        .is_none());
+}
+
+fn frustum() -> Frustum<f32> {
+    PerspectiveFov {
+        fovy: Rad(1f32),
+        aspect: 1f32,
+        near: 1f32,
+        far: 10f32,
+    }.to_frustum()
+}
+
+fn transform(dx: f32, dy: f32, dz: f32) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+    Decomposed {
+        scale: 1.,
+        rot: Quaternion::one(),
+        disp: Vector3::new(dx, dy, dz),
+    }
+}
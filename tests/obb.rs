@@ -0,0 +1,297 @@
+#[macro_use]
+extern crate approx;
+extern crate cgmath;
+extern crate collision;
+
+use cgmath::{Basis2, Decomposed, Point2, Point3, Quaternion, Rad, Vector2, Vector3};
+use cgmath::prelude::*;
+use collision::{Aabb2, Aabb3, Bound, Circle, Obb, Obb2, Obb3, Ray2, Ray3, Sphere};
+use collision::{ClosestPoint, Contains, Continuous, Discrete};
+
+#[test]
+fn test_obb2_corners() {
+    let obb = Obb2::from_aabb(
+        &Aabb2::new(Point2::new(-2f32, -1.), Point2::new(2., 1.)),
+        &transform(10., 0., std::f32::consts::PI / 2.),
+    );
+    assert_ulps_eq!(Point2::new(10., 0.), obb.center);
+    assert_ulps_eq!(Vector2::new(2., 1.), obb.half_extents);
+    let corners = obb.to_corners();
+    assert_ulps_eq!(Point2::new(11., -2.), corners[0], epsilon = 1e-6);
+    assert_ulps_eq!(Point2::new(9., 2.), corners[3], epsilon = 1e-6);
+    assert_ulps_eq!(Point2::new(9., -2.), obb.min_extent(), epsilon = 1e-6);
+    assert_ulps_eq!(Point2::new(11., 2.), obb.max_extent(), epsilon = 1e-6);
+}
+
+#[test]
+fn test_obb_generic() {
+    // the aliases are the generic OBB with the 2D and 3D vector and point types
+    let obb: Obb<f32, Vector2<f32>, Point2<f32>> = Obb2::new(
+        Point2::new(1., 2.),
+        [Vector2::unit_x(), Vector2::unit_y()],
+        Vector2::new(3., 4.),
+    );
+    assert_eq!(Point2::new(-2., -2.), obb.min_extent());
+    let obb: Obb<f32, Vector3<f32>, Point3<f32>> = Obb::new(
+        Point3::new(1., 2., 3.),
+        [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()],
+        Vector3::new(1., 1., 1.),
+    );
+    assert_eq!(Point3::new(2., 3., 4.), obb.max_extent());
+}
+
+#[test]
+fn test_obb2_contains() {
+    let obb = diagonal();
+    assert!(obb.contains(&Point2::new(0., 0.)));
+    assert!(obb.contains(&Point2::new(2., 2.)));
+    // inside the AABB of the OBB, but outside the OBB
+    assert!(!obb.contains(&Point2::new(2., -2.)));
+}
+
+#[test]
+fn test_obb2_closest_point() {
+    let obb = Obb2::from_aabb(
+        &Aabb2::new(Point2::new(-2f32, -1.), Point2::new(2., 1.)),
+        &transform(10., 0., std::f32::consts::PI / 2.),
+    );
+    let (closest, inside) = obb.closest_point(&Point2::new(13., 1.));
+    assert!(!inside);
+    assert_ulps_eq!(Point2::new(11., 1.), closest, epsilon = 1e-6);
+    let (closest, inside) = obb.closest_point(&Point2::new(10.2, 1.5));
+    assert!(inside);
+    assert_ulps_eq!(Point2::new(10.2, 2.), closest, epsilon = 1e-6);
+}
+
+#[test]
+fn test_obb2_obb2() {
+    let obb = diagonal();
+    let other = Obb2::new(
+        Point2::new(2., -2.),
+        [Vector2::unit_x(), Vector2::unit_y()],
+        Vector2::new(0.5, 0.5),
+    );
+    assert!(!obb.intersects(&other));
+    let other = Obb2::new(
+        Point2::new(2., -1.),
+        [Vector2::unit_x(), Vector2::unit_y()],
+        Vector2::new(1., 1.),
+    );
+    assert!(obb.intersects(&other));
+    assert!(other.intersects(&obb));
+}
+
+#[test]
+fn test_obb2_aabb2() {
+    let obb = diagonal();
+    assert!(!obb.intersects(&Aabb2::new(Point2::new(1.5, -2.5), Point2::new(2.5, -1.5))));
+    assert!(obb.intersects(&Aabb2::new(Point2::new(2.5, 2.5), Point2::new(4., 4.))));
+}
+
+#[test]
+fn test_obb2_circle() {
+    let obb = diagonal();
+    let circle = Circle {
+        center: Point2::new(2., -2.),
+        radius: 1.,
+    };
+    assert!(!obb.intersects(&circle));
+    let circle = Circle {
+        center: Point2::new(2., -2.),
+        radius: 2.,
+    };
+    assert!(obb.intersects(&circle));
+}
+
+#[test]
+fn test_obb2_ray() {
+    let obb = diagonal();
+    let ray = Ray2::new(Point2::new(-10., 0.), Vector2::new(1., 0.));
+    assert!(obb.intersects(&ray));
+    assert_ulps_eq!(
+        Point2::new(-2f32.sqrt(), 0.),
+        obb.intersection(&ray).unwrap(),
+        epsilon = 1e-5
+    );
+    let ray = Ray2::new(Point2::new(-10., -10.), Vector2::new(1., 1.).normalize());
+    assert_ulps_eq!(
+        Point2::new(-3., -3.),
+        obb.intersection(&ray).unwrap(),
+        epsilon = 1e-5
+    );
+    let ray = Ray2::new(Point2::new(10., -10.), Vector2::new(0., 1.));
+    assert!(!obb.intersects(&ray));
+    assert_eq!(None, obb.intersection(&ray));
+    let ray = Ray2::new(Point2::new(0., 0.), Vector2::new(1., 0.));
+    assert_eq!(Some(Point2::new(0., 0.)), obb.intersection(&ray));
+}
+
+#[test]
+fn test_obb2_from_points() {
+    // rotated 4x2 rectangle with some points inside, the AABB is much larger
+    let t = transform(1., 2., 0.3);
+    let points: Vec<_> = [
+        Point2::new(-2., -1.),
+        Point2::new(2., -1.),
+        Point2::new(2., 1.),
+        Point2::new(-2., 1.),
+        Point2::new(0., 0.),
+        Point2::new(1.5, -0.5),
+        Point2::new(-1., 0.8),
+    ]
+    .iter()
+    .map(|p| t.transform_point(*p))
+    .collect();
+    let obb = Obb2::from_points(&points);
+    assert_ulps_eq!(Point2::new(1., 2.), obb.center, epsilon = 1e-5);
+    assert_ulps_eq!(
+        8.,
+        4. * obb.half_extents.x * obb.half_extents.y,
+        epsilon = 1e-5
+    );
+    let grown = obb.with_margin(Vector2::new(1e-5, 1e-5));
+    assert!(points.iter().all(|p| grown.contains(p)));
+}
+
+#[test]
+fn test_obb2_from_points_degenerate() {
+    assert_eq!(Obb2::empty(), Obb2::<f32>::from_points(&[]));
+    let obb = Obb2::from_points(&[
+        Point2::new(1., 1.),
+        Point2::new(3., 3.),
+        Point2::new(2., 2.),
+    ]);
+    assert_ulps_eq!(Point2::new(2., 2.), obb.center);
+    assert_ulps_eq!(Vector2::new(2f32.sqrt(), 0.), obb.half_extents);
+}
+
+#[test]
+fn test_obb3_from_aabb() {
+    let obb = Obb3::from_aabb(
+        &Aabb3::new(Point3::new(-1f32, -2., -3.), Point3::new(1., 2., 3.)),
+        &transform3(
+            0.,
+            0.,
+            5.,
+            Quaternion::from_angle_z(Rad(std::f32::consts::PI / 2.)),
+        ),
+    );
+    assert_ulps_eq!(Point3::new(0., 0., 5.), obb.center);
+    assert_ulps_eq!(Vector3::new(1., 2., 3.), obb.half_extents);
+    assert_ulps_eq!(Point3::new(-2., -1., 2.), obb.min_extent(), epsilon = 1e-6);
+    assert_ulps_eq!(Point3::new(2., 1., 8.), obb.max_extent(), epsilon = 1e-6);
+    assert!(obb.contains(&Point3::new(1.5, 0.5, 7.)));
+    assert!(!obb.contains(&Point3::new(0.5, 1.5, 7.)));
+}
+
+#[test]
+fn test_obb3_obb3_face() {
+    let obb = cube(0., Quaternion::from_angle_z(Rad(std::f32::consts::PI / 4.)));
+    let other = cube(2.3, Quaternion::one());
+    assert!(obb.intersects(&other));
+    let other = cube(2.5, Quaternion::one());
+    assert!(!obb.intersects(&other));
+    assert!(!other.intersects(&obb));
+}
+
+#[test]
+fn test_obb3_obb3_edge() {
+    // the vertical edge of one cube faces the horizontal edge of the other, so only the cross
+    // product of the edges separates them
+    let obb = cube(0., Quaternion::from_angle_z(Rad(std::f32::consts::PI / 4.)));
+    let rotation = Quaternion::from_angle_y(Rad(std::f32::consts::PI / 4.));
+    let distance = 2. * 2f32.sqrt();
+    assert!(obb.intersects(&cube(distance - 0.1, rotation)));
+    assert!(!obb.intersects(&cube(distance + 0.1, rotation)));
+}
+
+#[test]
+fn test_obb3_aabb3() {
+    let obb = cube(0., Quaternion::from_angle_z(Rad(std::f32::consts::PI / 4.)));
+    assert!(obb.intersects(&Aabb3::new(
+        Point3::new(1.2, -0.5, -0.5),
+        Point3::new(2., 0.5, 0.5)
+    )));
+    // inside the AABB of the OBB, but outside the OBB
+    assert!(!obb.intersects(&Aabb3::new(
+        Point3::new(1., 1., -0.5),
+        Point3::new(2., 2., 0.5)
+    )));
+}
+
+#[test]
+fn test_obb3_sphere() {
+    let obb = cube(0., Quaternion::from_angle_z(Rad(std::f32::consts::PI / 4.)));
+    let sphere = Sphere {
+        center: Point3::new(1.5, 1.5, 0.),
+        radius: 0.7,
+    };
+    assert!(!obb.intersects(&sphere));
+    let sphere = Sphere {
+        center: Point3::new(1.5, 1.5, 0.),
+        radius: 1.2,
+    };
+    assert!(obb.intersects(&sphere));
+}
+
+#[test]
+fn test_obb3_ray() {
+    let obb = cube(0., Quaternion::from_angle_z(Rad(std::f32::consts::PI / 4.)));
+    let ray = Ray3::new(Point3::new(-10., 0., 0.5), Vector3::new(1., 0., 0.));
+    assert!(obb.intersects(&ray));
+    assert_ulps_eq!(
+        Point3::new(-2f32.sqrt(), 0., 0.5),
+        obb.intersection(&ray).unwrap(),
+        epsilon = 1e-5
+    );
+    let ray = Ray3::new(Point3::new(-10., 0., 1.5), Vector3::new(1., 0., 0.));
+    assert!(!obb.intersects(&ray));
+    let ray = Ray3::new(Point3::new(1.2, 1.2, 10.), Vector3::new(0., 0., -1.));
+    assert_eq!(None, obb.intersection(&ray));
+    let ray = Ray3::new(Point3::new(0.5, 0.5, 10.), Vector3::new(0., 0., -1.));
+    assert_ulps_eq!(
+        Point3::new(0.5, 0.5, 1.),
+        obb.intersection(&ray).unwrap(),
+        epsilon = 1e-5
+    );
+}
+
+// util
+/// Long box along the diagonal, reaching from (-3, -3) to (3, 3)
+fn diagonal() -> Obb2<f32> {
+    let axis = Vector2::new(1f32, 1.).normalize();
+    Obb2::new(
+        Point2::new(0., 0.),
+        [axis, Vector2::new(-axis.y, axis.x)],
+        Vector2::new(18f32.sqrt(), 1.),
+    )
+}
+
+fn transform(dx: f32, dy: f32, rot: f32) -> Decomposed<Vector2<f32>, Basis2<f32>> {
+    Decomposed {
+        scale: 1.,
+        rot: Rotation2::from_angle(Rad(rot)),
+        disp: Vector2::new(dx, dy),
+    }
+}
+
+/// Cube with half extents 1, placed along the X axis
+fn cube(dx: f32, rot: Quaternion<f32>) -> Obb3<f32> {
+    Obb3::from_aabb(
+        &Aabb3::new(Point3::new(-1., -1., -1.), Point3::new(1., 1., 1.)),
+        &transform3(dx, 0., 0., rot),
+    )
+}
+
+fn transform3(
+    dx: f32,
+    dy: f32,
+    dz: f32,
+    rot: Quaternion<f32>,
+) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+    Decomposed {
+        scale: 1.,
+        rot,
+        disp: Vector3::new(dx, dy, dz),
+    }
+}
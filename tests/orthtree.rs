@@ -0,0 +1,270 @@
+extern crate cgmath;
+extern crate collision;
+extern crate rand;
+
+use cgmath::{Deg, PerspectiveFov, Point2, Point3, Vector2, Vector3};
+use collision::{Aabb2, Aabb3, Frustum, Projection, Ray2, Ray3, Relation};
+use collision::dbvt::*;
+use collision::orthtree::*;
+use collision::prelude::*;
+use rand::Rng;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Value2 {
+    pub id: u32,
+    pub aabb: Aabb2<f32>,
+}
+
+impl Value2 {
+    pub fn new(id: u32, aabb: Aabb2<f32>) -> Self {
+        Self { id, aabb }
+    }
+}
+
+impl TreeValue for Value2 {
+    type Bound = Aabb2<f32>;
+
+    fn bound(&self) -> &Aabb2<f32> {
+        &self.aabb
+    }
+
+    fn get_bound_with_margin(&self) -> Aabb2<f32> {
+        self.aabb
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Value3 {
+    pub id: u32,
+    pub aabb: Aabb3<f32>,
+}
+
+impl TreeValue for Value3 {
+    type Bound = Aabb3<f32>;
+
+    fn bound(&self) -> &Aabb3<f32> {
+        &self.aabb
+    }
+
+    fn get_bound_with_margin(&self) -> Aabb3<f32> {
+        self.aabb
+    }
+}
+
+#[test]
+fn test_quadtree_split() {
+    let mut tree = Quadtree::new(aabb2(0., 0., 16., 16.)).with_leaf_capacity(2);
+    let a = tree.insert(Value2::new(1, aabb2(1., 1., 1., 1.)));
+    let b = tree.insert(Value2::new(2, aabb2(13., 1., 1., 1.)));
+    assert_eq!(Some(0), tree.value_depth(a));
+    // straddles the center, so stays in the root
+    let c = tree.insert(Value2::new(3, aabb2(7., 7., 2., 2.)));
+    assert_eq!(Some(0), tree.value_depth(c));
+    assert_eq!(Some(1), tree.value_depth(a));
+    assert_eq!(Some(1), tree.value_depth(b));
+
+    let d = tree.insert(Value2::new(4, aabb2(1., 3., 1., 1.)));
+    let e = tree.insert(Value2::new(5, aabb2(3., 3., 1., 1.)));
+    // all three are in the same cell at depth 2, so it is split again
+    assert_eq!(Some(3), tree.value_depth(a));
+    assert_eq!(Some(3), tree.value_depth(d));
+    assert_eq!(Some(3), tree.value_depth(e));
+    assert_eq!(3, tree.depth());
+    assert_eq!(5, tree.len());
+}
+
+#[test]
+fn test_quadtree_max_depth() {
+    let mut tree = Quadtree::new(aabb2(0., 0., 16., 16.))
+        .with_leaf_capacity(1)
+        .with_max_depth(3);
+    for i in 0..10 {
+        tree.insert(Value2::new(i, aabb2(0.1 * i as f32, 0., 0.05, 0.05)));
+    }
+    assert_eq!(3, tree.depth());
+    assert_eq!(10, tree.len());
+}
+
+#[test]
+fn test_quadtree_loose() {
+    let value = Value2::new(1, aabb2(7.5, 7.5, 1., 1.));
+    let mut tree = Quadtree::new(aabb2(0., 0., 16., 16.)).with_leaf_capacity(0);
+    let index = tree.insert(value.clone());
+    assert_eq!(Some(0), tree.value_depth(index));
+
+    let mut tree = Quadtree::new(aabb2(0., 0., 16., 16.))
+        .with_leaf_capacity(0)
+        .with_max_depth(8)
+        .with_looseness(2.);
+    let index = tree.insert(value);
+    assert_eq!(2., tree.looseness());
+    // the cells at depth 4 are of size 1, and the loose boxes of size 2, so the value fits
+    assert_eq!(Some(4), tree.value_depth(index));
+    let region = aabb2(8., 8., 1., 1.);
+    let results = tree.query(&mut DiscreteVisitor::<Aabb2<f32>, Value2>::new(&region));
+    assert_eq!(1, results.len());
+}
+
+#[test]
+fn test_quadtree_update_remove() {
+    let mut tree = Quadtree::new(aabb2(0., 0., 16., 16.)).with_leaf_capacity(1);
+    let a = tree.insert(Value2::new(1, aabb2(1., 1., 1., 1.)));
+    let b = tree.insert(Value2::new(2, aabb2(13., 13., 1., 1.)));
+    assert_eq!(Some(1), tree.value_depth(a));
+
+    // move across the center, and out of the root box
+    tree.update(a, Value2::new(1, aabb2(13., 1., 1., 1.)));
+    assert_eq!(Some(1), tree.value_depth(a));
+    assert_eq!(1, query_region(&tree, aabb2(12., 0., 4., 4.)).len());
+    assert!(query_region(&tree, aabb2(0., 0., 4., 4.)).is_empty());
+    tree.update(a, Value2::new(1, aabb2(20., 20., 1., 1.)));
+    assert_eq!(Some(0), tree.value_depth(a));
+    assert_eq!(vec![1], query_region(&tree, aabb2(19., 19., 4., 4.)));
+
+    assert_eq!(
+        Some(Value2::new(2, aabb2(13., 13., 1., 1.))),
+        tree.remove(b)
+    );
+    assert_eq!(None, tree.remove(b));
+    assert_eq!(None, tree.get(b));
+    assert_eq!(1, tree.len());
+    assert!(query_region(&tree, aabb2(0., 0., 16., 16.)).is_empty());
+
+    // the index is reused
+    assert_eq!(b, tree.insert(Value2::new(3, aabb2(5., 5., 1., 1.))));
+    assert_eq!(vec![1, 3], {
+        let mut ids: Vec<u32> = tree.iter().map(|(_, v)| v.id).collect();
+        ids.sort();
+        ids
+    });
+
+    tree.clear();
+    assert!(tree.is_empty());
+    assert_eq!(0, tree.depth());
+}
+
+#[test]
+fn test_quadtree_query_random() {
+    let mut rng = rand::thread_rng();
+    let mut tree = Quadtree::new(aabb2(-100., -100., 200., 200.)).with_leaf_capacity(4);
+    let mut values = Vec::new();
+    for i in 0..200 {
+        let x = rng.gen_range(-110., 100.);
+        let y = rng.gen_range(-110., 100.);
+        let value = Value2::new(i, aabb2(x, y, rng.gen_range(0.1, 10.), 5.));
+        tree.insert(value.clone());
+        values.push(value);
+    }
+    for _ in 0..20 {
+        let region = aabb2(
+            rng.gen_range(-100., 100.),
+            rng.gen_range(-100., 100.),
+            20.,
+            30.,
+        );
+        let mut expected: Vec<u32> = values
+            .iter()
+            .filter(|v| v.aabb.intersects(&region))
+            .map(|v| v.id)
+            .collect();
+        expected.sort();
+        assert_eq!(expected, query_region(&tree, region));
+    }
+}
+
+#[test]
+fn test_quadtree_ray() {
+    let mut tree = Quadtree::new(aabb2(0., 0., 16., 16.)).with_leaf_capacity(1);
+    tree.insert(Value2::new(1, aabb2(1., 1., 1., 1.)));
+    tree.insert(Value2::new(2, aabb2(5., 1., 1., 1.)));
+    tree.insert(Value2::new(3, aabb2(5., 10., 1., 1.)));
+    let ray = Ray2::new(Point2::new(0., 1.5), Vector2::new(1., 0.));
+    let mut visitor = ContinuousVisitor::<Ray2<f32>, Value2>::new(&ray);
+    let mut results = tree.query(&mut visitor);
+    results.sort_by_key(|&(v, _)| v.id);
+    assert_eq!(2, results.len());
+    assert_eq!(Point2::new(1., 1.5), results[0].1);
+    assert_eq!(Point2::new(5., 1.5), results[1].1);
+}
+
+#[test]
+fn test_octree_queries() {
+    let mut tree = Octree::new(aabb3(-8., -8., -8., 16.))
+        .with_leaf_capacity(2)
+        .with_max_depth(4);
+    assert_eq!(4, tree.max_depth());
+    assert_eq!(2, tree.leaf_capacity());
+    let mut id = 0;
+    for &x in &[-6., -2., 2., 6.] {
+        for &y in &[-6., -2., 2., 6.] {
+            for &z in &[-6., -2., 2., 6.] {
+                tree.insert(Value3 {
+                    id,
+                    aabb: aabb3(x - 0.5, y - 0.5, z - 0.5, 1.),
+                });
+                id += 1;
+            }
+        }
+    }
+    assert_eq!(64, tree.len());
+    assert_eq!(2, tree.depth());
+
+    // point
+    let point = Point3::new(2.2, -1.8, 6.);
+    let results = tree.query(&mut PointVisitor::<Point3<f32>, Value3>::new(&point));
+    assert_eq!(1, results.len());
+    assert!(results[0].0.aabb.contains(&point));
+
+    // box
+    let region = aabb3(-3., -3., -3., 6.);
+    let results = tree.query_aabb(&region);
+    assert_eq!(8, results.len());
+    assert!(results.iter().all(|v| v.aabb.intersects(&region)));
+    assert!(tree.query_aabb(&aabb3(20., 20., 20., 1.)).is_empty());
+
+    // ray along a row of values
+    let ray = Ray3::new(Point3::new(-10., 2., 6.), Vector3::new(1., 0., 0.));
+    let results = tree.query(&mut ContinuousVisitor::<Ray3<f32>, Value3>::new(&ray));
+    assert_eq!(4, results.len());
+    assert!(results.iter().all(|&(v, p)| v.aabb.contains(&p)));
+
+    // only the values in front of the camera
+    let frustum = frustum();
+    let results = tree.query(&mut FrustumVisitor::<f32, Value3>::new(&frustum));
+    assert!(!results.is_empty());
+    assert!(results.len() < 64);
+    for (value, relation) in results {
+        assert_ne!(Relation::Out, relation);
+        assert!(value.aabb.max.z < 0.);
+    }
+}
+
+fn query_region(tree: &Quadtree<f32, Value2>, region: Aabb2<f32>) -> Vec<u32> {
+    let mut ids: Vec<u32> = tree.query_aabb(&region).iter().map(|v| v.id).collect();
+    ids.sort();
+    ids
+}
+
+fn aabb3(minx: f32, miny: f32, minz: f32, size: f32) -> Aabb3<f32> {
+    Aabb3::new(
+        Point3::new(minx, miny, minz),
+        Point3::new(minx + size, miny + size, minz + size),
+    )
+}
+
+fn frustum() -> Frustum<f32> {
+    let projection = PerspectiveFov {
+        fovy: Deg(60.).into(),
+        aspect: 16. / 9.,
+        near: 0.1,
+        far: 10.0,
+    };
+    projection.to_frustum()
+}
+
+fn aabb2(minx: f32, miny: f32, width: f32, height: f32) -> Aabb2<f32> {
+    Aabb2::new(
+        Point2::new(minx, miny),
+        Point2::new(minx + width, miny + height),
+    )
+}
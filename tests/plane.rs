@@ -94,3 +94,107 @@ fn test_plane3_intersection() {
     assert!(point.is_none());
     assert!(!p0.intersects(&(p1, p2)));
 }
+
+#[test]
+fn test_sphere_intersection() {
+    let ground = Plane::new(Vector3::unit_y(), 1.0f64);
+    let sphere = Sphere {
+        center: Point3::new(3f64, 1.5f64, -2f64),
+        radius: 1f64,
+    };
+    assert!(ground.intersects(&sphere));
+    assert_ulps_eq!(ground.intersection(&sphere).unwrap(), &0.5f64);
+
+    let sphere = Sphere {
+        center: Point3::new(3f64, 2.5f64, -2f64),
+        radius: 1f64,
+    };
+    assert!(!ground.intersects(&sphere));
+    assert_eq!(ground.intersection(&sphere), None);
+
+    // completely below the ground
+    let sphere = Sphere {
+        center: Point3::new(0f64, -5f64, 0f64),
+        radius: 1f64,
+    };
+    assert_ulps_eq!(ground.intersection(&sphere).unwrap(), &7f64);
+}
+
+#[test]
+fn test_aabb_intersection() {
+    let ground = Plane::new(Vector3::unit_y(), 1.0f64);
+    let aabb = Aabb3::new(
+        Point3::new(0f64, 0.75f64, 0f64),
+        Point3::new(1f64, 2f64, 1f64),
+    );
+    assert!(ground.intersects(&aabb));
+    assert_ulps_eq!(ground.intersection(&aabb).unwrap(), &0.25f64);
+
+    let aabb = Aabb3::new(
+        Point3::new(0f64, 1.5f64, 0f64),
+        Point3::new(1f64, 2f64, 1f64),
+    );
+    assert!(!ground.intersects(&aabb));
+    assert_eq!(ground.intersection(&aabb), None);
+
+    let slope = Plane::new(Vector3::new(1f64, 1f64, 0f64).normalize(), 0f64);
+    let aabb = Aabb3::new(Point3::new(0f64, 0f64, 0f64), Point3::new(1f64, 1f64, 1f64));
+    assert!(slope.intersects(&aabb));
+    assert_ulps_eq!(slope.intersection(&aabb).unwrap(), &0f64);
+}
+
+#[test]
+fn test_primitive_intersection() {
+    let ground = Plane::new(Vector3::unit_y(), 1.0f64);
+    let cuboid = primitive::Cuboid::new(2f64, 2f64, 2f64);
+    let transform = Decomposed {
+        scale: 1f64,
+        rot: Quaternion::from_angle_z(Rad(0f64)),
+        disp: Vector3::new(10f64, 1.5f64, 0f64),
+    };
+    assert!(ground.intersects(&(&cuboid, &transform)));
+    assert_ulps_eq!(
+        ground.intersection(&(&cuboid, &transform)).unwrap(),
+        &0.5f64
+    );
+
+    // rotated 45 degrees, the corner reaches further down
+    let transform = Decomposed {
+        scale: 1f64,
+        rot: Quaternion::from_angle_z(Rad(std::f64::consts::FRAC_PI_4)),
+        disp: Vector3::new(10f64, 2.2f64, 0f64),
+    };
+    assert!(ground.intersects(&(&cuboid, &transform)));
+    assert_ulps_eq!(
+        ground.intersection(&(&cuboid, &transform)).unwrap(),
+        &(std::f64::consts::SQRT_2 - 1.2f64),
+        epsilon = 1e-10
+    );
+
+    let transform = Decomposed {
+        scale: 1f64,
+        rot: Quaternion::from_angle_z(Rad(0f64)),
+        disp: Vector3::new(10f64, 2.5f64, 0f64),
+    };
+    assert!(!ground.intersects(&(&cuboid, &transform)));
+    assert_eq!(ground.intersection(&(&cuboid, &transform)), None);
+}
+
+#[test]
+fn test_intersection_matches_relate_plane() {
+    let ground = Plane::new(Vector3::unit_y(), 1.0f64);
+    for &y in &[-3f64, 0f64, 1f64, 1.5f64, 2f64, 2.5f64, 5f64] {
+        let sphere = Sphere {
+            center: Point3::new(4f64, y, -2f64),
+            radius: 1f64,
+        };
+        let free = sphere.relate_plane(ground) == Relation::In;
+        assert_eq!(!free, ground.intersects(&sphere));
+        assert_eq!(!free, ground.intersection(&sphere).is_some());
+
+        let aabb = Aabb3::new(Point3::new(0f64, y - 1f64, 0f64), Point3::new(1f64, y, 1f64));
+        let free = aabb.relate_plane(ground) == Relation::In;
+        assert_eq!(!free, ground.intersects(&aabb));
+        assert_eq!(!free, ground.intersection(&aabb).is_some());
+    }
+}
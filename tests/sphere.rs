@@ -2,9 +2,12 @@ extern crate approx;
 
 extern crate cgmath;
 extern crate collision;
+extern crate rand;
 
 use cgmath::*;
 use collision::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
 
 #[test]
 fn test_intersection() {
@@ -218,3 +221,156 @@ fn test_surface_area() {
     // 4 * pi * r^2
     assert_eq!(4. * std::f64::consts::PI * 2. * 2., base.surface_area());
 }
+
+#[test]
+fn test_from_points() {
+    let mut points = vec![];
+    for &x in &[-1f64, 1.] {
+        for &y in &[-1., 1.] {
+            for &z in &[-1., 1.] {
+                points.push(Point3::new(x, y, z + 2.));
+            }
+        }
+    }
+    points.push(Point3::new(0.2, 0.5, 2.1));
+    points.push(Point3::new(-0.9, 0.1, 1.2));
+    let sphere = Sphere::from_points(&points);
+    assert_ulps_eq!(Point3::new(0., 0., 2.), sphere.center, epsilon = 1e-10);
+    assert_ulps_eq!(3f64.sqrt(), sphere.radius, epsilon = 1e-7);
+}
+
+#[test]
+fn test_from_points_degenerate() {
+    // coplanar, all on a circle
+    let points = [
+        Point3::new(1f64, 0., 0.),
+        Point3::new(0., 1., 0.),
+        Point3::new(-1., 0., 0.),
+        Point3::new(0., -1., 0.),
+        Point3::new(0.5, 0.5, 0.),
+    ];
+    let sphere = Sphere::from_points(&points);
+    assert_ulps_eq!(Point3::new(0., 0., 0.), sphere.center, epsilon = 1e-10);
+    assert_ulps_eq!(1., sphere.radius, epsilon = 1e-7);
+
+    // collinear
+    let points = [
+        Point3::new(0f64, 0., 1.),
+        Point3::new(0., 0., 4.),
+        Point3::new(0., 0., -2.),
+    ];
+    let sphere = Sphere::from_points(&points);
+    assert_ulps_eq!(Point3::new(0., 0., 1.), sphere.center, epsilon = 1e-10);
+    assert_ulps_eq!(3., sphere.radius, epsilon = 1e-7);
+
+    let sphere = Sphere::from_points(&[Point3::new(1f64, 2., 3.)]);
+    assert_eq!(Point3::new(1., 2., 3.), sphere.center);
+    assert_ulps_eq!(0., sphere.radius, epsilon = 1e-7);
+    assert_eq!(0., Sphere::<f64>::from_points(&[]).radius);
+}
+
+#[test]
+fn test_from_points_coincident_collinear() {
+    // repeated and collinear points in every order the boundary search can see them
+    let points = [
+        Point3::new(1f32, 1., 1.),
+        Point3::new(1., 1., 1.),
+        Point3::new(3., 3., 3.),
+        Point3::new(1., 1., 1.),
+        Point3::new(2., 2., 2.),
+        Point3::new(3., 3., 3.),
+        Point3::new(1.5, 1.5, 1.5),
+    ];
+    let mut rng = rand::thread_rng();
+    for _ in 0..50 {
+        let mut shuffled = points.to_vec();
+        shuffled.shuffle(&mut rng);
+        let sphere = Sphere::from_points(&shuffled);
+        assert_ulps_eq!(Point3::new(2., 2., 2.), sphere.center, epsilon = 1e-5);
+        assert_ulps_eq!(3f32.sqrt(), sphere.radius, epsilon = 1e-3);
+        for point in &shuffled {
+            assert!(sphere.contains(point), "{:?} outside {:?}", point, sphere);
+        }
+    }
+    let sphere = Sphere::from_points(&[Point3::new(1f64, 2., 3.); 5]);
+    assert_eq!(Point3::new(1., 2., 3.), sphere.center);
+    assert_ulps_eq!(0., sphere.radius, epsilon = 1e-7);
+}
+
+#[test]
+fn test_from_points_contains_all() {
+    // points on a twisted spiral, the sphere must contain all of them and touch at least 2
+    let points: Vec<_> = (0..200)
+        .map(|i| {
+            let t = f64::from(i) * 0.1;
+            Point3::new(t.cos() * (1. + t), t.sin() * 2., t * 0.3)
+        })
+        .collect();
+    let sphere = Sphere::from_points(&points);
+    let touching = points
+        .iter()
+        .filter(|p| {
+            let d = p.distance(sphere.center);
+            assert!(d <= sphere.radius + 1e-6);
+            d >= sphere.radius - 1e-6
+        })
+        .count();
+    assert!(touching >= 2);
+}
+
+#[test]
+fn test_from_points_encloses_random() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..200 {
+        let scale = rng.gen_range(0.1f32, 100.);
+        let points: Vec<_> = (0..rng.gen_range(1, 60))
+            .map(|_| {
+                Point3::new(
+                    rng.gen_range(-scale, scale),
+                    rng.gen_range(-scale, scale),
+                    rng.gen_range(-scale, scale),
+                )
+            })
+            .collect();
+        let sphere = Sphere::from_points(&points);
+        for point in &points {
+            assert!(sphere.contains(point), "{:?} outside {:?}", point, sphere);
+        }
+        // the order is fixed, so the result is too
+        assert_eq!(sphere, Sphere::from_points(&points));
+    }
+}
+
+#[test]
+fn test_sphere_conversions() {
+    let aabb = Aabb3::new(Point3::new(-1f32, 0., 1.), Point3::new(1., 2., 3.));
+    let sphere = Sphere::from(aabb);
+    assert_eq!(Point3::new(0., 1., 2.), sphere.center);
+    assert_ulps_eq!(3f32.sqrt(), sphere.radius);
+    let grown = Sphere {
+        center: sphere.center,
+        radius: sphere.radius + 1e-6,
+    };
+    assert!(aabb.to_corners().iter().all(|c| grown.contains(c)));
+    let sphere = Sphere {
+        center: Point3::new(0., 1., 2.),
+        radius: 2.,
+    };
+    assert_eq!(
+        Aabb3::new(Point3::new(-2., -1., 0.), Point3::new(2., 3., 4.)),
+        Aabb3::from(sphere)
+    );
+
+    let obb = Obb3::from(aabb);
+    assert_eq!(aabb, Aabb3::from(obb));
+    assert_eq!(Sphere::from(aabb), Sphere::from(obb));
+
+    let capsule = Capsule::new(Point3::new(0., 0., 0.), Point3::new(4., 0., 0.), 1.);
+    let sphere = Sphere::from(capsule);
+    assert_eq!(Point3::new(2., 0., 0.), sphere.center);
+    assert_eq!(3., sphere.radius);
+    assert_eq!(
+        Aabb3::new(Point3::new(-1., -1., -1.), Point3::new(5., 1., 1.)),
+        Aabb3::from(capsule)
+    );
+}